@@ -0,0 +1,6 @@
+-- Store the optional per-metric context (e.g. raw speed samples) sent with uploads
+
+ALTER TABLE performance_metrics
+  ADD COLUMN IF NOT EXISTS context JSONB;
+
+COMMENT ON COLUMN performance_metrics.context IS 'Optional metric metadata, e.g. speed_samples for run-to-run variance analysis';
@@ -0,0 +1,9 @@
+-- Record CPU thread count and NUMA node count on hardware profiles
+-- CPU-only inference speed depends heavily on both, so they are part of the profile identity
+
+ALTER TABLE hardware_profiles
+  ADD COLUMN IF NOT EXISTS cpu_threads INTEGER,
+  ADD COLUMN IF NOT EXISTS numa_nodes INTEGER;
+
+COMMENT ON COLUMN hardware_profiles.cpu_threads IS 'CPU threads used for inference (e.g. llama-bench n_threads)';
+COMMENT ON COLUMN hardware_profiles.numa_nodes IS 'Number of NUMA nodes on the host';
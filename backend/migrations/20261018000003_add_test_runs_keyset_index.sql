@@ -0,0 +1,3 @@
+-- Support keyset pagination of configurations ordered by (timestamp DESC, id DESC)
+
+CREATE INDEX IF NOT EXISTS idx_test_runs_timestamp_id ON test_runs(timestamp DESC, id DESC);
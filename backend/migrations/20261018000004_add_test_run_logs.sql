@@ -0,0 +1,14 @@
+-- Store each test run's raw backend console output (stdout/stderr) for debugging
+-- Kept out of test_runs so grid and detail queries don't drag large text columns along
+
+CREATE TABLE IF NOT EXISTS test_run_logs (
+    test_run_id UUID PRIMARY KEY REFERENCES test_runs(id) ON DELETE CASCADE,
+    log TEXT NOT NULL,
+    original_bytes BIGINT NOT NULL,
+    truncated BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON COLUMN test_run_logs.original_bytes IS 'Size of the log as uploaded, before any truncation';
+COMMENT ON COLUMN test_run_logs.truncated IS 'Whether the start of the log was dropped to fit the size limit';
@@ -0,0 +1,9 @@
+-- Tag test runs with an optional experiment label (e.g. a sweep campaign)
+
+ALTER TABLE test_runs
+  ADD COLUMN IF NOT EXISTS label TEXT;
+
+COMMENT ON COLUMN test_runs.label IS 'Optional campaign label, e.g. driver-550-retest, used to group and filter runs';
+
+-- Partial index for ?label= filters; unlabeled runs (the majority) stay out of it
+CREATE INDEX IF NOT EXISTS idx_test_runs_label ON test_runs(label) WHERE label IS NOT NULL;
@@ -0,0 +1,14 @@
+-- Curation flags for test runs: mark the trusted run of a config as canonical,
+-- or superseded/broken ones as deprecated, without deleting anything
+
+CREATE TABLE IF NOT EXISTS test_run_annotations (
+    test_run_id UUID PRIMARY KEY REFERENCES test_runs(id) ON DELETE CASCADE,
+    canonical BOOLEAN NOT NULL DEFAULT FALSE,
+    deprecated BOOLEAN NOT NULL DEFAULT FALSE,
+    reason TEXT,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT test_run_annotations_not_both CHECK (NOT (canonical AND deprecated))
+);
+
+COMMENT ON COLUMN test_run_annotations.reason IS 'Why the run was marked, e.g. "re-run after driver fix"';
@@ -0,0 +1,6 @@
+-- MMLU-Pro reports often give only per-category accuracies, so question
+-- counts are optional (as they already are in mmlu_scores_v2)
+
+ALTER TABLE mmlu_scores
+  ALTER COLUMN total_questions DROP NOT NULL,
+  ALTER COLUMN correct_answers DROP NOT NULL;
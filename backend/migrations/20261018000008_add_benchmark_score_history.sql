@@ -0,0 +1,21 @@
+-- Superseded benchmark scores. The *_scores_v2 tables keep one set per
+-- variant (the latest timestamp wins); uploads with keep_history move the
+-- set they replace here instead of deleting it.
+
+CREATE TABLE IF NOT EXISTS benchmark_score_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    model_variant_id UUID NOT NULL REFERENCES model_variants(id) ON DELETE CASCADE,
+    benchmark VARCHAR(255) NOT NULL,
+    category VARCHAR(255),
+    score DOUBLE PRECISION NOT NULL,
+    timestamp TIMESTAMPTZ NOT NULL,
+    context JSONB,
+    archived_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_benchmark_score_history_variant
+    ON benchmark_score_history (model_variant_id, benchmark, timestamp);
+
+COMMENT ON COLUMN benchmark_score_history.benchmark IS 'mmlu, gsm8k, humaneval, hellaswag, truthfulqa, or a generic benchmark name';
+COMMENT ON COLUMN benchmark_score_history.category IS 'MMLU category; NULL for single-score benchmarks';
+COMMENT ON COLUMN benchmark_score_history.score IS 'Score on a 0-100 scale';
@@ -0,0 +1,32 @@
+-- GPQA scores, per test run and per model variant. Like the other
+-- single-score benchmarks a variant holds one GPQA result; `subset` records
+-- which question set ("main", "diamond" or "extended") it came from.
+
+CREATE TABLE IF NOT EXISTS gpqa_scores (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    test_run_id UUID NOT NULL REFERENCES test_runs(id) ON DELETE CASCADE,
+    accuracy DOUBLE PRECISION NOT NULL CHECK (accuracy >= 0 AND accuracy <= 100),
+    total_questions INTEGER NOT NULL CHECK (total_questions > 0),
+    correct_answers INTEGER NOT NULL CHECK (correct_answers >= 0 AND correct_answers <= total_questions),
+    subset VARCHAR(50),
+    timestamp TIMESTAMPTZ DEFAULT NOW(),
+    context JSONB,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_gpqa_scores_test_run_id ON gpqa_scores(test_run_id);
+
+CREATE TABLE IF NOT EXISTS gpqa_scores_v2 (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    model_variant_id UUID NOT NULL REFERENCES model_variants(id) ON DELETE CASCADE,
+    accuracy DOUBLE PRECISION NOT NULL,
+    total_questions INT,
+    correct_answers INT,
+    subset VARCHAR(50),
+    timestamp TIMESTAMPTZ NOT NULL,
+    context JSONB,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(model_variant_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_gpqa_scores_v2_model_variant ON gpqa_scores_v2(model_variant_id);
@@ -0,0 +1,9 @@
+-- Record how many GPUs a hardware profile has
+-- Tensor-parallel runs across several cards are a different profile from a single card
+
+ALTER TABLE hardware_profiles
+  ADD COLUMN IF NOT EXISTS gpu_count INTEGER NOT NULL DEFAULT 1,
+  ADD COLUMN IF NOT EXISTS total_gpu_memory_gb INTEGER;
+
+COMMENT ON COLUMN hardware_profiles.gpu_count IS 'Number of GPUs used by the run (gpu_memory_gb is per GPU)';
+COMMENT ON COLUMN hardware_profiles.total_gpu_memory_gb IS 'Combined memory of all GPUs in GB, when reported';
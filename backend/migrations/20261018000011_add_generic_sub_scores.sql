@@ -0,0 +1,7 @@
+-- Keep per-subtask results of generic benchmarks on test runs too
+-- generic_benchmark_scores_v2 already has this column
+
+ALTER TABLE generic_benchmark_scores
+  ADD COLUMN IF NOT EXISTS sub_scores JSONB;
+
+COMMENT ON COLUMN generic_benchmark_scores.sub_scores IS 'Per-subtask scores as reported by the benchmark';
@@ -0,0 +1,8 @@
+-- Record which LoRA adapter a performance run used, so runs of a LoRA
+-- variant are kept apart from the base model's.
+-- '' means no adapter, matching model_variants.lora_adapter
+
+ALTER TABLE test_runs
+  ADD COLUMN IF NOT EXISTS lora_adapter VARCHAR(255) NOT NULL DEFAULT '';
+
+COMMENT ON COLUMN test_runs.lora_adapter IS 'LoRA adapter applied to the model, or empty for the base model';
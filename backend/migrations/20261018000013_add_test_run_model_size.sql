@@ -0,0 +1,10 @@
+-- Store model size and parameter count on the test run itself, so runs can
+-- be filtered by them without reading performance metric context.
+-- NULL when the uploader couldn't determine them
+
+ALTER TABLE test_runs
+  ADD COLUMN IF NOT EXISTS model_size_bytes BIGINT,
+  ADD COLUMN IF NOT EXISTS model_n_params BIGINT;
+
+COMMENT ON COLUMN test_runs.model_size_bytes IS 'Size of the model weights in bytes';
+COMMENT ON COLUMN test_runs.model_n_params IS 'Number of model parameters';
@@ -0,0 +1,87 @@
+-- Fold quantization labels to the normalized form the API now writes
+-- (trimmed, uppercase, no -GGUF/.GGUF suffix), so "q4_k_m" and "Q4_K_M"
+-- stop showing up as separate variants.
+-- Variants that collide after normalization are merged the same way the
+-- merge endpoint does it: per benchmark the newest scores win, and all
+-- score history moves to the surviving variant. MMLU categories are
+-- kept or replaced as a whole set, so one run's categories never mix
+-- with another's.
+
+CREATE FUNCTION pg_temp.normalize_quantization(q TEXT) RETURNS TEXT AS $$
+    SELECT regexp_replace(upper(btrim(q)), '[-.]GGUF$', '')
+$$ LANGUAGE SQL IMMUTABLE;
+
+UPDATE test_runs
+SET quantization = pg_temp.normalize_quantization(quantization)
+WHERE quantization <> pg_temp.normalize_quantization(quantization);
+
+DO $$
+DECLARE
+    dup RECORD;
+    score RECORD;
+    same_key TEXT;
+BEGIN
+    -- Keep the variant already spelled in normalized form, else the oldest
+    FOR dup IN
+        SELECT v.id AS from_id, keep.id AS into_id
+        FROM model_variants v
+        JOIN LATERAL (
+            SELECT k.id
+            FROM model_variants k
+            WHERE k.model_name = v.model_name
+              AND k.lora_adapter = v.lora_adapter
+              AND pg_temp.normalize_quantization(k.quantization)
+                = pg_temp.normalize_quantization(v.quantization)
+            ORDER BY k.quantization = pg_temp.normalize_quantization(k.quantization) DESC,
+                     k.created_at, k.id
+            LIMIT 1
+        ) keep ON keep.id <> v.id
+    LOOP
+        FOR score IN
+            SELECT * FROM (VALUES
+                ('mmlu_scores_v2', NULL),
+                ('gsm8k_scores_v2', NULL),
+                ('humaneval_scores_v2', NULL),
+                ('hellaswag_scores_v2', NULL),
+                ('truthfulqa_scores_v2', NULL),
+                ('gpqa_scores_v2', NULL),
+                ('generic_benchmark_scores_v2', 'benchmark_name')
+            ) AS t(tbl, key)
+        LOOP
+            same_key := CASE WHEN score.key IS NULL THEN ''
+                             ELSE format('AND %%1$s.%1$I = %%2$s.%1$I', score.key) END;
+
+            -- Drop the survivor's rows where the duplicate has newer ones
+            EXECUTE format(
+                'DELETE FROM %1$I dst WHERE dst.model_variant_id = $2
+                   AND (SELECT MAX(src.timestamp) FROM %1$I src
+                        WHERE src.model_variant_id = $1 ' || format(same_key, 'src', 'dst') || ')
+                     > (SELECT MAX(cur.timestamp) FROM %1$I cur
+                        WHERE cur.model_variant_id = $2 ' || format(same_key, 'cur', 'dst') || ')',
+                score.tbl)
+            USING dup.from_id, dup.into_id;
+
+            -- Whatever still conflicts is older than the survivor's
+            EXECUTE format(
+                'DELETE FROM %1$I src WHERE src.model_variant_id = $1
+                   AND EXISTS (SELECT 1 FROM %1$I dst
+                               WHERE dst.model_variant_id = $2 ' || format(same_key, 'dst', 'src') || ')',
+                score.tbl)
+            USING dup.from_id, dup.into_id;
+
+            EXECUTE format('UPDATE %I SET model_variant_id = $2 WHERE model_variant_id = $1', score.tbl)
+            USING dup.from_id, dup.into_id;
+        END LOOP;
+
+        UPDATE benchmark_score_history SET model_variant_id = dup.into_id
+        WHERE model_variant_id = dup.from_id;
+
+        DELETE FROM model_variants WHERE id = dup.from_id;
+    END LOOP;
+END
+$$;
+
+UPDATE model_variants
+SET quantization = pg_temp.normalize_quantization(quantization),
+    updated_at = CURRENT_TIMESTAMP
+WHERE quantization <> pg_temp.normalize_quantization(quantization);
@@ -0,0 +1,98 @@
+// backend/src/auth.rs
+// Optional API key required on requests that change data
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{error::AppError, AppState};
+
+/// Environment variable holding the API key
+pub const API_KEY_VAR: &str = "API_KEY";
+
+/// The key writes must present as `Authorization: Bearer <key>`, if any.
+///
+/// Only a SHA-256 digest of the key is kept, and presented keys are compared
+/// by digest so the comparison time says nothing about the key.
+#[derive(Clone)]
+pub struct ApiKey {
+    digest: Option<Arc<[u8]>>,
+}
+
+impl ApiKey {
+    pub fn new(key: Option<&str>) -> Self {
+        Self {
+            digest: key.map(|key| Sha256::digest(key.as_bytes()).to_vec().into()),
+        }
+    }
+
+    /// Key from `API_KEY`. Without it every request is allowed, as suits
+    /// local development.
+    pub fn from_env() -> Self {
+        match std::env::var(API_KEY_VAR) {
+            Ok(key) if !key.is_empty() => Self::new(Some(&key)),
+            _ => {
+                tracing::warn!("{} not set; uploads and deletes need no authentication", API_KEY_VAR);
+                Self::new(None)
+            }
+        }
+    }
+
+    /// Whether `headers` carry the key, or none is required
+    fn allows(&self, headers: &HeaderMap) -> bool {
+        let Some(digest) = &self.digest else {
+            return true;
+        };
+
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|key| Sha256::digest(key.trim().as_bytes()).as_slice() == &digest[..])
+    }
+}
+
+/// Reject requests that may change data (anything but GET, HEAD, OPTIONS and
+/// TRACE) with a 401 unless they carry the API key
+pub async fn require_api_key(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if request.method().is_safe() || state.api_key.allows(request.headers()) {
+        return next.run(request).await;
+    }
+
+    let mut response =
+        AppError::Unauthorized("This request needs an Authorization: Bearer <API key> header".to_string())
+            .into_response();
+    response
+        .headers_mut()
+        .insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(authorization: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, authorization.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_api_key_allows() {
+        let key = ApiKey::new(Some("s3cret"));
+        assert!(key.allows(&headers("Bearer s3cret")));
+        assert!(!key.allows(&headers("Bearer wrong")));
+        assert!(!key.allows(&headers("s3cret")));
+        assert!(!key.allows(&HeaderMap::new()));
+
+        // Without a key everything is allowed
+        assert!(ApiKey::new(None).allows(&HeaderMap::new()));
+    }
+}
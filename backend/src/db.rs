@@ -0,0 +1,393 @@
+// backend/src/db.rs
+// Database connection setup and query diagnostics
+
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, PgPool, Postgres, Transaction};
+
+/// Environment variable holding the slow-query threshold in milliseconds
+pub const LOG_SLOW_QUERIES_MS_VAR: &str = "LOG_SLOW_QUERIES_MS";
+
+/// Read the slow-query threshold from the environment.
+///
+/// Returns `None` when the variable is unset, empty, or not a valid number
+/// of milliseconds.
+pub fn slow_query_threshold() -> Option<Duration> {
+    std::env::var(LOG_SLOW_QUERIES_MS_VAR)
+        .ok()
+        .and_then(|value| parse_threshold_ms(&value))
+}
+
+fn parse_threshold_ms(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_millis)
+}
+
+/// Build connection options for the given URL.
+///
+/// Per-statement logging is demoted to TRACE so normal operation stays quiet.
+/// When `LOG_SLOW_QUERIES_MS` is set, any statement exceeding the threshold is
+/// logged at WARN with its SQL text, row count and elapsed time (emitted by
+/// sqlx under the `sqlx::query` target).
+pub fn connect_options(database_url: &str) -> Result<PgConnectOptions, sqlx::Error> {
+    let options = PgConnectOptions::from_str(database_url)?
+        .log_statements(log::LevelFilter::Trace);
+
+    let options = match slow_query_threshold() {
+        Some(threshold) => {
+            tracing::info!(
+                "Logging queries slower than {}ms",
+                threshold.as_millis()
+            );
+            options.log_slow_statements(log::LevelFilter::Warn, threshold)
+        }
+        None => options.log_slow_statements(log::LevelFilter::Off, Duration::MAX),
+    };
+
+    Ok(options)
+}
+
+/// Environment variable holding the maximum number of pooled connections
+pub const DB_MAX_CONNECTIONS_VAR: &str = "DB_MAX_CONNECTIONS";
+
+/// Environment variable holding how many seconds a query waits for a pooled
+/// connection before failing
+pub const DB_ACQUIRE_TIMEOUT_SECS_VAR: &str = "DB_ACQUIRE_TIMEOUT_SECS";
+
+const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+const DEFAULT_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connection pool sizing, from `DB_MAX_CONNECTIONS` and
+/// `DB_ACQUIRE_TIMEOUT_SECS`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolSettings {
+    pub max_connections: u32,
+    pub acquire_timeout: Duration,
+}
+
+impl PoolSettings {
+    /// Settings from the environment. Unset variables take the defaults (10
+    /// connections, 10 seconds); invalid ones are logged and do too.
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var(DB_MAX_CONNECTIONS_VAR).ok().as_deref(),
+            std::env::var(DB_ACQUIRE_TIMEOUT_SECS_VAR).ok().as_deref(),
+        )
+    }
+
+    fn from_values(max_connections: Option<&str>, acquire_timeout_secs: Option<&str>) -> Self {
+        Self {
+            max_connections: parse_positive(DB_MAX_CONNECTIONS_VAR, max_connections)
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS),
+            acquire_timeout: parse_positive::<u64>(DB_ACQUIRE_TIMEOUT_SECS_VAR, acquire_timeout_secs)
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_ACQUIRE_TIMEOUT),
+        }
+    }
+}
+
+/// A positive integer from `var`'s `value`; None, with a warning for
+/// anything but an unset or empty variable, otherwise
+fn parse_positive<T: FromStr + PartialOrd + Default>(var: &str, value: Option<&str>) -> Option<T> {
+    let value = value.map(str::trim).filter(|value| !value.is_empty())?;
+    match value.parse::<T>() {
+        Ok(parsed) if parsed > T::default() => Some(parsed),
+        _ => {
+            tracing::warn!("Ignoring {}={:?}: expected a positive integer", var, value);
+            None
+        }
+    }
+}
+
+/// Connect to the database using the options from `connect_options` and
+/// the pool settings from the environment, then check the connection with
+/// `SELECT 1` so an unreachable database fails startup rather than the
+/// first request.
+pub async fn connect(database_url: &str) -> Result<PgPool, sqlx::Error> {
+    let settings = PoolSettings::from_env();
+    tracing::info!(
+        "Database pool: up to {} connections, {}s acquire timeout",
+        settings.max_connections,
+        settings.acquire_timeout.as_secs()
+    );
+
+    let pool = PgPoolOptions::new()
+        .max_connections(settings.max_connections)
+        .acquire_timeout(settings.acquire_timeout)
+        .connect_with(connect_options(database_url)?)
+        .await?;
+    sqlx::query("SELECT 1").execute(&pool).await?;
+
+    Ok(pool)
+}
+
+/// Tables the API queries; `/ready` fails until all of them exist
+pub const REQUIRED_TABLES: [&str; 15] = [
+    "hardware_profiles",
+    "test_runs",
+    "performance_metrics",
+    "test_run_logs",
+    "test_run_annotations",
+    "gpqa_scores",
+    "model_variants",
+    "benchmark_score_history",
+    "mmlu_scores_v2",
+    "gsm8k_scores_v2",
+    "humaneval_scores_v2",
+    "hellaswag_scores_v2",
+    "truthfulqa_scores_v2",
+    "gpqa_scores_v2",
+    "generic_benchmark_scores_v2",
+];
+
+/// Required tables missing from the current schema, in `REQUIRED_TABLES` order
+pub async fn missing_tables(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
+    let present: Vec<String> = sqlx::query_scalar(
+        "SELECT table_name::text FROM information_schema.tables \
+         WHERE table_schema = current_schema() AND table_name = ANY($1)",
+    )
+    .bind(&REQUIRED_TABLES[..])
+    .fetch_all(pool)
+    .await?;
+
+    Ok(REQUIRED_TABLES
+        .iter()
+        .filter(|table| !present.iter().any(|p| p == *table))
+        .map(|table| table.to_string())
+        .collect())
+}
+
+/// SQLSTATE codes for transaction aborts that succeed when simply re-run
+/// (40001 serialization_failure, 40P01 deadlock_detected)
+pub const RETRYABLE_SQLSTATES: [&str; 2] = ["40001", "40P01"];
+
+/// Default number of attempts for write transactions
+pub const DEFAULT_TX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled on each subsequent attempt
+const RETRY_BASE_BACKOFF: Duration = Duration::from_millis(25);
+
+/// Whether a database error is a transient conflict worth retrying
+pub fn is_retryable_error(error: &sqlx::Error) -> bool {
+    match error {
+        sqlx::Error::Database(db_error) => db_error
+            .code()
+            .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref())),
+        _ => false,
+    }
+}
+
+/// Errors that can tell whether the operation that produced them may be retried
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+}
+
+impl Retryable for sqlx::Error {
+    fn is_retryable(&self) -> bool {
+        is_retryable_error(self)
+    }
+}
+
+/// Error returned from a transaction run by `with_retry_tx`
+#[derive(Debug)]
+pub enum TxError<E> {
+    /// A database error, with a description of the step that failed
+    Database { context: String, source: sqlx::Error },
+    /// A handler-level failure (not found, bad request, ...) returned as-is
+    Abort(E),
+}
+
+impl<E> TxError<E> {
+    /// Collapse into the handler's error type, using `map` to build the
+    /// response for database errors from a "context: error" message
+    pub fn into_inner(self, map: impl FnOnce(String) -> E) -> E {
+        match self {
+            TxError::Database { context, source } => map(format!("{}: {}", context, source)),
+            TxError::Abort(error) => error,
+        }
+    }
+}
+
+impl<E> Retryable for TxError<E> {
+    fn is_retryable(&self) -> bool {
+        match self {
+            TxError::Database { source, .. } => is_retryable_error(source),
+            TxError::Abort(_) => false,
+        }
+    }
+}
+
+/// Attach a step description to a database error inside a transaction
+pub trait TxContext<T> {
+    fn tx_context<E>(self, context: &str) -> Result<T, TxError<E>>;
+}
+
+impl<T> TxContext<T> for Result<T, sqlx::Error> {
+    fn tx_context<E>(self, context: &str) -> Result<T, TxError<E>> {
+        self.map_err(|source| TxError::Database {
+            context: context.to_string(),
+            source,
+        })
+    }
+}
+
+/// Boxed future returned by transaction closures passed to `with_retry_tx`
+pub type TxFuture<'c, T, E> = Pin<Box<dyn Future<Output = Result<T, TxError<E>>> + Send + 'c>>;
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or
+/// `max_attempts` is reached, backing off exponentially between attempts.
+pub async fn retry_on_conflict<T, E, F, Fut>(max_attempts: u32, mut op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Retryable,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(error) if attempt < max_attempts && error.is_retryable() => {
+                let backoff = RETRY_BASE_BACKOFF * 2u32.pow(attempt - 1);
+                tracing::warn!(
+                    "Transaction conflict on attempt {}/{}, retrying in {}ms",
+                    attempt,
+                    max_attempts,
+                    backoff.as_millis()
+                );
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run `f` inside a transaction, re-running the whole transaction when it is
+/// aborted with a serialization failure or deadlock.
+///
+/// `input` is handed to every attempt alongside the transaction. `f` may be
+/// called several times, so it must not have side effects outside the
+/// transaction. The transaction is committed when `f` succeeds and rolled
+/// back (on drop) when it fails.
+pub async fn with_retry_tx<I, T, E, F>(
+    pool: &PgPool,
+    max_attempts: u32,
+    input: &I,
+    f: F,
+) -> Result<T, TxError<E>>
+where
+    I: Sync + ?Sized,
+    F: for<'c> Fn(&'c mut Transaction<'static, Postgres>, &'c I) -> TxFuture<'c, T, E>,
+{
+    let f = &f;
+    retry_on_conflict(max_attempts, || async move {
+        let mut tx = pool.begin().await.tx_context("Failed to start transaction")?;
+        let value = f(&mut tx, input).await?;
+        tx.commit().await.tx_context("Failed to commit transaction")?;
+        Ok(value)
+    })
+    .await
+}
+
+/// Pool for tests that need a database; they are `#[ignore]`d and run with
+/// `cargo test -- --ignored` against a migrated `DATABASE_URL`
+#[cfg(test)]
+pub(crate) async fn test_pool() -> PgPool {
+    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for database tests");
+    PgPool::connect(&database_url).await.expect("Failed to connect to DATABASE_URL")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_parse_threshold_ms() {
+        assert_eq!(parse_threshold_ms("250"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_threshold_ms(" 0 "), Some(Duration::from_millis(0)));
+        assert_eq!(parse_threshold_ms(""), None);
+        assert_eq!(parse_threshold_ms("fast"), None);
+        assert_eq!(parse_threshold_ms("-5"), None);
+    }
+
+    #[test]
+    fn test_pool_settings_from_values() {
+        assert_eq!(
+            PoolSettings::from_values(None, None),
+            PoolSettings { max_connections: 10, acquire_timeout: Duration::from_secs(10) }
+        );
+        assert_eq!(
+            PoolSettings::from_values(Some("50"), Some(" 3 ")),
+            PoolSettings { max_connections: 50, acquire_timeout: Duration::from_secs(3) }
+        );
+        // Zero, negative and non-numeric values fall back to the defaults
+        assert_eq!(
+            PoolSettings::from_values(Some("0"), Some("soon")),
+            PoolSettings::from_values(None, None)
+        );
+        assert_eq!(PoolSettings::from_values(Some("-2"), Some("")), PoolSettings::from_values(None, None));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct ConflictError {
+        retryable: bool,
+    }
+
+    impl Retryable for ConflictError {
+        fn is_retryable(&self) -> bool {
+            self.retryable
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_conflict_recovers_after_conflicts() {
+        let attempts = AtomicU32::new(0);
+        let result = retry_on_conflict(3, || async {
+            // The first two attempts lose a serialization conflict
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(ConflictError { retryable: true })
+            } else {
+                Ok("committed")
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok("committed"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_conflict_gives_up() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), _> = retry_on_conflict(3, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(ConflictError { retryable: true })
+        })
+        .await;
+
+        assert_eq!(result, Err(ConflictError { retryable: true }));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_conflict_skips_non_retryable() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<(), _> = retry_on_conflict(3, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(ConflictError { retryable: false })
+        })
+        .await;
+
+        assert_eq!(result, Err(ConflictError { retryable: false }));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_non_database_errors_are_not_retryable() {
+        assert!(!is_retryable_error(&sqlx::Error::RowNotFound));
+        assert!(!is_retryable_error(&sqlx::Error::PoolTimedOut));
+    }
+}
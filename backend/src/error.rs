@@ -0,0 +1,159 @@
+// backend/src/error.rs
+// Handler error type and its mapping to HTTP status codes
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+
+use llm_benchmark_types::{ErrorCode, ErrorResponse, ValidationError};
+
+use crate::db::TxError;
+
+/// Error returned by API handlers, rendered as an `ErrorResponse` whose
+/// status and `code` follow from the cause:
+///
+/// | error                           | status | code               |
+/// |---------------------------------|--------|--------------------|
+/// | `BadRequest`                    | 400    | `bad_request`      |
+/// | `Validation`, `ValidationError` | 400    | `validation`       |
+/// | `Unauthorized`                  | 401    | `unauthorized`     |
+/// | `NotFound`, `sqlx` RowNotFound  | 404    | `not_found`        |
+/// | unique violation                | 409    | `conflict`         |
+/// | `Gone`                          | 410    | `gone`             |
+/// | `Unavailable`                   | 503    | `unavailable`      |
+/// | other database errors           | 500    | `database_error`   |
+#[derive(Debug)]
+pub enum AppError {
+    /// The request is malformed
+    BadRequest(String),
+    /// The request is well-formed but its contents fail validation
+    Validation(String),
+    /// The request needs an API key it doesn't carry
+    Unauthorized(String),
+    /// The requested resource doesn't exist
+    NotFound(String),
+    /// The resource existed but has expired
+    Gone(String),
+    /// A dependency (e.g. a table awaiting migration) isn't available yet
+    Unavailable(String),
+    /// A database error, with a description of the step that failed
+    Database { context: String, source: sqlx::Error },
+}
+
+impl AppError {
+    /// HTTP status for this error
+    pub fn status(&self) -> StatusCode {
+        match self {
+            AppError::BadRequest(_) | AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Gone(_) => StatusCode::GONE,
+            AppError::Unavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::Database { source: sqlx::Error::RowNotFound, .. } => StatusCode::NOT_FOUND,
+            AppError::Database { source: sqlx::Error::Database(e), .. } if e.is_unique_violation() => {
+                StatusCode::CONFLICT
+            }
+            AppError::Database { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn code(&self) -> ErrorCode {
+        match (self, self.status()) {
+            (AppError::Validation(_), _) => ErrorCode::Validation,
+            (_, StatusCode::BAD_REQUEST) => ErrorCode::BadRequest,
+            (_, StatusCode::UNAUTHORIZED) => ErrorCode::Unauthorized,
+            (_, StatusCode::NOT_FOUND) => ErrorCode::NotFound,
+            (_, StatusCode::CONFLICT) => ErrorCode::Conflict,
+            (_, StatusCode::GONE) => ErrorCode::Gone,
+            (_, StatusCode::SERVICE_UNAVAILABLE) => ErrorCode::Unavailable,
+            _ => ErrorCode::Database,
+        }
+    }
+
+    pub(crate) fn message(&self) -> String {
+        match self {
+            AppError::BadRequest(message)
+            | AppError::Validation(message)
+            | AppError::Unauthorized(message)
+            | AppError::NotFound(message)
+            | AppError::Gone(message)
+            | AppError::Unavailable(message) => message.clone(),
+            AppError::Database { context, source } => format!("{}: {}", context, source),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if status.is_server_error() {
+            tracing::error!("{}", self.message());
+        }
+
+        (status, Json(ErrorResponse::with_error_code(self.message(), self.code()))).into_response()
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(source: sqlx::Error) -> Self {
+        AppError::Database {
+            context: "Database error".to_string(),
+            source,
+        }
+    }
+}
+
+impl From<ValidationError> for AppError {
+    fn from(error: ValidationError) -> Self {
+        AppError::Validation(format!("Validation error: {}", error))
+    }
+}
+
+impl From<TxError<AppError>> for AppError {
+    fn from(error: TxError<AppError>) -> Self {
+        match error {
+            TxError::Database { context, source } => AppError::Database { context, source },
+            TxError::Abort(error) => error,
+        }
+    }
+}
+
+/// Attach a step description to a database error outside a transaction
+pub trait DbContext<T> {
+    fn db_context(self, context: &str) -> Result<T, AppError>;
+}
+
+impl<T> DbContext<T> for Result<T, sqlx::Error> {
+    fn db_context(self, context: &str) -> Result<T, AppError> {
+        self.map_err(|source| AppError::Database {
+            context: context.to_string(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_mapping() {
+        assert_eq!(AppError::from(sqlx::Error::RowNotFound).status(), StatusCode::NOT_FOUND);
+        assert_eq!(AppError::from(sqlx::Error::PoolTimedOut).status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let invalid = ValidationError::MissingField { field: "model_name".to_string() };
+        let invalid = AppError::from(invalid);
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(invalid.code(), ErrorCode::Validation);
+        assert_eq!(AppError::BadRequest("bad".to_string()).code(), ErrorCode::BadRequest);
+
+        // Aborts keep their own status; database errors keep their context
+        let abort = TxError::Abort(AppError::NotFound("Test run not found".to_string()));
+        assert_eq!(AppError::from(abort).status(), StatusCode::NOT_FOUND);
+        let failed: Result<(), _> = Err(sqlx::Error::PoolTimedOut);
+        let error = failed.db_context("Failed to load runs").unwrap_err();
+        assert!(error.message().starts_with("Failed to load runs: "));
+        assert_eq!(error.code(), ErrorCode::Database);
+    }
+}
@@ -0,0 +1,122 @@
+// backend/src/etag.rs
+// Conditional GET support: ETag generation and If-None-Match handling
+
+use std::collections::hash_map::DefaultHasher;
+use std::convert::Infallible;
+use std::hash::Hasher;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+
+/// The request's `If-None-Match` header, if any.
+///
+/// Handlers opt in to conditional GET by taking this extractor and wrapping
+/// their body with [`IfNoneMatch::json`] instead of `Json`.
+pub struct IfNoneMatch(Option<String>);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IfNoneMatch {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self(
+            parts
+                .headers
+                .get(header::IF_NONE_MATCH)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        ))
+    }
+}
+
+impl IfNoneMatch {
+    /// Respond with `value` as JSON tagged with an ETag, or with an empty
+    /// 304 if the client already holds that version
+    pub fn json<T: Serialize>(self, value: T) -> ETagJson<T> {
+        ETagJson {
+            value,
+            if_none_match: self.0,
+        }
+    }
+}
+
+/// JSON response carrying an ETag derived from a hash of the body
+pub struct ETagJson<T> {
+    value: T,
+    if_none_match: Option<String>,
+}
+
+impl<T: Serialize> IntoResponse for ETagJson<T> {
+    fn into_response(self) -> Response {
+        let body = match serde_json::to_vec(&self.value) {
+            Ok(body) => body,
+            Err(e) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+            }
+        };
+
+        let etag = etag_for(&body);
+        let etag_header = HeaderValue::from_str(&etag).expect("hex etag is a valid header value");
+
+        if self
+            .if_none_match
+            .as_deref()
+            .is_some_and(|candidates| etag_matches(candidates, &etag))
+        {
+            return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response();
+        }
+
+        (
+            [
+                (header::CONTENT_TYPE, HeaderValue::from_static("application/json")),
+                (header::ETAG, etag_header),
+            ],
+            body,
+        )
+            .into_response()
+    }
+}
+
+/// Strong ETag for a response body
+fn etag_for(body: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(body);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// Weak comparison of an `If-None-Match` value against `etag`, as RFC 9110
+/// requires for GET
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn respond(if_none_match: Option<&str>) -> Response {
+        IfNoneMatch(if_none_match.map(str::to_string))
+            .json(vec![1, 2, 3])
+            .into_response()
+    }
+
+    #[test]
+    fn etag_round_trip() {
+        let response = respond(None);
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+        assert_eq!(respond(Some(&etag)).status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(respond(Some(&format!("\"other\", W/{}", etag))).status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(respond(Some("*")).status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(respond(Some("\"stale\"")).status(), StatusCode::OK);
+    }
+}
@@ -0,0 +1,74 @@
+// handlers/annotation.rs
+// Canonical/deprecated annotations on test runs
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use llm_benchmark_types::{AnnotateRunResponse, RunAnnotation, Validate};
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    AppState,
+};
+
+/// Set (or replace) the annotation of a test run. Posting an empty
+/// annotation clears it.
+pub async fn annotate_test_run(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Json(request): Json<RunAnnotation>,
+) -> Result<Json<AnnotateRunResponse>, AppError> {
+    request.validate()?;
+
+    with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+        Box::pin(async move {
+            let exists = sqlx::query!("SELECT id FROM test_runs WHERE id = $1", test_run_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .tx_context("Failed to look up test run")?;
+
+            if exists.is_none() {
+                return Err(TxError::Abort(AppError::NotFound(format!("Test run {} not found", test_run_id))));
+            }
+
+            if request.is_empty() {
+                sqlx::query!("DELETE FROM test_run_annotations WHERE test_run_id = $1", test_run_id)
+                    .execute(&mut **tx)
+                    .await
+                    .tx_context("Failed to clear annotation")?;
+                return Ok(());
+            }
+
+            sqlx::query!(
+                r#"
+                INSERT INTO test_run_annotations (test_run_id, canonical, deprecated, reason)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (test_run_id) DO UPDATE SET
+                    canonical = EXCLUDED.canonical,
+                    deprecated = EXCLUDED.deprecated,
+                    reason = EXCLUDED.reason,
+                    updated_at = CURRENT_TIMESTAMP
+                "#,
+                test_run_id,
+                request.canonical,
+                request.deprecated,
+                request.reason.as_deref()
+            )
+            .execute(&mut **tx)
+            .await
+            .tx_context("Failed to store annotation")?;
+
+            Ok(())
+        })
+    })
+    .await?;
+
+    Ok(Json(AnnotateRunResponse {
+        test_run_id,
+        annotation: (!request.is_empty()).then_some(request),
+    }))
+}
@@ -344,7 +344,7 @@ pub async fn upload_benchmarks(
                     "#,
                     model_variant_id,
                     generic_score.benchmark_name,
-                    generic_score.overall_score,
+                    generic_score.score,
                     generic_score.sub_scores,
                     timestamp,
                     generic_score.context
@@ -1,26 +1,36 @@
 // handlers/benchmark_upload_raw.rs
 // Temporary version using raw SQL queries until migration is run
 
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
-};
+use axum::{extract::State, response::Json};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use sqlx::Row;
+use sqlx::{Postgres, QueryBuilder, Row};
 
 use llm_benchmark_types::{
-    UploadBenchmarkRequest, UploadBenchmarkResponse, 
-    benchmarks::BenchmarkScoreType,
+    UploadBenchmarkRequest, UploadBenchmarkResponse, normalize_quantization,
+    benchmarks::{BenchmarkScoreType, MMLUProScore},
 };
 
-use crate::AppState;
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    handlers::score_history::archive_current_scores,
+    AppState,
+};
 
 /// Upload benchmark scores for a model variant (raw SQL version)
 pub async fn upload_benchmarks_raw(
     State(state): State<AppState>,
     Json(request): Json<UploadBenchmarkRequest>,
-) -> Result<Json<UploadBenchmarkResponse>, (StatusCode, Json<UploadBenchmarkResponse>)> {
+) -> Result<Json<UploadBenchmarkResponse>, AppError> {
+    // Kept uploads are gated on the timestamp of the whole current set,
+    // which would drop the new categories of an older merge
+    if request.keep_history && request.merge {
+        return Err(AppError::BadRequest(
+            "keep_history and merge cannot be used together".to_string(),
+        ));
+    }
+
     // Check if v2 tables exist
     let tables_exist = sqlx::query(
         "SELECT EXISTS (SELECT FROM information_schema.tables WHERE table_name = 'model_variants')"
@@ -31,119 +41,369 @@ pub async fn upload_benchmarks_raw(
     .unwrap_or(false);
 
     if !tables_exist {
-        return Err((
-            StatusCode::SERVICE_UNAVAILABLE,
-            Json(UploadBenchmarkResponse {
-                success: false,
-                model_variant_id: None,
-                message: "Model variants tables not yet created. Please run migration 20250708000001_separate_benchmarks_from_hardware.sql".to_string(),
-                scores_uploaded: 0,
-            }),
+        return Err(AppError::Unavailable(
+            "Model variants tables not yet created. Please run migration 20250708000001_separate_benchmarks_from_hardware.sql".to_string(),
         ));
     }
 
-    // Start a transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(UploadBenchmarkResponse {
-                success: false,
-                model_variant_id: None,
-                message: format!("Failed to start transaction: {}", e),
-                scores_uploaded: 0,
-            }),
-        )
-    })?;
+    // The merge upsert needs the unique key its ON CONFLICT names
+    if request.merge && !mmlu_category_key_exists(&state.db).await? {
+        return Err(AppError::Unavailable(
+            "mmlu_scores_v2 has no unique (model_variant_id, category) constraint to merge categories on".to_string(),
+        ));
+    }
 
-    let lora_adapter = request.lora_adapter.as_deref().unwrap_or("");
+    let (model_variant_id, scores_uploaded) =
+        with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+            Box::pin(write_benchmark_scores(tx, request))
+        })
+        .await?;
 
-    // Find or create model variant
-    let model_variant_id = find_or_create_model_variant_raw(
-        &mut tx,
+    Ok(Json(UploadBenchmarkResponse {
+        success: true,
+        model_variant_id: Some(model_variant_id),
+        message: format!(
+            "Successfully uploaded {} benchmark scores for {}/{}",
+            scores_uploaded, request.model_name, request.quantization
+        ),
+        scores_uploaded,
+    }))
+}
+
+/// Whether `mmlu_scores_v2` has a unique index on exactly
+/// (model_variant_id, category)
+async fn mmlu_category_key_exists(db: &sqlx::PgPool) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1
+            FROM pg_index i
+            JOIN pg_class c ON c.oid = i.indrelid
+            WHERE c.relname = 'mmlu_scores_v2' AND i.indisunique
+              AND (SELECT array_agg(a.attname::text ORDER BY a.attname)
+                   FROM pg_attribute a
+                   WHERE a.attrelid = c.oid AND a.attnum = ANY(i.indkey))
+                  = ARRAY['category', 'model_variant_id']
+        )
+        "#,
+    )
+    .fetch_one(db)
+    .await
+}
+
+/// Replace the model variant's benchmark scores within `tx`, returning the
+/// variant id and number of scores written
+async fn write_benchmark_scores(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    request: &UploadBenchmarkRequest,
+) -> Result<(Uuid, usize), TxError<AppError>> {
+    write_variant_scores(
+        tx,
         &request.model_name,
         &request.quantization,
-        lora_adapter,
+        request.lora_adapter.as_deref().unwrap_or(""),
+        &request.benchmark_scores,
+        request.timestamp.unwrap_or_else(Utc::now),
+        ScoreWriteOptions {
+            keep_history: request.keep_history,
+            merge: request.merge,
+        },
     )
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(UploadBenchmarkResponse {
-                success: false,
-                model_variant_id: None,
-                message: format!("Failed to create model variant: {}", e),
-                scores_uploaded: 0,
-            }),
-        )
-    })?;
+}
+
+/// Multi-row insert of every category of an MMLU-Pro score. `categories`
+/// must not be empty.
+fn mmlu_categories_insert(
+    model_variant_id: Uuid,
+    mmlu_score: &MMLUProScore,
+    timestamp: DateTime<Utc>,
+) -> QueryBuilder<'_, Postgres> {
+    let mut query = QueryBuilder::new(
+        "INSERT INTO mmlu_scores_v2 \
+         (model_variant_id, category, score, total_questions, correct_answers, timestamp, context) ",
+    );
+    query.push_values(&mmlu_score.categories, |mut row, category_score| {
+        row.push_bind(model_variant_id)
+            .push_bind(&category_score.category)
+            .push_bind(category_score.score)
+            .push_bind(category_score.total_questions)
+            .push_bind(category_score.correct_answers)
+            .push_bind(timestamp)
+            .push_bind(&mmlu_score.context);
+    });
+    query
+}
+
+/// Replace a variant's MMLU-Pro category scores, returning how many were written
+async fn replace_mmlu_scores<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+    mmlu_score: &MMLUProScore,
+    timestamp: DateTime<Utc>,
+) -> Result<usize, TxError<E>> {
+    sqlx::query("DELETE FROM mmlu_scores_v2 WHERE model_variant_id = $1")
+        .bind(model_variant_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to delete existing MMLU scores")?;
+
+    // Insert new scores in one statement
+    if !mmlu_score.categories.is_empty() {
+        mmlu_categories_insert(model_variant_id, mmlu_score, timestamp)
+            .build()
+            .execute(&mut **tx)
+            .await
+            .tx_context("Failed to insert MMLU score")?;
+    }
+    Ok(mmlu_score.categories.len())
+}
+
+/// Merge or replace a variant's MMLU-Pro category scores
+async fn write_mmlu_scores<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+    mmlu_score: &MMLUProScore,
+    timestamp: DateTime<Utc>,
+    merge: bool,
+) -> Result<usize, TxError<E>> {
+    if merge {
+        upsert_mmlu_scores(tx, model_variant_id, mmlu_score, timestamp).await
+    } else {
+        replace_mmlu_scores(tx, model_variant_id, mmlu_score, timestamp).await
+    }
+}
+
+/// Update the uploaded MMLU-Pro categories of a variant and insert new ones,
+/// leaving its other categories alone, returning how many were written
+async fn upsert_mmlu_scores<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+    mmlu_score: &MMLUProScore,
+    timestamp: DateTime<Utc>,
+) -> Result<usize, TxError<E>> {
+    if !mmlu_score.categories.is_empty() {
+        mmlu_categories_insert(model_variant_id, mmlu_score, timestamp)
+            .push(
+                " ON CONFLICT (model_variant_id, category) DO UPDATE SET \
+                 score = EXCLUDED.score, total_questions = EXCLUDED.total_questions, \
+                 correct_answers = EXCLUDED.correct_answers, timestamp = EXCLUDED.timestamp, \
+                 context = EXCLUDED.context",
+            )
+            .build()
+            .execute(&mut **tx)
+            .await
+            .tx_context("Failed to merge MMLU scores")?;
+    }
+    Ok(mmlu_score.categories.len())
+}
+
+/// How `write_variant_scores` treats the scores an upload replaces
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ScoreWriteOptions {
+    /// Move replaced scores to the score history instead of deleting them
+    pub keep_history: bool,
+    /// Merge MMLU categories into the stored set instead of replacing it.
+    /// Not combined with `keep_history`
+    pub merge: bool,
+}
+
+/// Find or create the model variant and replace its scores for each
+/// benchmark in `scores`, returning the variant id and number of scores written.
+/// With `keep_history` the replaced scores are moved to the score history,
+/// and scores older than the current ones are only added to the history.
+/// With `merge` MMLU categories are upserted rather than replaced.
+pub(crate) async fn write_variant_scores<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_name: &str,
+    quantization: &str,
+    lora_adapter: &str,
+    scores: &[BenchmarkScoreType],
+    timestamp: DateTime<Utc>,
+    options: ScoreWriteOptions,
+) -> Result<(Uuid, usize), TxError<E>> {
+    // Find or create model variant
+    let model_variant_id = find_or_create_model_variant_raw(tx, model_name, quantization, lora_adapter)
+        .await
+        .tx_context("Failed to create model variant")?;
 
     // Process benchmark scores
-    let timestamp = request.timestamp.unwrap_or_else(chrono::Utc::now);
     let mut scores_uploaded = 0;
 
-    for score in &request.benchmark_scores {
+    for score in scores {
+        if options.keep_history {
+            if let Some(recorded) = archive_current_scores(tx, model_variant_id, score, timestamp).await? {
+                scores_uploaded += recorded;
+                continue;
+            }
+        }
+
         match score {
             BenchmarkScoreType::MMLU(mmlu_score) => {
-                // Delete existing MMLU scores
-                sqlx::query("DELETE FROM mmlu_scores_v2 WHERE model_variant_id = $1")
+                let mmlu_score = MMLUProScore::from(mmlu_score.clone());
+                scores_uploaded += write_mmlu_scores(tx, model_variant_id, &mmlu_score, timestamp, options.merge).await?;
+            }
+            BenchmarkScoreType::MMLUPro(mmlu_score) => {
+                scores_uploaded += write_mmlu_scores(tx, model_variant_id, mmlu_score, timestamp, options.merge).await?;
+            }
+            BenchmarkScoreType::GSM8K(gsm8k_score) => {
+                sqlx::query("DELETE FROM gsm8k_scores_v2 WHERE model_variant_id = $1")
+                    .bind(model_variant_id)
+                    .execute(&mut **tx)
+                    .await
+                    .tx_context("Failed to delete existing GSM8K score")?;
+
+                // Accuracy is stored as a fraction in the v2 table
+                let accuracy = if gsm8k_score.total_problems > 0 {
+                    gsm8k_score.problems_solved as f64 / gsm8k_score.total_problems as f64
+                } else {
+                    0.0
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO gsm8k_scores_v2 
+                    (model_variant_id, problems_solved, total_problems, accuracy, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(gsm8k_score.problems_solved)
+                .bind(gsm8k_score.total_problems)
+                .bind(accuracy)
+                .bind(timestamp)
+                .bind(&gsm8k_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert GSM8K score")?;
+                scores_uploaded += 1;
+            }
+            BenchmarkScoreType::HumanEval(humaneval_score) => {
+                sqlx::query("DELETE FROM humaneval_scores_v2 WHERE model_variant_id = $1")
+                    .bind(model_variant_id)
+                    .execute(&mut **tx)
+                    .await
+                    .tx_context("Failed to delete existing HumanEval score")?;
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO humaneval_scores_v2 
+                    (model_variant_id, pass_at_1, pass_at_10, pass_at_100, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(humaneval_score.pass_at_1)
+                .bind(humaneval_score.pass_at_10)
+                .bind(humaneval_score.pass_at_100)
+                .bind(timestamp)
+                .bind(&humaneval_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert HumanEval score")?;
+                scores_uploaded += 1;
+            }
+            BenchmarkScoreType::HellaSwag(hellaswag_score) => {
+                sqlx::query("DELETE FROM hellaswag_scores_v2 WHERE model_variant_id = $1")
+                    .bind(model_variant_id)
+                    .execute(&mut **tx)
+                    .await
+                    .tx_context("Failed to delete existing HellaSwag score")?;
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO hellaswag_scores_v2 
+                    (model_variant_id, accuracy, total_questions, correct_answers, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(hellaswag_score.accuracy)
+                .bind(hellaswag_score.total_questions)
+                .bind(hellaswag_score.correct_answers)
+                .bind(timestamp)
+                .bind(&hellaswag_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert HellaSwag score")?;
+                scores_uploaded += 1;
+            }
+            BenchmarkScoreType::TruthfulQA(truthfulqa_score) => {
+                sqlx::query("DELETE FROM truthfulqa_scores_v2 WHERE model_variant_id = $1")
                     .bind(model_variant_id)
-                    .execute(&mut *tx)
+                    .execute(&mut **tx)
                     .await
-                    .map_err(|e| {
-                        (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            Json(UploadBenchmarkResponse {
-                                success: false,
-                                model_variant_id: Some(model_variant_id),
-                                message: format!("Failed to delete existing MMLU scores: {}", e),
-                                scores_uploaded,
-                            }),
-                        )
-                    })?;
-
-                // Insert new scores
-                for category_score in &mmlu_score.categories {
-                    sqlx::query(
-                        r#"
-                        INSERT INTO mmlu_scores_v2 
-                        (model_variant_id, category, score, total_questions, correct_answers, timestamp, context)
-                        VALUES ($1, $2, $3, $4, $5, $6, $7)
-                        "#
-                    )
+                    .tx_context("Failed to delete existing TruthfulQA score")?;
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO truthfulqa_scores_v2 
+                    (model_variant_id, truthful_score, total_questions, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(truthfulqa_score.truthful_score)
+                .bind(truthfulqa_score.total_questions)
+                .bind(timestamp)
+                .bind(&truthfulqa_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert TruthfulQA score")?;
+                scores_uploaded += 1;
+            }
+            BenchmarkScoreType::Gpqa(gpqa_score) => {
+                sqlx::query("DELETE FROM gpqa_scores_v2 WHERE model_variant_id = $1")
                     .bind(model_variant_id)
-                    .bind(&category_score.category)
-                    .bind(category_score.score)
-                    .bind(category_score.total_questions)
-                    .bind(category_score.correct_answers)
-                    .bind(timestamp)
-                    .bind(&mmlu_score.context)
-                    .execute(&mut *tx)
+                    .execute(&mut **tx)
                     .await
-                    .map_err(|e| {
-                        (
-                            StatusCode::INTERNAL_SERVER_ERROR,
-                            Json(UploadBenchmarkResponse {
-                                success: false,
-                                model_variant_id: Some(model_variant_id),
-                                message: format!("Failed to insert MMLU score: {}", e),
-                                scores_uploaded,
-                            }),
-                        )
-                    })?;
-                }
-                scores_uploaded += mmlu_score.categories.len();
+                    .tx_context("Failed to delete existing GPQA score")?;
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO gpqa_scores_v2 
+                    (model_variant_id, accuracy, total_questions, correct_answers, subset, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(gpqa_score.accuracy)
+                .bind(gpqa_score.total_questions)
+                .bind(gpqa_score.correct_answers)
+                .bind(&gpqa_score.subset)
+                .bind(timestamp)
+                .bind(&gpqa_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert GPQA score")?;
+                scores_uploaded += 1;
             }
-            _ => {
-                // TODO: Implement other benchmark types
-                return Err((
-                    StatusCode::NOT_IMPLEMENTED,
-                    Json(UploadBenchmarkResponse {
-                        success: false,
-                        model_variant_id: Some(model_variant_id),
-                        message: "Only MMLU scores are currently supported in raw mode".to_string(),
-                        scores_uploaded,
-                    }),
-                ));
+            BenchmarkScoreType::Generic(generic_score) => {
+                sqlx::query(
+                    "DELETE FROM generic_benchmark_scores_v2 WHERE model_variant_id = $1 AND benchmark_name = $2"
+                )
+                .bind(model_variant_id)
+                .bind(&generic_score.benchmark_name)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to delete existing generic score")?;
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO generic_benchmark_scores_v2 
+                    (model_variant_id, benchmark_name, overall_score, sub_scores, timestamp, context)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#
+                )
+                .bind(model_variant_id)
+                .bind(&generic_score.benchmark_name)
+                .bind(generic_score.score)
+                .bind(&generic_score.sub_scores)
+                .bind(timestamp)
+                .bind(&generic_score.context)
+                .execute(&mut **tx)
+                .await
+                .tx_context("Failed to insert generic score")?;
+                scores_uploaded += 1;
             }
         }
     }
@@ -151,42 +411,11 @@ pub async fn upload_benchmarks_raw(
     // Update model variant timestamp
     sqlx::query("UPDATE model_variants SET updated_at = CURRENT_TIMESTAMP WHERE id = $1")
         .bind(model_variant_id)
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(UploadBenchmarkResponse {
-                    success: false,
-                    model_variant_id: Some(model_variant_id),
-                    message: format!("Failed to update model variant: {}", e),
-                    scores_uploaded,
-                }),
-            )
-        })?;
-
-    // Commit transaction
-    tx.commit().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(UploadBenchmarkResponse {
-                success: false,
-                model_variant_id: Some(model_variant_id),
-                message: format!("Failed to commit transaction: {}", e),
-                scores_uploaded,
-            }),
-        )
-    })?;
+        .tx_context("Failed to update model variant")?;
 
-    Ok(Json(UploadBenchmarkResponse {
-        success: true,
-        model_variant_id: Some(model_variant_id),
-        message: format!(
-            "Successfully uploaded {} benchmark scores for {}/{}",
-            scores_uploaded, request.model_name, request.quantization
-        ),
-        scores_uploaded,
-    }))
+    Ok((model_variant_id, scores_uploaded))
 }
 
 async fn find_or_create_model_variant_raw(
@@ -195,12 +424,14 @@ async fn find_or_create_model_variant_raw(
     quantization: &str,
     lora_adapter: &str,
 ) -> Result<Uuid, sqlx::Error> {
+    let quantization = normalize_quantization(quantization);
+
     // Try to find existing
     let existing = sqlx::query(
         "SELECT id FROM model_variants WHERE model_name = $1 AND quantization = $2 AND lora_adapter = $3"
     )
     .bind(model_name)
-    .bind(quantization)
+    .bind(&quantization)
     .bind(lora_adapter)
     .fetch_optional(&mut **tx)
     .await?;
@@ -216,10 +447,99 @@ async fn find_or_create_model_variant_raw(
     )
     .bind(id)
     .bind(model_name)
-    .bind(quantization)
+    .bind(&quantization)
     .bind(lora_adapter)
     .execute(&mut **tx)
     .await?;
 
     Ok(id)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_benchmark_types::benchmarks::{MMLUCategoryScore, MMLUScore};
+    use sqlx::Execute;
+
+    #[test]
+    fn test_mmlu_categories_insert_is_one_statement() {
+        let categories: Vec<MMLUCategoryScore> = (0..57)
+            .map(|i| MMLUCategoryScore {
+                category: format!("subject_{}", i),
+                score: 50.0,
+                total_questions: 100,
+                correct_answers: 50,
+            })
+            .collect();
+        let mmlu_score = MMLUProScore::from(MMLUScore::new(categories));
+
+        let mut query = mmlu_categories_insert(Uuid::nil(), &mmlu_score, Utc::now());
+        let sql = query.sql().to_string();
+
+        assert_eq!(sql.matches("INSERT INTO").count(), 1);
+        assert_eq!(sql.matches('(').count(), 1 + 57);
+        assert!(sql.ends_with("($393, $394, $395, $396, $397, $398, $399)"));
+        assert_eq!(query.build().sql(), sql);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_merge_upserts_mmlu_categories() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let category = |name: &str, score: f64| MMLUCategoryScore {
+            category: name.to_string(),
+            score,
+            total_questions: 100,
+            correct_answers: score as i32,
+        };
+        let upload = |categories| vec![BenchmarkScoreType::MMLU(MMLUScore::new(categories))];
+        let merge = ScoreWriteOptions { merge: true, ..Default::default() };
+        let model_name = format!("merge-test-{}", Uuid::new_v4());
+        let now = Utc::now();
+
+        let (model_variant_id, _) = write_variant_scores::<AppError>(
+            &mut tx,
+            &model_name,
+            "Q4_K_M",
+            "",
+            &upload(vec![category("biology", 60.0), category("law", 40.0)]),
+            now,
+            merge,
+        )
+        .await
+        .unwrap();
+
+        // An older merge still updates its categories and adds new ones
+        let (merged_id, written) = write_variant_scores::<AppError>(
+            &mut tx,
+            &model_name,
+            "Q4_K_M",
+            "",
+            &upload(vec![category("law", 55.0), category("physics", 70.0)]),
+            now - chrono::Duration::days(1),
+            merge,
+        )
+        .await
+        .unwrap();
+        assert_eq!(merged_id, model_variant_id);
+        assert_eq!(written, 2);
+
+        let scores: Vec<(String, f64)> = sqlx::query_as(
+            "SELECT category, score FROM mmlu_scores_v2 WHERE model_variant_id = $1 ORDER BY category",
+        )
+        .bind(model_variant_id)
+        .fetch_all(&mut *tx)
+        .await
+        .unwrap();
+        assert_eq!(
+            scores,
+            vec![
+                ("biology".to_string(), 60.0),
+                ("law".to_string(), 55.0),
+                ("physics".to_string(), 70.0),
+            ]
+        );
+    }
+}
@@ -2,69 +2,188 @@
 // Comparison related handlers
 
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
+use chrono::{Duration, Utc};
 use uuid::Uuid;
-use std::collections::HashMap;
 
 use llm_benchmark_types::{
-    ComparisonRequest, ComparisonData, ErrorResponse
+    build_perf_map, mmlu_categories, metric_names, normalize_quantization, ComparisonRequest,
+    ComparisonData, ComparisonPermalinkRequest, ComparisonPermalinkResponse, ConfigSummary,
+    VariantComparisonRequest, variant_display_name
 };
 
 use crate::{
+    error::{AppError, DbContext},
     models::PerformanceMetricQueryResult,
+    permalink::PermalinkError,
     AppState
 };
 
+const DEFAULT_PERMALINK_DAYS: i64 = 90;
+const MAX_PERMALINK_DAYS: i64 = 365;
+
 /// Compare two configurations
 pub async fn get_comparison(
     Query(params): Query<ComparisonRequest>,
     State(state): State<AppState>,
-) -> Result<Json<ComparisonData>, (StatusCode, Json<ErrorResponse>)> {
-    let uuid_a = params.config_a;
-    let uuid_b = params.config_b;
+) -> Result<Json<ComparisonData>, AppError> {
+    Ok(Json(build_comparison(&state.db, params.config_a, params.config_b).await?))
+}
 
-    // Get test run data for both configs using UUIDs
-    let config_a_data = get_config_data_by_uuid(&state.db, &uuid_a).await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Error fetching config A: {}", e))),
+/// Issue a signed, expiring token for sharing a comparison
+pub async fn comparison_permalink(
+    Query(params): Query<ComparisonPermalinkRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ComparisonPermalinkResponse>, AppError> {
+    let days = params.expires_in_days.unwrap_or(DEFAULT_PERMALINK_DAYS);
+    if !(1..=MAX_PERMALINK_DAYS).contains(&days) {
+        return Err(AppError::Validation(format!(
+            "expires_in_days must be between 1 and {}",
+            MAX_PERMALINK_DAYS
+        )));
+    }
+
+    // Don't hand out links to comparisons that can't be served
+    for config in [params.config_a, params.config_b] {
+        let exists = sqlx::query_scalar!(
+            "SELECT EXISTS(SELECT 1 FROM test_runs WHERE id = $1 AND status = 'completed')",
+            config
+        )
+        .fetch_one(&state.db)
+        .await
+        .db_context("Error checking configuration")?;
+        if exists != Some(true) {
+            return Err(AppError::NotFound(format!("Configuration {} not found", config)));
+        }
+    }
+
+    let expires_at = Utc::now() + Duration::days(days);
+    let token = state.permalinks.sign(params.config_a, params.config_b, expires_at);
+
+    Ok(Json(ComparisonPermalinkResponse {
+        url: format!("/api/comparison/by-token/{}", token),
+        token,
+        expires_at,
+    }))
+}
+
+/// Compare the two configurations encoded in a permalink token
+pub async fn get_comparison_by_token(
+    Path(token): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<ComparisonData>, AppError> {
+    let (config_a, config_b) = state.permalinks.verify(&token, Utc::now()).map_err(|e| match e {
+        PermalinkError::Invalid => AppError::BadRequest("Invalid comparison token".to_string()),
+        PermalinkError::Expired(at) => {
+            AppError::Gone(format!("Comparison link expired at {}", at.to_rfc3339()))
+        }
+    })?;
+
+    Ok(Json(build_comparison(&state.db, config_a, config_b).await?))
+}
+
+/// Compare two model variants by their benchmark scores, independent of
+/// test runs. Each side's performance comes from the variant's fastest
+/// completed run; a variant without runs has zeroed performance and an
+/// empty backend and hardware. 404 if either variant doesn't exist.
+pub async fn get_comparison_by_variant(
+    Query(params): Query<VariantComparisonRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ComparisonData>, AppError> {
+    let variant_a = Variant::new(&params.a_model, &params.a_quant, params.a_lora.as_deref());
+    let variant_b = Variant::new(&params.b_model, &params.b_quant, params.b_lora.as_deref());
+
+    for variant in [&variant_a, &variant_b] {
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM model_variants
+                WHERE model_name = $1 AND quantization = $2 AND lora_adapter = $3
             )
-        })?;
+            "#,
+            variant.model_name,
+            variant.quantization,
+            variant.lora_adapter
+        )
+        .fetch_one(&state.db)
+        .await
+        .db_context("Error checking model variant")?;
+        if exists != Some(true) {
+            return Err(AppError::NotFound(format!(
+                "No model variant {}",
+                variant_display_name(&variant.model_name, &variant.quantization, &variant.lora_adapter)
+            )));
+        }
+    }
+
+    let config_a = get_config_data_by_variant(&state.db, variant_a).await
+        .db_context("Error fetching variant A")?;
+    let config_b = get_config_data_by_variant(&state.db, variant_b).await
+        .db_context("Error fetching variant B")?;
+
+    let categories = get_category_comparison(&state.db, &config_a, &config_b).await
+        .db_context("Error fetching categories")?;
+
+    Ok(Json(ComparisonData {
+        config_a,
+        config_b,
+        categories,
+    }))
+}
+
+/// A model variant as keyed in `model_variants`
+struct Variant {
+    model_name: String,
+    quantization: String,
+    lora_adapter: String,
+}
+
+impl Variant {
+    fn new(model_name: &str, quantization: &str, lora_adapter: Option<&str>) -> Self {
+        Self {
+            model_name: model_name.to_string(),
+            quantization: normalize_quantization(quantization),
+            lora_adapter: lora_adapter.unwrap_or("").to_string(),
+        }
+    }
+}
+
+/// The test run a configuration summary takes its performance from
+struct SummaryRun {
+    id: Uuid,
+    backend: String,
+    hardware: String,
+}
+
+async fn build_comparison(
+    db: &sqlx::PgPool,
+    uuid_a: Uuid,
+    uuid_b: Uuid,
+) -> Result<ComparisonData, AppError> {
+    // Get test run data for both configs using UUIDs
+    let config_a_data = get_config_data_by_uuid(db, &uuid_a).await
+        .db_context("Error fetching config A")?;
     
-    let config_b_data = get_config_data_by_uuid(&state.db, &uuid_b).await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Error fetching config B: {}", e))),
-            )
-        })?;
+    let config_b_data = get_config_data_by_uuid(db, &uuid_b).await
+        .db_context("Error fetching config B")?;
 
     // Get category comparison
-    let categories = get_category_comparison(&state.db, &uuid_a, &uuid_b).await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Error fetching categories: {}", e))),
-            )
-        })?;
+    let categories = get_category_comparison(db, &config_a_data, &config_b_data).await
+        .db_context("Error fetching categories")?;
 
-    let comparison = ComparisonData {
+    Ok(ComparisonData {
         config_a: config_a_data,
         config_b: config_b_data,
         categories,
-    };
-
-    Ok(Json(comparison))
+    })
 }
 
 async fn get_config_data_by_uuid(
     db: &sqlx::PgPool,
     test_run_id: &Uuid,
-) -> Result<llm_benchmark_types::ConfigSummary, sqlx::Error> {
+) -> Result<ConfigSummary, sqlx::Error> {
     // Get test run data by UUID
     let test_run = sqlx::query!(
         r#"
@@ -72,6 +191,7 @@ async fn get_config_data_by_uuid(
             tr.id as test_run_id, 
             tr.model_name, 
             tr.quantization, 
+            tr.lora_adapter,
             tr.backend,
             tr.backend_version,
             hp.gpu_model, 
@@ -85,23 +205,79 @@ async fn get_config_data_by_uuid(
     .fetch_one(db)
     .await?;
 
-    // Get performance metrics
-    let performance_metrics = sqlx::query_as!(
-        PerformanceMetricQueryResult,
+    let variant = Variant {
+        model_name: test_run.model_name,
+        quantization: test_run.quantization,
+        lora_adapter: test_run.lora_adapter,
+    };
+    let run = SummaryRun {
+        id: test_run.test_run_id,
+        backend: test_run.backend,
+        hardware: format!("{}/{}", test_run.gpu_model, test_run.cpu_arch),
+    };
+
+    config_summary(db, variant, Some(run)).await
+}
+
+/// Summary of `variant` with the performance of its fastest completed run
+async fn get_config_data_by_variant(
+    db: &sqlx::PgPool,
+    variant: Variant,
+) -> Result<ConfigSummary, sqlx::Error> {
+    let run = sqlx::query!(
         r#"
-        SELECT metric_name, value, unit
-        FROM performance_metrics
-        WHERE test_run_id = $1
+        SELECT tr.id, tr.backend, hp.gpu_model, hp.cpu_arch
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        LEFT JOIN performance_metrics pm ON pm.test_run_id = tr.id
+            AND pm.metric_name = 'tokens_per_second'
+        WHERE tr.model_name = $1 AND tr.quantization = $2 AND tr.lora_adapter = $3
+          AND tr.status = 'completed'
+        ORDER BY pm.value DESC NULLS LAST, tr.timestamp DESC
+        LIMIT 1
         "#,
-        test_run.test_run_id
+        variant.model_name,
+        variant.quantization,
+        variant.lora_adapter
     )
-    .fetch_all(db)
-    .await?;
+    .fetch_optional(db)
+    .await?
+    .map(|row| SummaryRun {
+        id: row.id,
+        backend: row.backend,
+        hardware: format!("{}/{}", row.gpu_model, row.cpu_arch),
+    });
 
-    let perf_map: HashMap<String, f64> = performance_metrics
-        .into_iter()
-        .map(|row| (row.metric_name, row.value))
-        .collect();
+    config_summary(db, variant, run).await
+}
+
+/// Summary of `variant`, with performance metrics from `run` if given
+async fn config_summary(
+    db: &sqlx::PgPool,
+    variant: Variant,
+    run: Option<SummaryRun>,
+) -> Result<ConfigSummary, sqlx::Error> {
+    // Get performance metrics
+    let performance_metrics = match &run {
+        Some(run) => sqlx::query_as!(
+            PerformanceMetricQueryResult,
+            r#"
+            SELECT metric_name, value, unit
+            FROM performance_metrics
+            WHERE test_run_id = $1
+            "#,
+            run.id
+        )
+        .fetch_all(db)
+        .await?,
+        None => Vec::new(),
+    };
+
+    let perf_map = build_perf_map(
+        performance_metrics
+            .into_iter()
+            .map(|row| (row.metric_name, row.value)),
+    );
 
     // Get overall score from v2 benchmark scores
     let overall_score = sqlx::query!(
@@ -109,57 +285,52 @@ async fn get_config_data_by_uuid(
         SELECT AVG(ms.score) as avg_score
         FROM mmlu_scores_v2 ms
         JOIN model_variants mv ON ms.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         "#,
-        test_run.model_name,
-        test_run.quantization
+        variant.model_name,
+        variant.quantization,
+        variant.lora_adapter
     )
     .fetch_one(db)
     .await
     .map(|row| row.avg_score.unwrap_or(0.0))
     .unwrap_or(0.0);
 
-    let config_summary = llm_benchmark_types::ConfigSummary {
-        name: format!("{} {}", test_run.model_name, test_run.quantization),
-        model: test_run.model_name,
-        quantization: test_run.quantization,
-        backend: test_run.backend,
-        hardware: format!("{}/{}", test_run.gpu_model, test_run.cpu_arch),
+    let (backend, hardware) = run
+        .map(|run| (run.backend, run.hardware))
+        .unwrap_or_default();
+
+    let config_summary = ConfigSummary {
+        name: variant_display_name(&variant.model_name, &variant.quantization, &variant.lora_adapter),
+        model: variant.model_name,
+        quantization: variant.quantization,
+        lora_adapter: variant.lora_adapter,
+        backend,
+        hardware,
         overall_score,
         performance: llm_benchmark_types::PerformanceSummary {
             speed: perf_map.get("tokens_per_second").copied().unwrap_or(0.0),
             memory: perf_map.get("memory_usage_gb").copied().unwrap_or(0.0),
             loading_time: perf_map.get("model_loading_time").copied().unwrap_or(5.0),
             prompt_speed: perf_map.get("prompt_processing_speed").copied().unwrap_or(0.0),
+            first_token_latency_ms: perf_map.get(metric_names::FIRST_TOKEN_LATENCY_MS).copied(),
+            gpu_utilization_percent: perf_map.get(metric_names::GPU_UTILIZATION_PERCENT).copied(),
+            gpu_vram_used_gb: perf_map.get(metric_names::GPU_VRAM_USED_GB).copied(),
         },
     };
 
     Ok(config_summary)
 }
 
+/// Benchmark scores of the model variants of `config_a` and `config_b`,
+/// side by side per category
 async fn get_category_comparison(
     db: &sqlx::PgPool,
-    run_a_id: &Uuid,
-    run_b_id: &Uuid,
+    config_a: &ConfigSummary,
+    config_b: &ConfigSummary,
 ) -> Result<Vec<llm_benchmark_types::CategoryComparison>, sqlx::Error> {
     use std::collections::HashMap;
     
-    // Get model variants for both test runs
-    let run_a = sqlx::query!(
-        "SELECT model_name, quantization FROM test_runs WHERE id = $1",
-        run_a_id
-    )
-    .fetch_one(db)
-    .await?;
-    
-    let run_b = sqlx::query!(
-        "SELECT model_name, quantization FROM test_runs WHERE id = $1",
-        run_b_id
-    )
-    .fetch_one(db)
-    .await?;
-    
-    
     // Build a map of category names to scores for easier comparison
     let mut scores_map: HashMap<String, (Option<f64>, Option<f64>)> = HashMap::new();
     
@@ -169,11 +340,12 @@ async fn get_category_comparison(
         SELECT ms.category, ms.score
         FROM mmlu_scores_v2 ms
         JOIN model_variants mv ON ms.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         ORDER BY ms.category
         "#,
-        run_a.model_name,
-        run_a.quantization
+        config_a.model,
+        config_a.quantization,
+        config_a.lora_adapter
     )
     .fetch_all(db)
     .await?;
@@ -190,11 +362,12 @@ async fn get_category_comparison(
         SELECT ms.category, ms.score
         FROM mmlu_scores_v2 ms
         JOIN model_variants mv ON ms.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         ORDER BY ms.category
         "#,
-        run_b.model_name,
-        run_b.quantization
+        config_b.model,
+        config_b.quantization,
+        config_b.lora_adapter
     )
     .fetch_all(db)
     .await?;
@@ -212,11 +385,12 @@ async fn get_category_comparison(
         SELECT gs.accuracy
         FROM gsm8k_scores_v2 gs
         JOIN model_variants mv ON gs.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
-        run_a.model_name,
-        run_a.quantization
+        config_a.model,
+        config_a.quantization,
+        config_a.lora_adapter
     ).fetch_optional(db).await {
         if let Some(row) = gsm8k_a {
             scores_map.insert("GSM8K".to_string(), (Some(row.accuracy * 100.0), None));
@@ -228,17 +402,26 @@ async fn get_category_comparison(
         SELECT gs.accuracy
         FROM gsm8k_scores_v2 gs
         JOIN model_variants mv ON gs.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
-        run_b.model_name,
-        run_b.quantization
+        config_b.model,
+        config_b.quantization,
+        config_b.lora_adapter
     ).fetch_optional(db).await {
         if let Some(row) = gsm8k_b {
             let entry = scores_map.entry("GSM8K".to_string()).or_insert((None, None));
             entry.1 = Some(row.accuracy * 100.0);
         }
     }
+
+    // HumanEval, HellaSwag, TruthfulQA, GPQA and generic benchmarks
+    for (name, score) in single_score_benchmarks(db, &config_a.model, &config_a.quantization, &config_a.lora_adapter).await? {
+        scores_map.entry(name).or_insert((None, None)).0 = Some(score);
+    }
+    for (name, score) in single_score_benchmarks(db, &config_b.model, &config_b.quantization, &config_b.lora_adapter).await? {
+        scores_map.entry(name).or_insert((None, None)).1 = Some(score);
+    }
     
     // Convert map to comparison vector
     let mut comparisons: Vec<llm_benchmark_types::CategoryComparison> = scores_map
@@ -252,8 +435,59 @@ async fn get_category_comparison(
         })
         .collect();
     
-    // Sort by name for consistent ordering
-    comparisons.sort_by(|a, b| a.name.cmp(&b.name));
+    // Sort by name for consistent ordering, keeping MMLU categories in canonical order
+    comparisons.sort_by(|a, b| {
+        match (a.name.strip_prefix("MMLU - "), b.name.strip_prefix("MMLU - ")) {
+            (Some(cat_a), Some(cat_b)) => mmlu_categories::compare(cat_a, cat_b),
+            _ => a.name.cmp(&b.name),
+        }
+    });
     
     Ok(comparisons)
+}
+
+/// The model variant's HumanEval pass@1, HellaSwag, TruthfulQA and GPQA
+/// scores, plus generic benchmarks under their own names, named as they
+/// appear in a comparison
+async fn single_score_benchmarks(
+    db: &sqlx::PgPool,
+    model_name: &str,
+    quantization: &str,
+    lora_adapter: &str,
+) -> Result<Vec<(String, f64)>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT 'HumanEval pass@1' as "name!", hs.pass_at_1 as "score!"
+        FROM humaneval_scores_v2 hs
+        JOIN model_variants mv ON hs.model_variant_id = mv.id
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+        UNION ALL
+        SELECT 'HellaSwag', hs.accuracy
+        FROM hellaswag_scores_v2 hs
+        JOIN model_variants mv ON hs.model_variant_id = mv.id
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+        UNION ALL
+        SELECT 'TruthfulQA', ts.truthful_score
+        FROM truthfulqa_scores_v2 ts
+        JOIN model_variants mv ON ts.model_variant_id = mv.id
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+        UNION ALL
+        SELECT 'GPQA', gs.accuracy
+        FROM gpqa_scores_v2 gs
+        JOIN model_variants mv ON gs.model_variant_id = mv.id
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+        UNION ALL
+        SELECT gs.benchmark_name, gs.overall_score
+        FROM generic_benchmark_scores_v2 gs
+        JOIN model_variants mv ON gs.model_variant_id = mv.id
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+        "#,
+        model_name,
+        quantization,
+        lora_adapter
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.name, row.score)).collect())
 }
\ No newline at end of file
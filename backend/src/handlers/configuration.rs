@@ -2,54 +2,126 @@
 // Configuration listing and detail handlers
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
 };
 use uuid::Uuid;
-use std::collections::HashMap;
 
 use llm_benchmark_types::{
-    DetailData, ErrorResponse, ExperimentSummary, ConfigurationListResponse
+    build_perf_map, mmlu_categories, metric_names, speed_samples_from_context, DetailData,
+    ExperimentSummary, ConfigurationCursor, ConfigurationListRequest, ConfigurationListResponse,
+    MetricVarianceResponse, PerformanceMetric, RunAnnotation, SampleStatistics, variant_display_name,
 };
 
 use crate::{
+    error::{AppError, DbContext},
+    etag::{ETagJson, IfNoneMatch},
     models::PerformanceMetricQueryResult,
     AppState
 };
 
-/// Get list of available configurations
+const DEFAULT_CONFIGURATIONS_LIMIT: i64 = 50;
+const MAX_CONFIGURATIONS_LIMIT: i64 = 500;
+
+/// Get list of available configurations, newest first.
+///
+/// Returns `limit` configurations (default 50, capped at 500) after
+/// skipping `offset`. Passing `after=<next_cursor>` instead of an offset
+/// keeps pages stable while new runs are uploaded. `total_count` counts
+/// every matching run, not just the page. `label` restricts the list to one campaign's runs;
+/// `canonical=true` keeps only canonical runs and `exclude_deprecated=true`
+/// drops deprecated ones.
 pub async fn get_configurations(
+    Query(params): Query<ConfigurationListRequest>,
+    if_none_match: IfNoneMatch,
     State(state): State<AppState>,
-) -> Result<Json<ConfigurationListResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let experiments = sqlx::query!(
+) -> Result<ETagJson<ConfigurationListResponse>, AppError> {
+    let cursor = params
+        .after
+        .as_deref()
+        .map(str::parse::<ConfigurationCursor>)
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_CONFIGURATIONS_LIMIT)
+        .clamp(1, MAX_CONFIGURATIONS_LIMIT);
+    let offset = params.offset.unwrap_or(0);
+    if offset < 0 {
+        return Err(AppError::BadRequest("offset must not be negative".to_string()));
+    }
+
+    let total_count = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as "count!"
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        LEFT JOIN test_run_annotations ra ON ra.test_run_id = tr.id
+        WHERE tr.status = 'completed'
+          AND ($1::TEXT IS NULL OR tr.label = $1)
+          AND ($2::BOOL IS NOT TRUE OR ra.canonical IS TRUE)
+          AND ($3::BOOL IS NOT TRUE OR ra.deprecated IS NOT TRUE)
+        "#,
+        params.label,
+        params.canonical,
+        params.exclude_deprecated
+    )
+    .fetch_one(&state.db)
+    .await?
+    .count as usize;
+
+    // Fetch one extra row to find out whether another page follows
+    let mut experiments = sqlx::query!(
         r#"
         SELECT 
             tr.id,
             tr.model_name,
             tr.quantization,
+            tr.lora_adapter,
             tr.backend,
             CONCAT(hp.gpu_model, ' / ', hp.cpu_arch) as hardware_summary,
             NULL as overall_score,
             tr.timestamp,
-            tr.status
+            tr.status,
+            ra.canonical as "canonical?",
+            ra.deprecated as "deprecated?",
+            ra.reason
         FROM test_runs tr
         JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        LEFT JOIN test_run_annotations ra ON ra.test_run_id = tr.id
         -- Benchmark scores now handled separately
         WHERE tr.status = 'completed'
-        GROUP BY tr.id, tr.model_name, tr.quantization, tr.backend, 
-                 hp.gpu_model, hp.cpu_arch, tr.timestamp, tr.status
-        ORDER BY tr.timestamp DESC
-        "#
+          -- Runs without a timestamp sort last, so they follow every
+          -- timestamped cursor and only each other after a null one
+          AND ($2::UUID IS NULL
+               OR ($1::TIMESTAMPTZ IS NULL AND tr.timestamp IS NULL AND tr.id < $2)
+               OR ($1 IS NOT NULL AND (tr.timestamp IS NULL OR (tr.timestamp, tr.id) < ($1, $2))))
+          AND ($4::TEXT IS NULL OR tr.label = $4)
+          AND ($5::BOOL IS NOT TRUE OR ra.canonical IS TRUE)
+          AND ($6::BOOL IS NOT TRUE OR ra.deprecated IS NOT TRUE)
+        GROUP BY tr.id, tr.model_name, tr.quantization, tr.lora_adapter, tr.backend, 
+                 hp.gpu_model, hp.cpu_arch, tr.timestamp, tr.status,
+                 ra.canonical, ra.deprecated, ra.reason
+        ORDER BY tr.timestamp DESC NULLS LAST, tr.id DESC
+        LIMIT $3 OFFSET $7
+        "#,
+        cursor.and_then(|c| c.timestamp),
+        cursor.map(|c| c.id),
+        limit + 1,
+        params.label,
+        params.canonical,
+        params.exclude_deprecated,
+        offset
     )
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Database error: {}", e))),
-        )
-    })?;
+    .await?;
+
+    let has_more = experiments.len() as i64 > limit;
+    experiments.truncate(limit as usize);
+    let next_cursor = experiments
+        .last()
+        .filter(|_| has_more)
+        .map(|row| ConfigurationCursor { timestamp: row.timestamp, id: row.id }.to_string());
 
     let mut configurations = Vec::new();
     for row in experiments {
@@ -59,16 +131,26 @@ pub async fn get_configurations(
             SELECT AVG(ms.score) as avg_score
             FROM mmlu_scores_v2 ms
             JOIN model_variants mv ON ms.model_variant_id = mv.id
-            WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+            WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
             "#,
             row.model_name,
-            row.quantization
+            row.quantization,
+            row.lora_adapter
         )
         .fetch_one(&state.db)
         .await
         .map(|r| r.avg_score)
         .ok()
         .flatten();
+
+        let annotation = match (row.canonical, row.deprecated) {
+            (Some(canonical), Some(deprecated)) => Some(RunAnnotation {
+                canonical,
+                deprecated,
+                reason: row.reason,
+            }),
+            _ => None,
+        };
         
         configurations.push(ExperimentSummary {
             id: row.id,
@@ -86,14 +168,14 @@ pub async fn get_configurations(
                 "cancelled" => llm_benchmark_types::ExperimentStatus::Cancelled,
                 _ => llm_benchmark_types::ExperimentStatus::Completed,
             },
+            annotation,
         });
     }
 
-    let total_count = configurations.len();
-
-    Ok(Json(ConfigurationListResponse {
+    Ok(if_none_match.json(ConfigurationListResponse {
         configurations,
         total_count,
+        next_cursor,
     }))
 }
 
@@ -101,24 +183,14 @@ pub async fn get_configurations(
 pub async fn get_detail(
     Path(test_run_id): Path<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<DetailData>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<DetailData>, AppError> {
     // Get detailed config data
     let (config_detail, system_info) = get_detailed_config_data(&state.db, &test_run_id).await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Error fetching config details: {}", e))),
-            )
-        })?;
+        .db_context("Error fetching config details")?;
 
     // Get category scores
     let categories = get_category_scores(&state.db, &test_run_id).await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Error fetching categories: {}", e))),
-            )
-        })?;
+        .db_context("Error fetching categories")?;
 
     let detail_data = DetailData {
         config: config_detail,
@@ -129,6 +201,88 @@ pub async fn get_detail(
     Ok(Json(detail_data))
 }
 
+/// Get run-to-run variance of a test run's generation speed from its raw samples
+pub async fn get_detail_variance(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<MetricVarianceResponse>, AppError> {
+    let metric = sqlx::query!(
+        r#"
+        SELECT metric_name, value, unit, context
+        FROM performance_metrics
+        WHERE test_run_id = $1 AND metric_name = $2
+        ORDER BY created_at DESC
+        LIMIT 1
+        "#,
+        test_run_id,
+        metric_names::TOKENS_PER_SECOND
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| {
+        AppError::NotFound(format!(
+            "No {} metric found for test run {}",
+            metric_names::TOKENS_PER_SECOND,
+            test_run_id
+        ))
+    })?;
+
+    let statistics = metric
+        .context
+        .as_ref()
+        .and_then(speed_samples_from_context)
+        .and_then(|samples| SampleStatistics::from_samples(&samples))
+        .ok_or_else(|| AppError::NotFound(format!("Test run {} has no speed samples recorded", test_run_id)))?;
+
+    Ok(Json(MetricVarianceResponse {
+        test_run_id,
+        metric_name: metric.metric_name,
+        unit: metric.unit,
+        reported_value: metric.value,
+        statistics,
+    }))
+}
+
+/// Every performance metric stored for a test run, including ones the
+/// detail view doesn't summarize, oldest first. 404 if the run doesn't exist.
+pub async fn get_detail_metrics(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<PerformanceMetric>>, AppError> {
+    let exists = sqlx::query_scalar!("SELECT id FROM test_runs WHERE id = $1", test_run_id)
+        .fetch_optional(&state.db)
+        .await
+        .db_context("Failed to look up test run")?;
+    if exists.is_none() {
+        return Err(AppError::NotFound(format!("Test run {} not found", test_run_id)));
+    }
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT metric_name, value, unit, context, COALESCE(created_at, NOW()) as "created_at!"
+        FROM performance_metrics
+        WHERE test_run_id = $1
+        ORDER BY created_at, id
+        "#,
+        test_run_id
+    )
+    .fetch_all(&state.db)
+    .await
+    .db_context("Failed to fetch performance metrics")?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| PerformanceMetric {
+                metric_name: row.metric_name,
+                value: row.value,
+                unit: row.unit,
+                timestamp: row.created_at,
+                context: row.context,
+            })
+            .collect(),
+    ))
+}
+
 async fn get_detailed_config_data(
     db: &sqlx::PgPool,
     test_run_id: &Uuid,
@@ -140,15 +294,22 @@ async fn get_detailed_config_data(
             tr.id as test_run_id,
             tr.model_name,
             tr.quantization,
+            tr.lora_adapter,
             tr.backend,
             tr.backend_version,
+            tr.model_size_bytes,
+            tr.model_n_params,
             tr.timestamp,
             hp.gpu_model,
             hp.gpu_memory_gb,
+            hp.gpu_count,
+            hp.total_gpu_memory_gb,
             hp.cpu_model,
             hp.cpu_arch,
             hp.ram_gb,
             hp.ram_type,
+            hp.cpu_threads,
+            hp.numa_nodes,
             hp.virtualization_type,
             hp.optimizations
         FROM test_runs tr
@@ -173,10 +334,11 @@ async fn get_detailed_config_data(
     .fetch_all(db)
     .await?;
 
-    let perf_map: HashMap<String, f64> = performance_metrics
-        .into_iter()
-        .map(|row| (row.metric_name, row.value))
-        .collect();
+    let perf_map = build_perf_map(
+        performance_metrics
+            .into_iter()
+            .map(|row| (row.metric_name, row.value)),
+    );
 
     // Get overall score from v2 benchmark scores
     let overall_score = sqlx::query!(
@@ -184,10 +346,11 @@ async fn get_detailed_config_data(
         SELECT AVG(ms.score) as avg_score
         FROM mmlu_scores_v2 ms
         JOIN model_variants mv ON ms.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         "#,
         result.model_name,
-        result.quantization
+        result.quantization,
+        result.lora_adapter
     )
     .fetch_one(db)
     .await
@@ -195,17 +358,23 @@ async fn get_detailed_config_data(
     .unwrap_or(0.0);
 
     let config_detail = llm_benchmark_types::ConfigDetail {
-        name: format!("{} {}", result.model_name, result.quantization),
+        name: variant_display_name(&result.model_name, &result.quantization, &result.lora_adapter),
         model: result.model_name,
         quantization: result.quantization,
+        lora_adapter: result.lora_adapter,
         backend: result.backend,
         backend_version: result.backend_version,
+        model_size_bytes: result.model_size_bytes,
+        model_n_params: result.model_n_params,
         overall_score,
         performance: llm_benchmark_types::PerformanceSummary {
             speed: perf_map.get("tokens_per_second").copied().unwrap_or(0.0),
             memory: perf_map.get("memory_usage_gb").copied().unwrap_or(0.0),
             loading_time: perf_map.get("model_loading_time").copied().unwrap_or(5.0),
             prompt_speed: perf_map.get("prompt_processing_speed").copied().unwrap_or(0.0),
+            first_token_latency_ms: perf_map.get(metric_names::FIRST_TOKEN_LATENCY_MS).copied(),
+            gpu_utilization_percent: perf_map.get(metric_names::GPU_UTILIZATION_PERCENT).copied(),
+            gpu_vram_used_gb: perf_map.get(metric_names::GPU_VRAM_USED_GB).copied(),
         },
         test_run_date: result.timestamp.unwrap_or_else(|| chrono::Utc::now()).format("%Y-%m-%d %H:%M:%S UTC").to_string(),
     };
@@ -213,10 +382,14 @@ async fn get_detailed_config_data(
     let system_info = llm_benchmark_types::SystemInfo {
         gpu_model: result.gpu_model,
         gpu_memory_gb: result.gpu_memory_gb,
+        gpu_count: result.gpu_count,
+        total_gpu_memory_gb: result.total_gpu_memory_gb,
         cpu_model: result.cpu_model,
         cpu_arch: result.cpu_arch,
         ram_gb: result.ram_gb.unwrap_or(0),
         ram_type: result.ram_type.unwrap_or_else(|| "Unknown".to_string()),
+        cpu_threads: result.cpu_threads,
+        numa_nodes: result.numa_nodes,
         virtualization_type: result.virtualization_type,
         optimizations: result.optimizations.unwrap_or_default(),
     };
@@ -233,7 +406,7 @@ async fn get_category_scores(
     // First, get model variant info from test run
     let variant_info = sqlx::query!(
         r#"
-        SELECT tr.model_name, tr.quantization
+        SELECT tr.model_name, tr.quantization, tr.lora_adapter
         FROM test_runs tr
         WHERE tr.id = $1
         "#,
@@ -248,15 +421,19 @@ async fn get_category_scores(
         SELECT ms.category, ms.score, ms.total_questions, ms.correct_answers
         FROM mmlu_scores_v2 ms
         JOIN model_variants mv ON ms.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         ORDER BY ms.category
         "#,
         variant_info.model_name,
-        variant_info.quantization
+        variant_info.quantization,
+        variant_info.lora_adapter
     )
     .fetch_all(db)
     .await?;
     
+    let mut mmlu_scores = mmlu_scores;
+    mmlu_scores.sort_by(|a, b| mmlu_categories::compare(&a.category, &b.category));
+    
     for row in mmlu_scores {
         categories.push(llm_benchmark_types::CategoryScore {
             name: format!("MMLU - {}", row.category),
@@ -272,11 +449,12 @@ async fn get_category_scores(
         SELECT gs.accuracy, gs.problems_solved, gs.total_problems
         FROM gsm8k_scores_v2 gs
         JOIN model_variants mv ON gs.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
         variant_info.model_name,
-        variant_info.quantization
+        variant_info.quantization,
+        variant_info.lora_adapter
     )
     .fetch_optional(db)
     .await?;
@@ -296,11 +474,12 @@ async fn get_category_scores(
         SELECT hs.pass_at_1
         FROM humaneval_scores_v2 hs
         JOIN model_variants mv ON hs.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
         variant_info.model_name,
-        variant_info.quantization
+        variant_info.quantization,
+        variant_info.lora_adapter
     )
     .fetch_optional(db)
     .await?;
@@ -320,11 +499,12 @@ async fn get_category_scores(
         SELECT hs.accuracy, hs.total_questions, hs.correct_answers
         FROM hellaswag_scores_v2 hs
         JOIN model_variants mv ON hs.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
         variant_info.model_name,
-        variant_info.quantization
+        variant_info.quantization,
+        variant_info.lora_adapter
     )
     .fetch_optional(db)
     .await?;
@@ -344,11 +524,12 @@ async fn get_category_scores(
         SELECT ts.truthful_score, ts.total_questions
         FROM truthfulqa_scores_v2 ts
         JOIN model_variants mv ON ts.model_variant_id = mv.id
-        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = ''
+        WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
         LIMIT 1
         "#,
         variant_info.model_name,
-        variant_info.quantization
+        variant_info.quantization,
+        variant_info.lora_adapter
     )
     .fetch_optional(db)
     .await?;
@@ -6,10 +6,19 @@ use axum::{
     http::StatusCode,
     response::Json,
 };
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::AppState;
+use llm_benchmark_types::normalize_quantization;
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::{AppError, DbContext},
+    handlers::merge_variants::SCORE_TABLES,
+    AppState,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteResponse {
@@ -22,207 +31,168 @@ pub struct DeleteResponse {
 pub async fn delete_test_run(
     Path(test_run_id): Path<Uuid>,
     State(state): State<AppState>,
-) -> Result<Json<DeleteResponse>, (StatusCode, Json<DeleteResponse>)> {
-    // Start a transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Failed to start transaction: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
+) -> Result<Json<DeleteResponse>, AppError> {
+    let deleted_rows = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &test_run_id, |tx, test_run_id| {
+        Box::pin(async move {
+            ensure_test_run_exists(tx, test_run_id).await?;
+            delete_test_run_rows(tx, test_run_id).await
+        })
+    })
+    .await?;
 
-    // Check if the test run exists
+    Ok(Json(DeleteResponse {
+        success: true,
+        message: format!(
+            "Successfully deleted test run {}, clearing {}",
+            test_run_id,
+            cleared_tables(&deleted_rows)
+        ),
+        deleted_id: Some(test_run_id),
+    }))
+}
+
+/// Abort the transaction with a 404 if the test run doesn't exist
+async fn ensure_test_run_exists(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    test_run_id: &Uuid,
+) -> Result<(), TxError<AppError>> {
     let exists = sqlx::query!(
         "SELECT id FROM test_runs WHERE id = $1",
         test_run_id
     )
-    .fetch_optional(&mut *tx)
+    .fetch_optional(&mut **tx)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Database error: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
+    .tx_context("Database error")?;
 
     if exists.is_none() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Test run {} not found", test_run_id),
-                deleted_id: None,
-            }),
-        ));
+        return Err(TxError::Abort(AppError::NotFound(format!("Test run {} not found", test_run_id))));
     }
 
-    // Delete benchmark scores (they reference test_run_id)
-    // Due to CASCADE, these should be deleted automatically, but let's be explicit
-    sqlx::query!("DELETE FROM mmlu_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    Ok(())
+}
+
+/// Delete a test run's benchmark scores, metrics, log and annotation, then
+/// the run itself, returning the rows deleted from each related table.
+/// CASCADE would remove them too, but deleting them explicitly lets the
+/// response say what went.
+async fn delete_test_run_rows<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    test_run_id: &Uuid,
+) -> Result<Vec<(&'static str, u64)>, TxError<E>> {
+    let mut deleted_rows = Vec::with_capacity(10);
+
+    let rows = sqlx::query!("DELETE FROM mmlu_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete MMLU scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete MMLU scores")?
+        .rows_affected();
+    deleted_rows.push(("mmlu_scores", rows));
 
-    sqlx::query!("DELETE FROM gsm8k_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM gsm8k_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete GSM8K scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete GSM8K scores")?
+        .rows_affected();
+    deleted_rows.push(("gsm8k_scores", rows));
 
-    sqlx::query!("DELETE FROM humaneval_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM humaneval_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete HumanEval scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete HumanEval scores")?
+        .rows_affected();
+    deleted_rows.push(("humaneval_scores", rows));
 
-    sqlx::query!("DELETE FROM hellaswag_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM hellaswag_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete HellaSwag scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete HellaSwag scores")?
+        .rows_affected();
+    deleted_rows.push(("hellaswag_scores", rows));
 
-    sqlx::query!("DELETE FROM truthfulqa_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM truthfulqa_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete TruthfulQA scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete TruthfulQA scores")?
+        .rows_affected();
+    deleted_rows.push(("truthfulqa_scores", rows));
 
-    sqlx::query!("DELETE FROM generic_benchmark_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM gpqa_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete generic benchmark scores: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete GPQA scores")?
+        .rows_affected();
+    deleted_rows.push(("gpqa_scores", rows));
 
-    // Delete performance metrics
-    sqlx::query!("DELETE FROM performance_metrics WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM generic_benchmark_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete performance metrics: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete generic benchmark scores")?
+        .rows_affected();
+    deleted_rows.push(("generic_benchmark_scores", rows));
+
+    let rows = sqlx::query!("DELETE FROM performance_metrics WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to delete performance metrics")?
+        .rows_affected();
+    deleted_rows.push(("performance_metrics", rows));
+
+    let rows = sqlx::query!("DELETE FROM test_run_logs WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to delete log")?
+        .rows_affected();
+    deleted_rows.push(("test_run_logs", rows));
+
+    let rows = sqlx::query!("DELETE FROM test_run_annotations WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to delete annotation")?
+        .rows_affected();
+    deleted_rows.push(("test_run_annotations", rows));
 
     // Finally, delete the test run itself
     sqlx::query!("DELETE FROM test_runs WHERE id = $1", test_run_id)
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete test run: {}", e),
-                    deleted_id: None,
-                }),
-            )
-        })?;
+        .tx_context("Failed to delete test run")?;
 
-    // Commit the transaction
-    tx.commit().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Failed to commit transaction: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
+    Ok(deleted_rows)
+}
 
-    Ok(Json(DeleteResponse {
-        success: true,
-        message: format!("Successfully deleted test run {}", test_run_id),
-        deleted_id: Some(test_run_id),
-    }))
+/// Related tables a deletion removed rows from, e.g.
+/// "performance_metrics (12 rows), gsm8k_scores (1 row)"
+fn cleared_tables(deleted_rows: &[(&str, u64)]) -> String {
+    let cleared: Vec<String> = deleted_rows
+        .iter()
+        .filter(|(_, rows)| *rows > 0)
+        .map(|(table, rows)| format!("{} ({} row{})", table, rows, if *rows == 1 { "" } else { "s" }))
+        .collect();
+    if cleared.is_empty() {
+        "no related rows".to_string()
+    } else {
+        cleared.join(", ")
+    }
 }
 
-/// Delete all test runs for a specific model and quantization
+/// Delete all test runs for a specific model and quantization (matched in
+/// its normalized form, as stored). Responds with 206 Partial Content if
+/// only some of them could be deleted, listing the runs that were in
+/// `deleted_ids` and each failure in `errors`.
 pub async fn delete_by_model_quant(
     State(state): State<AppState>,
     Json(request): Json<DeleteByModelQuantRequest>,
-) -> Result<Json<DeleteMultipleResponse>, (StatusCode, Json<DeleteMultipleResponse>)> {
+) -> Result<(StatusCode, Json<DeleteMultipleResponse>), AppError> {
     // Find all matching test runs
+    let quantization = normalize_quantization(&request.quantization);
     let test_runs = sqlx::query!(
         "SELECT id FROM test_runs WHERE model_name = $1 AND quantization = $2",
         request.model_name,
-        request.quantization
+        quantization
     )
     .fetch_all(&state.db)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteMultipleResponse {
-                success: false,
-                message: format!("Failed to find test runs: {}", e),
-                deleted_count: 0,
-                deleted_ids: vec![],
-            }),
-        )
-    })?;
+    .db_context("Failed to find test runs")?;
 
     let mut deleted_ids = Vec::new();
     let mut errors = Vec::new();
@@ -236,28 +206,40 @@ pub async fn delete_by_model_quant(
     }
 
     if !errors.is_empty() {
-        return Err((
+        return Ok((
             StatusCode::PARTIAL_CONTENT,
             Json(DeleteMultipleResponse {
                 success: false,
-                message: format!("Some deletions failed: {}", errors.join(", ")),
+                message: format!(
+                    "Deleted {} of {} test runs for {}/{}; {} failed",
+                    deleted_ids.len(),
+                    deleted_ids.len() + errors.len(),
+                    request.model_name,
+                    quantization,
+                    errors.len()
+                ),
                 deleted_count: deleted_ids.len(),
                 deleted_ids,
+                errors,
             }),
         ));
     }
 
-    Ok(Json(DeleteMultipleResponse {
-        success: true,
-        message: format!(
-            "Successfully deleted {} test runs for {}/{}",
-            deleted_ids.len(),
-            request.model_name,
-            request.quantization
-        ),
-        deleted_count: deleted_ids.len(),
-        deleted_ids,
-    }))
+    Ok((
+        StatusCode::OK,
+        Json(DeleteMultipleResponse {
+            success: true,
+            message: format!(
+                "Successfully deleted {} test runs for {}/{}",
+                deleted_ids.len(),
+                request.model_name,
+                quantization
+            ),
+            deleted_count: deleted_ids.len(),
+            deleted_ids,
+            errors,
+        }),
+    ))
 }
 
 #[derive(Debug, Deserialize)]
@@ -271,57 +253,155 @@ pub struct DeleteMultipleResponse {
     pub success: bool,
     pub message: String,
     pub deleted_count: usize,
+    /// Test runs deleted, including on partial failure
     pub deleted_ids: Vec<Uuid>,
+    /// One entry per test run that couldn't be deleted, naming it
+    pub errors: Vec<String>,
 }
 
-// Internal helper function to delete a test run
+// Internal helper function to delete a test run. Once the last run of its
+// model, quantization and LoRA adapter is gone, that model variant and its
+// v2 scores are deleted too.
 async fn delete_test_run_internal(db: &sqlx::PgPool, test_run_id: Uuid) -> Result<(), String> {
-    let mut tx = db.begin().await.map_err(|e| e.to_string())?;
-
-    // Delete all related data
-    sqlx::query!("DELETE FROM mmlu_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    sqlx::query!("DELETE FROM gsm8k_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+    with_retry_tx(db, DEFAULT_TX_ATTEMPTS, &test_run_id, |tx, test_run_id| {
+        Box::pin(async move {
+            let run = sqlx::query!(
+                "SELECT model_name, quantization, lora_adapter FROM test_runs WHERE id = $1",
+                test_run_id
+            )
+            .fetch_optional(&mut **tx)
+            .await
+            .tx_context("Failed to look up test run")?;
+
+            delete_test_run_rows::<String>(tx, test_run_id).await?;
+
+            let Some(run) = run else {
+                return Ok(());
+            };
+
+            let orphaned_variant = sqlx::query_scalar!(
+                r#"
+                SELECT mv.id
+                FROM model_variants mv
+                WHERE mv.model_name = $1 AND mv.quantization = $2 AND mv.lora_adapter = $3
+                  AND NOT EXISTS (
+                      SELECT 1 FROM test_runs tr
+                      WHERE tr.model_name = mv.model_name AND tr.quantization = mv.quantization
+                        AND tr.lora_adapter = mv.lora_adapter
+                  )
+                "#,
+                run.model_name,
+                run.quantization,
+                run.lora_adapter
+            )
+            .fetch_optional(&mut **tx)
+            .await
+            .tx_context("Failed to look up model variant")?;
 
-    sqlx::query!("DELETE FROM humaneval_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+            if let Some(model_variant_id) = orphaned_variant {
+                delete_model_variant_rows::<String>(tx, model_variant_id).await?;
+            }
 
-    sqlx::query!("DELETE FROM hellaswag_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+    })
+    .await
+    .map_err(|e| e.into_inner(|message| message))
+}
 
-    sqlx::query!("DELETE FROM truthfulqa_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+/// Delete a model variant with all of its v2 benchmark scores and score
+/// history in one transaction, returning the rows deleted per table
+pub async fn delete_model_variant(
+    State(state): State<AppState>,
+    Json(request): Json<DeleteModelVariantRequest>,
+) -> Result<Json<DeleteModelVariantResponse>, AppError> {
+    let quantization = normalize_quantization(&request.quantization);
+    let lora_adapter = request.lora_adapter.as_deref().unwrap_or("");
+    let target = (request.model_name.as_str(), quantization.as_str(), lora_adapter);
+
+    let (model_variant_id, deleted_rows) = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &target, |tx, target| {
+        Box::pin(async move {
+            let (model_name, quantization, lora_adapter) = *target;
+            let model_variant_id = sqlx::query_scalar!(
+                "SELECT id FROM model_variants WHERE model_name = $1 AND quantization = $2 AND lora_adapter = $3",
+                model_name,
+                quantization,
+                lora_adapter
+            )
+            .fetch_optional(&mut **tx)
+            .await
+            .tx_context("Failed to look up model variant")?
+            .ok_or_else(|| {
+                TxError::Abort(AppError::NotFound(format!(
+                    "Model variant {}/{} not found",
+                    model_name, quantization
+                )))
+            })?;
+
+            let deleted_rows = delete_model_variant_rows(tx, model_variant_id).await?;
+            Ok((model_variant_id, deleted_rows))
+        })
+    })
+    .await?;
+
+    Ok(Json(DeleteModelVariantResponse {
+        success: true,
+        message: format!(
+            "Successfully deleted model variant {}/{}",
+            request.model_name, quantization
+        ),
+        deleted_id: Some(model_variant_id),
+        deleted_rows,
+    }))
+}
 
-    sqlx::query!("DELETE FROM generic_benchmark_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+/// Delete a model variant's scores from every v2 table, its score history
+/// and the variant itself, returning the rows deleted per table
+async fn delete_model_variant_rows<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+) -> Result<BTreeMap<String, u64>, TxError<E>> {
+    let mut deleted_rows = BTreeMap::new();
+
+    let tables = SCORE_TABLES
+        .iter()
+        .map(|&(_, table, _)| table)
+        .chain(["benchmark_score_history"]);
+    for table in tables {
+        let rows = sqlx::query(&format!("DELETE FROM {} WHERE model_variant_id = $1", table))
+            .bind(model_variant_id)
+            .execute(&mut **tx)
+            .await
+            .tx_context(&format!("Failed to delete from {}", table))?
+            .rows_affected();
+        deleted_rows.insert(table.to_string(), rows);
+    }
 
-    sqlx::query!("DELETE FROM performance_metrics WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx)
+    let rows = sqlx::query!("DELETE FROM model_variants WHERE id = $1", model_variant_id)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| e.to_string())?;
+        .tx_context("Failed to delete model variant")?
+        .rows_affected();
+    deleted_rows.insert("model_variants".to_string(), rows);
 
-    sqlx::query!("DELETE FROM test_runs WHERE id = $1", test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| e.to_string())?;
+    Ok(deleted_rows)
+}
 
-    tx.commit().await.map_err(|e| e.to_string())?;
+#[derive(Debug, Deserialize)]
+pub struct DeleteModelVariantRequest {
+    pub model_name: String,
+    pub quantization: String,
+    /// None/empty means the base model
+    pub lora_adapter: Option<String>,
+}
 
-    Ok(())
+#[derive(Debug, Serialize)]
+pub struct DeleteModelVariantResponse {
+    pub success: bool,
+    pub message: String,
+    pub deleted_id: Option<Uuid>,
+    /// Rows deleted per table
+    pub deleted_rows: BTreeMap<String, u64>,
 }
 
 /// Delete specific benchmark scores from a test run
@@ -329,48 +409,7 @@ pub async fn delete_benchmark_scores(
     Path(test_run_id): Path<Uuid>,
     State(state): State<AppState>,
     Json(request): Json<DeleteBenchmarkRequest>,
-) -> Result<Json<DeleteResponse>, (StatusCode, Json<DeleteResponse>)> {
-    // Start a transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Failed to start transaction: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
-
-    // Check if the test run exists
-    let exists = sqlx::query!(
-        "SELECT id FROM test_runs WHERE id = $1",
-        test_run_id
-    )
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Database error: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
-
-    if exists.is_none() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Test run {} not found", test_run_id),
-                deleted_id: None,
-            }),
-        ));
-    }
-
+) -> Result<Json<DeleteResponse>, AppError> {
     // Delete the specific benchmark scores
     let table_name = match request.benchmark_type.to_lowercase().as_str() {
         "mmlu" => "mmlu_scores",
@@ -378,48 +417,34 @@ pub async fn delete_benchmark_scores(
         "humaneval" => "humaneval_scores",
         "hellaswag" => "hellaswag_scores",
         "truthfulqa" => "truthfulqa_scores",
+        "gpqa" => "gpqa_scores",
         "generic" => "generic_benchmark_scores",
         _ => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Invalid benchmark type: {}", request.benchmark_type),
-                    deleted_id: None,
-                }),
-            ));
+            return Err(AppError::BadRequest(format!(
+                "Invalid benchmark type: {}",
+                request.benchmark_type
+            )));
         }
     };
 
-    // Execute the delete query
-    let query = format!("DELETE FROM {} WHERE test_run_id = $1", table_name);
-    let rows_affected = sqlx::query(&query)
-        .bind(test_run_id)
-        .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(DeleteResponse {
-                    success: false,
-                    message: format!("Failed to delete {} scores: {}", request.benchmark_type, e),
-                    deleted_id: None,
-                }),
-            )
-        })?
-        .rows_affected();
-
-    // Commit the transaction
-    tx.commit().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(DeleteResponse {
-                success: false,
-                message: format!("Failed to commit transaction: {}", e),
-                deleted_id: None,
-            }),
-        )
-    })?;
+    let target = (test_run_id, table_name, request.benchmark_type.as_str());
+    let rows_affected = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &target, |tx, target| {
+        Box::pin(async move {
+            let (test_run_id, table_name, benchmark_type) = *target;
+            ensure_test_run_exists(tx, &test_run_id).await?;
+
+            // Execute the delete query
+            let query = format!("DELETE FROM {} WHERE test_run_id = $1", table_name);
+            let result = sqlx::query(&query)
+                .bind(test_run_id)
+                .execute(&mut **tx)
+                .await
+                .tx_context(&format!("Failed to delete {} scores", benchmark_type))?;
+
+            Ok(result.rows_affected())
+        })
+    })
+    .await?;
 
     Ok(Json(DeleteResponse {
         success: true,
@@ -434,4 +459,18 @@ pub async fn delete_benchmark_scores(
 #[derive(Debug, Deserialize)]
 pub struct DeleteBenchmarkRequest {
     pub benchmark_type: String,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleared_tables() {
+        assert_eq!(
+            cleared_tables(&[("gsm8k_scores", 1), ("mmlu_scores", 0), ("performance_metrics", 12)]),
+            "gsm8k_scores (1 row), performance_metrics (12 rows)"
+        );
+        assert_eq!(cleared_tables(&[("gsm8k_scores", 0)]), "no related rows");
+    }
+}
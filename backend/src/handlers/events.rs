@@ -0,0 +1,67 @@
+// handlers/events.rs
+// Server-sent events for live upload notifications
+
+use std::convert::Infallible;
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use chrono::Utc;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, WatchStream},
+    Stream, StreamExt,
+};
+use uuid::Uuid;
+
+use llm_benchmark_types::RunUploadedEvent;
+
+use crate::AppState;
+
+/// Events buffered per subscriber before a slow one starts missing them
+pub const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Stream upload notifications as server-sent events.
+///
+/// Each committed upload is sent as a `run_uploaded` event carrying a
+/// [`RunUploadedEvent`]. A subscriber that falls more than
+/// `EVENT_CHANNEL_CAPACITY` events behind gets a `lagged` event with the
+/// number of skipped events instead, and should refetch its view.
+///
+/// The stream ends when the server starts shutting down, so open
+/// subscriptions don't hold up the graceful shutdown.
+pub async fn stream_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let shutting_down = WatchStream::new(state.shutdown.clone())
+        .filter(|shutting_down| *shutting_down)
+        .map(|_| None);
+
+    let stream = BroadcastStream::new(state.events.subscribe())
+        .map(Some)
+        .merge(shutting_down)
+        .map_while(|message| message)
+        .map(|message| {
+            Ok(match message {
+                Ok(event) => Event::default()
+                    .event("run_uploaded")
+                    .json_data(&event)
+                    .unwrap_or_else(|e| Event::default().event("error").data(e.to_string())),
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => Event::default()
+                    .event("lagged")
+                    .data(format!("{{\"skipped\":{}}}", skipped)),
+            })
+        });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Notify subscribers that an upload committed `test_run_id`
+pub(crate) fn publish_run_uploaded(state: &AppState, test_run_id: Uuid, model_name: &str) {
+    // Sending only fails when nobody is subscribed, which is fine
+    let _ = state.events.send(RunUploadedEvent {
+        test_run_id,
+        model_name: model_name.to_string(),
+        timestamp: Utc::now(),
+    });
+}
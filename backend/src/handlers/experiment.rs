@@ -1,59 +1,302 @@
 // handlers/experiment.rs
 // Experiment upload related handlers
 
-use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
-};
+use axum::{extract::State, http::StatusCode, response::Json};
 use uuid::Uuid;
 
 use llm_benchmark_types::{
-    UploadExperimentRequest, UploadExperimentResponse, ErrorResponse, Validate,
-    normalize_quantization,
+    UploadExperimentRequest, UploadExperimentResponse, UploadCompleteRequest, UploadCompleteResponse,
+    UploadExperimentsRequest, UploadExperimentsResponse,
+    ValidateExperimentResponse, ExperimentRun, Validate, normalize_quantization,
 };
 
-use crate::AppState;
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    handlers::{
+        benchmark_upload_raw::{write_variant_scores, ScoreWriteOptions}, events::publish_run_uploaded,
+        test_run_log::store_log,
+    },
+    AppState,
+};
 
-/// Upload a new experiment run
+/// Upload a new experiment run. Invalid runs are rejected with a 400.
+///
+/// A run identical to a stored one under a different ID (same model,
+/// quantization, LoRA adapter, backend, backend version, hardware and
+/// timestamp) is rejected with a 409 carrying the existing run's ID, unless
+/// `force` is set, in which case it replaces that run's data under the
+/// existing ID.
 pub async fn upload_experiment(
     State(state): State<AppState>,
     Json(request): Json<UploadExperimentRequest>,
-) -> Result<Json<UploadExperimentResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Validate the experiment data
-    if let Err(validation_error) = request.experiment_run.validate() {
-        return Ok(Json(UploadExperimentResponse::failure(
-            format!("Validation error: {}", validation_error)
-        )));
-    }
+) -> Result<(StatusCode, Json<UploadExperimentResponse>), AppError> {
+    request.experiment_run.validate()?;
 
     // Get warnings
     let warnings = request.experiment_run.warnings();
 
-    // Start a transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to start transaction: {}", e))),
-        )
-    })?;
+    // Write everything in one transaction, retried on serialization conflicts
+    let written = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+        Box::pin(write_unless_duplicate(tx, &request.experiment_run, request.force))
+    })
+    .await?;
+
+    let test_run_id = match written {
+        Written::Stored(test_run_id) => test_run_id,
+        Written::Duplicate(existing) => {
+            return Ok((StatusCode::CONFLICT, Json(UploadExperimentResponse::conflict(existing))));
+        }
+    };
+
+    publish_run_uploaded(&state, test_run_id, &request.experiment_run.model_name);
+
+    if warnings.is_empty() {
+        Ok((StatusCode::OK, Json(UploadExperimentResponse::success(test_run_id))))
+    } else {
+        Ok((StatusCode::OK, Json(UploadExperimentResponse::success_with_warnings(test_run_id, warnings))))
+    }
+}
+
+/// Outcome of writing a single uploaded run
+pub(crate) enum Written {
+    /// The run was stored under this ID
+    Stored(Uuid),
+    /// Nothing was written; an identical run exists under this ID
+    Duplicate(Uuid),
+}
 
+/// Write `run` unless it duplicates a stored run under another ID; with
+/// `force` it replaces that run's data under the existing ID instead
+pub(crate) async fn write_unless_duplicate(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    run: &ExperimentRun,
+    force: bool,
+) -> Result<Written, TxError<AppError>> {
+    match find_duplicate_run(tx, run).await? {
+        Some(existing) if !force => Ok(Written::Duplicate(existing)),
+        Some(existing) => {
+            let run = ExperimentRun { id: existing, ..run.clone() };
+            Ok(Written::Stored(write_experiment(tx, &run).await?))
+        }
+        None => Ok(Written::Stored(write_experiment(tx, run).await?)),
+    }
+}
+
+/// ID of a stored run identical to `run` but under a different ID: same
+/// model, quantization, LoRA adapter, backend, backend version, hardware
+/// and timestamp
+pub(crate) async fn find_duplicate_run(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    run: &ExperimentRun,
+) -> Result<Option<Uuid>, TxError<AppError>> {
+    let hardware_profile_id = insert_or_find_hardware_profile(tx, &run.hardware_config)
+        .await
+        .tx_context("Hardware profile error")?;
+
+    let existing = sqlx::query_scalar!(
+        r#"
+        SELECT id FROM test_runs
+        WHERE model_name = $1 AND quantization = $2 AND backend = $3
+              AND backend_version = $4 AND hardware_profile_id = $5
+              AND timestamp = $6 AND id <> $7 AND lora_adapter = $8
+        ORDER BY id
+        LIMIT 1
+        "#,
+        run.model_name,
+        normalize_quantization(&run.quantization),
+        run.backend,
+        run.backend_version,
+        hardware_profile_id,
+        run.timestamp,
+        run.id,
+        run.lora_adapter.as_deref().unwrap_or("")
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .tx_context("Failed to check for a duplicate test run")?;
+
+    Ok(existing)
+}
+
+/// Upload several experiment runs in one request.
+///
+/// Every run is validated first. By default the batch is all or nothing: an
+/// invalid run rejects it with a 400 naming each failing run, and the runs
+/// are written in a single transaction. With `continue_on_error` invalid
+/// runs are skipped and each valid run is written in its own transaction,
+/// so one failure doesn't undo the others.
+///
+/// Runs identical to a stored one are reported as conflicts in their own
+/// result and not written, as with a single upload, unless `force` is set.
+pub async fn upload_experiments(
+    State(state): State<AppState>,
+    Json(request): Json<UploadExperimentsRequest>,
+) -> Result<Json<UploadExperimentsResponse>, AppError> {
+    if request.experiment_runs.is_empty() {
+        return Err(AppError::BadRequest("No experiment runs provided".to_string()));
+    }
+
+    let errors: Vec<Option<String>> = request
+        .experiment_runs
+        .iter()
+        .map(|run| run.validate().err().map(|e| format!("Validation error: {}", e)))
+        .collect();
+
+    let mut results = Vec::with_capacity(request.experiment_runs.len());
+
+    if request.continue_on_error {
+        for (run, error) in request.experiment_runs.iter().zip(errors) {
+            if let Some(error) = error {
+                results.push(UploadExperimentResponse::failure(error));
+                continue;
+            }
+
+            let written = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, run, |tx, run| {
+                Box::pin(write_unless_duplicate(tx, run, request.force))
+            })
+            .await;
+
+            results.push(match written {
+                Ok(written) => batch_result(&state, run, written),
+                Err(error) => UploadExperimentResponse::failure(AppError::from(error).message()),
+            });
+        }
+    } else {
+        let invalid: Vec<String> = errors
+            .iter()
+            .enumerate()
+            .filter_map(|(index, error)| error.as_ref().map(|error| format!("run {}: {}", index, error)))
+            .collect();
+        if !invalid.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "{} of {} runs failed validation; nothing was stored ({})",
+                invalid.len(),
+                request.experiment_runs.len(),
+                invalid.join("; ")
+            )));
+        }
+
+        let written = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+            Box::pin(async move {
+                let mut written = Vec::with_capacity(request.experiment_runs.len());
+                for run in &request.experiment_runs {
+                    written.push(write_unless_duplicate(tx, run, request.force).await?);
+                }
+                Ok(written)
+            })
+        })
+        .await?;
+
+        for (run, written) in request.experiment_runs.iter().zip(written) {
+            results.push(batch_result(&state, run, written));
+        }
+    }
+
+    let uploaded = results.iter().filter(|result| result.success).count();
+    Ok(Json(UploadExperimentsResponse {
+        failed: results.len() - uploaded,
+        uploaded,
+        results,
+    }))
+}
+
+/// Per-run result of a batch upload, announcing stored runs
+fn batch_result(state: &AppState, run: &ExperimentRun, written: Written) -> UploadExperimentResponse {
+    match written {
+        Written::Stored(test_run_id) => {
+            publish_run_uploaded(state, test_run_id, &run.model_name);
+            UploadExperimentResponse::success_with_warnings(test_run_id, run.warnings())
+        }
+        Written::Duplicate(existing) => UploadExperimentResponse::conflict(existing),
+    }
+}
+
+/// Upload a test run and its model variant's benchmark scores together, so
+/// the performance and quality results land (or fail) as one unit
+pub async fn upload_complete(
+    State(state): State<AppState>,
+    Json(request): Json<UploadCompleteRequest>,
+) -> Result<Json<UploadCompleteResponse>, AppError> {
+    request.validate()?;
+
+    let warnings = request.warnings();
+
+    let (test_run_id, model_variant_id, scores_uploaded) =
+        with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+            Box::pin(write_complete(tx, request))
+        })
+        .await?;
+
+    publish_run_uploaded(&state, test_run_id, &request.experiment_run.model_name);
+
+    Ok(Json(UploadCompleteResponse {
+        success: true,
+        test_run_id: Some(test_run_id),
+        model_variant_id: Some(model_variant_id),
+        scores_uploaded,
+        error: None,
+        warnings,
+    }))
+}
+
+/// Write the test run and the variant's v2 benchmark scores within `tx`
+async fn write_complete(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    request: &UploadCompleteRequest,
+) -> Result<(Uuid, Uuid, usize), TxError<AppError>> {
+    let run = &request.experiment_run;
+    let test_run_id = write_experiment(tx, run).await?;
+
+    // Key the variant the same way the test run's quantization is stored
+    let quantization = normalize_quantization(&run.quantization);
+    let (model_variant_id, scores_uploaded) = write_variant_scores(
+        tx,
+        &run.model_name,
+        &quantization,
+        run.lora_adapter.as_deref().unwrap_or(""),
+        &request.benchmark_scores,
+        run.timestamp,
+        ScoreWriteOptions::default(),
+    )
+    .await?;
+
+    Ok((test_run_id, model_variant_id, scores_uploaded))
+}
+
+/// Validate an experiment against the upload rules without storing it
+pub async fn validate_experiment(
+    Json(request): Json<UploadExperimentRequest>,
+) -> Json<ValidateExperimentResponse> {
+    let error = request
+        .experiment_run
+        .validate()
+        .err()
+        .map(|e| format!("Validation error: {}", e));
+
+    Json(ValidateExperimentResponse {
+        valid: error.is_none(),
+        error,
+        warnings: request.experiment_run.warnings(),
+    })
+}
+
+/// Insert or update the test run, its metrics and scores within `tx`
+pub(crate) async fn write_experiment(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    run: &ExperimentRun,
+) -> Result<Uuid, TxError<AppError>> {
     // Insert or find hardware profile
-    let hardware_profile_id = insert_or_find_hardware_profile(&mut tx, &request.experiment_run.hardware_config)
+    let hardware_profile_id = insert_or_find_hardware_profile(tx, &run.hardware_config)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Hardware profile error: {}", e))),
-            )
-        })?;
+        .tx_context("Hardware profile error")?;
 
     // Normalize quantization (strip redundant -GGUF suffix, etc.)
-    let quantization = normalize_quantization(&request.experiment_run.quantization);
+    let quantization = normalize_quantization(&run.quantization);
 
     // Use provided experiment ID
-    let test_run_id = request.experiment_run.id;
-    let status_str = match request.experiment_run.status {
+    let test_run_id = run.id;
+    let status_str = match run.status {
         llm_benchmark_types::ExperimentStatus::Pending => "pending",
         llm_benchmark_types::ExperimentStatus::Running => "running",
         llm_benchmark_types::ExperimentStatus::Completed => "completed",
@@ -67,8 +310,9 @@ pub async fn upload_experiment(
         INSERT INTO test_runs (id, model_name, quantization, backend, backend_version,
                               hardware_profile_id, timestamp, status, notes,
                               concurrent_requests, max_context_length, load_pattern,
-                              dataset_name, gpu_power_limit_watts)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                              dataset_name, gpu_power_limit_watts, label, lora_adapter,
+                              model_size_bytes, model_n_params)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
         ON CONFLICT (id) DO UPDATE SET
             model_name = EXCLUDED.model_name,
             quantization = EXCLUDED.quantization,
@@ -82,31 +326,34 @@ pub async fn upload_experiment(
             max_context_length = EXCLUDED.max_context_length,
             load_pattern = EXCLUDED.load_pattern,
             dataset_name = EXCLUDED.dataset_name,
-            gpu_power_limit_watts = EXCLUDED.gpu_power_limit_watts
+            gpu_power_limit_watts = EXCLUDED.gpu_power_limit_watts,
+            label = EXCLUDED.label,
+            lora_adapter = EXCLUDED.lora_adapter,
+            model_size_bytes = EXCLUDED.model_size_bytes,
+            model_n_params = EXCLUDED.model_n_params
         "#,
         test_run_id,
-        request.experiment_run.model_name,
+        run.model_name,
         quantization,
-        request.experiment_run.backend,
-        request.experiment_run.backend_version,
+        run.backend,
+        run.backend_version,
         hardware_profile_id,
-        request.experiment_run.timestamp,
+        run.timestamp,
         status_str,
-        request.experiment_run.notes,
-        request.experiment_run.concurrent_requests,
-        request.experiment_run.max_context_length,
-        request.experiment_run.load_pattern,
-        request.experiment_run.dataset_name,
-        request.experiment_run.gpu_power_limit_watts
+        run.notes,
+        run.concurrent_requests,
+        run.max_context_length,
+        run.load_pattern,
+        run.dataset_name,
+        run.gpu_power_limit_watts,
+        run.label,
+        run.lora_adapter.as_deref().unwrap_or(""),
+        run.model_size_bytes,
+        run.model_n_params
     )
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to insert test run: {}", e))),
-        )
-    })?;
+    .tx_context("Failed to insert test run")?;
 
     // Delete existing performance metrics for this test run to allow re-upload
     sqlx::query!(
@@ -115,76 +362,59 @@ pub async fn upload_experiment(
         "#,
         test_run_id
     )
-    .execute(&mut *tx)
+    .execute(&mut **tx)
     .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to delete old performance metrics: {}", e))),
-        )
-    })?;
+    .tx_context("Failed to delete old performance metrics")?;
 
     // Insert performance metrics
-    for metric in &request.experiment_run.performance_metrics {
+    for metric in &run.performance_metrics {
         sqlx::query!(
             r#"
-            INSERT INTO performance_metrics (test_run_id, metric_name, value, unit)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO performance_metrics (test_run_id, metric_name, value, unit, context)
+            VALUES ($1, $2, $3, $4, $5)
             "#,
             test_run_id,
             metric.metric_name,
             metric.value,
-            metric.unit
+            metric.unit,
+            metric.context
         )
-        .execute(&mut *tx)
+        .execute(&mut **tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Failed to insert performance metric: {}", e))),
-            )
-        })?;
+        .tx_context("Failed to insert performance metric")?;
     }
 
     // Delete existing benchmark scores for this test run to allow re-upload
     sqlx::query!("DELETE FROM mmlu_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old MMLU scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old MMLU scores")?;
     sqlx::query!("DELETE FROM gsm8k_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old GSM8K scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old GSM8K scores")?;
     sqlx::query!("DELETE FROM humaneval_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old HumanEval scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old HumanEval scores")?;
     sqlx::query!("DELETE FROM hellaswag_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old HellaSwag scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old HellaSwag scores")?;
     sqlx::query!("DELETE FROM truthfulqa_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old TruthfulQA scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old TruthfulQA scores")?;
+    sqlx::query!("DELETE FROM gpqa_scores WHERE test_run_id = $1", test_run_id)
+        .execute(&mut **tx).await.tx_context("Failed to delete old GPQA scores")?;
     sqlx::query!("DELETE FROM generic_benchmark_scores WHERE test_run_id = $1", test_run_id)
-        .execute(&mut *tx).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(ErrorResponse::new(format!("Failed to delete old generic scores: {}", e)))))?;
+        .execute(&mut **tx).await.tx_context("Failed to delete old generic scores")?;
 
     // Insert benchmark scores
-    for score in &request.experiment_run.benchmark_scores {
-        crate::models::benchmark_queries::insert_benchmark_score(&mut tx, &test_run_id, score)
+    for score in &run.benchmark_scores {
+        crate::models::benchmark_queries::insert_benchmark_score(tx, &test_run_id, score)
             .await
-            .map_err(|e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ErrorResponse::new(format!("Failed to insert benchmark score: {}", e))),
-                )
-            })?;
+            .tx_context("Failed to insert benchmark score")?;
     }
 
-    // Commit transaction
-    tx.commit().await.map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Failed to commit transaction: {}", e))),
-        )
-    })?;
-
-    if warnings.is_empty() {
-        Ok(Json(UploadExperimentResponse::success(test_run_id)))
-    } else {
-        Ok(Json(UploadExperimentResponse::success_with_warnings(test_run_id, warnings)))
+    // Store the console log, if any, alongside the run
+    if let Some(log) = &run.log {
+        store_log(tx, test_run_id, log)
+            .await
+            .tx_context("Failed to store log")?;
     }
+
+    Ok(test_run_id)
 }
 
 async fn insert_or_find_hardware_profile(
@@ -198,12 +428,20 @@ async fn insert_or_find_hardware_profile(
         WHERE gpu_model = $1 AND cpu_model = $2 AND cpu_arch = $3 
               AND ((ram_gb IS NULL AND $4::INT IS NULL) OR ram_gb = $4)
               AND ((ram_type IS NULL AND $5::TEXT IS NULL) OR ram_type = $5)
+              AND cpu_threads IS NOT DISTINCT FROM $6::INT
+              AND numa_nodes IS NOT DISTINCT FROM $7::INT
+              AND gpu_count = $8
+              AND total_gpu_memory_gb IS NOT DISTINCT FROM $9::INT
         "#,
         hardware_config.gpu_model,
         hardware_config.cpu_model,
         hardware_config.cpu_arch,
         hardware_config.ram_gb,
-        hardware_config.ram_type
+        hardware_config.ram_type,
+        hardware_config.cpu_threads,
+        hardware_config.numa_nodes,
+        hardware_config.gpu_count,
+        hardware_config.total_gpu_memory_gb
     )
     .fetch_one(&mut **tx)
     .await
@@ -216,17 +454,21 @@ async fn insert_or_find_hardware_profile(
     sqlx::query!(
         r#"
         INSERT INTO hardware_profiles 
-        (id, gpu_model, gpu_memory_gb, cpu_model, cpu_arch, ram_gb, ram_type, 
-         virtualization_type, optimizations)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        (id, gpu_model, gpu_memory_gb, gpu_count, total_gpu_memory_gb, cpu_model, cpu_arch,
+         ram_gb, ram_type, cpu_threads, numa_nodes, virtualization_type, optimizations)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         "#,
         hardware_profile_id,
         hardware_config.gpu_model,
         hardware_config.gpu_memory_gb,
+        hardware_config.gpu_count,
+        hardware_config.total_gpu_memory_gb,
         hardware_config.cpu_model,
         hardware_config.cpu_arch,
         hardware_config.ram_gb,
         hardware_config.ram_type,
+        hardware_config.cpu_threads,
+        hardware_config.numa_nodes,
         hardware_config.virtualization_type,
         &hardware_config.optimizations
     )
@@ -234,4 +476,68 @@ async fn insert_or_find_hardware_profile(
     .await?;
 
     Ok(hardware_profile_id)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_benchmark_types::HardwareConfig;
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_write_unless_duplicate() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let mut run = ExperimentRun::new(
+            Uuid::new_v4(),
+            format!("duplicate-test-{}", Uuid::new_v4()),
+            "Q4_K_M".to_string(),
+            "llama.cpp".to_string(),
+            "b4000".to_string(),
+            HardwareConfig::cpu_only("AMD EPYC 7763".to_string(), "Zen3".to_string(), Some(256), None),
+        );
+        run.notes = Some("first".to_string());
+        let Written::Stored(stored) = write_unless_duplicate(&mut tx, &run, false).await.unwrap() else {
+            panic!("first upload should be stored");
+        };
+
+        // The same run under a new ID is a duplicate and nothing is written
+        let again = ExperimentRun { id: Uuid::new_v4(), notes: Some("second".to_string()), ..run.clone() };
+        assert!(matches!(
+            write_unless_duplicate(&mut tx, &again, false).await.unwrap(),
+            Written::Duplicate(existing) if existing == stored
+        ));
+        let notes: Option<String> = sqlx::query_scalar("SELECT notes FROM test_runs WHERE id = $1")
+            .bind(stored)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(notes.as_deref(), Some("first"));
+
+        // Forced, it replaces the stored run under its ID
+        assert!(matches!(
+            write_unless_duplicate(&mut tx, &again, true).await.unwrap(),
+            Written::Stored(id) if id == stored
+        ));
+        let notes: Option<String> = sqlx::query_scalar("SELECT notes FROM test_runs WHERE id = $1")
+            .bind(stored)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
+        assert_eq!(notes.as_deref(), Some("second"));
+
+        // A LoRA run of the same base model is a different run
+        let lora = ExperimentRun {
+            id: Uuid::new_v4(),
+            lora_adapter: Some("duplicate-test-lora".to_string()),
+            ..run.clone()
+        };
+        assert!(matches!(
+            write_unless_duplicate(&mut tx, &lora, false).await.unwrap(),
+            Written::Stored(id) if id == lora.id
+        ));
+
+        tx.rollback().await.unwrap();
+    }
+}
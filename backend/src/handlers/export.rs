@@ -0,0 +1,292 @@
+// handlers/export.rs
+// Newline-delimited JSON export of every experiment run, for backups
+
+use std::collections::HashMap;
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    BoxError,
+};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use uuid::Uuid;
+
+use llm_benchmark_types::{ExperimentRun, ExperimentStatus, ExportRequest, HardwareConfig, PerformanceMetric};
+
+use crate::{models::benchmark_queries, AppState};
+
+/// Runs loaded per database round trip
+const EXPORT_PAGE_SIZE: i64 = 100;
+
+/// Lines serialized ahead of a slow client
+const EXPORT_CHANNEL_CAPACITY: usize = 16;
+
+/// Stream every experiment run as JSON Lines, oldest first: one complete
+/// `ExperimentRun` (hardware, performance metrics and benchmark scores) per
+/// line. Console logs aren't included. Runs stored without a timestamp
+/// come first, exported with the Unix epoch as theirs. With `since`, only
+/// runs timestamped after it are exported.
+///
+/// Runs are read a page at a time and written as the client consumes them,
+/// so the export never holds the whole dataset. A database error after the
+/// response has started aborts the body, leaving the last line incomplete.
+/// So does the server shutting down, so an export can't hold up the
+/// graceful shutdown.
+pub async fn export_runs(
+    Query(params): Query<ExportRequest>,
+    State(state): State<AppState>,
+) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(export_lines(state.db, params.since, state.shutdown)),
+    )
+        .into_response()
+}
+
+/// Lines of an export, read by a background task that stops when
+/// `shutdown` turns true and is cancelled if the stream is dropped
+fn export_lines(
+    db: PgPool,
+    since: Option<DateTime<Utc>>,
+    mut shutdown: watch::Receiver<bool>,
+) -> impl Stream<Item = Result<String, BoxError>> {
+    let (lines, receiver) = mpsc::channel(EXPORT_CHANNEL_CAPACITY);
+
+    let export = AbortOnDrop(tokio::spawn(async move {
+        let result = tokio::select! {
+            biased;
+            _ = shutdown.wait_for(|shutting_down| *shutting_down) => {
+                Err(BoxError::from("Export interrupted: the server is shutting down"))
+            }
+            result = send_runs(&db, since, &lines) => result,
+        };
+        if let Err(error) = result {
+            tracing::error!("Export failed: {}", error);
+            let _ = lines.send(Err(error)).await;
+        }
+    }));
+
+    ReceiverStream::new(receiver).map(move |line| {
+        let _ = &export;
+        line
+    })
+}
+
+/// Aborts the task when dropped, e.g. with the response body of a client
+/// that disconnected
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Send each run newer than `since` to `lines` as a JSON line, stopping
+/// early if the client disconnects
+async fn send_runs(
+    db: &PgPool,
+    since: Option<DateTime<Utc>>,
+    lines: &mpsc::Sender<Result<String, BoxError>>,
+) -> Result<(), BoxError> {
+    let mut after: Option<(DateTime<Utc>, Uuid)> = None;
+
+    loop {
+        let runs = load_page(db, since, after).await?;
+        let Some(last) = runs.last() else {
+            return Ok(());
+        };
+        after = Some((last.timestamp, last.id));
+
+        for run in runs {
+            let mut line = serde_json::to_string(&run)?;
+            line.push('\n');
+            if lines.send(Ok(line)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Up to `EXPORT_PAGE_SIZE` runs newer than `since`, ordered by timestamp
+/// (the epoch when missing) then ID and starting after the `after` key
+async fn load_page(
+    db: &PgPool,
+    since: Option<DateTime<Utc>>,
+    after: Option<(DateTime<Utc>, Uuid)>,
+) -> Result<Vec<ExperimentRun>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT tr.id, tr.model_name, tr.quantization, tr.lora_adapter, tr.backend,
+               tr.backend_version, tr.model_size_bytes, tr.model_n_params,
+               COALESCE(tr.timestamp, 'epoch') as "timestamp!", tr.status, tr.notes,
+               tr.concurrent_requests, tr.max_context_length, tr.load_pattern,
+               tr.dataset_name, tr.gpu_power_limit_watts, tr.label,
+               hp.gpu_model, hp.gpu_memory_gb, hp.gpu_count, hp.total_gpu_memory_gb,
+               hp.cpu_model, hp.cpu_arch, hp.ram_gb, hp.ram_type, hp.cpu_threads,
+               hp.numa_nodes, hp.virtualization_type,
+               COALESCE(hp.optimizations, '{}') as "optimizations!"
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON hp.id = tr.hardware_profile_id
+        WHERE ($1::TIMESTAMPTZ IS NULL OR tr.timestamp > $1)
+          AND ($2::TIMESTAMPTZ IS NULL OR (COALESCE(tr.timestamp, 'epoch'), tr.id) > ($2, $3::UUID))
+        ORDER BY COALESCE(tr.timestamp, 'epoch'), tr.id
+        LIMIT $4
+        "#,
+        since,
+        after.map(|(timestamp, _)| timestamp),
+        after.map(|(_, id)| id),
+        EXPORT_PAGE_SIZE
+    )
+    .fetch_all(db)
+    .await?;
+
+    let ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+    let mut metrics = load_metrics(db, &ids).await?;
+
+    let mut runs = Vec::with_capacity(rows.len());
+    for row in rows {
+        let status = match row.status.as_str() {
+            "pending" => ExperimentStatus::Pending,
+            "running" => ExperimentStatus::Running,
+            "completed" => ExperimentStatus::Completed,
+            "failed" => ExperimentStatus::Failed,
+            "cancelled" => ExperimentStatus::Cancelled,
+            _ => ExperimentStatus::Completed,
+        };
+
+        runs.push(ExperimentRun {
+            id: row.id,
+            model_name: row.model_name,
+            quantization: row.quantization,
+            lora_adapter: Some(row.lora_adapter).filter(|lora| !lora.is_empty()),
+            backend: row.backend,
+            backend_version: row.backend_version,
+            model_size_bytes: row.model_size_bytes,
+            model_n_params: row.model_n_params,
+            hardware_config: HardwareConfig {
+                gpu_model: row.gpu_model,
+                gpu_memory_gb: row.gpu_memory_gb,
+                gpu_count: row.gpu_count,
+                total_gpu_memory_gb: row.total_gpu_memory_gb,
+                cpu_model: row.cpu_model,
+                cpu_arch: row.cpu_arch,
+                ram_gb: row.ram_gb,
+                ram_type: row.ram_type,
+                cpu_threads: row.cpu_threads,
+                numa_nodes: row.numa_nodes,
+                virtualization_type: row.virtualization_type,
+                optimizations: row.optimizations,
+            },
+            performance_metrics: metrics.remove(&row.id).unwrap_or_default(),
+            benchmark_scores: benchmark_queries::get_all_benchmark_scores_for_test_run(db, &row.id).await?,
+            timestamp: row.timestamp,
+            notes: row.notes,
+            status,
+            concurrent_requests: row.concurrent_requests,
+            max_context_length: row.max_context_length,
+            load_pattern: row.load_pattern,
+            dataset_name: row.dataset_name,
+            gpu_power_limit_watts: row.gpu_power_limit_watts,
+            label: row.label,
+            log: None,
+        });
+    }
+
+    Ok(runs)
+}
+
+/// Performance metrics of each of `test_run_ids`, in upload order
+async fn load_metrics(
+    db: &PgPool,
+    test_run_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<PerformanceMetric>>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT test_run_id as "test_run_id!", metric_name, value, unit, context,
+               COALESCE(created_at, NOW()) as "created_at!"
+        FROM performance_metrics
+        WHERE test_run_id = ANY($1)
+        ORDER BY created_at, id
+        "#,
+        test_run_ids
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut metrics: HashMap<Uuid, Vec<PerformanceMetric>> = HashMap::new();
+    for row in rows {
+        metrics.entry(row.test_run_id).or_default().push(PerformanceMetric {
+            metric_name: row.metric_name,
+            value: row.value,
+            unit: row.unit,
+            timestamp: row.created_at,
+            context: row.context,
+        });
+    }
+
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_export_stops_on_shutdown() {
+        // Never connects: the export ends before reading anything
+        let db = PgPool::connect_lazy("postgres://localhost/unused").unwrap();
+        let (_shutdown_sender, shutdown) = watch::channel(true);
+
+        let lines: Vec<_> = export_lines(db, None, shutdown).collect().await;
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].as_ref().unwrap_err().to_string().contains("shutting down"));
+    }
+
+    /// Export pages read through the pool, so the rows are committed and
+    /// removed again at the end
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_export_includes_runs_without_timestamp() {
+        let pool = crate::db::test_pool().await;
+
+        let hardware_profile_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO hardware_profiles (gpu_model, gpu_memory_gb, cpu_model, cpu_arch) VALUES ('RTX 4090', 24, 'Ryzen 9 7950X', 'x86_64') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO test_runs (model_name, quantization, backend, backend_version, hardware_profile_id, timestamp, status)
+             VALUES ($1, 'Q4_K_M', 'llama.cpp', 'b4000', $2, NULL, 'completed') RETURNING id",
+        )
+        .bind(format!("export-test-{}", Uuid::new_v4()))
+        .bind(hardware_profile_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let first = load_page(&pool, None, None).await;
+        let after = load_page(&pool, None, Some((DateTime::UNIX_EPOCH, id))).await;
+
+        sqlx::query("DELETE FROM test_runs WHERE id = $1").bind(id).execute(&pool).await.unwrap();
+        sqlx::query("DELETE FROM hardware_profiles WHERE id = $1")
+            .bind(hardware_profile_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Sorted first under the epoch, and the cursor moves past it
+        let run = first.unwrap().into_iter().find(|run| run.id == id).expect("run without a timestamp is exported");
+        assert_eq!(run.timestamp, DateTime::UNIX_EPOCH);
+        assert!(after.unwrap().iter().all(|run| run.id != id));
+    }
+}
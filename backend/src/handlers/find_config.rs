@@ -0,0 +1,205 @@
+// handlers/find_config.rs
+// "Help me choose": the most efficient config meeting quality and latency SLOs
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use sqlx::{postgres::PgRow, PgConnection, Row};
+
+use llm_benchmark_types::{ConfigCandidate, FindConfigRequest, FindConfigResponse, PerformanceMetric};
+
+use crate::{
+    error::AppError,
+    handlers::grouped_performance::{variant_score_sql, QUALITY_BENCHMARKS},
+    AppState,
+};
+
+const DEFAULT_RUNNERS_UP: i64 = 5;
+const MAX_RUNNERS_UP: i64 = 50;
+
+/// Find the completed run with the best tokens/kWh among those meeting
+/// `min_quality` on `benchmark` and `max_ttft_ms`.
+///
+/// Runs without power data can't be ranked by efficiency, so they follow
+/// the ones that can; ties and power-less runs are ordered by speed. A run
+/// without a TTFT measurement never meets a latency SLO.
+pub async fn find_config(
+    Query(params): Query<FindConfigRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<FindConfigResponse>, AppError> {
+    let benchmark = params.benchmark.as_deref().unwrap_or("mmlu").to_lowercase();
+    let score_sql = variant_score_sql(&benchmark).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Unknown benchmark '{}' (expected one of {})",
+            benchmark,
+            QUALITY_BENCHMARKS.join(", ")
+        ))
+    })?;
+    let runners_up = params.limit.unwrap_or(DEFAULT_RUNNERS_UP).clamp(0, MAX_RUNNERS_UP);
+
+    let mut conn = state.db.acquire().await?;
+    let rows = find_candidates(&mut conn, score_sql, &params, runners_up + 1).await?;
+
+    let qualifying_count = rows.first().map_or(0, |row| row.get("qualifying_count"));
+    let mut candidates = rows.into_iter().map(|row| ConfigCandidate {
+        test_run_id: row.get("id"),
+        model_name: row.get("model_name"),
+        quantization: row.get("quantization"),
+        lora_adapter: row.get("lora_adapter"),
+        backend: row.get("backend"),
+        hardware: row.get("hardware"),
+        quality_score: row.get("quality_score"),
+        first_token_latency_ms: row.get("first_token_latency_ms"),
+        tokens_per_second: row.get("tokens_per_second"),
+        gpu_power_watts: row.get("gpu_power_watts"),
+        tokens_per_kwh: row
+            .get::<Option<f64>, _>("gpu_power_watts")
+            .and_then(|watts| PerformanceMetric::tokens_per_kwh(row.get("tokens_per_second"), watts))
+            .map(|metric| metric.value),
+    });
+
+    Ok(Json(FindConfigResponse {
+        benchmark,
+        qualifying_count,
+        best: candidates.next(),
+        runners_up: candidates.collect(),
+    }))
+}
+
+/// Candidate runs meeting the request's SLOs, best first, each with the
+/// count of all qualifying runs. `score_sql` is the quality benchmark's
+/// `variant_score_sql` expression.
+async fn find_candidates(
+    conn: &mut PgConnection,
+    score_sql: &str,
+    params: &FindConfigRequest,
+    limit: i64,
+) -> Result<Vec<PgRow>, sqlx::Error> {
+    sqlx::query(&format!(
+        r#"
+        WITH candidates AS (
+            SELECT
+                tr.id,
+                tr.model_name,
+                tr.quantization,
+                tr.lora_adapter,
+                tr.backend,
+                CONCAT(hp.gpu_model, ' / ', hp.cpu_model) as hardware,
+                pm_speed.value as tokens_per_second,
+                pm_ttft.value as first_token_latency_ms,
+                pm_power.value as gpu_power_watts,
+                {} as quality_score
+            FROM test_runs tr
+            JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+            LEFT JOIN model_variants mv ON mv.model_name = tr.model_name AND mv.quantization = tr.quantization
+                AND mv.lora_adapter = tr.lora_adapter
+            JOIN performance_metrics pm_speed ON pm_speed.test_run_id = tr.id
+                AND pm_speed.metric_name = 'tokens_per_second'
+            LEFT JOIN performance_metrics pm_ttft ON pm_ttft.test_run_id = tr.id
+                AND pm_ttft.metric_name = 'first_token_latency_ms'
+            LEFT JOIN performance_metrics pm_power ON pm_power.test_run_id = tr.id
+                AND pm_power.metric_name = 'gpu_power_watts'
+            WHERE tr.status = 'completed'
+        )
+        -- Tokens per watt ranks the same as tokens/kWh, which is computed
+        -- from the returned speed and power
+        SELECT
+            *,
+            COUNT(*) OVER () as qualifying_count
+        FROM candidates
+        WHERE ($1::FLOAT8 IS NULL OR quality_score >= $1)
+          AND ($2::FLOAT8 IS NULL OR first_token_latency_ms <= $2)
+        ORDER BY CASE WHEN gpu_power_watts > 0 THEN tokens_per_second / gpu_power_watts END DESC NULLS LAST,
+                 tokens_per_second DESC, id
+        LIMIT $3
+        "#,
+        score_sql
+    ))
+    .bind(params.min_quality)
+    .bind(params.max_ttft_ms)
+    .bind(limit)
+    .fetch_all(conn)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    /// (LoRA adapter, quality score) of `model_name`'s candidates
+    async fn candidate_scores(
+        conn: &mut PgConnection,
+        model_name: &str,
+        min_quality: Option<f64>,
+    ) -> Vec<(String, Option<f64>)> {
+        let params = FindConfigRequest { benchmark: None, min_quality, max_ttft_ms: None, limit: None };
+        let mut rows: Vec<(String, Option<f64>)> = find_candidates(conn, variant_score_sql("mmlu").unwrap(), &params, MAX_RUNNERS_UP)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|row| row.get::<String, _>("model_name") == model_name)
+            .map(|row| (row.get("lora_adapter"), row.get("quality_score")))
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        rows
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_candidates_match_lora_variant() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let model_name = format!("find-config-test-{}", Uuid::new_v4());
+        let hardware_profile_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO hardware_profiles (gpu_model, gpu_memory_gb, cpu_model, cpu_arch) VALUES ('RTX 4090', 24, 'Ryzen 9 7950X', 'x86_64') RETURNING id",
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap();
+
+        // Base and LoRA variants of one quantization, scored differently
+        for (lora_adapter, score) in [("", 40.0), ("find-config-lora", 80.0)] {
+            sqlx::query(
+                r#"
+                WITH variant AS (
+                    INSERT INTO model_variants (model_name, quantization, lora_adapter)
+                    VALUES ($1, 'Q4_K_M', $2)
+                    RETURNING id
+                ), run AS (
+                    INSERT INTO test_runs (model_name, quantization, lora_adapter, backend, backend_version, hardware_profile_id, status)
+                    VALUES ($1, 'Q4_K_M', $2, 'llama.cpp', 'b1', $3, 'completed')
+                    RETURNING id
+                ), scores AS (
+                    INSERT INTO mmlu_scores_v2 (model_variant_id, category, score, timestamp)
+                    SELECT id, 'overall', $4, NOW() FROM variant
+                )
+                INSERT INTO performance_metrics (test_run_id, metric_name, value, unit)
+                SELECT id, 'tokens_per_second', 1000000, 'tok/s' FROM run
+                UNION ALL SELECT id, 'gpu_power_watts', 1, 'W' FROM run
+                "#,
+            )
+            .bind(&model_name)
+            .bind(lora_adapter)
+            .bind(hardware_profile_id)
+            .bind(score)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        }
+
+        // One row per run, each with its own variant's score
+        assert_eq!(
+            candidate_scores(&mut tx, &model_name, None).await,
+            vec![("".to_string(), Some(40.0)), ("find-config-lora".to_string(), Some(80.0))]
+        );
+        assert_eq!(
+            candidate_scores(&mut tx, &model_name, Some(60.0)).await,
+            vec![("find-config-lora".to_string(), Some(80.0))]
+        );
+
+        tx.rollback().await.unwrap();
+    }
+}
@@ -3,50 +3,200 @@
 
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
     response::Json,
 };
-use sqlx::Row;
-use std::collections::HashMap;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use std::{cmp::Ordering, collections::HashMap};
 
 use llm_benchmark_types::{
     GroupedPerformanceRequest, GroupedPerformanceResponse,
-    ModelPerformanceGroup, QuantizationPerformance, ErrorResponse,
-    HardwarePlatformPerformance,
-    hardware::HardwareCategory,
+    ModelPerformanceGroup, QuantizationPerformance,
+    HardwarePlatformPerformance, BenchmarkWeights, PerformanceMetric,
+    hardware::{is_benchmark_only_model, HardwareCategory},
 };
 
-use crate::AppState;
-
-/// Determine hardware category from GPU and CPU model strings
-fn determine_hardware_category(gpu_model: &str, cpu_model: &str) -> HardwareCategory {
-    // Check GPU first
-    if gpu_model.contains("RTX") || gpu_model.contains("GTX") {
-        HardwareCategory::ConsumerGpu
-    } else if gpu_model.contains("A100") || gpu_model.contains("H100") 
-        || gpu_model.contains("L4") || gpu_model.contains("L40")
-        || gpu_model.contains("V100") || gpu_model.contains("T4") {
-        HardwareCategory::DatacenterGpu
-    } else if gpu_model == "CPU Only" || gpu_model == "N/A" || gpu_model.starts_with("CPU") {
-        // CPU only - check CPU model
-        if cpu_model.contains("Xeon") || cpu_model.contains("EPYC") {
-            HardwareCategory::DatacenterCpu
-        } else {
-            HardwareCategory::ConsumerCpu
+use crate::{
+    error::{AppError, DbContext},
+    AppState,
+};
+
+/// Benchmarks with a `<name>_score` column in the grouped performance query
+pub(crate) const QUALITY_BENCHMARKS: [&str; 5] = ["mmlu", "gsm8k", "humaneval", "hellaswag", "truthfulqa"];
+
+/// SQL expression for the score (0–100) of the model variant aliased `mv`
+/// on one of `QUALITY_BENCHMARKS`. MMLU averages its categories; the others
+/// hold one score per variant.
+pub(crate) fn variant_score_sql(benchmark: &str) -> Option<&'static str> {
+    match benchmark {
+        "mmlu" => Some("(SELECT AVG(ms.score) FROM mmlu_scores_v2 ms WHERE ms.model_variant_id = mv.id)"),
+        "gsm8k" => Some("(SELECT gs.accuracy * 100 FROM gsm8k_scores_v2 gs WHERE gs.model_variant_id = mv.id)"),
+        "humaneval" => Some("(SELECT hs.pass_at_1 FROM humaneval_scores_v2 hs WHERE hs.model_variant_id = mv.id)"),
+        "hellaswag" => Some("(SELECT hs.accuracy FROM hellaswag_scores_v2 hs WHERE hs.model_variant_id = mv.id)"),
+        "truthfulqa" => Some("(SELECT ts.truthful_score FROM truthfulqa_scores_v2 ts WHERE ts.model_variant_id = mv.id)"),
+        _ => None,
+    }
+}
+
+/// All completed runs with their performance metrics and a
+/// `<benchmark>_score` column per quality benchmark. JOINs model_variants
+/// to get per-variant quality scores (LoRA variants produce separate rows).
+fn candidates_query() -> String {
+    let scores: Vec<String> = QUALITY_BENCHMARKS
+        .iter()
+        .filter_map(|benchmark| {
+            variant_score_sql(benchmark).map(|sql| format!("{} as {}_score", sql, benchmark))
+        })
+        .collect();
+
+    format!(
+        r#"
+        SELECT
+            tr.id,
+            tr.model_name,
+            tr.quantization,
+            tr.backend,
+            tr.concurrent_requests,
+            tr.max_context_length,
+            tr.load_pattern,
+            tr.dataset_name,
+            tr.gpu_power_limit_watts,
+            pm_speed.value as tokens_per_second,
+            pm_memory.value as memory_gb,
+            pm_power.value as gpu_power_watts,
+            CONCAT(hp.gpu_model, ' / ', hp.cpu_model) as hardware,
+            hp.gpu_model,
+            hp.cpu_arch,
+            hp.cpu_model,
+            mv.lora_adapter,
+            {}
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        LEFT JOIN model_variants mv ON mv.model_name = tr.model_name AND mv.quantization = tr.quantization
+            AND mv.lora_adapter = tr.lora_adapter
+        LEFT JOIN performance_metrics pm_speed ON pm_speed.test_run_id = tr.id
+            AND pm_speed.metric_name = 'tokens_per_second'
+        LEFT JOIN performance_metrics pm_memory ON pm_memory.test_run_id = tr.id
+            AND pm_memory.metric_name = 'memory_usage_gb'
+        LEFT JOIN performance_metrics pm_power ON pm_power.test_run_id = tr.id
+            AND pm_power.metric_name = 'gpu_power_watts'
+        WHERE tr.status = 'completed'
+        ORDER BY tr.model_name
+        "#,
+        scores.join(",\n            ")
+    )
+}
+
+/// A completed run as gathered by the grouped performance query
+pub(crate) struct Candidate {
+    pub model_name: String,
+    pub gpu_model: String,
+    pub cpu_model: String,
+    /// Measured speed; `config.tokens_per_second` is 0 without one
+    pub tokens_per_second: Option<f64>,
+    /// Measured memory; `config.memory_gb` is 0 without one
+    pub memory_gb: Option<f64>,
+    /// Quality score; `config.quality_score` is 0 without one
+    pub quality_score: Option<f64>,
+    pub config: QuantizationPerformance,
+}
+
+/// Completed runs with performance data, scored on `benchmark` or, given
+/// `weights`, on a weighted composite. Benchmark-only and generic
+/// placeholder runs are left out.
+pub(crate) async fn fetch_candidates(
+    db: &PgPool,
+    benchmark: &str,
+    weights: Option<&BenchmarkWeights>,
+) -> Result<Vec<Candidate>, sqlx::Error> {
+    let rows = sqlx::query(&candidates_query()).fetch_all(db).await?;
+
+    let mut candidates = Vec::new();
+    for row in rows {
+        let tokens_per_second: Option<f64> = row.get("tokens_per_second");
+        let memory_gb: Option<f64> = row.get("memory_gb");
+        let gpu_power_watts: Option<f64> = row.get("gpu_power_watts");
+        let benchmark_scores: Vec<(&str, f64)> = QUALITY_BENCHMARKS
+            .iter()
+            .filter_map(|name| {
+                row.get::<Option<f64>, _>(format!("{}_score", name).as_str())
+                    .map(|score| (*name, score))
+            })
+            .collect();
+        let quality_score = match weights {
+            Some(weights) => weights.weighted_score(benchmark_scores.iter().copied()),
+            None => benchmark_scores
+                .iter()
+                .find(|(name, _)| *name == benchmark)
+                .map(|(_, score)| *score),
+        };
+        let gpu_model: String = row.get("gpu_model");
+        let cpu_model: String = row.get("cpu_model");
+
+        // Skip entries without any performance data (benchmark-only entries)
+        if tokens_per_second.is_none() && memory_gb.is_none() {
+            continue;
         }
-    } else {
-        // Unknown GPU, default to consumer
-        HardwareCategory::ConsumerGpu
+
+        // Also skip obvious generic entries
+        if is_benchmark_only_model(&gpu_model) || is_benchmark_only_model(&cpu_model) {
+            continue;
+        }
+
+        let tokens_per_kwh = tokens_per_second
+            .zip(gpu_power_watts)
+            .and_then(|(speed, power)| PerformanceMetric::tokens_per_kwh(speed, power))
+            .map(|metric| metric.value);
+
+        let config = QuantizationPerformance {
+            id: row.get("id"),
+            quantization: row.get("quantization"),
+            lora_adapter: row.try_get("lora_adapter").unwrap_or_default(),
+            quality_score: quality_score.unwrap_or(0.0),
+            tokens_per_second: tokens_per_second.unwrap_or(0.0),
+            memory_gb: memory_gb.unwrap_or(0.0),
+            backend: row.get("backend"),
+            hardware: row.get("hardware"),
+            hardware_category: HardwareCategory::from_models(&gpu_model, &cpu_model),
+            concurrent_requests: row.get("concurrent_requests"),
+            max_context_length: row.get("max_context_length"),
+            load_pattern: row.get("load_pattern"),
+            dataset_name: row.get("dataset_name"),
+            gpu_power_limit_watts: row.get("gpu_power_limit_watts"),
+            gpu_power_watts,
+            tokens_per_kwh,
+        };
+
+        candidates.push(Candidate {
+            model_name: row.get("model_name"),
+            gpu_model,
+            cpu_model,
+            tokens_per_second,
+            memory_gb,
+            quality_score,
+            config,
+        });
     }
+
+    Ok(candidates)
 }
 
-/// Get grouped model performance with best quantization per model
+/// Get grouped model performance with best quantization per model.
+///
+/// Returns every model, or `limit` of them with a `next_cursor` to pass as
+/// `cursor` for the next page. `total_count` counts every model either way.
 pub async fn get_grouped_performance(
     Query(params): Query<GroupedPerformanceRequest>,
     State(state): State<AppState>,
-) -> Result<Json<GroupedPerformanceResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<GroupedPerformanceResponse>, AppError> {
     // Default to MMLU if no benchmark specified
     let benchmark = params.benchmark.as_deref().unwrap_or("mmlu");
+
+    // A profile or explicit weights replace the single benchmark with a
+    // weighted composite
+    let weights = BenchmarkWeights::from_params(params.profile.as_deref(), params.weights.as_deref())
+        .map_err(AppError::BadRequest)?;
     
     // Parse hardware categories from comma-separated string
     let filter_categories: Vec<HardwareCategory> = if let Some(ref categories_str) = params.hardware_categories {
@@ -64,86 +214,9 @@ pub async fn get_grouped_performance(
         Vec::new()
     };
     
-    // Get all test runs with their performance metrics and quality scores
-    // JOIN model_variants to get per-variant quality scores (LoRA variants produce separate rows)
-    let query = r#"
-        WITH test_run_data AS (
-            SELECT
-                tr.id,
-                tr.model_name,
-                tr.quantization,
-                tr.backend,
-                tr.concurrent_requests,
-                tr.max_context_length,
-                tr.load_pattern,
-                tr.dataset_name,
-                tr.gpu_power_limit_watts,
-                pm_speed.value as tokens_per_second,
-                pm_memory.value as memory_gb,
-                pm_power.value as gpu_power_watts,
-                CONCAT(hp.gpu_model, ' / ', hp.cpu_model) as hardware,
-                hp.gpu_model,
-                hp.cpu_arch,
-                hp.cpu_model,
-                mv.lora_adapter,
-                CASE
-                    WHEN $1 = 'mmlu' THEN (
-                        SELECT AVG(ms.score)
-                        FROM mmlu_scores_v2 ms
-                        WHERE ms.model_variant_id = mv.id
-                    )
-                    WHEN $1 = 'gsm8k' THEN (
-                        SELECT gs.accuracy * 100
-                        FROM gsm8k_scores_v2 gs
-                        WHERE gs.model_variant_id = mv.id
-                        LIMIT 1
-                    )
-                    WHEN $1 = 'humaneval' THEN (
-                        SELECT hs.pass_at_1
-                        FROM humaneval_scores_v2 hs
-                        WHERE hs.model_variant_id = mv.id
-                        LIMIT 1
-                    )
-                    WHEN $1 = 'hellaswag' THEN (
-                        SELECT hs.accuracy
-                        FROM hellaswag_scores_v2 hs
-                        WHERE hs.model_variant_id = mv.id
-                        LIMIT 1
-                    )
-                    WHEN $1 = 'truthfulqa' THEN (
-                        SELECT ts.truthful_score
-                        FROM truthfulqa_scores_v2 ts
-                        WHERE ts.model_variant_id = mv.id
-                        LIMIT 1
-                    )
-                    WHEN $1 = 'none' THEN NULL
-                    ELSE NULL
-                END as quality_score
-            FROM test_runs tr
-            JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
-            LEFT JOIN model_variants mv ON mv.model_name = tr.model_name AND mv.quantization = tr.quantization
-            LEFT JOIN performance_metrics pm_speed ON pm_speed.test_run_id = tr.id
-                AND pm_speed.metric_name = 'tokens_per_second'
-            LEFT JOIN performance_metrics pm_memory ON pm_memory.test_run_id = tr.id
-                AND pm_memory.metric_name = 'memory_usage_gb'
-            LEFT JOIN performance_metrics pm_power ON pm_power.test_run_id = tr.id
-                AND pm_power.metric_name = 'gpu_power_watts'
-            WHERE tr.status = 'completed'
-        )
-        SELECT * FROM test_run_data
-        ORDER BY model_name, quality_score DESC NULLS LAST
-    "#;
-
-    let rows = sqlx::query(query)
-        .bind(benchmark)
-        .fetch_all(&state.db)
+    let candidates = fetch_candidates(&state.db, benchmark, weights.as_ref())
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Database error: {}", e))),
-            )
-        })?;
+        .db_context("Failed to load performance data")?;
 
     // Derive optimization goal from sort_by parameter
     let sort_by = params.sort_by.as_deref().unwrap_or("quality");
@@ -154,50 +227,9 @@ pub async fn get_grouped_performance(
     let mut model_hardware_groups: HashMap<String, HashMap<String, Vec<QuantizationPerformance>>> = HashMap::new();
     let mut total_platforms_by_model: HashMap<String, usize> = HashMap::new();
 
-    for row in rows {
-        let model_name: String = row.get("model_name");
-        let tokens_per_second: Option<f64> = row.get("tokens_per_second");
-        let memory_gb: Option<f64> = row.get("memory_gb");
-        let gpu_power_watts: Option<f64> = row.get("gpu_power_watts");
-        let quality_score: Option<f64> = row.get("quality_score");
-        let gpu_model: String = row.get("gpu_model");
-        let _cpu_arch: String = row.get("cpu_arch");
-        let cpu_model: String = row.get("cpu_model");
-        let concurrent_requests: Option<i32> = row.get("concurrent_requests");
-        let max_context_length: Option<i32> = row.get("max_context_length");
-        let load_pattern: Option<String> = row.get("load_pattern");
-        let dataset_name: Option<String> = row.get("dataset_name");
-        let gpu_power_limit_watts: Option<i32> = row.get("gpu_power_limit_watts");
-        let hardware: String = row.get("hardware");
-        let lora_adapter: String = row.try_get("lora_adapter").unwrap_or_default();
-
-        // Calculate tokens/kWh: (tokens/second × 3,600,000) / watts
-        let tokens_per_kwh = if let (Some(speed), Some(power)) = (tokens_per_second, gpu_power_watts) {
-            if power > 0.0 {
-                Some((speed * 3_600_000.0) / power)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        
-        // Skip entries without any performance data (benchmark-only entries)
-        if tokens_per_second.is_none() && memory_gb.is_none() {
-            continue;
-        }
-        
-        // Also skip obvious generic entries
-        if gpu_model.contains("Generic") || cpu_model.contains("Generic") || 
-           gpu_model.contains("Benchmark Only") || cpu_model.contains("Benchmark Only") {
-            continue;
-        }
-        
-        // Determine hardware category
-        let hardware_category = determine_hardware_category(&gpu_model, &cpu_model);
-        
+    for Candidate { model_name, gpu_model, cpu_model, tokens_per_second, memory_gb, quality_score, config } in candidates {
         // Apply hardware category filter
-        if !filter_categories.is_empty() && !filter_categories.contains(&hardware_category) {
+        if !filter_categories.is_empty() && !filter_categories.contains(&config.hardware_category) {
             continue;
         }
         
@@ -228,25 +260,6 @@ pub async fn get_grouped_performance(
                 continue;
             }
         }
-        
-        let config = QuantizationPerformance {
-            id: row.get("id"),
-            quantization: row.get("quantization"),
-            lora_adapter: lora_adapter.clone(),
-            quality_score: quality_score.unwrap_or(0.0),
-            tokens_per_second: tokens_per_second.unwrap_or(0.0),
-            memory_gb: memory_gb.unwrap_or(0.0),
-            backend: row.get("backend"),
-            hardware: hardware.clone(),
-            hardware_category,
-            concurrent_requests,
-            max_context_length,
-            load_pattern,
-            dataset_name,
-            gpu_power_limit_watts,
-            gpu_power_watts,
-            tokens_per_kwh,
-        };
 
         // Group by (model, lora_adapter) → hardware platform
         // Use gpu_model as key for GPU workloads, cpu_model for CPU-only
@@ -258,10 +271,10 @@ pub async fn get_grouped_performance(
 
         // Include lora_adapter in the model-level grouping key so LoRA variants
         // appear as separate rows with different quality scores but same performance
-        let model_key = if lora_adapter.is_empty() {
+        let model_key = if config.lora_adapter.is_empty() {
             model_name.clone()
         } else {
-            format!("{}||lora:{}", model_name, lora_adapter)
+            format!("{}||lora:{}", model_name, config.lora_adapter)
         };
 
         model_hardware_groups
@@ -350,7 +363,7 @@ pub async fn get_grouped_performance(
     };
 
     // Convert to response format - build hardware platforms per model
-    let mut models: Vec<ModelPerformanceGroup> = model_hardware_groups
+    let models: Vec<ModelPerformanceGroup> = model_hardware_groups
         .into_iter()
         .filter_map(|(model_key, hardware_map)| {
             if hardware_map.is_empty() {
@@ -467,137 +480,295 @@ pub async fn get_grouped_performance(
         })
         .collect();
     
-    // Apply sorting with tiebreakers
-    use std::cmp::Ordering;
-
-    match params.sort_by.as_deref() {
-        Some("quality") => {
-            models.sort_by(|a, b| {
-                // Primary: quality
-                let quality_cmp = b.best_hardware.best_config.quality_score
-                    .partial_cmp(&a.best_hardware.best_config.quality_score)
-                    .unwrap_or(Ordering::Equal);
-                if quality_cmp != Ordering::Equal {
-                    return if params.sort_direction.as_deref() == Some("asc") {
-                        quality_cmp.reverse()
-                    } else {
-                        quality_cmp
-                    };
-                }
-                // Tiebreaker 1: speed
-                let speed_cmp = b.best_hardware.best_config.tokens_per_second
-                    .partial_cmp(&a.best_hardware.best_config.tokens_per_second)
-                    .unwrap_or(Ordering::Equal);
-                if speed_cmp != Ordering::Equal {
-                    return speed_cmp;
-                }
-                // Tiebreaker 2: efficiency
-                let eff_a = a.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                let eff_b = b.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                eff_b.partial_cmp(&eff_a).unwrap_or(Ordering::Equal)
-            });
+    let order = GroupOrder::from_params(params.sort_by.as_deref(), params.sort_direction.as_deref());
+    let after = params
+        .cursor
+        .as_deref()
+        .map(|cursor| GroupCursor::decode(cursor, order))
+        .transpose()
+        .map_err(AppError::BadRequest)?;
+
+    let total_count = models.len();
+    let (models, next_cursor) = paginate(models, order, after.as_ref(), params.limit);
+
+    Ok(Json(GroupedPerformanceResponse {
+        models,
+        total_count,
+        benchmark_used: benchmark.to_string(),
+        next_cursor,
+    }))
+}
+
+/// Field models are ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortField {
+    Quality,
+    Speed,
+    Efficiency,
+    Memory,
+    ModelName,
+}
+
+/// Order of models in the grouped view. Quality, speed and efficiency rank
+/// highest first and break ties on the other two; memory and model name rank
+/// ascending. `reversed` flips the primary field only. Ties left after that
+/// fall to model name then LoRA adapter, so the order is total.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct GroupOrder {
+    field: SortField,
+    reversed: bool,
+}
+
+impl GroupOrder {
+    /// Order for `sort_by` and `sort_direction`; an unknown or missing field
+    /// is quality, highest first
+    fn from_params(sort_by: Option<&str>, sort_direction: Option<&str>) -> Self {
+        let (field, reversing_direction) = match sort_by {
+            Some("quality") => (SortField::Quality, "asc"),
+            Some("speed") => (SortField::Speed, "asc"),
+            Some("efficiency") => (SortField::Efficiency, "asc"),
+            Some("memory") => (SortField::Memory, "desc"),
+            Some("model_name") => (SortField::ModelName, "desc"),
+            _ => return Self { field: SortField::Quality, reversed: false },
+        };
+        Self {
+            field,
+            reversed: sort_direction == Some(reversing_direction),
         }
-        Some("speed") => {
-            models.sort_by(|a, b| {
-                // Primary: speed
-                let speed_cmp = b.best_hardware.best_config.tokens_per_second
-                    .partial_cmp(&a.best_hardware.best_config.tokens_per_second)
-                    .unwrap_or(Ordering::Equal);
-                if speed_cmp != Ordering::Equal {
-                    return if params.sort_direction.as_deref() == Some("asc") {
-                        speed_cmp.reverse()
-                    } else {
-                        speed_cmp
-                    };
-                }
-                // Tiebreaker 1: quality
-                let quality_cmp = b.best_hardware.best_config.quality_score
-                    .partial_cmp(&a.best_hardware.best_config.quality_score)
-                    .unwrap_or(Ordering::Equal);
-                if quality_cmp != Ordering::Equal {
-                    return quality_cmp;
-                }
-                // Tiebreaker 2: efficiency
-                let eff_a = a.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                let eff_b = b.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                eff_b.partial_cmp(&eff_a).unwrap_or(Ordering::Equal)
-            });
+    }
+
+    fn compare(self, a: &GroupSortKey, b: &GroupSortKey) -> Ordering {
+        let higher_first = |a: f64, b: f64| b.total_cmp(&a);
+        let by_name = a.model_name.cmp(&b.model_name).then_with(|| a.lora_adapter.cmp(&b.lora_adapter));
+
+        let primary = match self.field {
+            SortField::Quality => higher_first(a.quality, b.quality),
+            SortField::Speed => higher_first(a.speed, b.speed),
+            SortField::Efficiency => higher_first(a.efficiency, b.efficiency),
+            SortField::Memory => a.memory.total_cmp(&b.memory),
+            SortField::ModelName => by_name,
+        };
+        let primary = if self.reversed { primary.reverse() } else { primary };
+
+        let tiebreakers = match self.field {
+            SortField::Quality => higher_first(a.speed, b.speed)
+                .then(higher_first(a.efficiency, b.efficiency)),
+            SortField::Speed => higher_first(a.quality, b.quality)
+                .then(higher_first(a.efficiency, b.efficiency)),
+            SortField::Efficiency => higher_first(a.quality, b.quality)
+                .then(higher_first(a.speed, b.speed)),
+            SortField::Memory | SortField::ModelName => Ordering::Equal,
+        };
+
+        primary.then(tiebreakers).then(by_name)
+    }
+}
+
+/// Everything a `GroupOrder` compares, taken from a model's best config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct GroupSortKey {
+    quality: f64,
+    speed: f64,
+    efficiency: f64,
+    memory: f64,
+    model_name: String,
+    lora_adapter: String,
+}
+
+impl GroupSortKey {
+    fn of(model: &ModelPerformanceGroup) -> Self {
+        let config = &model.best_hardware.best_config;
+        Self {
+            quality: cursor_safe(config.quality_score),
+            speed: cursor_safe(config.tokens_per_second),
+            efficiency: cursor_safe(config.tokens_per_kwh.unwrap_or(0.0)),
+            memory: cursor_safe(config.memory_gb),
+            model_name: model.model_name.clone(),
+            lora_adapter: config.lora_adapter.clone(),
         }
-        Some("efficiency") => {
-            models.sort_by(|a, b| {
-                // Primary: efficiency
-                let eff_a = a.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                let eff_b = b.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                let eff_cmp = eff_b.partial_cmp(&eff_a).unwrap_or(Ordering::Equal);
-                if eff_cmp != Ordering::Equal {
-                    return if params.sort_direction.as_deref() == Some("asc") {
-                        eff_cmp.reverse()
-                    } else {
-                        eff_cmp
-                    };
-                }
-                // Tiebreaker 1: quality
-                let quality_cmp = b.best_hardware.best_config.quality_score
-                    .partial_cmp(&a.best_hardware.best_config.quality_score)
-                    .unwrap_or(Ordering::Equal);
-                if quality_cmp != Ordering::Equal {
-                    return quality_cmp;
-                }
-                // Tiebreaker 2: speed
-                b.best_hardware.best_config.tokens_per_second
-                    .partial_cmp(&a.best_hardware.best_config.tokens_per_second)
-                    .unwrap_or(Ordering::Equal)
-            });
+    }
+}
+
+/// `value` as a finite number, since JSON turns NaN and infinities into
+/// null and the cursor wouldn't decode. NaN ranks as 0, like a missing
+/// efficiency; infinities become the largest finite values.
+fn cursor_safe(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(f64::MIN, f64::MAX)
+    }
+}
+
+/// Position after the last model of a page, handed out as base64url JSON.
+/// It carries the order it was issued under so it can't be replayed
+/// against another.
+#[derive(Debug, Serialize, Deserialize)]
+struct GroupCursor {
+    order: GroupOrder,
+    after: GroupSortKey,
+}
+
+impl GroupCursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).expect("cursor serializes"))
+    }
+
+    /// The key in `cursor`, which must have been issued under `order`
+    fn decode(cursor: &str, order: GroupOrder) -> Result<GroupSortKey, String> {
+        let cursor: GroupCursor = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| format!("Invalid cursor '{}'", cursor))?;
+        if cursor.order != order {
+            return Err("Cursor was issued for a different sort_by or sort_direction".to_string());
         }
-        Some("memory") => {
-            models.sort_by(|a, b| {
-                let cmp = a.best_hardware.best_config.memory_gb
-                    .partial_cmp(&b.best_hardware.best_config.memory_gb)
-                    .unwrap();
-                if params.sort_direction.as_deref() == Some("desc") {
-                    cmp.reverse()
-                } else {
-                    cmp
-                }
-            });
+        Ok(cursor.after)
+    }
+}
+
+/// Sort `models` by `order` and return up to `limit` of them after the
+/// `after` key (all of them without a limit), with the cursor for the next
+/// page if any models remain
+fn paginate(
+    models: Vec<ModelPerformanceGroup>,
+    order: GroupOrder,
+    after: Option<&GroupSortKey>,
+    limit: Option<usize>,
+) -> (Vec<ModelPerformanceGroup>, Option<String>) {
+    let mut keyed: Vec<(GroupSortKey, ModelPerformanceGroup)> = models
+        .into_iter()
+        .map(|model| (GroupSortKey::of(&model), model))
+        .collect();
+    keyed.sort_by(|(a, _), (b, _)| order.compare(a, b));
+
+    let start = after.map_or(0, |after| {
+        keyed.partition_point(|(key, _)| order.compare(key, after) != Ordering::Greater)
+    });
+    let end = limit.map_or(keyed.len(), |limit| (start + limit.max(1)).min(keyed.len()));
+
+    let next_cursor = (end < keyed.len()).then(|| {
+        GroupCursor { order, after: keyed[end - 1].0.clone() }.encode()
+    });
+    let page = keyed.drain(start..end).map(|(_, model)| model).collect();
+    (page, next_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::quantization_performance;
+
+    fn group(model_name: &str, lora_adapter: &str, quality: f64, speed: f64, memory: f64) -> ModelPerformanceGroup {
+        let best_config = QuantizationPerformance {
+            lora_adapter: lora_adapter.to_string(),
+            memory_gb: memory,
+            ..quantization_performance("Q4_K_M", speed, quality)
+        };
+        let best_hardware = HardwarePlatformPerformance {
+            hardware: "RTX 4090".to_string(),
+            hardware_category: HardwareCategory::ConsumerGpu,
+            best_config,
+            total_configs: 1,
+        };
+        ModelPerformanceGroup {
+            model_name: model_name.to_string(),
+            best_hardware,
+            total_hardware_platforms: 1,
+            qualifying_platforms: 1,
+            all_hardware_platforms: None,
         }
-        Some("model_name") => {
-            models.sort_by(|a, b| {
-                let cmp = a.model_name.cmp(&b.model_name);
-                if params.sort_direction.as_deref() == Some("desc") {
-                    cmp.reverse()
-                } else {
-                    cmp
-                }
-            });
+    }
+
+    /// Names (with LoRA adapter) of every model, read `limit` at a time
+    fn page_through(models: impl Fn() -> Vec<ModelPerformanceGroup>, order: GroupOrder, limit: usize) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let after = cursor.as_deref().map(|cursor| GroupCursor::decode(cursor, order).unwrap());
+            let (page, next_cursor) = paginate(models(), order, after.as_ref(), Some(limit));
+            assert!(page.len() <= limit);
+            seen.extend(page.iter().map(|model| {
+                format!("{}+{}", model.model_name, model.best_hardware.best_config.lora_adapter)
+            }));
+            match next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return seen,
+            }
         }
-        _ => {
-            // Default: sort by quality score descending with tiebreakers
-            models.sort_by(|a, b| {
-                let quality_cmp = b.best_hardware.best_config.quality_score
-                    .partial_cmp(&a.best_hardware.best_config.quality_score)
-                    .unwrap_or(Ordering::Equal);
-                if quality_cmp != Ordering::Equal {
-                    return quality_cmp;
-                }
-                let speed_cmp = b.best_hardware.best_config.tokens_per_second
-                    .partial_cmp(&a.best_hardware.best_config.tokens_per_second)
-                    .unwrap_or(Ordering::Equal);
-                if speed_cmp != Ordering::Equal {
-                    return speed_cmp;
+    }
+
+    #[test]
+    fn test_paginate_visits_every_model_once() {
+        // Ties on every sort field, including models that differ only by LoRA
+        let models = || {
+            vec![
+                group("qwen-7b", "", 70.0, 50.0, 8.0),
+                group("llama-8b", "", 70.0, 50.0, 8.0),
+                group("llama-8b", "code", 70.0, 50.0, 8.0),
+                group("mistral-7b", "", 65.0, 80.0, 6.0),
+                group("phi-3", "", 55.0, 120.0, 4.0),
+                group("gemma-9b", "", 72.0, 40.0, 10.0),
+                group("llama-70b", "", 80.0, 10.0, 40.0),
+                group("qwen-14b", "", 70.0, 30.0, 8.0),
+            ]
+        };
+
+        for sort_by in ["quality", "speed", "efficiency", "memory", "model_name", "unknown"] {
+            for direction in ["asc", "desc"] {
+                let order = GroupOrder::from_params(Some(sort_by), Some(direction));
+                let (all, next_cursor) = paginate(models(), order, None, None);
+                assert!(next_cursor.is_none());
+                let expected: Vec<String> = all
+                    .iter()
+                    .map(|model| format!("{}+{}", model.model_name, model.best_hardware.best_config.lora_adapter))
+                    .collect();
+
+                for limit in 1..=9 {
+                    assert_eq!(
+                        page_through(models, order, limit),
+                        expected,
+                        "sort_by={} sort_direction={} limit={}",
+                        sort_by,
+                        direction,
+                        limit
+                    );
                 }
-                let eff_a = a.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                let eff_b = b.best_hardware.best_config.tokens_per_kwh.unwrap_or(0.0);
-                eff_b.partial_cmp(&eff_a).unwrap_or(Ordering::Equal)
-            });
+            }
         }
     }
-    
-    let total_count = models.len();
-    
-    Ok(Json(GroupedPerformanceResponse {
-        models,
-        total_count,
-        benchmark_used: benchmark.to_string(),
-    }))
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cursor_with_non_finite_values() {
+        let models = || {
+            vec![
+                group("a", "", f64::NAN, 10.0, 1.0),
+                group("b", "", 50.0, f64::INFINITY, f64::NAN),
+                group("c", "", 60.0, 5.0, f64::NEG_INFINITY),
+                group("d", "", 70.0, 1.0, 2.0),
+            ]
+        };
+
+        for sort_by in ["quality", "speed", "memory"] {
+            let order = GroupOrder::from_params(Some(sort_by), None);
+            let (all, _) = paginate(models(), order, None, None);
+            let expected: Vec<String> = all.iter().map(|model| format!("{}+", model.model_name)).collect();
+            assert_eq!(page_through(models, order, 1), expected, "sort_by={}", sort_by);
+        }
+    }
+
+    #[test]
+    fn test_cursor_rejects_other_order() {
+        let order = GroupOrder::from_params(Some("speed"), None);
+        let models = vec![group("a", "", 1.0, 1.0, 1.0), group("b", "", 1.0, 1.0, 1.0)];
+        let (_, cursor) = paginate(models, order, None, Some(1));
+        let cursor = cursor.unwrap();
+
+        assert!(GroupCursor::decode(&cursor, order).is_ok());
+        assert!(GroupCursor::decode(&cursor, GroupOrder::from_params(Some("speed"), Some("asc"))).is_err());
+        assert!(GroupCursor::decode(&cursor, GroupOrder::from_params(Some("memory"), None)).is_err());
+        assert!(GroupCursor::decode("not a cursor", order).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+// handlers/hardware.rs
+// GPU and CPU models seen in hardware profiles, for filter dropdowns
+
+use axum::{extract::State, response::Json};
+
+use llm_benchmark_types::{CpuModelCount, GpuModelCount, HardwarePlaceholder};
+
+use crate::{
+    error::{AppError, DbContext},
+    AppState,
+};
+
+/// Every distinct GPU model in the hardware profiles with its number of
+/// completed test runs, most-run first. Placeholders ("CPU Only", the
+/// benchmark-only profile) are included and flagged in `placeholder`.
+pub async fn list_gpus(State(state): State<AppState>) -> Result<Json<Vec<GpuModelCount>>, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT hp.gpu_model, COUNT(tr.id) as "test_run_count!"
+        FROM hardware_profiles hp
+        LEFT JOIN test_runs tr ON tr.hardware_profile_id = hp.id AND tr.status = 'completed'
+        GROUP BY hp.gpu_model
+        ORDER BY 2 DESC, hp.gpu_model
+        "#
+    )
+    .fetch_all(&state.db)
+    .await
+    .db_context("Failed to list GPU models")?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| GpuModelCount {
+                placeholder: HardwarePlaceholder::of_gpu_model(&row.gpu_model),
+                gpu_model: row.gpu_model,
+                test_run_count: row.test_run_count,
+            })
+            .collect(),
+    ))
+}
+
+/// Every distinct CPU model and architecture in the hardware profiles with
+/// its number of completed test runs, most-run first. The benchmark-only
+/// placeholder is included and flagged in `placeholder`.
+pub async fn list_cpus(State(state): State<AppState>) -> Result<Json<Vec<CpuModelCount>>, AppError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT hp.cpu_model, hp.cpu_arch, COUNT(tr.id) as "test_run_count!"
+        FROM hardware_profiles hp
+        LEFT JOIN test_runs tr ON tr.hardware_profile_id = hp.id AND tr.status = 'completed'
+        GROUP BY hp.cpu_model, hp.cpu_arch
+        ORDER BY 3 DESC, hp.cpu_model, hp.cpu_arch
+        "#
+    )
+    .fetch_all(&state.db)
+    .await
+    .db_context("Failed to list CPU models")?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| CpuModelCount {
+                placeholder: HardwarePlaceholder::of_cpu_model(&row.cpu_model),
+                cpu_model: row.cpu_model,
+                cpu_arch: row.cpu_arch,
+                test_run_count: row.test_run_count,
+            })
+            .collect(),
+    ))
+}
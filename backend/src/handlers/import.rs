@@ -0,0 +1,305 @@
+// handlers/import.rs
+// Bulk import of experiment runs from JSON Lines, the inverse of /api/export
+
+use axum::{body::Body, extract::State, response::Json};
+use tokio_stream::StreamExt;
+
+use llm_benchmark_types::{ExperimentRun, ImportLineError, ImportResponse, Validate};
+
+use crate::{
+    db::{with_retry_tx, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    handlers::{
+        events::publish_run_uploaded,
+        experiment::{write_unless_duplicate, Written},
+        test_run_log::MAX_LOG_BODY_BYTES,
+    },
+    AppState,
+};
+
+/// Runs written per transaction
+const IMPORT_BATCH_SIZE: usize = 100;
+
+/// Longest line accepted, the same as the single-run upload body limit
+const MAX_IMPORT_LINE_BYTES: usize = MAX_LOG_BODY_BYTES;
+
+/// Import experiment runs from a JSON Lines body, one `ExperimentRun` per
+/// line as written by `/api/export`. Blank lines are ignored.
+///
+/// The body is read as it arrives and valid runs are written in
+/// transactions of `IMPORT_BATCH_SIZE`, keeping their IDs and timestamps.
+/// Re-importing a run replaces the one with its ID, and a run identical to
+/// a stored one under another ID is skipped as a duplicate, so importing
+/// the same export twice is harmless. Lines that aren't valid JSON or fail
+/// validation are reported and skipped. A batch that fails to write is
+/// rolled back and its runs retried one per transaction, so only the lines
+/// that fail on their own are reported with the database error.
+pub async fn import_runs(
+    State(state): State<AppState>,
+    body: Body,
+) -> Result<Json<ImportResponse>, AppError> {
+    let mut chunks = body.into_data_stream();
+    let mut lines = LineSplitter::new(MAX_IMPORT_LINE_BYTES);
+    let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+    let mut response = ImportResponse::default();
+
+    loop {
+        let chunk = chunks.next().await.transpose().map_err(|e| {
+            AppError::BadRequest(format!("Failed to read request body: {}", e))
+        })?;
+        let end = chunk.is_none();
+        let chunk_lines = match &chunk {
+            Some(chunk) => lines.push(chunk),
+            None => lines.finish().into_iter().collect(),
+        };
+
+        for (line_number, line) in chunk_lines {
+            match parse_line(line) {
+                Ok(Some(run)) => batch.push((line_number, run)),
+                Ok(None) => {}
+                Err(error) => response.errors.push(ImportLineError { line: line_number, error }),
+            }
+            if batch.len() == IMPORT_BATCH_SIZE {
+                write_batch(&state, std::mem::take(&mut batch), &mut response).await;
+            }
+        }
+
+        if end {
+            break;
+        }
+    }
+    write_batch(&state, batch, &mut response).await;
+
+    response.errors.sort_by_key(|error| error.line);
+    response.failed = response.errors.len();
+    Ok(Json(response))
+}
+
+/// The run on a line, None for a blank line
+fn parse_line(line: Result<Vec<u8>, usize>) -> Result<Option<ExperimentRun>, String> {
+    let line = line.map_err(|length| {
+        format!("Line is {} bytes, over the {} byte limit", length, MAX_IMPORT_LINE_BYTES)
+    })?;
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+
+    let run: ExperimentRun =
+        serde_json::from_slice(&line).map_err(|e| format!("Invalid JSON: {}", e))?;
+    run.validate().map_err(|e| format!("Validation error: {}", e))?;
+    Ok(Some(run))
+}
+
+/// Write `batch` in one transaction, recording the outcome of each line.
+/// If the transaction fails, each run is written in its own instead.
+async fn write_batch(
+    state: &AppState,
+    batch: Vec<(usize, ExperimentRun)>,
+    response: &mut ImportResponse,
+) {
+    if batch.is_empty() {
+        return;
+    }
+
+    match write_runs(state, &batch).await {
+        Err(_) if batch.len() > 1 => {
+            for run in &batch {
+                let run = std::slice::from_ref(run);
+                record_outcome(state, run, write_runs(state, run).await, response);
+            }
+        }
+        written => record_outcome(state, &batch, written, response),
+    }
+}
+
+/// Write `runs` in one transaction, returning the database error's message
+/// if it fails
+async fn write_runs(state: &AppState, runs: &[(usize, ExperimentRun)]) -> Result<Vec<Written>, String> {
+    with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, runs, |tx, runs| {
+        Box::pin(async move {
+            let mut written = Vec::with_capacity(runs.len());
+            for (_, run) in runs {
+                written.push(write_unless_duplicate(tx, run, false).await?);
+            }
+            Ok(written)
+        })
+    })
+    .await
+    .map_err(|error| AppError::from(error).message())
+}
+
+/// Count the runs written by `write_runs`, announcing the stored ones, or
+/// report its error against each of their lines
+fn record_outcome(
+    state: &AppState,
+    runs: &[(usize, ExperimentRun)],
+    written: Result<Vec<Written>, String>,
+    response: &mut ImportResponse,
+) {
+    match written {
+        Ok(written) => {
+            for ((_, run), written) in runs.iter().zip(written) {
+                match written {
+                    Written::Stored(test_run_id) => {
+                        publish_run_uploaded(state, test_run_id, &run.model_name);
+                        response.imported += 1;
+                    }
+                    Written::Duplicate(_) => response.duplicates += 1,
+                }
+            }
+        }
+        Err(error) => response.errors.extend(runs.iter().map(|(line, _)| ImportLineError {
+            line: *line,
+            error: error.clone(),
+        })),
+    }
+}
+
+/// Splits a body arriving in chunks into numbered lines. A line longer than
+/// `max_line_bytes` is dropped as it arrives and yielded as `Err(length)`.
+struct LineSplitter {
+    max_line_bytes: usize,
+    pending: Vec<u8>,
+    line_number: usize,
+    /// Length so far of the oversized line being dropped, if any
+    overflow: Option<usize>,
+}
+
+impl LineSplitter {
+    fn new(max_line_bytes: usize) -> Self {
+        Self {
+            max_line_bytes,
+            pending: Vec::new(),
+            line_number: 0,
+            overflow: None,
+        }
+    }
+
+    /// Lines completed by `chunk`
+    fn push(&mut self, chunk: &[u8]) -> Vec<(usize, Result<Vec<u8>, usize>)> {
+        let mut lines = Vec::new();
+        for piece in chunk.split_inclusive(|&b| b == b'\n') {
+            let (piece, complete) = match piece.strip_suffix(b"\n") {
+                Some(piece) => (piece, true),
+                None => (piece, false),
+            };
+
+            match &mut self.overflow {
+                Some(length) => *length += piece.len(),
+                None if self.pending.len() + piece.len() > self.max_line_bytes => {
+                    self.overflow = Some(self.pending.len() + piece.len());
+                    self.pending = Vec::new();
+                }
+                None => self.pending.extend_from_slice(piece),
+            }
+
+            if complete {
+                lines.push(self.take_line());
+            }
+        }
+        lines
+    }
+
+    /// The final line, if the body didn't end with a newline
+    fn finish(&mut self) -> Option<(usize, Result<Vec<u8>, usize>)> {
+        (self.overflow.is_some() || !self.pending.is_empty()).then(|| self.take_line())
+    }
+
+    fn take_line(&mut self) -> (usize, Result<Vec<u8>, usize>) {
+        self.line_number += 1;
+        let line = match self.overflow.take() {
+            Some(length) => Err(length),
+            None => Ok(std::mem::take(&mut self.pending)),
+        };
+        (self.line_number, line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(splitter: &mut LineSplitter, chunks: &[&[u8]]) -> Vec<(usize, Result<Vec<u8>, usize>)> {
+        let mut lines: Vec<_> = chunks.iter().flat_map(|chunk| splitter.push(chunk)).collect();
+        lines.extend(splitter.finish());
+        lines
+    }
+
+    #[test]
+    fn test_line_splitter_joins_chunks() {
+        let mut splitter = LineSplitter::new(100);
+        assert_eq!(
+            lines(&mut splitter, &[b"{\"a\"", b":1}\n\n{\"b\":2}\n{\"c", b"\":3}"]),
+            vec![
+                (1, Ok(b"{\"a\":1}".to_vec())),
+                (2, Ok(Vec::new())),
+                (3, Ok(b"{\"b\":2}".to_vec())),
+                (4, Ok(b"{\"c\":3}".to_vec())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_splitter_drops_long_lines() {
+        let mut splitter = LineSplitter::new(4);
+        assert_eq!(
+            lines(&mut splitter, &[b"abc", b"defg\nhij\nklmno", b"pq"]),
+            vec![(1, Err(7)), (2, Ok(b"hij".to_vec())), (3, Err(7))]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_write_batch_reports_only_failing_lines() {
+        use llm_benchmark_types::{metric_names, HardwareConfig, PerformanceMetric};
+        use tokio::sync::{broadcast, watch};
+        use uuid::Uuid;
+
+        let (events, _) = broadcast::channel(1);
+        let (_shutdown_sender, shutdown) = watch::channel(false);
+        let state = AppState {
+            db: crate::db::test_pool().await,
+            events,
+            permalinks: crate::permalink::PermalinkSigner::new(b"test secret"),
+            api_key: crate::auth::ApiKey::new(None),
+            shutdown,
+        };
+
+        let model_name = format!("import-test-{}", Uuid::new_v4());
+        let batch: Vec<(usize, ExperimentRun)> = (1..=3)
+            .map(|line| {
+                let mut run = ExperimentRun::new(
+                    Uuid::new_v4(),
+                    model_name.clone(),
+                    "Q4_K_M".to_string(),
+                    "llama.cpp".to_string(),
+                    format!("b{}", line),
+                    HardwareConfig::cpu_only("AMD EPYC 7763".to_string(), "Zen3".to_string(), Some(256), None),
+                );
+                run.add_performance_metric(PerformanceMetric::new(
+                    metric_names::TOKENS_PER_SECOND.to_string(),
+                    42.0,
+                    "tok/s".to_string(),
+                ));
+                // Too long for the column, so only this run fails to write
+                if line == 2 {
+                    run.load_pattern = Some("x".repeat(60));
+                }
+                (line, run)
+            })
+            .collect();
+
+        let mut response = ImportResponse::default();
+        write_batch(&state, batch, &mut response).await;
+
+        sqlx::query("DELETE FROM test_runs WHERE model_name = $1")
+            .bind(&model_name)
+            .execute(&state.db)
+            .await
+            .unwrap();
+
+        assert_eq!(response.imported, 2);
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].line, 2);
+    }
+}
@@ -0,0 +1,97 @@
+// handlers/leaderboard.rs
+// Models ranked by their best score on one benchmark
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use sqlx::Row;
+
+use llm_benchmark_types::{LeaderboardEntry, LeaderboardRequest};
+
+use crate::{
+    error::{AppError, DbContext},
+    handlers::grouped_performance::{variant_score_sql, QUALITY_BENCHMARKS},
+    AppState,
+};
+
+const DEFAULT_LEADERBOARD_LIMIT: i64 = 20;
+const MAX_LEADERBOARD_LIMIT: i64 = 100;
+
+/// Top models on `benchmark`, one entry per model for its best-scoring
+/// quantization (base models only, no LoRA adapters).
+///
+/// Only quantizations with a completed run on real hardware are ranked, so
+/// scores uploaded against the "Generic (Benchmark Only)" placeholder
+/// don't appear on their own.
+pub async fn get_leaderboard(
+    Query(params): Query<LeaderboardRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<LeaderboardEntry>>, AppError> {
+    let benchmark = params.benchmark.as_deref().unwrap_or("mmlu").to_lowercase();
+    let score_sql = variant_score_sql(&benchmark).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Unknown benchmark '{}' (expected one of {})",
+            benchmark,
+            QUALITY_BENCHMARKS.join(", ")
+        ))
+    })?;
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_LEADERBOARD_LIMIT)
+        .clamp(1, MAX_LEADERBOARD_LIMIT);
+
+    let query = format!(
+        r#"
+        WITH scored AS (
+            SELECT
+                mv.model_name,
+                mv.quantization,
+                {} as score,
+                (
+                    SELECT tr.backend
+                    FROM test_runs tr
+                    JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+                    WHERE tr.model_name = mv.model_name
+                      AND tr.quantization = mv.quantization
+                      AND tr.lora_adapter = mv.lora_adapter
+                      AND tr.status = 'completed'
+                      AND hp.gpu_model NOT LIKE '%Generic%' AND hp.cpu_model NOT LIKE '%Generic%'
+                      AND hp.gpu_model NOT LIKE '%Benchmark Only%' AND hp.cpu_model NOT LIKE '%Benchmark Only%'
+                    ORDER BY tr.timestamp DESC NULLS LAST
+                    LIMIT 1
+                ) as backend
+            FROM model_variants mv
+            WHERE mv.lora_adapter = ''
+        ),
+        best AS (
+            SELECT DISTINCT ON (model_name) model_name, quantization, score, backend
+            FROM scored
+            WHERE score IS NOT NULL AND backend IS NOT NULL
+            ORDER BY model_name, score DESC, quantization
+        )
+        SELECT model_name, quantization, score, backend
+        FROM best
+        ORDER BY score DESC, model_name
+        LIMIT $1
+        "#,
+        score_sql
+    );
+
+    let rows = sqlx::query(&query)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+        .db_context("Failed to load leaderboard")?;
+
+    Ok(Json(
+        rows.into_iter()
+            .map(|row| LeaderboardEntry {
+                model_name: row.get("model_name"),
+                best_quantization: row.get("quantization"),
+                score: row.get("score"),
+                backend: row.get("backend"),
+            })
+            .collect(),
+    ))
+}
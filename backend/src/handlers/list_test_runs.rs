@@ -8,9 +8,10 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::AppState;
+use crate::{handlers::model_search::escape_like, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct ListTestRunsParams {
@@ -26,7 +27,7 @@ pub struct TestRunInfo {
     pub model_name: String,
     pub quantization: String,
     pub backend: String,
-    pub timestamp: DateTime<Utc>,
+    pub timestamp: Option<DateTime<Utc>>,
     pub has_performance_metrics: bool,
     pub benchmarks: Vec<String>,
 }
@@ -41,41 +42,9 @@ pub async fn list_test_runs(
     Query(params): Query<ListTestRunsParams>,
     State(state): State<AppState>,
 ) -> Result<Json<ListTestRunsResponse>, (StatusCode, Json<serde_json::Value>)> {
-    let limit = params.limit.unwrap_or(50).min(200); // Max 200 results
-    
-    // Build the query
-    let mut query = String::from(
-        r#"
-        SELECT DISTINCT
-            tr.id,
-            tr.model_name,
-            tr.quantization,
-            tr.backend,
-            tr.timestamp,
-            EXISTS(SELECT 1 FROM performance_metrics WHERE test_run_id = tr.id) as has_performance_metrics
-        FROM test_runs tr
-        WHERE tr.status = 'completed'
-        "#
-    );
-    
-    let mut conditions = Vec::new();
-    
-    if let Some(model) = &params.model_name {
-        conditions.push(format!("tr.model_name ILIKE '%{}%'", model));
-    }
-    
-    if let Some(quant) = &params.quantization {
-        conditions.push(format!("tr.quantization = '{}'", quant));
-    }
-    
-    if !conditions.is_empty() {
-        query.push_str(&format!(" AND {}", conditions.join(" AND ")));
-    }
-    
-    query.push_str(&format!(" ORDER BY tr.timestamp DESC LIMIT {}", limit));
-    
     // Execute main query
-    let rows = sqlx::query_as::<_, TestRunRow>(&query)
+    let rows = test_runs_query(&params)?
+        .build_query_as::<TestRunRow>()
         .fetch_all(&state.db)
         .await
         .map_err(|e| {
@@ -112,13 +81,79 @@ pub async fn list_test_runs(
     }))
 }
 
+/// Score tables checked for each run, with the benchmark name reported for them
+const BENCHMARK_TABLES: [(&str, &str); 6] = [
+    ("mmlu_scores", "MMLU"),
+    ("gsm8k_scores", "GSM8K"),
+    ("humaneval_scores", "HumanEval"),
+    ("hellaswag_scores", "HellaSwag"),
+    ("truthfulqa_scores", "TruthfulQA"),
+    ("generic_benchmark_scores", "Generic"),
+];
+
+/// Build the listing query with the filters and limit bound as parameters.
+/// `benchmark_type` picks a table from `BENCHMARK_TABLES` and is rejected
+/// if it names none of them.
+fn test_runs_query(
+    params: &ListTestRunsParams,
+) -> Result<QueryBuilder<'static, Postgres>, (StatusCode, Json<serde_json::Value>)> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 200); // Max 200 results
+
+    let mut query = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT DISTINCT
+            tr.id,
+            tr.model_name,
+            tr.quantization,
+            tr.backend,
+            tr.timestamp,
+            EXISTS(SELECT 1 FROM performance_metrics WHERE test_run_id = tr.id) as has_performance_metrics
+        FROM test_runs tr
+        WHERE tr.status = 'completed'
+        "#,
+    );
+
+    if let Some(model) = &params.model_name {
+        query
+            .push(" AND tr.model_name ILIKE ")
+            .push_bind(format!("%{}%", escape_like(model)))
+            .push(" ESCAPE '\\'");
+    }
+
+    if let Some(quant) = &params.quantization {
+        query.push(" AND tr.quantization = ").push_bind(quant.clone());
+    }
+
+    if let Some(benchmark) = &params.benchmark_type {
+        let (table, _) = BENCHMARK_TABLES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(benchmark))
+            .ok_or_else(|| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": format!("Unknown benchmark type: {}", benchmark)
+                    })),
+                )
+            })?;
+        query
+            .push(" AND EXISTS(SELECT 1 FROM ")
+            .push(*table)
+            .push(" WHERE test_run_id = tr.id)");
+    }
+
+    query.push(" ORDER BY tr.timestamp DESC NULLS LAST LIMIT ").push_bind(limit);
+
+    Ok(query)
+}
+
 #[derive(sqlx::FromRow)]
 struct TestRunRow {
     id: Uuid,
     model_name: String,
     quantization: String,
     backend: String,
-    timestamp: DateTime<Utc>,
+    timestamp: Option<DateTime<Utc>>,
     has_performance_metrics: bool,
 }
 
@@ -129,16 +164,7 @@ async fn get_benchmarks_for_test_run(
     let mut benchmarks = Vec::new();
     
     // Check each benchmark table
-    let tables = vec![
-        ("mmlu_scores", "MMLU"),
-        ("gsm8k_scores", "GSM8K"),
-        ("humaneval_scores", "HumanEval"),
-        ("hellaswag_scores", "HellaSwag"),
-        ("truthfulqa_scores", "TruthfulQA"),
-        ("generic_benchmark_scores", "Generic"),
-    ];
-    
-    for (table, name) in tables {
+    for (table, name) in BENCHMARK_TABLES {
         let query = format!("SELECT EXISTS(SELECT 1 FROM {} WHERE test_run_id = $1)", table);
         let exists: (bool,) = sqlx::query_as(&query)
             .bind(test_run_id)
@@ -159,4 +185,67 @@ async fn get_benchmarks_for_test_run(
     }
     
     Ok(benchmarks)
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_are_bound() {
+        let params = ListTestRunsParams {
+            model_name: Some("O'Brien_7B".to_string()),
+            quantization: Some("Q4_K_M' OR '1'='1".to_string()),
+            benchmark_type: Some("mmlu".to_string()),
+            limit: Some(1000),
+        };
+
+        let query = test_runs_query(&params).unwrap();
+        let sql = query.sql();
+        assert!(sql.contains("tr.model_name ILIKE $1"));
+        assert!(sql.contains("tr.quantization = $2"));
+        assert!(sql.contains("EXISTS(SELECT 1 FROM mmlu_scores WHERE test_run_id = tr.id)"));
+        assert!(sql.ends_with("LIMIT $3"));
+        assert!(!sql.contains("O'Brien"));
+
+        let unknown = ListTestRunsParams {
+            benchmark_type: Some("mmlu_scores; DROP TABLE test_runs".to_string()),
+            ..params
+        };
+        assert!(matches!(test_runs_query(&unknown), Err((StatusCode::BAD_REQUEST, _))));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_model_filter_matches_quotes_literally() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let suffix = Uuid::new_v4().simple().to_string();
+        for model_name in [format!("O'Brien_7B-{}", suffix), format!("O'BrienX7B-{}", suffix)] {
+            sqlx::query(
+                "INSERT INTO test_runs (model_name, quantization, backend, backend_version, status) \
+                 VALUES ($1, 'Q4_K_M', 'llama.cpp', 'b4000', 'completed')",
+            )
+            .bind(model_name)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        }
+
+        let params = ListTestRunsParams {
+            model_name: Some(format!("o'brien_7b-{}", suffix)),
+            quantization: None,
+            benchmark_type: None,
+            limit: None,
+        };
+        let rows = test_runs_query(&params)
+            .unwrap()
+            .build_query_as::<TestRunRow>()
+            .fetch_all(&mut *tx)
+            .await
+            .unwrap();
+        let names: Vec<String> = rows.into_iter().map(|row| row.model_name).collect();
+        assert_eq!(names, vec![format!("O'Brien_7B-{}", suffix)]);
+    }
+}
@@ -0,0 +1,79 @@
+// handlers/memory_fit.rs
+// "Will this model fit?" check against a given amount of GPU memory
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+
+use llm_benchmark_types::{
+    estimate_memory_gb, normalize_quantization, parameter_count_billions,
+    HardwareConfig, MemoryFitRequest, MemoryFitResponse,
+};
+
+use crate::{error::AppError, AppState};
+
+/// Check whether a model/quantization fits in the given GPU memory
+pub async fn check_fit(
+    Query(params): Query<MemoryFitRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<MemoryFitResponse>, AppError> {
+    if params.gpu_memory_gb <= 0 {
+        return Err(AppError::BadRequest("gpu_memory_gb must be positive".to_string()));
+    }
+
+    let quantization = normalize_quantization(&params.quantization);
+
+    // Prefer memory measured in real runs of this model variant
+    let measured = sqlx::query!(
+        r#"
+        SELECT MAX(pm.value) as max_memory_gb
+        FROM performance_metrics pm
+        JOIN test_runs tr ON pm.test_run_id = tr.id
+        WHERE tr.model_name = $1 AND tr.quantization = $2
+            AND pm.metric_name IN ('memory_usage_gb', 'peak_memory_gb')
+            AND pm.value > 0
+        "#,
+        params.model,
+        quantization
+    )
+    .fetch_one(&state.db)
+    .await?
+    .max_memory_gb;
+
+    let (required_memory_gb, source) = match measured {
+        Some(memory_gb) => (memory_gb, "measured"),
+        None => {
+            let estimated = parameter_count_billions(&params.model)
+                .and_then(|params_b| estimate_memory_gb(params_b, &quantization))
+                .ok_or_else(|| {
+                    AppError::NotFound(format!(
+                        "No memory data for {} {} and its size can't be estimated",
+                        params.model, quantization
+                    ))
+                })?;
+            (estimated, "estimated")
+        }
+    };
+
+    let hardware = HardwareConfig::new(
+        "Requested GPU".to_string(),
+        params.gpu_memory_gb,
+        String::new(),
+        String::new(),
+        None,
+        None,
+    );
+    let fits = hardware.supports_memory_gb(required_memory_gb.ceil() as i32);
+    let available_gb = hardware.effective_memory_gb().unwrap_or(0) as f64;
+
+    Ok(Json(MemoryFitResponse {
+        model_name: params.model,
+        quantization,
+        gpu_memory_gb: params.gpu_memory_gb,
+        required_memory_gb,
+        source: source.to_string(),
+        fits,
+        headroom_gb: available_gb - required_memory_gb,
+    }))
+}
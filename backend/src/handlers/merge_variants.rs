@@ -0,0 +1,167 @@
+// handlers/merge_variants.rs
+// Admin cleanup: merge duplicate model variants
+
+use axum::{extract::State, response::Json};
+use uuid::Uuid;
+
+use llm_benchmark_types::{MergeVariantsRequest, MergeVariantsResponse, MergedBenchmarkRows};
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    AppState,
+};
+
+/// v2 score tables as (benchmark, table, key column). Conflicts are resolved
+/// per key within a benchmark; without a key the variant's rows for that
+/// table are kept or dropped as a set, so an MMLU run is never mixed with
+/// categories from another run.
+pub(crate) const SCORE_TABLES: &[(&str, &str, Option<&str>)] = &[
+    ("mmlu", "mmlu_scores_v2", None),
+    ("gsm8k", "gsm8k_scores_v2", None),
+    ("humaneval", "humaneval_scores_v2", None),
+    ("hellaswag", "hellaswag_scores_v2", None),
+    ("truthfulqa", "truthfulqa_scores_v2", None),
+    ("gpqa", "gpqa_scores_v2", None),
+    ("generic", "generic_benchmark_scores_v2", Some("benchmark_name")),
+];
+
+/// Merge `from_id` into `into_id`: move its benchmark scores over, keeping
+/// the newer scores where both variants have results for a benchmark, then
+/// delete the emptied variant
+pub async fn merge_variants(
+    State(state): State<AppState>,
+    Json(request): Json<MergeVariantsRequest>,
+) -> Result<Json<MergeVariantsResponse>, AppError> {
+    if request.from_id == request.into_id {
+        return Err(AppError::BadRequest("from_id and into_id must differ".to_string()));
+    }
+
+    let benchmarks = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+        Box::pin(merge_variant_rows(tx, request.from_id, request.into_id))
+    })
+    .await?;
+
+    Ok(Json(MergeVariantsResponse {
+        into_id: request.into_id,
+        benchmarks,
+    }))
+}
+
+/// Merge `from_id` into `into_id` within `tx`, returning the score rows
+/// moved and dropped per benchmark
+pub(crate) async fn merge_variant_rows(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    from_id: Uuid,
+    into_id: Uuid,
+) -> Result<Vec<MergedBenchmarkRows>, TxError<AppError>> {
+    for id in [from_id, into_id] {
+        ensure_variant_exists(tx, id).await?;
+    }
+
+    let mut benchmarks = Vec::new();
+    for &(benchmark, table, key) in SCORE_TABLES {
+        let same_key = |a: &str, b: &str| match key {
+            Some(key) => format!("AND {a}.{key} = {b}.{key}"),
+            None => String::new(),
+        };
+
+        // Drop the target's rows where the source has newer ones
+        let replaced = sqlx::query(&format!(
+            r#"
+            DELETE FROM {table} dst
+            WHERE dst.model_variant_id = $2
+              AND (SELECT MAX(src.timestamp) FROM {table} src
+                   WHERE src.model_variant_id = $1 {src_key})
+                > (SELECT MAX(cur.timestamp) FROM {table} cur
+                   WHERE cur.model_variant_id = $2 {cur_key})
+            "#,
+            src_key = same_key("src", "dst"),
+            cur_key = same_key("cur", "dst"),
+        ))
+        .bind(from_id)
+        .bind(into_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context(&format!("Failed to replace {} scores", benchmark))?
+        .rows_affected();
+
+        // Whatever still conflicts is older (or no newer) than the target's
+        let superseded = sqlx::query(&format!(
+            r#"
+            DELETE FROM {table} src
+            WHERE src.model_variant_id = $1
+              AND EXISTS (SELECT 1 FROM {table} dst
+                          WHERE dst.model_variant_id = $2 {dst_key})
+            "#,
+            dst_key = same_key("dst", "src"),
+        ))
+        .bind(from_id)
+        .bind(into_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context(&format!("Failed to drop superseded {} scores", benchmark))?
+        .rows_affected();
+
+        let moved = sqlx::query(&format!(
+            "UPDATE {table} SET model_variant_id = $2 WHERE model_variant_id = $1"
+        ))
+        .bind(from_id)
+        .bind(into_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context(&format!("Failed to move {} scores", benchmark))?
+        .rows_affected();
+
+        benchmarks.push(MergedBenchmarkRows {
+            benchmark: benchmark.to_string(),
+            moved,
+            discarded: replaced + superseded,
+        });
+    }
+
+    // Superseded scores never conflict, so all of them move
+    sqlx::query!(
+        "UPDATE benchmark_score_history SET model_variant_id = $2 WHERE model_variant_id = $1",
+        from_id,
+        into_id
+    )
+    .execute(&mut **tx)
+    .await
+    .tx_context("Failed to move score history")?;
+
+    sqlx::query!("DELETE FROM model_variants WHERE id = $1", from_id)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to delete merged variant")?;
+
+    sqlx::query!(
+        "UPDATE model_variants SET updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+        into_id
+    )
+    .execute(&mut **tx)
+    .await
+    .tx_context("Failed to update model variant")?;
+
+    Ok(benchmarks)
+}
+
+/// Abort the transaction with a 404 if the model variant doesn't exist
+async fn ensure_variant_exists(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    id: Uuid,
+) -> Result<(), TxError<AppError>> {
+    let exists = sqlx::query!(
+        "SELECT id FROM model_variants WHERE id = $1",
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .tx_context("Failed to look up model variant")?;
+
+    if exists.is_none() {
+        return Err(TxError::Abort(AppError::NotFound(format!("Model variant {} not found", id))));
+    }
+
+    Ok(())
+}
@@ -8,20 +8,58 @@ pub mod experiment;
 pub mod grouped_performance;
 pub mod delete;
 pub mod model_hardware_analysis;
-// pub mod list_test_runs; // Disabled until migration is run
+pub mod model_card;
+pub mod memory_fit;
+pub mod find_config;
+pub mod pareto;
+pub mod leaderboard;
+pub mod model_search;
+pub mod score_history;
+pub mod model_variant_benchmarks;
+pub mod merge_variants;
+pub mod rename_model;
+pub mod test_run_log;
+pub mod test_run_benchmarks;
+pub mod annotation;
+pub mod events;
+pub mod export;
+pub mod import;
+pub mod hardware;
+pub mod stats;
+pub mod list_test_runs;
 // pub mod benchmark_upload; // Disabled until migration is run
 pub mod benchmark_upload_raw;
-// pub mod performance_v2; // Disabled until migration is run
+pub mod performance_v2;
+#[cfg(test)]
+mod test_support;
 
 // Re-export public handler functions for use in main.rs
-pub use performance::get_performance_grid;
-pub use comparison::get_comparison;
-pub use configuration::{get_configurations, get_detail};
-pub use experiment::upload_experiment;
+pub use performance::{get_performance_grid, get_performance_grid_csv};
+pub use comparison::{comparison_permalink, get_comparison, get_comparison_by_token, get_comparison_by_variant};
+pub use configuration::{get_configurations, get_detail, get_detail_metrics, get_detail_variance};
+pub use experiment::{upload_experiment, upload_experiments, upload_complete, validate_experiment};
 pub use grouped_performance::get_grouped_performance;
-pub use delete::{delete_test_run, delete_by_model_quant, delete_benchmark_scores};
+pub use delete::{delete_test_run, delete_by_model_quant, delete_model_variant, delete_benchmark_scores};
 pub use model_hardware_analysis::get_model_hardware_analysis;
-// pub use list_test_runs::list_test_runs; // Disabled until migration is run
+pub use model_card::{get_model_card, get_quality_retention};
+pub use memory_fit::check_fit;
+pub use find_config::find_config;
+pub use pareto::get_pareto;
+pub use leaderboard::get_leaderboard;
+pub use model_search::search_models;
+pub use score_history::get_score_history;
+pub use model_variant_benchmarks::get_model_variant_benchmarks;
+pub use merge_variants::merge_variants;
+pub use rename_model::rename_model;
+pub use test_run_log::{attach_log, get_log, MAX_LOG_BODY_BYTES};
+pub use test_run_benchmarks::attach_benchmarks;
+pub use annotation::annotate_test_run;
+pub use events::{stream_events, EVENT_CHANNEL_CAPACITY};
+pub use export::export_runs;
+pub use import::import_runs;
+pub use hardware::{list_cpus, list_gpus};
+pub use stats::get_stats;
+pub use list_test_runs::list_test_runs;
 // pub use benchmark_upload::upload_benchmarks; // Disabled until migration is run
 pub use benchmark_upload_raw::upload_benchmarks_raw;
-// pub use performance_v2::get_performance_grid_v2; // Disabled until migration is run
\ No newline at end of file
+pub use performance_v2::get_performance_grid_v2;
\ No newline at end of file
@@ -0,0 +1,269 @@
+// handlers/model_card.rs
+// Model card endpoint aggregating everything known about a model, and
+// quality retention across its quantizations
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use sqlx::PgPool;
+use std::collections::HashMap;
+
+use llm_benchmark_types::{
+    model_family, parameter_count_billions, quantization_sort_key, select_baseline_quantization,
+    ExperimentStatus, ExperimentSummary, HardwareCategory, ModelCard, ModelCardBenchmark, ModelCardHardware,
+    ModelCardRequest, QualityRetentionRequest, QualityRetentionResponse, QuantizationRetention,
+    RunAnnotation,
+};
+
+use crate::{error::AppError, AppState};
+
+const DEFAULT_RUNS_LIMIT: i64 = 50;
+const MAX_RUNS_LIMIT: i64 = 500;
+
+/// Get a model card with quantizations, hardware, benchmark scores and test runs
+pub async fn get_model_card(
+    Path(model_name): Path<String>,
+    Query(params): Query<ModelCardRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ModelCard>, AppError> {
+    let model_name = urlencoding::decode(&model_name)
+        .map_err(|e| AppError::BadRequest(format!("Invalid model name encoding: {}", e)))?
+        .to_string();
+
+    let offset = params.offset.unwrap_or(0).max(0);
+    let limit = params.limit.unwrap_or(DEFAULT_RUNS_LIMIT).clamp(1, MAX_RUNS_LIMIT);
+
+    // Quantizations from both test runs and benchmark-only model variants
+    let mut quantizations: Vec<String> = sqlx::query!(
+        r#"
+        SELECT quantization as "quantization!" FROM test_runs WHERE model_name = $1
+        UNION
+        SELECT quantization as "quantization!" FROM model_variants WHERE model_name = $1
+        "#,
+        model_name
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|row| row.quantization)
+    .collect();
+
+    if quantizations.is_empty() {
+        return Err(AppError::NotFound(format!("Model {} not found", model_name)));
+    }
+    quantizations.sort_by_key(|q| quantization_sort_key(q));
+
+    let hardware = sqlx::query!(
+        r#"
+        SELECT hp.gpu_model, hp.gpu_memory_gb, hp.cpu_model, hp.cpu_arch,
+               COUNT(*) as "run_count!"
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        WHERE tr.model_name = $1
+        GROUP BY hp.gpu_model, hp.gpu_memory_gb, hp.cpu_model, hp.cpu_arch
+        ORDER BY COUNT(*) DESC, hp.gpu_model
+        "#,
+        model_name
+    )
+    .fetch_all(&state.db)
+    .await?
+    .into_iter()
+    .map(|row| ModelCardHardware {
+        hardware_category: HardwareCategory::from_models(&row.gpu_model, &row.cpu_model),
+        gpu_model: row.gpu_model,
+        gpu_memory_gb: row.gpu_memory_gb,
+        cpu_model: row.cpu_model,
+        cpu_arch: row.cpu_arch,
+        run_count: row.run_count,
+    })
+    .collect();
+
+    let benchmark_scores = fetch_benchmark_scores(&state.db, &model_name)
+        .await?;
+
+    let total_runs = sqlx::query!(
+        r#"SELECT COUNT(*) as "count!" FROM test_runs WHERE model_name = $1"#,
+        model_name
+    )
+    .fetch_one(&state.db)
+    .await?
+    .count as usize;
+
+    let run_rows = sqlx::query!(
+        r#"
+        SELECT
+            tr.id,
+            tr.model_name,
+            tr.quantization,
+            tr.backend,
+            CONCAT(hp.gpu_model, ' / ', hp.cpu_arch) as hardware_summary,
+            tr.timestamp,
+            tr.status,
+            ra.canonical as "canonical?",
+            ra.deprecated as "deprecated?",
+            ra.reason
+        FROM test_runs tr
+        JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
+        LEFT JOIN test_run_annotations ra ON ra.test_run_id = tr.id
+        WHERE tr.model_name = $1
+        ORDER BY tr.timestamp DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        model_name,
+        limit,
+        offset
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    // Base-model MMLU average per quantization, used as each run's overall score
+    let mmlu_by_quant: HashMap<&str, f64> = benchmark_scores
+        .iter()
+        .filter(|s| s.benchmark == "mmlu" && s.lora_adapter.is_empty())
+        .map(|s| (s.quantization.as_str(), s.score))
+        .collect();
+
+    let runs = run_rows
+        .into_iter()
+        .map(|row| ExperimentSummary {
+            overall_score: mmlu_by_quant.get(row.quantization.as_str()).copied(),
+            id: row.id,
+            model_name: row.model_name,
+            quantization: row.quantization,
+            backend: row.backend,
+            hardware_summary: row.hardware_summary.unwrap_or_default(),
+            timestamp: row.timestamp.unwrap_or_else(chrono::Utc::now),
+            status: match row.status.as_str() {
+                "pending" => ExperimentStatus::Pending,
+                "running" => ExperimentStatus::Running,
+                "failed" => ExperimentStatus::Failed,
+                "cancelled" => ExperimentStatus::Cancelled,
+                _ => ExperimentStatus::Completed,
+            },
+            annotation: match (row.canonical, row.deprecated) {
+                (Some(canonical), Some(deprecated)) => Some(RunAnnotation {
+                    canonical,
+                    deprecated,
+                    reason: row.reason,
+                }),
+                _ => None,
+            },
+        })
+        .collect();
+
+    Ok(Json(ModelCard {
+        family: model_family(&model_name),
+        parameter_count_b: parameter_count_billions(&model_name),
+        model_name,
+        quantizations,
+        hardware,
+        benchmark_scores,
+        runs,
+        total_runs,
+        offset,
+        limit,
+    }))
+}
+
+/// Headline score per benchmark for every variant of a model
+async fn fetch_benchmark_scores(
+    db: &PgPool,
+    model_name: &str,
+) -> Result<Vec<ModelCardBenchmark>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT mv.quantization as "quantization!", mv.lora_adapter as "lora_adapter!",
+               s.benchmark as "benchmark!", s.score as "score!"
+        FROM model_variants mv
+        JOIN (
+            SELECT model_variant_id, 'mmlu' as benchmark, AVG(score) as score
+            FROM mmlu_scores_v2 GROUP BY model_variant_id
+            UNION ALL
+            SELECT model_variant_id, 'gsm8k', accuracy * 100.0 FROM gsm8k_scores_v2
+            UNION ALL
+            SELECT model_variant_id, 'humaneval', pass_at_1 FROM humaneval_scores_v2
+            UNION ALL
+            SELECT model_variant_id, 'hellaswag', accuracy FROM hellaswag_scores_v2
+            UNION ALL
+            SELECT model_variant_id, 'truthfulqa', truthful_score FROM truthfulqa_scores_v2
+            UNION ALL
+            SELECT model_variant_id, benchmark_name, overall_score FROM generic_benchmark_scores_v2
+        ) s ON s.model_variant_id = mv.id
+        WHERE mv.model_name = $1
+        ORDER BY mv.lora_adapter, s.benchmark
+        "#,
+        model_name
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ModelCardBenchmark {
+            quantization: row.quantization,
+            lora_adapter: row.lora_adapter,
+            benchmark: row.benchmark,
+            score: row.score,
+        })
+        .collect())
+}
+
+/// Get each quantization's benchmark scores relative to the highest-precision
+/// variant of the model
+pub async fn get_quality_retention(
+    Path(model_name): Path<String>,
+    Query(params): Query<QualityRetentionRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<QualityRetentionResponse>, AppError> {
+    let model_name = urlencoding::decode(&model_name)
+        .map_err(|e| AppError::BadRequest(format!("Invalid model name encoding: {}", e)))?
+        .to_string();
+    let lora_adapter = params.lora.unwrap_or_default();
+
+    let scores: Vec<ModelCardBenchmark> = fetch_benchmark_scores(&state.db, &model_name)
+        .await?
+        .into_iter()
+        .filter(|s| s.lora_adapter == lora_adapter)
+        .collect();
+
+    let (baseline_quantization, baseline_is_full_precision) =
+        select_baseline_quantization(scores.iter().map(|s| s.quantization.as_str()))
+            .map(|(q, full)| (q.to_string(), full))
+            .ok_or_else(|| AppError::NotFound(format!("No benchmark scores for {}", model_name)))?;
+
+    let baseline_scores: HashMap<&str, f64> = scores
+        .iter()
+        .filter(|s| s.quantization == baseline_quantization)
+        .map(|s| (s.benchmark.as_str(), s.score))
+        .collect();
+
+    let mut retention: Vec<QuantizationRetention> = scores
+        .iter()
+        .map(|s| {
+            let baseline_score = baseline_scores.get(s.benchmark.as_str()).copied();
+            QuantizationRetention {
+                quantization: s.quantization.clone(),
+                benchmark: s.benchmark.clone(),
+                score: s.score,
+                baseline_score,
+                retention_percent: baseline_score
+                    .filter(|&b| b > 0.0)
+                    .map(|b| s.score / b * 100.0),
+            }
+        })
+        .collect();
+    retention.sort_by(|a, b| {
+        quantization_sort_key(&a.quantization)
+            .cmp(&quantization_sort_key(&b.quantization))
+            .then_with(|| a.benchmark.cmp(&b.benchmark))
+    });
+
+    Ok(Json(QualityRetentionResponse {
+        model_name,
+        lora_adapter,
+        baseline_quantization,
+        baseline_is_full_precision,
+        retention,
+    }))
+}
@@ -3,15 +3,14 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use llm_benchmark_types::ErrorResponse;
+use llm_benchmark_types::{is_cpu_only_gpu_model, mmlu_categories, quantization_sort_key, PerformanceMetric};
 
-use crate::AppState;
+use crate::{error::AppError, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct AnalysisQueryParams {
@@ -21,10 +20,16 @@ pub struct AnalysisQueryParams {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ModelHardwareAnalysis {
     pub model_name: String,
+    /// GPU model, or the CPU model for CPU-only runs
     pub gpu_model: String,
+    /// Runs had no GPU; the analysis is keyed on the CPU model
+    pub cpu_only: bool,
     pub total_configurations: usize,
     pub backends: Vec<BackendGroup>,
     pub quantizations: Vec<QuantizationSummary>,
+    /// `best_efficiency_power_limit` of the quantization whose mean
+    /// tokens/kWh at that limit is highest; None without power data
+    pub recommended_power_limit: Option<i32>,
     pub heatmap_data: HeatmapData,
 }
 
@@ -41,73 +46,59 @@ pub struct QuantizationSummary {
     pub best_speed: f64,
     pub best_ttft: Option<f64>,
     pub best_tokens_per_kwh: Option<f64>,
+    /// Power limit with the highest mean tokens/kWh across concurrency
+    /// levels; None when no run has both a power limit and power data
+    pub best_efficiency_power_limit: Option<i32>,
     pub quality_score: f64,
     pub configuration_count: usize,
-    pub category_scores: HashMap<String, f64>,
+    pub category_scores: BTreeMap<String, f64>,
+    /// Keys of `category_scores` in canonical MMLU-Pro order
+    pub category_order: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeatmapData {
     pub quantizations: Vec<String>,
+    /// What `power_limits` holds: `gpu_power_limit_watts`, or `cpu_threads`
+    /// for CPU-only runs
+    pub power_axis: String,
     pub power_limits: Vec<i32>,
     pub concurrent_requests: Vec<i32>,
     // Map: key -> power_limit -> concurrent_requests -> metric
     // Key is "backend||quantization" composite key
-    pub speed_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>>,
-    pub ttft_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>>,
-    pub tpot_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>>,
-    pub itl_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>>,
-    pub efficiency_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>>,
+    pub speed_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>>,
+    pub ttft_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>>,
+    pub tpot_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>>,
+    pub itl_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>>,
+    pub efficiency_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>>,
 }
 
-/// Sort quantizations in a logical order (full precision first, then quantized)
-fn quantization_sort_key(quant: &str) -> (u8, String) {
-    let priority = match quant {
-        // Full precision formats (highest priority)
-        "FP32" => 0,
-        "BF16" => 1,
-        "FP16" => 2,
-        "FP8_DYNAMIC" => 3,
-        "FP8" => 4,
-        // Weight-only quantization
-        q if q.starts_with("W") && q.contains("A16") => 10,
-        q if q.starts_with("W") && q.contains("A8") => 11,
-        // Full quantization
-        q if q.starts_with("W") => 20,
-        // GGUF-style quantization
-        q if q.starts_with("Q") => 30,
-        // Everything else
-        _ => 99,
-    };
-    (priority, quant.to_string())
-}
-
-/// Get model+hardware analysis data for visualizations
+/// Get model+hardware analysis data for visualizations.
+///
+/// The hardware is a GPU model. Runs without a GPU (`gpu_model` "CPU Only" or
+/// "N/A") are keyed on their CPU model instead, with the thread count taking
+/// the place of the power limit.
 pub async fn get_model_hardware_analysis(
     Path((model_name, gpu_model_param)): Path<(String, String)>,
     Query(query_params): Query<AnalysisQueryParams>,
     State(state): State<AppState>,
-) -> Result<Json<ModelHardwareAnalysis>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Json<ModelHardwareAnalysis>, AppError> {
     let lora_adapter = query_params.lora.as_deref().unwrap_or("");
     // Decode URL-encoded model name and gpu model
     let model_name = urlencoding::decode(&model_name)
-        .map_err(|e| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(format!("Invalid model name encoding: {}", e))),
-            )
-        })?
+        .map_err(|e| AppError::BadRequest(format!("Invalid model name encoding: {}", e)))?
         .to_string();
 
     let gpu_model = urlencoding::decode(&gpu_model_param)
-        .map_err(|e| {
-            (
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse::new(format!("Invalid GPU model encoding: {}", e))),
-            )
-        })?
+        .map_err(|e| AppError::BadRequest(format!("Invalid GPU model encoding: {}", e)))?
         .to_string();
 
+    if is_cpu_only_gpu_model(&gpu_model) {
+        return Err(AppError::BadRequest(
+            "CPU-only runs are analyzed by CPU model; pass the CPU model instead".to_string(),
+        ));
+    }
+
     // Aggregate metrics across all runs for each unique configuration (backend, quantization, power_limit, concurrent_requests)
     // Using GROUP BY instead of ROW_NUMBER to combine metrics from runs that may have different metrics available
     let test_runs = sqlx::query!(
@@ -116,7 +107,9 @@ pub async fn get_model_hardware_analysis(
             tr.backend as "backend!",
             tr.quantization as "quantization!",
             tr.concurrent_requests as "concurrent_requests?",
-            tr.gpu_power_limit_watts as "gpu_power_limit_watts?",
+            CASE WHEN hp.gpu_model IN ('CPU Only', 'N/A') THEN hp.cpu_threads
+                ELSE tr.gpu_power_limit_watts END as "gpu_power_limit_watts?",
+            BOOL_AND(hp.gpu_model IN ('CPU Only', 'N/A')) as "cpu_only!",
             MAX(pm_speed.value) as "tokens_per_second?",
             MIN(pm_ttft.value) as "ttft?",
             MIN(pm_tpot.value) as "tpot?",
@@ -135,34 +128,29 @@ pub async fn get_model_hardware_analysis(
         LEFT JOIN performance_metrics pm_power
             ON tr.id = pm_power.test_run_id AND pm_power.metric_name = 'gpu_power_watts'
         WHERE tr.model_name = $1
-            AND hp.gpu_model = $2
+            AND CASE WHEN hp.gpu_model IN ('CPU Only', 'N/A') THEN hp.cpu_model
+                ELSE hp.gpu_model END = $2
             AND tr.status = 'completed'
-        GROUP BY tr.backend, tr.quantization, tr.concurrent_requests, tr.gpu_power_limit_watts
+        GROUP BY tr.backend, tr.quantization, tr.concurrent_requests, 4
         "#,
         model_name,
         gpu_model
     )
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse::new(format!("Database error: {}", e))),
-        )
-    })?;
+    .await?;
 
     if test_runs.is_empty() {
-        return Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse::new(
-                "No test runs found for this model+hardware combination".to_string(),
-            )),
+        return Err(AppError::NotFound(
+            "No test runs found for this model+hardware combination".to_string(),
         ));
     }
 
+    // A CPU model name never matches a GPU model, so runs are all one or the other
+    let cpu_only = test_runs.iter().all(|run| run.cpu_only);
+
     // Aggregate data by (backend, quantization)
     // Tuple: (power_limit, concurrent, speed, ttft, tpot, itl, gpu_power, tokens_per_kwh)
-    let mut quant_map: HashMap<(String, String), Vec<(i32, i32, f64, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>)>> = HashMap::new();
+    let mut quant_map: BTreeMap<(String, String), Vec<(i32, i32, f64, Option<f64>, Option<f64>, Option<f64>, Option<f64>, Option<f64>)>> = BTreeMap::new();
     let mut all_power_limits = std::collections::BTreeSet::new();
     let mut all_concurrent_requests = std::collections::BTreeSet::new();
 
@@ -177,16 +165,9 @@ pub async fn get_model_hardware_analysis(
         let itl = run.itl;
         let gpu_power = run.gpu_power_watts;
 
-        // Calculate tokens/kWh: (tokens/second × 3,600,000) / watts
-        let tokens_per_kwh = if let Some(power) = gpu_power {
-            if power > 0.0 {
-                Some((speed * 3_600_000.0) / power)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let tokens_per_kwh = gpu_power
+            .and_then(|power| PerformanceMetric::tokens_per_kwh(speed, power))
+            .map(|metric| metric.value);
 
         all_power_limits.insert(power_limit);
         all_concurrent_requests.insert(concurrent);
@@ -199,6 +180,8 @@ pub async fn get_model_hardware_analysis(
 
     // Get quality scores for each quantization and build summaries
     let mut quantization_summaries = Vec::new();
+    // (power limit, mean tokens/kWh) of the most efficient quantization
+    let mut recommended: Option<(i32, f64)> = None;
     for ((backend, quant), runs) in quant_map.iter() {
         // Get category-level scores (filtered by LoRA adapter)
         let category_scores_rows = sqlx::query!(
@@ -217,7 +200,7 @@ pub async fn get_model_hardware_analysis(
         .await
         .unwrap_or_default();
 
-        let mut category_scores = HashMap::new();
+        let mut category_scores = BTreeMap::new();
         let mut total_score = 0.0;
         let mut count = 0;
 
@@ -230,6 +213,9 @@ pub async fn get_model_hardware_analysis(
 
         let quality_score = if count > 0 { total_score / count as f64 } else { 0.0 };
 
+        let mut category_order: Vec<String> = category_scores.keys().cloned().collect();
+        category_order.sort_by(|a, b| mmlu_categories::compare(a, b));
+
         let best_speed = runs.iter().map(|(_, _, speed, _, _, _, _, _)| *speed).fold(0.0_f64, f64::max);
         let best_ttft = runs
             .iter()
@@ -239,6 +225,16 @@ pub async fn get_model_hardware_analysis(
             .iter()
             .filter_map(|(_, _, _, _, _, _, _, tokens_kwh)| *tokens_kwh)
             .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let best_efficiency = best_efficiency_power_limit(
+            runs.iter().filter_map(|(power_limit, _, _, _, _, _, _, tokens_kwh)| {
+                Some((*power_limit, (*tokens_kwh)?))
+            }),
+        );
+        if let Some((power_limit, mean)) = best_efficiency {
+            if recommended.is_none_or(|(_, best)| mean > best) {
+                recommended = Some((power_limit, mean));
+            }
+        }
 
         quantization_summaries.push(QuantizationSummary {
             quantization: quant.clone(),
@@ -246,12 +242,16 @@ pub async fn get_model_hardware_analysis(
             best_speed,
             best_ttft,
             best_tokens_per_kwh,
+            best_efficiency_power_limit: best_efficiency.map(|(power_limit, _)| power_limit),
             quality_score,
             configuration_count: runs.len(),
             category_scores,
+            category_order,
         });
     }
 
+    let recommended_power_limit = recommended.map(|(power_limit, _)| power_limit);
+
     // Sort by backend then by quantization logical order
     quantization_summaries.sort_by(|a, b| {
         a.backend.cmp(&b.backend)
@@ -274,64 +274,65 @@ pub async fn get_model_hardware_analysis(
     }
 
     // Build heatmap data using composite keys "backend||quantization"
-    let mut speed_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>> = HashMap::new();
-    let mut ttft_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>> = HashMap::new();
-    let mut tpot_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>> = HashMap::new();
-    let mut itl_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>> = HashMap::new();
-    let mut efficiency_data: HashMap<String, HashMap<i32, HashMap<i32, f64>>> = HashMap::new();
+    let mut speed_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>> = BTreeMap::new();
+    let mut ttft_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>> = BTreeMap::new();
+    let mut tpot_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>> = BTreeMap::new();
+    let mut itl_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>> = BTreeMap::new();
+    let mut efficiency_data: BTreeMap<String, BTreeMap<i32, BTreeMap<i32, f64>>> = BTreeMap::new();
 
     for ((backend, quant), runs) in quant_map.iter() {
         let composite_key = format!("{}||{}", backend, quant);
-        let quant_speed_map = speed_data.entry(composite_key.clone()).or_insert_with(HashMap::new);
-        let quant_ttft_map = ttft_data.entry(composite_key.clone()).or_insert_with(HashMap::new);
-        let quant_tpot_map = tpot_data.entry(composite_key.clone()).or_insert_with(HashMap::new);
-        let quant_itl_map = itl_data.entry(composite_key.clone()).or_insert_with(HashMap::new);
-        let quant_efficiency_map = efficiency_data.entry(composite_key).or_insert_with(HashMap::new);
+        let quant_speed_map = speed_data.entry(composite_key.clone()).or_insert_with(BTreeMap::new);
+        let quant_ttft_map = ttft_data.entry(composite_key.clone()).or_insert_with(BTreeMap::new);
+        let quant_tpot_map = tpot_data.entry(composite_key.clone()).or_insert_with(BTreeMap::new);
+        let quant_itl_map = itl_data.entry(composite_key.clone()).or_insert_with(BTreeMap::new);
+        let quant_efficiency_map = efficiency_data.entry(composite_key).or_insert_with(BTreeMap::new);
 
         for (power_limit, concurrent, speed, ttft, tpot, itl, _gpu_power, tokens_per_kwh) in runs {
             quant_speed_map
                 .entry(*power_limit)
-                .or_insert_with(HashMap::new)
+                .or_insert_with(BTreeMap::new)
                 .insert(*concurrent, *speed);
 
             if let Some(ttft_val) = ttft {
                 quant_ttft_map
                     .entry(*power_limit)
-                    .or_insert_with(HashMap::new)
+                    .or_insert_with(BTreeMap::new)
                     .insert(*concurrent, *ttft_val);
             }
 
             if let Some(tpot_val) = tpot {
                 quant_tpot_map
                     .entry(*power_limit)
-                    .or_insert_with(HashMap::new)
+                    .or_insert_with(BTreeMap::new)
                     .insert(*concurrent, *tpot_val);
             }
 
             if let Some(itl_val) = itl {
                 quant_itl_map
                     .entry(*power_limit)
-                    .or_insert_with(HashMap::new)
+                    .or_insert_with(BTreeMap::new)
                     .insert(*concurrent, *itl_val);
             }
 
             if let Some(efficiency_val) = tokens_per_kwh {
                 quant_efficiency_map
                     .entry(*power_limit)
-                    .or_insert_with(HashMap::new)
+                    .or_insert_with(BTreeMap::new)
                     .insert(*concurrent, *efficiency_val);
             }
         }
     }
 
-    // Collect and sort composite keys for heatmap quantizations list
-    let mut heatmap_quantizations: Vec<String> = quant_map.keys()
-        .map(|(backend, quant)| format!("{}||{}", backend, quant))
+    // Collect composite keys for heatmap quantizations list, in summary order
+    let heatmap_quantizations: Vec<String> = quantization_summaries
+        .iter()
+        .map(|summary| format!("{}||{}", summary.backend, summary.quantization))
         .collect();
-    heatmap_quantizations.sort();
 
     let heatmap_data = HeatmapData {
         quantizations: heatmap_quantizations,
+        power_axis: if cpu_only { "cpu_threads" } else { "gpu_power_limit_watts" }.to_string(),
         power_limits: all_power_limits.into_iter().collect(),
         concurrent_requests: all_concurrent_requests.into_iter().collect(),
         speed_data,
@@ -344,9 +345,55 @@ pub async fn get_model_hardware_analysis(
     Ok(Json(ModelHardwareAnalysis {
         model_name: model_name.clone(),
         gpu_model,
+        cpu_only,
         total_configurations: test_runs.len(),
         backends,
         quantizations: quantization_summaries,
+        recommended_power_limit,
         heatmap_data,
     }))
 }
+
+/// The power limit with the highest mean tokens/kWh among
+/// `(power_limit, tokens_per_kwh)` pairs, with that mean; the lower limit
+/// wins a tie. Runs without a recorded limit (0) are skipped.
+fn best_efficiency_power_limit(runs: impl Iterator<Item = (i32, f64)>) -> Option<(i32, f64)> {
+    let mut by_limit: BTreeMap<i32, (f64, usize)> = BTreeMap::new();
+    for (power_limit, tokens_per_kwh) in runs.filter(|(power_limit, _)| *power_limit > 0) {
+        let (sum, count) = by_limit.entry(power_limit).or_default();
+        *sum += tokens_per_kwh;
+        *count += 1;
+    }
+
+    by_limit
+        .into_iter()
+        .map(|(power_limit, (sum, count))| (power_limit, sum / count as f64))
+        // max_by keeps the last of equal elements, so walk from the highest limit
+        .rev()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_efficiency_power_limit() {
+        // 300W is the knee: its mean over both concurrency levels beats 450W and 250W
+        let runs = [
+            (450, 90_000.0),
+            (450, 110_000.0),
+            (300, 120_000.0),
+            (300, 115_000.0),
+            (250, 100_000.0),
+        ];
+        assert_eq!(best_efficiency_power_limit(runs.into_iter()), Some((300, 117_500.0)));
+
+        // Ties go to the lower limit
+        assert_eq!(best_efficiency_power_limit([(350, 1.0), (300, 1.0)].into_iter()), Some((300, 1.0)));
+
+        // Runs without a power limit don't count
+        assert_eq!(best_efficiency_power_limit([(0, 200_000.0), (300, 1.0)].into_iter()), Some((300, 1.0)));
+        assert_eq!(best_efficiency_power_limit([(0, 200_000.0)].into_iter()), None);
+    }
+}
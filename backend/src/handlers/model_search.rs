@@ -0,0 +1,83 @@
+// handlers/model_search.rs
+// Model name search for autocomplete
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+
+use llm_benchmark_types::{ModelSearchRequest, ModelSearchResponse};
+
+use crate::{
+    error::{AppError, DbContext},
+    AppState,
+};
+
+const DEFAULT_SEARCH_LIMIT: i64 = 10;
+const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// Distinct model names containing `q`, case-insensitively. Names that start
+/// with `q` come first, then the rest alphabetically.
+///
+/// Both benchmarked models (completed test runs) and models that only have
+/// quality scores (model variants) are searched.
+pub async fn search_models(
+    Query(params): Query<ModelSearchRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ModelSearchResponse>, AppError> {
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if !(1..=MAX_SEARCH_LIMIT).contains(&limit) {
+        return Err(AppError::Validation(format!(
+            "limit must be between 1 and {}",
+            MAX_SEARCH_LIMIT
+        )));
+    }
+
+    let query = escape_like(params.q.trim());
+    let contains = format!("%{}%", query);
+    let prefix = format!("{}%", query);
+
+    let rows = sqlx::query!(
+        r#"
+        WITH names AS (
+            SELECT model_name FROM test_runs
+            WHERE status = 'completed' AND model_name ILIKE $1
+            UNION
+            SELECT model_name FROM model_variants
+            WHERE model_name ILIKE $1
+        )
+        SELECT model_name as "model_name!", COUNT(*) OVER () as "total_matched!"
+        FROM names
+        ORDER BY model_name ILIKE $2 DESC, LOWER(model_name), model_name
+        LIMIT $3
+        "#,
+        contains,
+        prefix,
+        limit
+    )
+    .fetch_all(&state.db)
+    .await
+    .db_context("Failed to search model names")?;
+
+    Ok(Json(ModelSearchResponse {
+        total_matched: rows.first().map_or(0, |row| row.total_matched),
+        models: rows.into_iter().map(|row| row.model_name).collect(),
+    }))
+}
+
+/// Escape `ILIKE` wildcards so the query matches literally
+pub(crate) fn escape_like(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("mistral"), "mistral");
+        assert_eq!(escape_like("llama_3%"), "llama\\_3\\%");
+        assert_eq!(escape_like("a\\b"), "a\\\\b");
+    }
+}
@@ -0,0 +1,45 @@
+// handlers/model_variant_benchmarks.rs
+// Benchmark scores of a model variant, independent of any test run
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use llm_benchmark_types::{
+    benchmarks::BenchmarkScoreType, normalize_quantization, ModelVariantBenchmarksRequest,
+};
+
+use crate::{
+    error::{AppError, DbContext},
+    models::benchmark_queries,
+    AppState,
+};
+
+/// Every benchmark score stored for a model variant, read from the v2
+/// tables. 404 if the variant doesn't exist.
+pub async fn get_model_variant_benchmarks(
+    Query(params): Query<ModelVariantBenchmarksRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<BenchmarkScoreType>>, AppError> {
+    let quantization = normalize_quantization(&params.quantization);
+    let lora_adapter = params.lora.as_deref().unwrap_or("");
+
+    let model_variant_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM model_variants WHERE model_name = $1 AND quantization = $2 AND lora_adapter = $3",
+    )
+    .bind(&params.model)
+    .bind(&quantization)
+    .bind(lora_adapter)
+    .fetch_optional(&state.db)
+    .await
+    .db_context("Failed to look up model variant")?
+    .ok_or_else(|| AppError::NotFound(format!("No model variant {} {}", params.model, quantization)))?;
+
+    let scores = benchmark_queries::get_all_benchmark_scores_for_model_variant(&state.db, &model_variant_id)
+        .await
+        .db_context("Failed to fetch benchmark scores")?;
+
+    Ok(Json(scores))
+}
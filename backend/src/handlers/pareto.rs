@@ -0,0 +1,98 @@
+// handlers/pareto.rs
+// Speed vs. quality Pareto frontier of a model's configurations
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+
+use llm_benchmark_types::{ParetoRequest, ParetoResponse, QuantizationPerformance};
+
+use crate::{
+    error::{AppError, DbContext},
+    handlers::grouped_performance::{fetch_candidates, Candidate, QUALITY_BENCHMARKS},
+    AppState,
+};
+
+/// Configurations of `model` that no other configuration beats on both
+/// tokens/s and `benchmark` score, slowest first.
+///
+/// Runs without a speed or a quality score can't be placed on the frontier
+/// and are left out.
+pub async fn get_pareto(
+    Query(params): Query<ParetoRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ParetoResponse>, AppError> {
+    let benchmark = params.benchmark.as_deref().unwrap_or("mmlu").to_lowercase();
+    if !QUALITY_BENCHMARKS.contains(&benchmark.as_str()) {
+        return Err(AppError::BadRequest(format!(
+            "Unknown benchmark '{}' (expected one of {})",
+            benchmark,
+            QUALITY_BENCHMARKS.join(", ")
+        )));
+    }
+
+    let candidates: Vec<QuantizationPerformance> = fetch_candidates(&state.db, &benchmark, None)
+        .await
+        .db_context("Failed to load performance data")?
+        .into_iter()
+        .filter(|candidate| candidate.model_name == params.model)
+        .filter_map(|Candidate { tokens_per_second, quality_score, config, .. }| {
+            (tokens_per_second.is_some() && quality_score.is_some()).then_some(config)
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No runs of {} with both a speed and a {} score",
+            params.model, benchmark
+        )));
+    }
+
+    Ok(Json(ParetoResponse {
+        model_name: params.model,
+        benchmark,
+        candidates: candidates.len(),
+        frontier: pareto_frontier(candidates),
+    }))
+}
+
+/// Configurations not strictly beaten on both speed and quality by another,
+/// sorted by speed ascending. Points tied on both axes dominate neither
+/// one another, so all of them are kept.
+fn pareto_frontier(configs: Vec<QuantizationPerformance>) -> Vec<QuantizationPerformance> {
+    let dominated = |config: &QuantizationPerformance| {
+        configs.iter().any(|other| {
+            other.tokens_per_second > config.tokens_per_second && other.quality_score > config.quality_score
+        })
+    };
+
+    let mut frontier: Vec<QuantizationPerformance> =
+        configs.iter().filter(|config| !dominated(config)).cloned().collect();
+    frontier.sort_by(|a, b| a.tokens_per_second.total_cmp(&b.tokens_per_second));
+    frontier
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::test_support::quantization_performance as config;
+
+    #[test]
+    fn test_pareto_frontier() {
+        let frontier = pareto_frontier(vec![
+            config("Q8_0", 60.0, 70.0),
+            config("Q4_K_M", 100.0, 65.0),
+            // Slower and worse than Q8_0
+            config("Q6_K", 55.0, 68.0),
+            // Tied with Q4_K_M on both axes
+            config("Q4_K_S", 100.0, 65.0),
+            // Faster than Q4_K_M but not better, so it doesn't beat it
+            config("Q2_K", 100.0, 50.0),
+            config("F16", 30.0, 71.0),
+        ]);
+
+        let quantizations: Vec<&str> = frontier.iter().map(|c| c.quantization.as_str()).collect();
+        assert_eq!(quantizations, ["F16", "Q8_0", "Q4_K_M", "Q4_K_S", "Q2_K"]);
+    }
+}
@@ -3,74 +3,484 @@
 
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
-    response::Json,
+    http::header,
+    response::{IntoResponse, Response},
 };
 
+use sqlx::{Postgres, QueryBuilder};
+
 use llm_benchmark_types::{
-    PerformanceGridRow, PerformanceGridRequest, ErrorResponse
+    quantization_sort_key, PerformanceGridRow, PerformanceGridRequest, MetricFilter, BenchmarkWeights
 };
 
 use crate::{
+    error::AppError,
+    etag::{ETagJson, IfNoneMatch},
     models::{PerformanceGridQueryResult, benchmark_queries},
     AppState
 };
 
-/// Get performance grid data with optional filtering
+/// Get performance grid data with optional filtering.
+///
+/// Besides the fixed filters, any number of `metric`/`op`/`value` triples
+/// restrict the grid to runs whose metric satisfies the comparison.
+/// `profile`/`weights` turn `overall_score` into a weighted composite, and
+/// `canonical`/`exclude_deprecated` filter on run annotations.
 pub async fn get_performance_grid(
-    Query(_params): Query<PerformanceGridRequest>,
+    Query(params): Query<PerformanceGridRequest>,
+    Query(pairs): Query<Vec<(String, String)>>,
+    if_none_match: IfNoneMatch,
+    State(state): State<AppState>,
+) -> Result<ETagJson<Vec<PerformanceGridRow>>, AppError> {
+    let grid_rows = load_grid_rows(&state, &params, &pairs).await?;
+    Ok(if_none_match.json(grid_rows))
+}
+
+/// The performance grid as a CSV attachment, taking the same filters as
+/// `get_performance_grid`. Numbers have two decimal places; a missing
+/// `overall_score` is an empty cell.
+pub async fn get_performance_grid_csv(
+    Query(params): Query<PerformanceGridRequest>,
+    Query(pairs): Query<Vec<(String, String)>>,
     State(state): State<AppState>,
-) -> Result<Json<Vec<PerformanceGridRow>>, (StatusCode, Json<ErrorResponse>)> {
-    // Build WHERE clause based on filters - fix unused variable warning
-    let _where_conditions: Vec<String> = Vec::new();
-    
-    // For now, we'll use a basic query without dynamic parameters
-    // You can enhance this later with proper parameter binding
-    let query = r#"
+) -> Result<Response, AppError> {
+    let grid_rows = load_grid_rows(&state, &params, &pairs).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"performance-grid.csv\""),
+        ],
+        grid_csv(&grid_rows),
+    )
+        .into_response())
+}
+
+/// Grid rows matching `params` and the metric filters in `pairs`, sorted
+/// by model then quantization precision
+async fn load_grid_rows(
+    state: &AppState,
+    params: &PerformanceGridRequest,
+    pairs: &[(String, String)],
+) -> Result<Vec<PerformanceGridRow>, AppError> {
+    let metric_filters = MetricFilter::from_query_pairs(pairs)
+        .map_err(AppError::BadRequest)?;
+    let weights = BenchmarkWeights::from_params(params.profile.as_deref(), params.weights.as_deref())
+        .map_err(AppError::BadRequest)?;
+
+    let rows = grid_query(params, &metric_filters)
+        .build_query_as::<PerformanceGridQueryResult>()
+        .fetch_all(&state.db)
+        .await?;
+
+    // Get benchmark scores for each row
+    let mut grid_rows = Vec::new();
+    for row in rows {
+        let overall_score = match &weights {
+            Some(weights) => benchmark_queries::get_weighted_benchmark_score_for_test_run(&state.db, &row.test_run_id, weights)
+                .await
+                .ok()
+                .flatten(),
+            None => benchmark_queries::get_aggregated_benchmark_scores_for_test_run(&state.db, &row.test_run_id)
+                .await
+                .ok(),
+        };
+        
+        let mut grid_row: PerformanceGridRow = row.into();
+        grid_row.overall_score = overall_score;
+        grid_rows.push(grid_row);
+    }
+
+    // SQL orders quantizations alphabetically; put them in precision order
+    grid_rows.sort_by(|a, b| {
+        a.model_name
+            .cmp(&b.model_name)
+            .then_with(|| quantization_sort_key(&a.quantization).cmp(&quantization_sort_key(&b.quantization)))
+    });
+
+    Ok(grid_rows)
+}
+
+/// The grid query: one row per (model, quantization, LoRA adapter, backend,
+/// GPU, CPU architecture, concurrent requests) with the best speed and
+/// memory seen across its runs. Filters apply to individual runs before they
+/// are combined, and the row reports the fastest run's id.
+fn grid_query<'a>(params: &'a PerformanceGridRequest, metric_filters: &'a [MetricFilter]) -> QueryBuilder<'a, Postgres> {
+    let mut query = QueryBuilder::<Postgres>::new(
+        r#"
         SELECT 
-            tr.id as test_run_id,
+            (ARRAY_AGG(tr.id ORDER BY pm_speed.value DESC NULLS LAST))[1] as test_run_id,
             tr.model_name,
             tr.quantization,
+            tr.lora_adapter,
             tr.backend,
             hp.gpu_model,
             hp.cpu_arch,
-            hp.virtualization_type,
-            pm_speed.value as tokens_per_second,
-            pm_memory.value as memory_gb,
-            NULL as overall_score
+            MAX(hp.virtualization_type) as virtualization_type,
+            tr.concurrent_requests,
+            MAX(pm_speed.value) as tokens_per_second,
+            MIN(pm_memory.value) as memory_gb,
+            MIN(pm_ttft.value) as first_token_latency_ms,
+            NULL as overall_score,
+            COUNT(DISTINCT tr.id) as run_count
         FROM test_runs tr
         JOIN hardware_profiles hp ON tr.hardware_profile_id = hp.id
         LEFT JOIN performance_metrics pm_speed ON tr.id = pm_speed.test_run_id 
             AND pm_speed.metric_name = 'tokens_per_second'
         LEFT JOIN performance_metrics pm_memory ON tr.id = pm_memory.test_run_id 
             AND pm_memory.metric_name = 'memory_usage_gb'
+        LEFT JOIN performance_metrics pm_ttft ON tr.id = pm_ttft.test_run_id 
+            AND pm_ttft.metric_name = 'first_token_latency_ms'
         -- Benchmark scores now handled separately
         WHERE tr.status = 'completed'
-        -- No GROUP BY needed without aggregation
-        ORDER BY tr.model_name, tr.quantization
-        "#;
+        "#,
+    );
 
-    let rows = sqlx::query_as::<_, PerformanceGridQueryResult>(query)
-        .fetch_all(&state.db)
+    if let Some(max_memory_gb) = params.max_memory_gb {
+        query.push(" AND pm_memory.value <= ").push_bind(max_memory_gb);
+    }
+    if let Some(min_speed) = params.min_speed {
+        query.push(" AND pm_speed.value >= ").push_bind(min_speed);
+    }
+    if let Some(backends) = &params.backends {
+        push_in_list(&mut query, "tr.backend", backends);
+    }
+    if let Some(models) = &params.models {
+        push_in_list(&mut query, "tr.model_name", models);
+    }
+    if let Some(hardware_types) = &params.hardware_types {
+        // The same split as `PerformanceGridRow::hardware_type`
+        push_in_list(
+            &mut query,
+            "(CASE WHEN hp.gpu_model ILIKE '%cpu%' THEN 'cpu_only' ELSE 'gpu' END)",
+            hardware_types,
+        );
+    }
+    if let Some(label) = &params.label {
+        query.push(" AND tr.label = ").push_bind(label);
+    }
+    if let Some(concurrent_requests) = params.concurrent_requests {
+        query.push(" AND tr.concurrent_requests = ").push_bind(concurrent_requests);
+    }
+    if params.canonical == Some(true) {
+        query.push(" AND EXISTS (SELECT 1 FROM test_run_annotations ra WHERE ra.test_run_id = tr.id AND ra.canonical)");
+    }
+    if params.exclude_deprecated == Some(true) {
+        query.push(" AND NOT EXISTS (SELECT 1 FROM test_run_annotations ra WHERE ra.test_run_id = tr.id AND ra.deprecated)");
+    }
+    for filter in metric_filters {
+        query
+            .push(" AND EXISTS (SELECT 1 FROM performance_metrics pm_filter WHERE pm_filter.test_run_id = tr.id AND pm_filter.metric_name = ")
+            .push_bind(&filter.metric)
+            .push(" AND pm_filter.value ")
+            .push(filter.op.as_sql())
+            .push(" ")
+            .push_bind(filter.value)
+            .push(")");
+    }
+    query.push(" GROUP BY tr.model_name, tr.quantization, tr.lora_adapter, tr.backend, hp.gpu_model, hp.cpu_arch, tr.concurrent_requests");
+    query.push(" ORDER BY tr.model_name, tr.quantization");
+    query
+}
+
+/// Restrict `expr` to `values`; an empty list doesn't filter
+fn push_in_list<'a>(query: &mut QueryBuilder<'a, Postgres>, expr: &str, values: &'a [String]) {
+    if values.is_empty() {
+        return;
+    }
+
+    query.push(" AND ").push(expr).push(" IN (");
+    let mut list = query.separated(", ");
+    for value in values {
+        list.push_bind(value);
+    }
+    list.push_unseparated(")");
+}
+
+/// Render grid rows as CSV with a header row
+fn grid_csv(rows: &[PerformanceGridRow]) -> String {
+    let mut csv = String::from(
+        "model_name,quantization,lora_adapter,backend,gpu_model,cpu_arch,concurrent_requests,tokens_per_second,memory_gb,overall_score\n",
+    );
+    for row in rows {
+        let fields = [
+            csv_field(&row.model_name),
+            csv_field(&row.quantization),
+            csv_field(&row.lora_adapter),
+            csv_field(&row.backend),
+            csv_field(&row.gpu_model),
+            csv_field(&row.cpu_arch),
+            row.concurrent_requests.map(|n| n.to_string()).unwrap_or_default(),
+            format!("{:.2}", row.tokens_per_second),
+            format!("{:.2}", row.memory_gb),
+            row.overall_score.map(|score| format!("{:.2}", score)).unwrap_or_default(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_grid_csv() {
+        let row = |model_name: &str, overall_score| PerformanceGridRow {
+            id: Uuid::new_v4(),
+            model_name: model_name.to_string(),
+            quantization: "Q4_K_M".to_string(),
+            lora_adapter: String::new(),
+            backend: "llama.cpp".to_string(),
+            tokens_per_second: 45.2,
+            memory_gb: 18.456,
+            first_token_latency_ms: None,
+            gpu_model: "RTX 4090".to_string(),
+            cpu_arch: "x86_64".to_string(),
+            hardware_type: "gpu".to_string(),
+            concurrent_requests: Some(1),
+            overall_score,
+            run_count: 1,
+        };
+
+        let mut lora = row("llama-3-8b", None);
+        lora.lora_adapter = "sql-coder".to_string();
+        lora.concurrent_requests = None;
+
+        let csv = grid_csv(&[row("llama-3-8b", Some(63.7371)), row("Mistral \"Small\", 24B", None), lora]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "model_name,quantization,lora_adapter,backend,gpu_model,cpu_arch,concurrent_requests,tokens_per_second,memory_gb,overall_score"
+        );
+        assert_eq!(lines[1], "llama-3-8b,Q4_K_M,,llama.cpp,RTX 4090,x86_64,1,45.20,18.46,63.74");
+        assert_eq!(lines[2], "\"Mistral \"\"Small\"\", 24B\",Q4_K_M,,llama.cpp,RTX 4090,x86_64,1,45.20,18.46,");
+        assert_eq!(lines[3], "llama-3-8b,Q4_K_M,sql-coder,llama.cpp,RTX 4090,x86_64,,45.20,18.46,");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_grid_query_combines_repeated_runs() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let label = format!("grid-test-{}", Uuid::new_v4());
+        let hardware_profile_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO hardware_profiles (gpu_model, gpu_memory_gb, cpu_model, cpu_arch) VALUES ('RTX 4090', 24, 'Ryzen 9 7950X', 'x86_64') RETURNING id",
+        )
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse::new(format!("Database error: {}", e))),
+        .unwrap();
+
+        // The same configuration run twice: the first is slower but uses less memory
+        for (speed, memory) in [(40.0, 9.0), (50.0, 10.0)] {
+            let test_run_id: Uuid = sqlx::query_scalar(
+                r#"
+                INSERT INTO test_runs (model_name, quantization, backend, backend_version, hardware_profile_id, status, label)
+                VALUES ('grid-test-model', 'Q4_K_M', 'llama.cpp', 'b1', $1, 'completed', $2)
+                RETURNING id
+                "#,
             )
-        })?;
+            .bind(hardware_profile_id)
+            .bind(&label)
+            .fetch_one(&mut *tx)
+            .await
+            .unwrap();
 
-    // Get benchmark scores for each row
-    let mut grid_rows = Vec::new();
-    for row in rows {
-        let overall_score = benchmark_queries::get_aggregated_benchmark_scores_for_test_run(&state.db, &row.test_run_id)
+            sqlx::query(
+                "INSERT INTO performance_metrics (test_run_id, metric_name, value, unit) VALUES ($1, 'tokens_per_second', $2, 'tok/s'), ($1, 'memory_usage_gb', $3, 'GB')",
+            )
+            .bind(test_run_id)
+            .bind(speed)
+            .bind(memory)
+            .execute(&mut *tx)
             .await
-            .ok();
-        
-        let mut grid_row: PerformanceGridRow = row.into();
-        grid_row.overall_score = overall_score;
-        grid_rows.push(grid_row);
+            .unwrap();
+        }
+
+        // A LoRA variant of the same model gets its own row
+        sqlx::query(
+            r#"
+            INSERT INTO test_runs (model_name, quantization, lora_adapter, backend, backend_version, hardware_profile_id, status, label)
+            VALUES ('grid-test-model', 'Q4_K_M', 'grid-test-lora', 'llama.cpp', 'b1', $1, 'completed', $2)
+            "#,
+        )
+        .bind(hardware_profile_id)
+        .bind(&label)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        let rows = labelled_rows(&mut tx, &label, serde_json::json!({})).await;
+        tx.rollback().await.unwrap();
+
+        assert_eq!(rows.len(), 2);
+        let base = rows.iter().find(|row| row.lora_adapter.is_empty()).unwrap();
+        assert_eq!(base.run_count, 2);
+        assert_eq!(base.tokens_per_second, Some(50.0));
+        assert_eq!(base.memory_gb, Some(9.0));
+        assert!(rows.iter().any(|row| row.lora_adapter == "grid-test-lora" && row.run_count == 1));
     }
 
-    Ok(Json(grid_rows))
-}
\ No newline at end of file
+    /// Insert a completed run with speed and memory metrics
+    async fn insert_filter_run(
+        tx: &mut sqlx::Transaction<'static, Postgres>,
+        label: &str,
+        gpu_model: &str,
+        model_name: &str,
+        backend: &str,
+        speed: f64,
+        memory: f64,
+    ) {
+        let hardware_profile_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO hardware_profiles (gpu_model, gpu_memory_gb, cpu_model, cpu_arch) VALUES ($1, 24, 'Ryzen 9 7950X', 'x86_64') RETURNING id",
+        )
+        .bind(gpu_model)
+        .fetch_one(&mut **tx)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            r#"
+            WITH run AS (
+                INSERT INTO test_runs (model_name, quantization, backend, backend_version, hardware_profile_id, status, label)
+                VALUES ($1, 'Q4_K_M', $2, 'b1', $3, 'completed', $4)
+                RETURNING id
+            )
+            INSERT INTO performance_metrics (test_run_id, metric_name, value, unit)
+            SELECT id, 'tokens_per_second', $5, 'tok/s' FROM run
+            UNION ALL SELECT id, 'memory_usage_gb', $6, 'GB' FROM run
+            "#,
+        )
+        .bind(model_name)
+        .bind(backend)
+        .bind(hardware_profile_id)
+        .bind(label)
+        .bind(speed)
+        .bind(memory)
+        .execute(&mut **tx)
+        .await
+        .unwrap();
+    }
+
+    /// Grid rows for `label` with `filters` (query parameters as JSON) applied
+    async fn labelled_rows(
+        tx: &mut sqlx::Transaction<'static, Postgres>,
+        label: &str,
+        filters: serde_json::Value,
+    ) -> Vec<PerformanceGridQueryResult> {
+        let mut params = serde_json::json!({ "label": label });
+        params.as_object_mut().unwrap().extend(filters.as_object().unwrap().clone());
+        let params: PerformanceGridRequest = serde_json::from_value(params).unwrap();
+
+        grid_query(&params, &[])
+            .build_query_as::<PerformanceGridQueryResult>()
+            .fetch_all(&mut **tx)
+            .await
+            .unwrap()
+    }
+
+    /// (model, backend, GPU) of the grid rows for `label` with `filters` applied
+    async fn filtered_rows(
+        tx: &mut sqlx::Transaction<'static, Postgres>,
+        label: &str,
+        filters: serde_json::Value,
+    ) -> Vec<(String, String, String)> {
+        let mut rows: Vec<_> = labelled_rows(tx, label, filters)
+            .await
+            .into_iter()
+            .map(|row| (row.model_name, row.backend, row.gpu_model))
+            .collect();
+        rows.sort();
+        rows
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_grid_query_filters() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let label = format!("grid-filter-test-{}", Uuid::new_v4());
+        insert_filter_run(&mut tx, &label, "RTX 4090", "grid-filter-a", "llama.cpp", 50.0, 10.0).await;
+        insert_filter_run(&mut tx, &label, "RTX 4090", "grid-filter-b", "vllm", 100.0, 30.0).await;
+        insert_filter_run(&mut tx, &label, "CPU Only", "grid-filter-a", "llama.cpp", 10.0, 8.0).await;
+
+        let row = |model: &str, backend: &str, gpu: &str| (model.to_string(), backend.to_string(), gpu.to_string());
+        let a_gpu = row("grid-filter-a", "llama.cpp", "RTX 4090");
+        let a_cpu = row("grid-filter-a", "llama.cpp", "CPU Only");
+        let b_gpu = row("grid-filter-b", "vllm", "RTX 4090");
+
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({})).await, vec![a_cpu.clone(), a_gpu.clone(), b_gpu.clone()]);
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "max_memory_gb": 10.0 })).await, vec![a_cpu.clone(), a_gpu.clone()]);
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "min_speed": 50.0 })).await, vec![a_gpu.clone(), b_gpu.clone()]);
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "backends": "vllm" })).await, vec![b_gpu.clone()]);
+        // Filter values are bound, so quotes match nothing rather than break the query
+        assert!(filtered_rows(&mut tx, &label, serde_json::json!({ "backends": "a'; DROP TABLE test_runs;--" })).await.is_empty());
+        assert!(filtered_rows(&mut tx, &label, serde_json::json!({ "models": "x' OR '1'='1" })).await.is_empty());
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "models": "grid-filter-a" })).await, vec![a_cpu.clone(), a_gpu.clone()]);
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "hardware_types": "cpu_only" })).await, vec![a_cpu.clone()]);
+        assert_eq!(filtered_rows(&mut tx, &label, serde_json::json!({ "hardware_types": "gpu" })).await, vec![a_gpu.clone(), b_gpu.clone()]);
+        assert_eq!(
+            filtered_rows(&mut tx, &label, serde_json::json!({
+                "models": "grid-filter-a,grid-filter-b",
+                "backends": "llama.cpp",
+                "hardware_types": "gpu",
+                "min_speed": 20.0,
+                "max_memory_gb": 20.0,
+            }))
+            .await,
+            vec![a_gpu]
+        );
+
+        tx.rollback().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a database (DATABASE_URL)"]
+    async fn test_grid_query_splits_concurrency() {
+        let pool = crate::db::test_pool().await;
+        let mut tx = pool.begin().await.unwrap();
+
+        let label = format!("grid-concurrency-test-{}", Uuid::new_v4());
+        insert_filter_run(&mut tx, &label, "RTX 4090", "grid-concurrency", "vllm", 40.0, 10.0).await;
+        insert_filter_run(&mut tx, &label, "RTX 4090", "grid-concurrency", "vllm", 400.0, 12.0).await;
+        // The faster run served batched requests
+        sqlx::query(
+            r#"
+            UPDATE test_runs tr SET concurrent_requests = CASE WHEN pm.value > 100 THEN 8 ELSE 1 END
+            FROM performance_metrics pm
+            WHERE pm.test_run_id = tr.id AND pm.metric_name = 'tokens_per_second' AND tr.label = $1
+            "#,
+        )
+        .bind(&label)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        let mut rows: Vec<(Option<i32>, Option<f64>)> = labelled_rows(&mut tx, &label, serde_json::json!({}))
+            .await
+            .into_iter()
+            .map(|row| (row.concurrent_requests, row.tokens_per_second))
+            .collect();
+        rows.sort_by_key(|(concurrent_requests, _)| *concurrent_requests);
+        assert_eq!(rows, vec![(Some(1), Some(40.0)), (Some(8), Some(400.0))]);
+
+        let interactive = labelled_rows(&mut tx, &label, serde_json::json!({ "concurrent_requests": 1 })).await;
+        assert_eq!(interactive.len(), 1);
+        assert_eq!(interactive[0].tokens_per_second, Some(40.0));
+
+        tx.rollback().await.unwrap();
+    }
+}
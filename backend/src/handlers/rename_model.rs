@@ -0,0 +1,138 @@
+// handlers/rename_model.rs
+// Admin cleanup: rename a model, merging it into another of the new name
+
+use axum::{extract::State, response::Json};
+
+use llm_benchmark_types::{MergedBenchmarkRows, RenameModelRequest, RenameModelResponse};
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    handlers::merge_variants::merge_variant_rows,
+    AppState,
+};
+
+/// Rename `from_model_name` to `to_model_name` in every test run and model
+/// variant in one transaction. A variant whose quantization and LoRA adapter
+/// already exist under the new name is merged into that variant (newest
+/// score per benchmark kept) and deleted. 404 if nothing has the old name.
+pub async fn rename_model(
+    State(state): State<AppState>,
+    Json(request): Json<RenameModelRequest>,
+) -> Result<Json<RenameModelResponse>, AppError> {
+    let (from, to) = rename_names(&request)?;
+
+    let response = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &(from, to), |tx, names| {
+        Box::pin(rename_model_rows(tx, names.0, names.1))
+    })
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// The stored name to match and the new name. The old name is used as
+/// given, so a name stored with stray whitespace can be cleaned up; only
+/// the new name is trimmed.
+fn rename_names(request: &RenameModelRequest) -> Result<(&str, &str), AppError> {
+    let from = request.from_model_name.as_str();
+    let to = request.to_model_name.trim();
+    if from.is_empty() || to.is_empty() {
+        return Err(AppError::BadRequest("from_model_name and to_model_name must not be empty".to_string()));
+    }
+    if from == to {
+        return Err(AppError::BadRequest("from_model_name and to_model_name must differ".to_string()));
+    }
+    Ok((from, to))
+}
+
+async fn rename_model_rows(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    from: &str,
+    to: &str,
+) -> Result<RenameModelResponse, TxError<AppError>> {
+    let variants = sqlx::query!(
+        r#"
+        SELECT src.id, dst.id as "existing_id?"
+        FROM model_variants src
+        LEFT JOIN model_variants dst ON dst.model_name = $2
+            AND dst.quantization = src.quantization AND dst.lora_adapter = src.lora_adapter
+        WHERE src.model_name = $1
+        "#,
+        from,
+        to
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .tx_context("Failed to look up model variants")?;
+
+    let mut response = RenameModelResponse {
+        test_runs_updated: 0,
+        variants_renamed: 0,
+        variants_merged: 0,
+        benchmarks: Vec::new(),
+    };
+
+    for variant in variants {
+        let Some(existing_id) = variant.existing_id else {
+            sqlx::query!(
+                "UPDATE model_variants SET model_name = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+                variant.id,
+                to
+            )
+            .execute(&mut **tx)
+            .await
+            .tx_context("Failed to rename model variant")?;
+            response.variants_renamed += 1;
+            continue;
+        };
+
+        let merged = merge_variant_rows(tx, variant.id, existing_id).await?;
+        add_merged_rows(&mut response.benchmarks, merged);
+        response.variants_merged += 1;
+    }
+
+    response.test_runs_updated = sqlx::query!("UPDATE test_runs SET model_name = $2 WHERE model_name = $1", from, to)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to rename test runs")?
+        .rows_affected();
+
+    if response.test_runs_updated == 0 && response.variants_renamed == 0 && response.variants_merged == 0 {
+        return Err(TxError::Abort(AppError::NotFound(format!("No test runs or model variants for model {}", from))));
+    }
+
+    Ok(response)
+}
+
+/// Add one merge's per-benchmark counts to the running totals
+fn add_merged_rows(totals: &mut Vec<MergedBenchmarkRows>, merged: Vec<MergedBenchmarkRows>) {
+    for rows in merged {
+        match totals.iter_mut().find(|total| total.benchmark == rows.benchmark) {
+            Some(total) => {
+                total.moved += rows.moved;
+                total.discarded += rows.discarded;
+            }
+            None => totals.push(rows),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_names() {
+        let request = |from: &str, to: &str| RenameModelRequest {
+            from_model_name: from.to_string(),
+            to_model_name: to.to_string(),
+        };
+
+        // Only the new name is trimmed, so a padded stored name can be fixed
+        assert_eq!(rename_names(&request("llama-3 ", " llama-3")).unwrap(), ("llama-3 ", "llama-3"));
+        assert_eq!(rename_names(&request("a", " b ")).unwrap(), ("a", "b"));
+        assert!(rename_names(&request("llama-3", " llama-3 ")).is_err());
+        assert!(rename_names(&request("", "b")).is_err());
+        assert!(rename_names(&request("a", "  ")).is_err());
+    }
+}
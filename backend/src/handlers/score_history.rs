@@ -0,0 +1,239 @@
+// handlers/score_history.rs
+// Superseded benchmark scores kept by uploads with keep_history
+
+use axum::{
+    extract::{Query, State},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use sqlx::Row;
+use uuid::Uuid;
+
+use llm_benchmark_types::{
+    benchmarks::{BenchmarkScore, BenchmarkScoreType, MMLUProScore},
+    normalize_quantization, ScoreHistoryEntry, ScoreHistoryRequest, ScoreHistoryResponse,
+};
+
+use crate::{
+    db::{TxContext, TxError},
+    error::{AppError, DbContext},
+    AppState,
+};
+
+/// Where a benchmark's current scores live in the v2 tables, with SQL for
+/// the category (MMLU category or GPQA subset) and the 0-100 score of a row
+struct ScoreSource {
+    table: &'static str,
+    category: &'static str,
+    score: &'static str,
+    /// Generic benchmarks share a table and are told apart by name
+    generic: bool,
+}
+
+fn score_source(benchmark: &str) -> ScoreSource {
+    let (table, category, score) = match benchmark {
+        "mmlu" => ("mmlu_scores_v2", "category", "score"),
+        "gsm8k" => ("gsm8k_scores_v2", "NULL", "accuracy * 100"),
+        "humaneval" => ("humaneval_scores_v2", "NULL", "pass_at_1"),
+        "hellaswag" => ("hellaswag_scores_v2", "NULL", "accuracy"),
+        "truthfulqa" => ("truthfulqa_scores_v2", "NULL", "truthful_score"),
+        "gpqa" => ("gpqa_scores_v2", "subset", "accuracy"),
+        _ => {
+            return ScoreSource {
+                table: "generic_benchmark_scores_v2",
+                category: "NULL",
+                score: "overall_score",
+                generic: true,
+            }
+        }
+    };
+    ScoreSource { table, category, score, generic: false }
+}
+
+impl ScoreSource {
+    /// `WHERE` clause selecting a variant's rows: `$1` is the variant id and,
+    /// for generic benchmarks, `$2` the benchmark name
+    fn variant_filter(&self) -> &'static str {
+        if self.generic {
+            "model_variant_id = $1 AND benchmark_name = $2"
+        } else {
+            "model_variant_id = $1"
+        }
+    }
+}
+
+/// Make room for `score` under keep_history. If the variant's current set
+/// for the benchmark is no newer than `timestamp`, it is moved to the
+/// history and `None` is returned so the caller replaces it as usual.
+/// Otherwise `score` is older than what reads already use, so it goes
+/// straight to the history and the number of rows written is returned.
+pub(crate) async fn archive_current_scores<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+    score: &BenchmarkScoreType,
+    timestamp: DateTime<Utc>,
+) -> Result<Option<usize>, TxError<E>> {
+    let benchmark = score.benchmark_name();
+    let source = score_source(benchmark);
+
+    let query = format!(
+        "SELECT MAX(timestamp) FROM {} WHERE {}",
+        source.table,
+        source.variant_filter()
+    );
+    let mut current_query = sqlx::query_scalar(&query).bind(model_variant_id);
+    if source.generic {
+        current_query = current_query.bind(benchmark);
+    }
+    let current: Option<DateTime<Utc>> = current_query
+        .fetch_one(&mut **tx)
+        .await
+        .tx_context("Failed to look up current scores")?;
+
+    if current.is_some_and(|current| current > timestamp) {
+        return record_history(tx, model_variant_id, score, timestamp).await.map(Some);
+    }
+
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO benchmark_score_history
+        (model_variant_id, benchmark, category, score, timestamp, context)
+        SELECT model_variant_id, $2, {}, {}, timestamp, context
+        FROM {}
+        WHERE {}
+        "#,
+        source.category,
+        source.score,
+        source.table,
+        source.variant_filter()
+    ))
+    .bind(model_variant_id)
+    .bind(benchmark)
+    .execute(&mut **tx)
+    .await
+    .tx_context("Failed to archive current scores")?;
+
+    Ok(None)
+}
+
+/// Write `score` directly to the history, returning the number of rows written
+async fn record_history<E>(
+    tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+    model_variant_id: Uuid,
+    score: &BenchmarkScoreType,
+    timestamp: DateTime<Utc>,
+) -> Result<usize, TxError<E>> {
+    let (rows, context) = match score {
+        BenchmarkScoreType::MMLU(mmlu_score) => {
+            let mmlu_score = MMLUProScore::from(mmlu_score.clone());
+            (category_rows(&mmlu_score), mmlu_score.context)
+        }
+        BenchmarkScoreType::MMLUPro(mmlu_score) => (category_rows(mmlu_score), mmlu_score.context.clone()),
+        BenchmarkScoreType::GSM8K(s) => (vec![(None, score.overall_score())], s.context.clone()),
+        BenchmarkScoreType::HumanEval(s) => (vec![(None, score.overall_score())], s.context.clone()),
+        BenchmarkScoreType::HellaSwag(s) => (vec![(None, score.overall_score())], s.context.clone()),
+        BenchmarkScoreType::TruthfulQA(s) => (vec![(None, score.overall_score())], s.context.clone()),
+        BenchmarkScoreType::Gpqa(s) => (vec![(s.subset.clone(), score.overall_score())], s.context.clone()),
+        BenchmarkScoreType::Generic(s) => (vec![(None, score.overall_score())], s.context.clone()),
+    };
+
+    let written = rows.len();
+    for (category, value) in rows {
+        sqlx::query(
+            r#"
+            INSERT INTO benchmark_score_history
+            (model_variant_id, benchmark, category, score, timestamp, context)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(model_variant_id)
+        .bind(score.benchmark_name())
+        .bind(category)
+        .bind(value)
+        .bind(timestamp)
+        .bind(&context)
+        .execute(&mut **tx)
+        .await
+        .tx_context("Failed to record score history")?;
+    }
+
+    Ok(written)
+}
+
+fn category_rows(mmlu_score: &MMLUProScore) -> Vec<(Option<String>, f64)> {
+    mmlu_score
+        .categories
+        .iter()
+        .map(|c| (Some(c.category.clone()), c.score))
+        .collect()
+}
+
+/// Every score kept for a model variant on one benchmark, oldest first.
+///
+/// The current set (the one read endpoints use) comes from the v2 score
+/// tables; earlier sets are only there if they were replaced by an upload
+/// with keep_history.
+pub async fn get_score_history(
+    Query(params): Query<ScoreHistoryRequest>,
+    State(state): State<AppState>,
+) -> Result<Json<ScoreHistoryResponse>, AppError> {
+    let quantization = normalize_quantization(&params.quantization);
+    let lora_adapter = params.lora_adapter.as_deref().unwrap_or("");
+
+    let model_variant_id: Uuid = sqlx::query_scalar(
+        "SELECT id FROM model_variants WHERE model_name = $1 AND quantization = $2 AND lora_adapter = $3",
+    )
+    .bind(&params.model)
+    .bind(&quantization)
+    .bind(lora_adapter)
+    .fetch_optional(&state.db)
+    .await
+    .db_context("Failed to look up model variant")?
+    .ok_or_else(|| {
+        AppError::NotFound(format!("No benchmark scores for {} {}", params.model, quantization))
+    })?;
+
+    // Known benchmarks are case-insensitive; generic names are matched as given
+    let lowered = params.benchmark.to_lowercase();
+    let benchmark = if score_source(&lowered).generic { params.benchmark.clone() } else { lowered };
+    let source = score_source(&benchmark);
+
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT timestamp, {category} as category, {score} as score, TRUE as current
+        FROM {table}
+        WHERE {filter}
+        UNION ALL
+        SELECT timestamp, category, score, FALSE as current
+        FROM benchmark_score_history
+        WHERE model_variant_id = $1 AND benchmark = $2
+        ORDER BY timestamp, category
+        "#,
+        category = source.category,
+        score = source.score,
+        table = source.table,
+        filter = source.variant_filter(),
+    ))
+    .bind(model_variant_id)
+    .bind(&benchmark)
+    .fetch_all(&state.db)
+    .await
+    .db_context("Failed to fetch score history")?;
+
+    let entries = rows
+        .iter()
+        .map(|row| ScoreHistoryEntry {
+            timestamp: row.get("timestamp"),
+            category: row.get("category"),
+            score: row.get("score"),
+            current: row.get("current"),
+        })
+        .collect();
+
+    Ok(Json(ScoreHistoryResponse {
+        model_name: params.model,
+        quantization,
+        benchmark,
+        entries,
+    }))
+}
@@ -0,0 +1,85 @@
+// handlers/stats.rs
+// Headline numbers for the dashboard landing page
+
+use axum::{extract::State, response::Json};
+
+use llm_benchmark_types::{HardwarePlaceholder, PlatformStats};
+
+use crate::{
+    error::{AppError, DbContext},
+    AppState,
+};
+
+/// Counts of completed test runs, model variants, GPUs and benchmark scores,
+/// computed with aggregate queries run concurrently
+pub async fn get_stats(State(state): State<AppState>) -> Result<Json<PlatformStats>, AppError> {
+    let runs = sqlx::query!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM test_runs WHERE status = 'completed') as "total_test_runs!",
+            (SELECT MAX(timestamp) FROM test_runs WHERE status = 'completed') as newest_run_timestamp,
+            (SELECT COUNT(*) FROM (
+                SELECT model_name, quantization, lora_adapter FROM test_runs WHERE status = 'completed'
+                UNION
+                SELECT model_name, quantization, lora_adapter FROM model_variants
+            ) variants) as "total_model_variants!"
+        "#
+    )
+    .fetch_one(&state.db);
+
+    // Few enough distinct GPU models to filter placeholders here rather than
+    // repeat their names in SQL
+    let gpu_models = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT hp.gpu_model
+        FROM hardware_profiles hp
+        JOIN test_runs tr ON tr.hardware_profile_id = hp.id AND tr.status = 'completed'
+        "#
+    )
+    .fetch_all(&state.db);
+
+    // MMLU and MMLU-Pro share a table with a row per category, and a generic
+    // benchmark may reuse a built-in name like "gsm8k", so count distinct
+    // variants per name across every table
+    let benchmarks = sqlx::query!(
+        r#"
+        SELECT benchmark_name as "benchmark_name!", COUNT(DISTINCT model_variant_id) as "variant_count!"
+        FROM (
+            SELECT 'mmlu' as benchmark_name, model_variant_id FROM mmlu_scores_v2
+            UNION ALL
+            SELECT 'gsm8k', model_variant_id FROM gsm8k_scores_v2
+            UNION ALL
+            SELECT 'humaneval', model_variant_id FROM humaneval_scores_v2
+            UNION ALL
+            SELECT 'hellaswag', model_variant_id FROM hellaswag_scores_v2
+            UNION ALL
+            SELECT 'truthfulqa', model_variant_id FROM truthfulqa_scores_v2
+            UNION ALL
+            SELECT 'gpqa', model_variant_id FROM gpqa_scores_v2
+            UNION ALL
+            SELECT benchmark_name, model_variant_id FROM generic_benchmark_scores_v2
+        ) scores
+        GROUP BY benchmark_name
+        "#
+    )
+    .fetch_all(&state.db);
+
+    let (runs, gpu_models, benchmarks) = tokio::join!(runs, gpu_models, benchmarks);
+    let runs = runs.db_context("Failed to count test runs")?;
+    let gpu_models = gpu_models.db_context("Failed to list GPU models")?;
+    let benchmarks = benchmarks.db_context("Failed to count benchmark scores")?;
+
+    Ok(Json(PlatformStats {
+        total_test_runs: runs.total_test_runs,
+        total_model_variants: runs.total_model_variants,
+        total_gpus: gpu_models
+            .iter()
+            .filter(|gpu_model| HardwarePlaceholder::of_gpu_model(gpu_model).is_none())
+            .count(),
+        benchmarks_by_type: benchmarks
+            .into_iter()
+            .map(|row| (row.benchmark_name, row.variant_count as usize))
+            .collect(),
+        newest_run_timestamp: runs.newest_run_timestamp,
+    }))
+}
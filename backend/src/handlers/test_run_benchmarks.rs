@@ -0,0 +1,59 @@
+// handlers/test_run_benchmarks.rs
+// Benchmark scores attached to existing test runs
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use llm_benchmark_types::{AttachBenchmarksResponse, BenchmarkScore, BenchmarkScoreType};
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    models::benchmark_queries::insert_benchmark_score,
+    AppState,
+};
+
+/// Attach benchmark scores to an existing test run. Either every score is
+/// stored or, if one fails, none are.
+pub async fn attach_benchmarks(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Json(scores): Json<Vec<BenchmarkScoreType>>,
+) -> Result<Json<AttachBenchmarksResponse>, AppError> {
+    if scores.is_empty() {
+        return Err(AppError::BadRequest("No benchmark scores provided".to_string()));
+    }
+    for score in &scores {
+        score.validate()?;
+    }
+
+    with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &scores, |tx, scores| {
+        Box::pin(async move {
+            let exists = sqlx::query!("SELECT id FROM test_runs WHERE id = $1", test_run_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .tx_context("Failed to look up test run")?;
+
+            if exists.is_none() {
+                return Err(TxError::Abort(AppError::NotFound(format!("Test run {} not found", test_run_id))));
+            }
+
+            for score in scores {
+                insert_benchmark_score(tx, &test_run_id, score)
+                    .await
+                    .tx_context("Failed to insert benchmark score")?;
+            }
+
+            Ok(())
+        })
+    })
+    .await?;
+
+    Ok(Json(AttachBenchmarksResponse {
+        test_run_id,
+        scores_uploaded: scores.len(),
+    }))
+}
@@ -0,0 +1,147 @@
+// handlers/test_run_log.rs
+// Console logs attached to test runs
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use uuid::Uuid;
+
+use llm_benchmark_types::{AttachLogRequest, AttachLogResponse, TestRunLog};
+
+use crate::{
+    db::{with_retry_tx, TxContext, TxError, DEFAULT_TX_ATTEMPTS},
+    error::AppError,
+    AppState,
+};
+
+/// Largest log stored per test run; longer logs keep only their tail, where
+/// the errors usually are
+pub const MAX_LOG_BYTES: usize = 1024 * 1024;
+
+/// Request body limit for routes that may carry a log, so oversized logs
+/// reach the handler to be truncated rather than being rejected outright
+pub const MAX_LOG_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Attach (or replace) the console log of a test run
+pub async fn attach_log(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+    Json(request): Json<AttachLogRequest>,
+) -> Result<Json<AttachLogResponse>, AppError> {
+    let truncated = with_retry_tx(&state.db, DEFAULT_TX_ATTEMPTS, &request, |tx, request| {
+        Box::pin(async move {
+            let exists = sqlx::query!("SELECT id FROM test_runs WHERE id = $1", test_run_id)
+                .fetch_optional(&mut **tx)
+                .await
+                .tx_context("Failed to look up test run")?;
+
+            if exists.is_none() {
+                return Err(TxError::Abort(AppError::NotFound(format!("Test run {} not found", test_run_id))));
+            }
+
+            store_log(tx, test_run_id, &request.log)
+                .await
+                .tx_context("Failed to store log")
+        })
+    })
+    .await?;
+
+    Ok(Json(AttachLogResponse {
+        test_run_id,
+        original_bytes: request.log.len() as i64,
+        truncated,
+    }))
+}
+
+/// Fetch the console log of a test run
+pub async fn get_log(
+    Path(test_run_id): Path<Uuid>,
+    State(state): State<AppState>,
+) -> Result<Json<TestRunLog>, AppError> {
+    let row = sqlx::query!(
+        r#"
+        SELECT log, original_bytes, truncated
+        FROM test_run_logs
+        WHERE test_run_id = $1
+        "#,
+        test_run_id
+    )
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No log stored for test run {}", test_run_id)))?;
+
+    Ok(Json(TestRunLog {
+        test_run_id,
+        log: row.log,
+        original_bytes: row.original_bytes,
+        truncated: row.truncated,
+    }))
+}
+
+/// Store `log` for the test run, replacing any earlier log. Returns whether
+/// it had to be truncated.
+pub(crate) async fn store_log(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    test_run_id: Uuid,
+    log: &str,
+) -> Result<bool, sqlx::Error> {
+    let (stored, truncated) = truncate_log(log, MAX_LOG_BYTES);
+
+    sqlx::query!(
+        r#"
+        INSERT INTO test_run_logs (test_run_id, log, original_bytes, truncated)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (test_run_id) DO UPDATE SET
+            log = EXCLUDED.log,
+            original_bytes = EXCLUDED.original_bytes,
+            truncated = EXCLUDED.truncated,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+        test_run_id,
+        stored,
+        log.len() as i64,
+        truncated
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(truncated)
+}
+
+/// Keep the last `max_bytes` of `log` (on a char boundary), prefixed with a
+/// note saying how much was dropped
+fn truncate_log(log: &str, max_bytes: usize) -> (String, bool) {
+    if log.len() <= max_bytes {
+        return (log.to_string(), false);
+    }
+
+    let mut start = log.len() - max_bytes;
+    while !log.is_char_boundary(start) {
+        start += 1;
+    }
+
+    (
+        format!("[log truncated: first {} bytes omitted]\n{}", start, &log[start..]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_log_keeps_tail() {
+        assert_eq!(truncate_log("short log", 16), ("short log".to_string(), false));
+
+        let (stored, truncated) = truncate_log("line 1\nline 2\nerror: boom", 11);
+        assert!(truncated);
+        assert_eq!(stored, "[log truncated: first 14 bytes omitted]\nerror: boom");
+
+        // Never splits a multi-byte character
+        let (stored, truncated) = truncate_log("ééé", 3);
+        assert!(truncated);
+        assert!(stored.ends_with("\né"));
+    }
+}
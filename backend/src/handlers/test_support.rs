@@ -0,0 +1,32 @@
+// handlers/test_support.rs
+// Constructors shared by handler tests
+
+use llm_benchmark_types::{hardware::HardwareCategory, QuantizationPerformance};
+use uuid::Uuid;
+
+/// A llama.cpp config on a consumer GPU with no memory, power or load
+/// details; tests override the fields they care about
+pub(crate) fn quantization_performance(
+    quantization: &str,
+    tokens_per_second: f64,
+    quality_score: f64,
+) -> QuantizationPerformance {
+    QuantizationPerformance {
+        id: Uuid::new_v4(),
+        quantization: quantization.to_string(),
+        lora_adapter: String::new(),
+        quality_score,
+        tokens_per_second,
+        memory_gb: 0.0,
+        backend: "llama.cpp".to_string(),
+        hardware: "RTX 4090 / Ryzen 9".to_string(),
+        hardware_category: HardwareCategory::ConsumerGpu,
+        concurrent_requests: None,
+        max_context_length: None,
+        load_pattern: None,
+        dataset_name: None,
+        gpu_power_limit_watts: None,
+        gpu_power_watts: None,
+        tokens_per_kwh: None,
+    }
+}
@@ -3,17 +3,17 @@
 
 use chrono::Utc;
 use llm_benchmark_types::{
-    MMLUScore, MMLUCategoryScore, GSM8KScore, HumanEvalScore, 
-    HellaSwagScore, TruthfulQAScore, GenericBenchmarkScore, BenchmarkScoreType
+    MMLUProScore, MMLUProCategoryScore, GSM8KScore, HumanEvalScore, 
+    HellaSwagScore, TruthfulQAScore, GpqaScore, GenericBenchmarkScore, BenchmarkScoreType
 };
 
 use super::benchmark_models::*;
 
 // Helper functions for converting database rows to types crate structs
 
-pub fn mmlu_rows_to_score(rows: Vec<MMLUScoreRow>) -> MMLUScore {
-    let categories = rows.into_iter().map(|row| {
-        MMLUCategoryScore {
+pub fn mmlu_rows_to_score(rows: Vec<MMLUScoreRow>) -> MMLUProScore {
+    let categories: Vec<MMLUProCategoryScore> = rows.into_iter().map(|row| {
+        MMLUProCategoryScore {
             category: row.category,
             score: row.score,
             total_questions: row.total_questions,
@@ -21,8 +21,12 @@ pub fn mmlu_rows_to_score(rows: Vec<MMLUScoreRow>) -> MMLUScore {
         }
     }).collect();
 
-    MMLUScore {
+    // Only known if every category has a count
+    let num_questions_total = categories.iter().map(|c| c.total_questions).sum();
+
+    MMLUProScore {
         categories,
+        num_questions_total,
         timestamp: Utc::now(), // Use current time as aggregate timestamp
         context: None,
     }
@@ -68,12 +72,24 @@ pub fn truthfulqa_row_to_score(row: TruthfulQAScoreRow) -> TruthfulQAScore {
     }
 }
 
+pub fn gpqa_row_to_score(row: GpqaScoreRow) -> GpqaScore {
+    GpqaScore {
+        accuracy: row.accuracy,
+        total_questions: row.total_questions,
+        correct_answers: row.correct_answers,
+        subset: row.subset,
+        timestamp: row.timestamp.unwrap_or_else(Utc::now),
+        context: row.context,
+    }
+}
+
 pub fn generic_row_to_score(row: GenericBenchmarkScoreRow) -> GenericBenchmarkScore {
     GenericBenchmarkScore {
         benchmark_name: row.benchmark_name,
         score: row.score,
         total_questions: row.total_questions,
         correct_answers: row.correct_answers,
+        sub_scores: row.sub_scores,
         timestamp: row.timestamp.unwrap_or_else(|| Utc::now()),
         context: row.context,
     }
@@ -82,9 +98,9 @@ pub fn generic_row_to_score(row: GenericBenchmarkScoreRow) -> GenericBenchmarkSc
 // Helper functions for converting benchmark scores to database insert parameters
 
 pub fn mmlu_score_to_insert_rows(
-    score: &MMLUScore, 
+    score: &MMLUProScore, 
     test_run_id: uuid::Uuid
-) -> Vec<(uuid::Uuid, String, f64, i32, i32, chrono::DateTime<Utc>, Option<serde_json::Value>)> {
+) -> Vec<(uuid::Uuid, String, f64, Option<i32>, Option<i32>, chrono::DateTime<Utc>, Option<serde_json::Value>)> {
     score.categories.iter().map(|category| {
         (
             test_run_id,
@@ -154,16 +170,38 @@ pub fn truthfulqa_score_to_insert_params(
     )
 }
 
+/// (test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context)
+pub type GpqaInsertParams = (uuid::Uuid, f64, i32, i32, Option<String>, chrono::DateTime<Utc>, Option<serde_json::Value>);
+
+pub fn gpqa_score_to_insert_params(
+    score: &GpqaScore, 
+    test_run_id: uuid::Uuid
+) -> GpqaInsertParams {
+    (
+        test_run_id,
+        score.accuracy,
+        score.total_questions,
+        score.correct_answers,
+        score.subset.clone(),
+        score.timestamp,
+        score.context.clone(),
+    )
+}
+
+/// (test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context)
+pub type GenericInsertParams = (uuid::Uuid, String, f64, Option<i32>, Option<i32>, Option<serde_json::Value>, chrono::DateTime<Utc>, Option<serde_json::Value>);
+
 pub fn generic_score_to_insert_params(
     score: &GenericBenchmarkScore, 
     test_run_id: uuid::Uuid
-) -> (uuid::Uuid, String, f64, Option<i32>, Option<i32>, chrono::DateTime<Utc>, Option<serde_json::Value>) {
+) -> GenericInsertParams {
     (
         test_run_id,
         score.benchmark_name.clone(),
         score.score,
         score.total_questions,
         score.correct_answers,
+        score.sub_scores.clone(),
         score.timestamp,
         score.context.clone(),
     )
@@ -172,11 +210,12 @@ pub fn generic_score_to_insert_params(
 // Helper function to determine benchmark type from BenchmarkScoreType
 pub fn get_benchmark_type_name(score: &BenchmarkScoreType) -> String {
     match score {
-        BenchmarkScoreType::MMLU(_) => "mmlu".to_string(),
+        BenchmarkScoreType::MMLU(_) | BenchmarkScoreType::MMLUPro(_) => "mmlu".to_string(),
         BenchmarkScoreType::GSM8K(_) => "gsm8k".to_string(),
         BenchmarkScoreType::HumanEval(_) => "humaneval".to_string(),
         BenchmarkScoreType::HellaSwag(_) => "hellaswag".to_string(),
         BenchmarkScoreType::TruthfulQA(_) => "truthfulqa".to_string(),
+        BenchmarkScoreType::Gpqa(_) => "gpqa".to_string(),
         BenchmarkScoreType::Generic(score) => score.benchmark_name.clone(),
     }
 }
\ No newline at end of file
@@ -12,8 +12,8 @@ pub struct MMLUScoreRow {
     pub test_run_id: Uuid,
     pub category: String,
     pub score: f64,
-    pub total_questions: i32,
-    pub correct_answers: i32,
+    pub total_questions: Option<i32>,
+    pub correct_answers: Option<i32>,
     pub timestamp: Option<DateTime<Utc>>,
     pub context: Option<serde_json::Value>,
     pub created_at: Option<DateTime<Utc>>,
@@ -71,6 +71,20 @@ pub struct TruthfulQAScoreRow {
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// GPQA score database table row
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct GpqaScoreRow {
+    pub id: Uuid,
+    pub test_run_id: Uuid,
+    pub accuracy: f64,
+    pub total_questions: i32,
+    pub correct_answers: i32,
+    pub subset: Option<String>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub context: Option<serde_json::Value>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
 /// Generic benchmark score database table row
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct GenericBenchmarkScoreRow {
@@ -80,6 +94,7 @@ pub struct GenericBenchmarkScoreRow {
     pub score: f64,
     pub total_questions: Option<i32>,
     pub correct_answers: Option<i32>,
+    pub sub_scores: Option<serde_json::Value>,
     pub timestamp: Option<DateTime<Utc>>,
     pub context: Option<serde_json::Value>,
     pub created_at: Option<DateTime<Utc>>,
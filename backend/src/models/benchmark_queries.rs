@@ -1,19 +1,19 @@
 // models/benchmark_queries.rs
 // Query helper functions for benchmark data across multiple tables
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 use llm_benchmark_types::{
-    BenchmarkScoreType, BenchmarkScore
+    mmlu_categories, BenchmarkScoreType, BenchmarkScore, BenchmarkWeights, MMLUProScore
 };
 
 use super::benchmark_models::*;
 use super::benchmark_conversions::{
     mmlu_score_to_insert_rows, gsm8k_score_to_insert_params, 
     humaneval_score_to_insert_params, hellaswag_score_to_insert_params,
-    truthfulqa_score_to_insert_params, generic_score_to_insert_params,
+    truthfulqa_score_to_insert_params, gpqa_score_to_insert_params, generic_score_to_insert_params,
     mmlu_rows_to_score, gsm8k_row_to_score, humaneval_row_to_score,
-    hellaswag_row_to_score, truthfulqa_row_to_score, generic_row_to_score
+    hellaswag_row_to_score, truthfulqa_row_to_score, gpqa_row_to_score, generic_row_to_score
 };
 
 /// Get all benchmark scores for a specific test run
@@ -33,7 +33,7 @@ pub async fn get_all_benchmark_scores_for_test_run(
 
     if !mmlu_rows.is_empty() {
         let mmlu_score = mmlu_rows_to_score(mmlu_rows);
-        scores.push(BenchmarkScoreType::MMLU(mmlu_score));
+        scores.push(BenchmarkScoreType::MMLUPro(mmlu_score));
     }
 
     // Get GSM8K scores
@@ -88,10 +88,23 @@ pub async fn get_all_benchmark_scores_for_test_run(
         scores.push(BenchmarkScoreType::TruthfulQA(truthfulqa_score));
     }
 
+    // Get GPQA scores
+    let gpqa_rows = sqlx::query_as!(
+        GpqaScoreRow,
+        "SELECT id, test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context, created_at 
+         FROM gpqa_scores WHERE test_run_id = $1",
+        test_run_id
+    ).fetch_all(db).await?;
+
+    for row in gpqa_rows {
+        let gpqa_score = gpqa_row_to_score(row);
+        scores.push(BenchmarkScoreType::Gpqa(gpqa_score));
+    }
+
     // Get Generic benchmark scores
     let generic_rows = sqlx::query_as!(
         GenericBenchmarkScoreRow,
-        "SELECT id, test_run_id, benchmark_name, score, total_questions, correct_answers, timestamp, context, created_at 
+        "SELECT id, test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context, created_at 
          FROM generic_benchmark_scores WHERE test_run_id = $1",
         test_run_id
     ).fetch_all(db).await?;
@@ -104,6 +117,97 @@ pub async fn get_all_benchmark_scores_for_test_run(
     Ok(scores)
 }
 
+/// Get all benchmark scores of a model variant from the v2 tables.
+///
+/// Rows are read into the v1 row structs so the same conversions apply;
+/// their `test_run_id` holds the variant id. Fields the v2 tables don't
+/// keep (HumanEval problem count, TruthfulQA helpful score, generic
+/// question counts) come back as 0 or None.
+pub async fn get_all_benchmark_scores_for_model_variant(
+    db: &PgPool,
+    model_variant_id: &Uuid,
+) -> Result<Vec<BenchmarkScoreType>, sqlx::Error> {
+    let mut scores = Vec::new();
+
+    let mmlu_rows = sqlx::query_as!(
+        MMLUScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, category, score, total_questions, correct_answers,
+                  timestamp as "timestamp?", context, created_at
+           FROM mmlu_scores_v2 WHERE model_variant_id = $1"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+
+    if !mmlu_rows.is_empty() {
+        let timestamp = mmlu_rows.iter().filter_map(|row| row.timestamp).max();
+        let mut mmlu_score = mmlu_rows_to_score(mmlu_rows);
+        mmlu_score.categories.sort_by(|a, b| mmlu_categories::compare(&a.category, &b.category));
+        if let Some(timestamp) = timestamp {
+            mmlu_score.timestamp = timestamp;
+        }
+        scores.push(BenchmarkScoreType::MMLUPro(mmlu_score));
+    }
+
+    let gsm8k_rows = sqlx::query_as!(
+        GSM8KScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, problems_solved, total_problems,
+                  timestamp as "timestamp?", context, created_at
+           FROM gsm8k_scores_v2 WHERE model_variant_id = $1"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(gsm8k_rows.into_iter().map(|row| BenchmarkScoreType::GSM8K(gsm8k_row_to_score(row))));
+
+    let humaneval_rows = sqlx::query_as!(
+        HumanEvalScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, pass_at_1, pass_at_10, pass_at_100, 0 as "total_problems!",
+                  timestamp as "timestamp?", context, created_at
+           FROM humaneval_scores_v2 WHERE model_variant_id = $1"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(humaneval_rows.into_iter().map(|row| BenchmarkScoreType::HumanEval(humaneval_row_to_score(row))));
+
+    let hellaswag_rows = sqlx::query_as!(
+        HellaSwagScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, accuracy,
+                  COALESCE(total_questions, 0) as "total_questions!", COALESCE(correct_answers, 0) as "correct_answers!",
+                  timestamp as "timestamp?", context, created_at
+           FROM hellaswag_scores_v2 WHERE model_variant_id = $1"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(hellaswag_rows.into_iter().map(|row| BenchmarkScoreType::HellaSwag(hellaswag_row_to_score(row))));
+
+    let truthfulqa_rows = sqlx::query_as!(
+        TruthfulQAScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, truthful_score, NULL::FLOAT8 as helpful_score,
+                  COALESCE(total_questions, 0) as "total_questions!",
+                  timestamp as "timestamp?", context, created_at
+           FROM truthfulqa_scores_v2 WHERE model_variant_id = $1"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(truthfulqa_rows.into_iter().map(|row| BenchmarkScoreType::TruthfulQA(truthfulqa_row_to_score(row))));
+
+    let gpqa_rows = sqlx::query_as!(
+        GpqaScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, accuracy,
+                  COALESCE(total_questions, 0) as "total_questions!", COALESCE(correct_answers, 0) as "correct_answers!",
+                  subset, timestamp as "timestamp?", context, created_at
+           FROM gpqa_scores_v2 WHERE model_variant_id = $1 ORDER BY subset"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(gpqa_rows.into_iter().map(|row| BenchmarkScoreType::Gpqa(gpqa_row_to_score(row))));
+
+    let generic_rows = sqlx::query_as!(
+        GenericBenchmarkScoreRow,
+        r#"SELECT id, model_variant_id as test_run_id, benchmark_name, overall_score as score,
+                  NULL::INT4 as total_questions, NULL::INT4 as correct_answers, sub_scores,
+                  timestamp as "timestamp?", context, created_at
+           FROM generic_benchmark_scores_v2 WHERE model_variant_id = $1 ORDER BY benchmark_name"#,
+        model_variant_id
+    ).fetch_all(db).await?;
+    scores.extend(generic_rows.into_iter().map(|row| BenchmarkScoreType::Generic(generic_row_to_score(row))));
+
+    Ok(scores)
+}
+
 /// Get aggregated benchmark scores for performance grid (overall scores only)
 pub async fn get_aggregated_benchmark_scores_for_test_run(
     db: &PgPool,
@@ -119,6 +223,17 @@ pub async fn get_aggregated_benchmark_scores_for_test_run(
     Ok(total_score / scores.len() as f64)
 }
 
+/// Weighted composite of all benchmark scores for a test run, or `None`
+/// if it has no score for any weighted benchmark
+pub async fn get_weighted_benchmark_score_for_test_run(
+    db: &PgPool,
+    test_run_id: &Uuid,
+    weights: &BenchmarkWeights,
+) -> Result<Option<f64>, sqlx::Error> {
+    let scores = get_all_benchmark_scores_for_test_run(db, test_run_id).await?;
+    Ok(weights.weighted_score(scores.iter().map(|s| (s.benchmark_name(), s.overall_score()))))
+}
+
 /// Get benchmark scores for a specific benchmark type
 pub async fn get_benchmark_scores_by_type(
     db: &PgPool,
@@ -138,7 +253,7 @@ pub async fn get_benchmark_scores_by_type(
                 Ok(None)
             } else {
                 let mmlu_score = mmlu_rows_to_score(rows);
-                Ok(Some(BenchmarkScoreType::MMLU(mmlu_score)))
+                Ok(Some(BenchmarkScoreType::MMLUPro(mmlu_score)))
             }
         }
         "gsm8k" => {
@@ -205,11 +320,27 @@ pub async fn get_benchmark_scores_by_type(
                 None => Ok(None)
             }
         }
+        "gpqa" => {
+            let row = sqlx::query_as!(
+                GpqaScoreRow,
+                "SELECT id, test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context, created_at 
+                 FROM gpqa_scores WHERE test_run_id = $1",
+                test_run_id
+            ).fetch_optional(db).await?;
+
+            match row {
+                Some(row) => {
+                    let gpqa_score = gpqa_row_to_score(row);
+                    Ok(Some(BenchmarkScoreType::Gpqa(gpqa_score)))
+                }
+                None => Ok(None)
+            }
+        }
         _ => {
             // Try generic benchmark scores
             let row = sqlx::query_as!(
                 GenericBenchmarkScoreRow,
-                "SELECT id, test_run_id, benchmark_name, score, total_questions, correct_answers, timestamp, context, created_at 
+                "SELECT id, test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context, created_at 
                  FROM generic_benchmark_scores WHERE test_run_id = $1 AND benchmark_name = $2",
                 test_run_id, benchmark_type
             ).fetch_optional(db).await?;
@@ -225,6 +356,31 @@ pub async fn get_benchmark_scores_by_type(
     }
 }
 
+/// Insert every category of an MMLU-Pro score in one statement
+async fn insert_mmlu_score(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    test_run_id: &Uuid,
+    mmlu_score: &MMLUProScore,
+) -> Result<(), sqlx::Error> {
+    let rows = mmlu_score_to_insert_rows(mmlu_score, *test_run_id);
+    if !rows.is_empty() {
+        let mut query = QueryBuilder::<Postgres>::new(
+            "INSERT INTO mmlu_scores (test_run_id, category, score, total_questions, correct_answers, timestamp, context) ",
+        );
+        query.push_values(rows, |mut row, (test_run_id, category, score, total_questions, correct_answers, timestamp, context)| {
+            row.push_bind(test_run_id)
+                .push_bind(category)
+                .push_bind(score)
+                .push_bind(total_questions)
+                .push_bind(correct_answers)
+                .push_bind(timestamp)
+                .push_bind(context);
+        });
+        query.build().execute(&mut **tx).await?;
+    }
+    Ok(())
+}
+
 /// Insert benchmark scores into appropriate tables
 pub async fn insert_benchmark_score(
     tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
@@ -233,14 +389,10 @@ pub async fn insert_benchmark_score(
 ) -> Result<(), sqlx::Error> {
     match score {
         BenchmarkScoreType::MMLU(mmlu_score) => {
-            let rows = mmlu_score_to_insert_rows(mmlu_score, *test_run_id);
-            for (test_run_id, category, score, total_questions, correct_answers, timestamp, context) in rows {
-                sqlx::query!(
-                    "INSERT INTO mmlu_scores (test_run_id, category, score, total_questions, correct_answers, timestamp, context) 
-                     VALUES ($1, $2, $3, $4, $5, $6, $7)",
-                    test_run_id, category, score, total_questions, correct_answers, timestamp, context
-                ).execute(&mut **tx).await?;
-            }
+            insert_mmlu_score(tx, test_run_id, &MMLUProScore::from(mmlu_score.clone())).await?;
+        }
+        BenchmarkScoreType::MMLUPro(mmlu_score) => {
+            insert_mmlu_score(tx, test_run_id, mmlu_score).await?;
         }
         BenchmarkScoreType::GSM8K(gsm8k_score) => {
             let (test_run_id, problems_solved, total_problems, timestamp, context) = gsm8k_score_to_insert_params(gsm8k_score, *test_run_id);
@@ -274,12 +426,20 @@ pub async fn insert_benchmark_score(
                 test_run_id, truthful_score, helpful_score, total_questions, timestamp, context
             ).execute(&mut **tx).await?;
         }
-        BenchmarkScoreType::Generic(generic_score) => {
-            let (test_run_id, benchmark_name, score, total_questions, correct_answers, timestamp, context) = generic_score_to_insert_params(generic_score, *test_run_id);
+        BenchmarkScoreType::Gpqa(gpqa_score) => {
+            let (test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context) = gpqa_score_to_insert_params(gpqa_score, *test_run_id);
             sqlx::query!(
-                "INSERT INTO generic_benchmark_scores (test_run_id, benchmark_name, score, total_questions, correct_answers, timestamp, context) 
+                "INSERT INTO gpqa_scores (test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context) 
                  VALUES ($1, $2, $3, $4, $5, $6, $7)",
-                test_run_id, benchmark_name, score, total_questions, correct_answers, timestamp, context
+                test_run_id, accuracy, total_questions, correct_answers, subset, timestamp, context
+            ).execute(&mut **tx).await?;
+        }
+        BenchmarkScoreType::Generic(generic_score) => {
+            let (test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context) = generic_score_to_insert_params(generic_score, *test_run_id);
+            sqlx::query!(
+                "INSERT INTO generic_benchmark_scores (test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context) 
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+                test_run_id, benchmark_name, score, total_questions, correct_answers, sub_scores, timestamp, context
             ).execute(&mut **tx).await?;
         }
     }
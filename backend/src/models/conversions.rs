@@ -24,13 +24,17 @@ impl From<PerformanceGridQueryResult> for PerformanceGridRow {
             id: row.test_run_id,
             model_name: row.model_name,
             quantization: row.quantization,
+            lora_adapter: row.lora_adapter,
             backend: row.backend,
             tokens_per_second: row.tokens_per_second.unwrap_or(0.0),
             memory_gb: row.memory_gb.unwrap_or(0.0),
+            first_token_latency_ms: row.first_token_latency_ms,
             gpu_model: row.gpu_model,
             cpu_arch: row.cpu_arch,
             hardware_type,
+            concurrent_requests: row.concurrent_requests,
             overall_score: row.overall_score,
+            run_count: row.run_count,
         }
     }
 }
@@ -56,6 +60,7 @@ impl TestRunRow {
             overall_score,
             timestamp: self.timestamp,
             status,
+            annotation: None,
         }
     }
 }
@@ -65,10 +70,14 @@ impl HardwareProfileRow {
         HardwareConfig {
             gpu_model: self.gpu_model.clone(),
             gpu_memory_gb: self.gpu_memory_gb,
+            gpu_count: self.gpu_count,
+            total_gpu_memory_gb: self.total_gpu_memory_gb,
             cpu_model: self.cpu_model.clone(),
             cpu_arch: self.cpu_arch.clone(),
             ram_gb: self.ram_gb,
             ram_type: self.ram_type.clone(),
+            cpu_threads: self.cpu_threads,
+            numa_nodes: self.numa_nodes,
             virtualization_type: self.virtualization_type.clone(),
             optimizations: self.optimizations.clone(),
         }
@@ -78,10 +87,14 @@ impl HardwareProfileRow {
         SystemInfo {
             gpu_model: self.gpu_model.clone(),
             gpu_memory_gb: self.gpu_memory_gb,
+            gpu_count: self.gpu_count,
+            total_gpu_memory_gb: self.total_gpu_memory_gb,
             cpu_model: self.cpu_model.clone(),
             cpu_arch: self.cpu_arch.clone(),
             ram_gb: self.ram_gb.unwrap_or(0),
             ram_type: self.ram_type.clone().unwrap_or_else(|| "Unknown".to_string()),
+            cpu_threads: self.cpu_threads,
+            numa_nodes: self.numa_nodes,
             virtualization_type: self.virtualization_type.clone(),
             optimizations: self.optimizations.clone(),
         }
@@ -25,10 +25,14 @@ pub struct HardwareProfileRow {
     pub id: Uuid,
     pub gpu_model: String,
     pub gpu_memory_gb: i32,
+    pub gpu_count: i32,
+    pub total_gpu_memory_gb: Option<i32>,
     pub cpu_model: String,
     pub cpu_arch: String,
     pub ram_gb: Option<i32>,
     pub ram_type: Option<String>,
+    pub cpu_threads: Option<i32>,
+    pub numa_nodes: Option<i32>,
     pub virtualization_type: Option<String>,
     pub optimizations: Vec<String>,
 }
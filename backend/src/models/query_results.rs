@@ -10,13 +10,17 @@ pub struct PerformanceGridQueryResult {
     pub test_run_id: Uuid,
     pub model_name: String,
     pub quantization: String,
+    pub lora_adapter: String,
     pub backend: String,
     pub gpu_model: String,
     pub cpu_arch: String,
     pub virtualization_type: Option<String>,
+    pub concurrent_requests: Option<i32>,
     pub tokens_per_second: Option<f64>,
     pub memory_gb: Option<f64>,
+    pub first_token_latency_ms: Option<f64>,
     pub overall_score: Option<f64>,
+    pub run_count: i64,
 }
 
 /// Result type for configuration data queries
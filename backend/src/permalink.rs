@@ -0,0 +1,138 @@
+// backend/src/permalink.rs
+// Signed, expiring tokens for sharing a comparison
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+/// Environment variable holding the permalink signing secret
+pub const PERMALINK_SECRET_VAR: &str = "PERMALINK_SECRET";
+
+/// Bytes of the HMAC-SHA256 tag kept in a token
+const TAG_LEN: usize = 16;
+
+/// Token payload: both config ids, then the expiry as big-endian Unix seconds
+const PAYLOAD_LEN: usize = 16 + 16 + 8;
+
+/// Why a permalink token was rejected
+#[derive(Debug, PartialEq)]
+pub enum PermalinkError {
+    /// Not a token we issued, or altered since
+    Invalid,
+    /// Validly signed but past its expiry
+    Expired(DateTime<Utc>),
+}
+
+/// Issues and checks comparison permalink tokens.
+///
+/// A token is the two config ids and an expiry, followed by a truncated
+/// HMAC-SHA256 of those bytes, base64url-encoded (75 characters).
+#[derive(Clone)]
+pub struct PermalinkSigner {
+    secret: Arc<[u8]>,
+}
+
+impl PermalinkSigner {
+    pub fn new(secret: &[u8]) -> Self {
+        Self { secret: secret.into() }
+    }
+
+    /// Signer keyed by `PERMALINK_SECRET`. Without it a random per-process
+    /// secret is used, so links stop working when the server restarts.
+    pub fn from_env() -> Self {
+        match std::env::var(PERMALINK_SECRET_VAR) {
+            Ok(secret) if !secret.is_empty() => Self::new(secret.as_bytes()),
+            _ => {
+                tracing::warn!(
+                    "{} not set; comparison permalinks will not survive a restart",
+                    PERMALINK_SECRET_VAR
+                );
+                let secret = [Uuid::new_v4().into_bytes(), Uuid::new_v4().into_bytes()].concat();
+                Self::new(&secret)
+            }
+        }
+    }
+
+    /// Token for comparing `config_a` with `config_b` until `expires_at`
+    pub fn sign(&self, config_a: Uuid, config_b: Uuid, expires_at: DateTime<Utc>) -> String {
+        let mut token = Vec::with_capacity(PAYLOAD_LEN + TAG_LEN);
+        token.extend_from_slice(config_a.as_bytes());
+        token.extend_from_slice(config_b.as_bytes());
+        token.extend_from_slice(&expires_at.timestamp().to_be_bytes());
+
+        let tag = self.mac(&token).finalize().into_bytes();
+        token.extend_from_slice(&tag[..TAG_LEN]);
+
+        URL_SAFE_NO_PAD.encode(token)
+    }
+
+    /// The two config ids in `token`, if it is authentic and unexpired at `now`
+    pub fn verify(&self, token: &str, now: DateTime<Utc>) -> Result<(Uuid, Uuid), PermalinkError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| PermalinkError::Invalid)?;
+        if bytes.len() != PAYLOAD_LEN + TAG_LEN {
+            return Err(PermalinkError::Invalid);
+        }
+
+        let (payload, tag) = bytes.split_at(PAYLOAD_LEN);
+        self.mac(payload)
+            .verify_truncated_left(tag)
+            .map_err(|_| PermalinkError::Invalid)?;
+
+        let config_a = Uuid::from_slice(&payload[..16]).map_err(|_| PermalinkError::Invalid)?;
+        let config_b = Uuid::from_slice(&payload[16..32]).map_err(|_| PermalinkError::Invalid)?;
+        let expiry = i64::from_be_bytes(payload[32..].try_into().map_err(|_| PermalinkError::Invalid)?);
+        let expires_at = Utc
+            .timestamp_opt(expiry, 0)
+            .single()
+            .ok_or(PermalinkError::Invalid)?;
+
+        if expires_at <= now {
+            return Err(PermalinkError::Expired(expires_at));
+        }
+
+        Ok((config_a, config_b))
+    }
+
+    fn mac(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload);
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_permalink_round_trip_tamper_and_expiry() {
+        let signer = PermalinkSigner::new(b"test secret");
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+        let expires_at = now + Duration::days(1);
+
+        let token = signer.sign(a, b, expires_at);
+        assert_eq!(token.len(), 75);
+        assert_eq!(signer.verify(&token, now), Ok((a, b)));
+
+        // Another secret, a flipped character or garbage are all rejected
+        assert_eq!(PermalinkSigner::new(b"other").verify(&token, now), Err(PermalinkError::Invalid));
+        let mut tampered = token.clone().into_bytes();
+        tampered[3] = if tampered[3] == b'A' { b'B' } else { b'A' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert_eq!(signer.verify(&tampered, now), Err(PermalinkError::Invalid));
+        assert_eq!(signer.verify("not-a-token", now), Err(PermalinkError::Invalid));
+
+        assert!(matches!(
+            signer.verify(&token, expires_at + Duration::seconds(1)),
+            Err(PermalinkError::Expired(_))
+        ));
+    }
+}
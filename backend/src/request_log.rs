@@ -0,0 +1,77 @@
+// backend/src/request_log.rs
+// Per-request logging: method, path, status and latency of every request
+
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::Request,
+    middleware::Next,
+    response::Response,
+};
+
+/// Log each request at info level once it has been answered. For 5xx
+/// responses the body (an `ErrorResponse`) is logged at error level as
+/// well; it is buffered to do so and passed on unchanged.
+pub async fn log_requests(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16();
+    let latency = start.elapsed();
+    tracing::info!(%method, %path, status, ?latency, "request");
+
+    if !response.status().is_server_error() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => {
+            tracing::error!(%method, %path, status, body = %String::from_utf8_lossy(&bytes), "request failed");
+            Body::from(bytes)
+        }
+        Err(e) => {
+            tracing::error!(%method, %path, status, "request failed; error body unreadable: {}", e);
+            Body::empty()
+        }
+    };
+    Response::from_parts(parts, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    use crate::error::AppError;
+
+    #[tokio::test]
+    async fn test_error_body_passes_through() {
+        let app = Router::new()
+            .route("/fail", get(|| async { Err::<(), _>(AppError::Unavailable("backend down".to_string())) }))
+            .route("/ok", get(|| async { "fine" }))
+            .layer(axum::middleware::from_fn(log_requests));
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri("/fail").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(error["error"], "backend down");
+
+        let response = app
+            .oneshot(Request::builder().uri("/ok").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"fine");
+    }
+}
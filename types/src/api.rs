@@ -1,17 +1,25 @@
 // llm-benchmark-types/src/api.rs
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
-use crate::{ExperimentRun, ExperimentSummary};
-use crate::hardware::HardwareCategory;
+use crate::{ExperimentRun, ExperimentSummary, RunAnnotation, SampleStatistics};
+use crate::hardware::{HardwareCategory, HardwarePlaceholder};
 
 /// Request to upload a new experiment run
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadExperimentRequest {
     /// The experiment data to upload
     pub experiment_run: ExperimentRun,
+
+    /// Overwrite an existing identical run (same model, quantization,
+    /// backend, backend version, hardware and timestamp) instead of
+    /// rejecting the upload as a duplicate
+    #[serde(default)]
+    pub force: bool,
 }
 
 /// Response from experiment upload
@@ -20,7 +28,8 @@ pub struct UploadExperimentResponse {
     /// Whether the upload was successful
     pub success: bool,
 
-    /// ID of the created test run (if successful)
+    /// ID of the created test run (if successful), or of the existing run
+    /// a duplicate upload conflicts with
     pub test_run_id: Option<Uuid>,
 
     /// Error message (if failed)
@@ -30,6 +39,76 @@ pub struct UploadExperimentResponse {
     pub warnings: Vec<String>,
 }
 
+/// Request to upload several experiment runs at once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadExperimentsRequest {
+    /// The experiment runs to upload
+    pub experiment_runs: Vec<ExperimentRun>,
+
+    /// Store the runs that pass validation and write cleanly, each in its
+    /// own transaction, instead of rejecting the whole batch on the first
+    /// failure
+    #[serde(default)]
+    pub continue_on_error: bool,
+
+    /// Overwrite stored runs identical to ones in the batch instead of
+    /// reporting them as conflicts, as `UploadExperimentRequest::force`
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Response from a batch experiment upload
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadExperimentsResponse {
+    /// Outcome of each run, in request order
+    pub results: Vec<UploadExperimentResponse>,
+
+    /// Number of runs stored
+    pub uploaded: usize,
+
+    /// Number of runs rejected or that failed to store
+    pub failed: usize,
+}
+
+/// Response from a JSON Lines import
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportResponse {
+    /// Runs stored, including re-imports that replaced a run with the same ID
+    pub imported: usize,
+
+    /// Runs skipped because an identical run exists under another ID
+    pub duplicates: usize,
+
+    /// Lines that were rejected or failed to store
+    pub failed: usize,
+
+    /// Why each failed line failed, in line order
+    pub errors: Vec<ImportLineError>,
+}
+
+/// A line of a JSON Lines import that wasn't stored
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportLineError {
+    /// 1-based line number in the request body
+    pub line: usize,
+
+    /// What went wrong
+    pub error: String,
+}
+
+/// Response from validating an experiment without storing it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidateExperimentResponse {
+    /// Whether the experiment would be accepted for upload
+    pub valid: bool,
+
+    /// Validation error (if invalid)
+    pub error: Option<String>,
+
+    /// Validation warnings (non-fatal issues)
+    pub warnings: Vec<String>,
+}
+
 /// Request for performance grid data
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PerformanceGridRequest {
@@ -39,14 +118,155 @@ pub struct PerformanceGridRequest {
     /// Minimum speed filter (in tok/s)
     pub min_speed: Option<f64>,
 
-    /// Backend filter
+    /// Backend filter (comma-separated in a query string)
+    #[serde(default, deserialize_with = "comma_separated")]
     pub backends: Option<Vec<String>>,
 
-    /// Hardware type filter
+    /// Hardware type filter, "gpu" or "cpu_only" as in
+    /// `PerformanceGridRow::hardware_type` (comma-separated in a query string)
+    #[serde(default, deserialize_with = "comma_separated")]
     pub hardware_types: Option<Vec<String>>,
 
-    /// Model name filter
+    /// Model name filter (comma-separated in a query string)
+    #[serde(default, deserialize_with = "comma_separated")]
     pub models: Option<Vec<String>>,
+
+    /// Only include runs tagged with this campaign label
+    pub label: Option<String>,
+
+    /// Only include runs with this many concurrent requests (1 for
+    /// interactive use)
+    pub concurrent_requests: Option<i32>,
+
+    /// Only include runs annotated as canonical
+    pub canonical: Option<bool>,
+
+    /// Leave out runs annotated as deprecated
+    pub exclude_deprecated: Option<bool>,
+
+    /// Weighting profile used for `overall_score` (plain mean when unset)
+    pub profile: Option<String>,
+
+    /// Custom `benchmark:weight` pairs (comma-separated) overriding the
+    /// profile's weights
+    pub weights: Option<String>,
+}
+
+/// Deserialize a list given either as a sequence or, as query strings carry
+/// it, a comma-separated string
+fn comma_separated<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<String>>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum List {
+        Joined(String),
+        Items(Vec<String>),
+    }
+
+    Ok(Option::<List>::deserialize(deserializer)?.map(|list| match list {
+        List::Joined(joined) => joined
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect(),
+        List::Items(items) => items,
+    }))
+}
+
+/// Comparison applied by a [`MetricFilter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricFilterOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl MetricFilterOp {
+    /// SQL comparison operator
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            MetricFilterOp::Lt => "<",
+            MetricFilterOp::Le => "<=",
+            MetricFilterOp::Gt => ">",
+            MetricFilterOp::Ge => ">=",
+            MetricFilterOp::Eq => "=",
+        }
+    }
+}
+
+impl std::str::FromStr for MetricFilterOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lt" => Ok(MetricFilterOp::Lt),
+            "le" => Ok(MetricFilterOp::Le),
+            "gt" => Ok(MetricFilterOp::Gt),
+            "ge" => Ok(MetricFilterOp::Ge),
+            "eq" => Ok(MetricFilterOp::Eq),
+            _ => Err(format!("Invalid op '{}': expected one of lt, le, gt, ge, eq", s)),
+        }
+    }
+}
+
+/// Filter on an arbitrary performance metric, e.g. `metric=ttft_p95_ms&op=lt&value=200`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricFilter {
+    /// Metric name as stored in `performance_metrics`
+    pub metric: String,
+
+    /// Comparison against the metric's value
+    pub op: MetricFilterOp,
+
+    /// Value to compare against
+    pub value: f64,
+}
+
+impl MetricFilter {
+    /// Collect filters from repeated `metric`, `op` and `value` query
+    /// parameters, pairing them up by position
+    pub fn from_query_pairs(pairs: &[(String, String)]) -> Result<Vec<Self>, String> {
+        let values_of = |key: &str| -> Vec<&str> {
+            pairs.iter().filter(|(k, _)| k == key).map(|(_, v)| v.as_str()).collect()
+        };
+        let metrics = values_of("metric");
+        let ops = values_of("op");
+        let values = values_of("value");
+
+        if metrics.len() != ops.len() || metrics.len() != values.len() {
+            return Err(format!(
+                "Each metric filter needs a metric, op and value (got {} metric, {} op, {} value)",
+                metrics.len(),
+                ops.len(),
+                values.len()
+            ));
+        }
+
+        metrics
+            .into_iter()
+            .zip(ops)
+            .zip(values)
+            .map(|((metric, op), value)| {
+                if !crate::is_known_metric_name(metric) {
+                    return Err(format!("Unknown metric '{}'", metric));
+                }
+                let value: f64 = value
+                    .parse()
+                    .map_err(|_| format!("Invalid value '{}' for metric '{}'", value, metric))?;
+                if !value.is_finite() {
+                    return Err(format!("Invalid value '{}' for metric '{}'", value, metric));
+                }
+                Ok(MetricFilter {
+                    metric: metric.to_string(),
+                    op: op.parse()?,
+                    value,
+                })
+            })
+            .collect()
+    }
 }
 
 /// Request for grouped model performance data
@@ -75,12 +295,27 @@ pub struct GroupedPerformanceRequest {
 
     /// Optimization goal ("throughput", "latency", "efficiency")
     pub optimize_for: Option<String>,
+
+    /// Weighting profile ("coding", "reasoning", "chat", "general"); when
+    /// set, quality is a weighted composite instead of a single benchmark
+    pub profile: Option<String>,
+
+    /// Custom `benchmark:weight` pairs (comma-separated) overriding the
+    /// profile's weights
+    pub weights: Option<String>,
+
+    /// Maximum number of models to return (default: all)
+    pub limit: Option<usize>,
+
+    /// Only return models after this cursor (from `next_cursor`); it must
+    /// come from a request with the same `sort_by` and `sort_direction`
+    pub cursor: Option<String>,
 }
 
 /// Row in the performance grid
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PerformanceGridRow {
-    /// Test run ID
+    /// ID of the fastest test run for this configuration
     pub id: Uuid,
 
     /// Model name
@@ -89,6 +324,9 @@ pub struct PerformanceGridRow {
     /// Quantization scheme
     pub quantization: String,
 
+    /// LoRA adapter name (empty string means base model)
+    pub lora_adapter: String,
+
     /// Backend used
     pub backend: String,
 
@@ -98,6 +336,9 @@ pub struct PerformanceGridRow {
     /// Memory usage in GB
     pub memory_gb: f64,
 
+    /// Mean time to first token in ms (if measured)
+    pub first_token_latency_ms: Option<f64>,
+
     /// GPU model
     pub gpu_model: String,
 
@@ -107,8 +348,16 @@ pub struct PerformanceGridRow {
     /// Hardware type (gpu/cpu_only)
     pub hardware_type: String,
 
+    /// Concurrent requests the runs were made with (None if not recorded).
+    /// Runs at different concurrency get separate rows.
+    pub concurrent_requests: Option<i32>,
+
     /// Overall quality score (if available)
     pub overall_score: Option<f64>,
+
+    /// Number of test runs combined into this row; speed is the best and
+    /// memory the lowest seen across them
+    pub run_count: i64,
 }
 
 /// Response for grouped model performance
@@ -122,6 +371,10 @@ pub struct GroupedPerformanceResponse {
     
     /// Benchmark used for quality scoring
     pub benchmark_used: String,
+
+    /// Cursor for the next page (None when there are no more models)
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 /// A model with its best hardware configuration
@@ -222,6 +475,54 @@ pub struct ComparisonRequest {
     pub config_b: Uuid,
 }
 
+/// Request for comparison between two model variants, independent of test runs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VariantComparisonRequest {
+    /// Model name of the first variant
+    pub a_model: String,
+
+    /// Quantization of the first variant
+    pub a_quant: String,
+
+    /// LoRA adapter of the first variant (omit for base model)
+    pub a_lora: Option<String>,
+
+    /// Model name of the second variant
+    pub b_model: String,
+
+    /// Quantization of the second variant
+    pub b_quant: String,
+
+    /// LoRA adapter of the second variant (omit for base model)
+    pub b_lora: Option<String>,
+}
+
+/// Request for a shareable link to a comparison
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComparisonPermalinkRequest {
+    /// First configuration ID
+    pub config_a: Uuid,
+
+    /// Second configuration ID
+    pub config_b: Uuid,
+
+    /// Days until the link expires (default 90, at most 365)
+    pub expires_in_days: Option<i64>,
+}
+
+/// Signed link to a comparison
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComparisonPermalinkResponse {
+    /// Tamper-proof token encoding both configuration IDs and the expiry
+    pub token: String,
+
+    /// API path serving the comparison for this token
+    pub url: String,
+
+    /// When the token stops being accepted
+    pub expires_at: DateTime<Utc>,
+}
+
 /// Comparison data between two configurations
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ComparisonData {
@@ -247,6 +548,9 @@ pub struct ConfigSummary {
     /// Quantization scheme
     pub quantization: String,
 
+    /// LoRA adapter name (empty string means base model)
+    pub lora_adapter: String,
+
     /// Backend used
     pub backend: String,
 
@@ -274,6 +578,18 @@ pub struct PerformanceSummary {
 
     /// Prompt processing speed (tok/s)
     pub prompt_speed: f64,
+
+    /// Mean time to first token (ms), if the run measured it
+    #[serde(default)]
+    pub first_token_latency_ms: Option<f64>,
+
+    /// Mean GPU utilization (%), if the run recorded GPU stats
+    #[serde(default)]
+    pub gpu_utilization_percent: Option<f64>,
+
+    /// VRAM actually in use (GB), if the run recorded GPU stats
+    #[serde(default)]
+    pub gpu_vram_used_gb: Option<f64>,
 }
 
 /// Comparison between two configurations for a specific category
@@ -314,12 +630,21 @@ pub struct ConfigDetail {
     /// Quantization scheme
     pub quantization: String,
 
+    /// LoRA adapter name (empty string means base model)
+    pub lora_adapter: String,
+
     /// Backend used
     pub backend: String,
 
     /// Backend version
     pub backend_version: String,
 
+    /// Size of the model weights in bytes, if known
+    pub model_size_bytes: Option<i64>,
+
+    /// Number of model parameters, if known
+    pub model_n_params: Option<i64>,
+
     /// Overall score
     pub overall_score: f64,
 
@@ -352,9 +677,15 @@ pub struct SystemInfo {
     /// GPU model
     pub gpu_model: String,
 
-    /// GPU memory in GB
+    /// GPU memory in GB per GPU
     pub gpu_memory_gb: i32,
 
+    /// Number of GPUs
+    pub gpu_count: i32,
+
+    /// Combined memory of all GPUs in GB, if reported
+    pub total_gpu_memory_gb: Option<i32>,
+
     /// CPU model
     pub cpu_model: String,
 
@@ -367,6 +698,12 @@ pub struct SystemInfo {
     /// RAM type
     pub ram_type: String,
 
+    /// CPU threads used for inference
+    pub cpu_threads: Option<i32>,
+
+    /// Number of NUMA nodes
+    pub numa_nodes: Option<i32>,
+
     /// Virtualization type
     pub virtualization_type: Option<String>,
 
@@ -374,6 +711,28 @@ pub struct SystemInfo {
     pub optimizations: Vec<String>,
 }
 
+/// Query parameters for listing configurations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigurationListRequest {
+    /// Only return configurations after this cursor (from `next_cursor`)
+    pub after: Option<String>,
+
+    /// Maximum number of configurations to return (default: 50, at most 500)
+    pub limit: Option<i64>,
+
+    /// Number of configurations to skip (default: 0)
+    pub offset: Option<i64>,
+
+    /// Only return runs tagged with this campaign label
+    pub label: Option<String>,
+
+    /// Only return runs annotated as canonical
+    pub canonical: Option<bool>,
+
+    /// Leave out runs annotated as deprecated
+    pub exclude_deprecated: Option<bool>,
+}
+
 /// List of available configurations
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ConfigurationListResponse {
@@ -382,6 +741,633 @@ pub struct ConfigurationListResponse {
 
     /// Total count (for pagination)
     pub total_count: usize,
+
+    /// Cursor for the next page (None when there are no more configurations)
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Keyset pagination cursor over `(timestamp DESC NULLS LAST, id DESC)`,
+/// encoded as `<RFC 3339 timestamp>,<id>`, or `null,<id>` once the page
+/// reaches runs without a timestamp
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfigurationCursor {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub id: Uuid,
+}
+
+impl std::fmt::Display for ConfigurationCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.timestamp {
+            Some(timestamp) => write!(
+                f,
+                "{},{}",
+                timestamp.to_rfc3339_opts(chrono::SecondsFormat::Micros, true),
+                self.id
+            ),
+            None => write!(f, "null,{}", self.id),
+        }
+    }
+}
+
+impl std::str::FromStr for ConfigurationCursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (timestamp, id) = s
+            .split_once(',')
+            .ok_or_else(|| format!("Invalid cursor '{}': expected <timestamp>,<id>", s))?;
+        let timestamp = match timestamp {
+            "null" => None,
+            _ => Some(
+                DateTime::parse_from_rfc3339(timestamp)
+                    .map_err(|e| format!("Invalid cursor timestamp '{}': {}", timestamp, e))?
+                    .with_timezone(&Utc),
+            ),
+        };
+        let id = id
+            .parse()
+            .map_err(|e| format!("Invalid cursor id '{}': {}", id, e))?;
+        Ok(Self { timestamp, id })
+    }
+}
+
+/// Query parameters for the model card endpoint
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ModelCardRequest {
+    /// Number of test runs to skip (default 0)
+    pub offset: Option<i64>,
+
+    /// Maximum number of test runs to return (default 50, max 500)
+    pub limit: Option<i64>,
+}
+
+/// Everything known about a single model
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelCard {
+    /// Model name
+    pub model_name: String,
+
+    /// Model family inferred from the name (e.g., "llama", "qwen")
+    pub family: Option<String>,
+
+    /// Parameter count in billions inferred from the name
+    pub parameter_count_b: Option<f64>,
+
+    /// All quantizations with test runs or benchmark scores
+    pub quantizations: Vec<String>,
+
+    /// All hardware the model has been tested on
+    pub hardware: Vec<ModelCardHardware>,
+
+    /// Benchmark scores for every model variant
+    pub benchmark_scores: Vec<ModelCardBenchmark>,
+
+    /// Page of test runs, newest first
+    pub runs: Vec<ExperimentSummary>,
+
+    /// Total number of test runs (for pagination)
+    pub total_runs: usize,
+
+    /// Offset of the returned page
+    pub offset: i64,
+
+    /// Limit used for the returned page
+    pub limit: i64,
+}
+
+/// Hardware a model has been tested on
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelCardHardware {
+    /// GPU model
+    pub gpu_model: String,
+
+    /// GPU memory in GB
+    pub gpu_memory_gb: i32,
+
+    /// CPU model
+    pub cpu_model: String,
+
+    /// CPU architecture
+    pub cpu_arch: String,
+
+    /// Hardware category
+    pub hardware_category: HardwareCategory,
+
+    /// Number of test runs on this hardware
+    pub run_count: i64,
+}
+
+/// Headline score for one benchmark on one model variant
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelCardBenchmark {
+    /// Quantization scheme
+    pub quantization: String,
+
+    /// LoRA adapter name (empty string means base model)
+    pub lora_adapter: String,
+
+    /// Benchmark name (e.g., "mmlu", "gsm8k")
+    pub benchmark: String,
+
+    /// Score percentage (MMLU is averaged across categories)
+    pub score: f64,
+}
+
+/// Query parameters for the quality retention endpoint
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QualityRetentionRequest {
+    /// LoRA adapter name (omit for base model)
+    pub lora: Option<String>,
+}
+
+/// Benchmark quality of each quantization relative to a baseline variant
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QualityRetentionResponse {
+    /// Model name
+    pub model_name: String,
+
+    /// LoRA adapter name (empty string means base model)
+    pub lora_adapter: String,
+
+    /// Highest-precision quantization with benchmark scores
+    pub baseline_quantization: String,
+
+    /// Whether the baseline is 16-bit or wider; false means retention is
+    /// relative to the best available quantized variant
+    pub baseline_is_full_precision: bool,
+
+    /// Per-quantization, per-benchmark retention
+    pub retention: Vec<QuantizationRetention>,
+}
+
+/// One benchmark score compared against the baseline's score
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QuantizationRetention {
+    /// Quantization scheme
+    pub quantization: String,
+
+    /// Benchmark name (e.g., "mmlu", "gsm8k")
+    pub benchmark: String,
+
+    /// Score percentage
+    pub score: f64,
+
+    /// Baseline score on the same benchmark (None if the baseline lacks it)
+    pub baseline_score: Option<f64>,
+
+    /// Score as a percentage of the baseline score
+    pub retention_percent: Option<f64>,
+}
+
+/// Query parameters for the memory-fit check
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryFitRequest {
+    /// Model name
+    pub model: String,
+
+    /// Quantization scheme
+    pub quantization: String,
+
+    /// Available GPU memory in GB
+    pub gpu_memory_gb: i32,
+}
+
+/// Whether a model variant fits in a given amount of GPU memory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryFitResponse {
+    /// Model name
+    pub model_name: String,
+
+    /// Quantization scheme
+    pub quantization: String,
+
+    /// Available GPU memory in GB
+    pub gpu_memory_gb: i32,
+
+    /// Memory required by the model in GB
+    pub required_memory_gb: f64,
+
+    /// Where the requirement came from ("measured" or "estimated")
+    pub source: String,
+
+    /// Whether the model fits in the available memory
+    pub fits: bool,
+
+    /// Available minus required memory in GB (negative if it doesn't fit)
+    pub headroom_gb: f64,
+}
+
+/// Query parameters for `/api/leaderboard`
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct LeaderboardRequest {
+    /// Benchmark to rank by (default: mmlu)
+    pub benchmark: Option<String>,
+
+    /// Number of models to return (default: 20, at most 100)
+    pub limit: Option<i64>,
+}
+
+/// A model's best score on the leaderboard benchmark
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// Model name
+    pub model_name: String,
+
+    /// Quantization with the best score
+    pub best_quantization: String,
+
+    /// Score on the benchmark (0-100)
+    pub score: f64,
+
+    /// Backend of the most recent run of that quantization
+    pub backend: String,
+}
+
+/// Query parameters for `/api/pareto`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParetoRequest {
+    /// Model to compare configurations of
+    pub model: String,
+
+    /// Benchmark to use for quality scoring (default: mmlu)
+    pub benchmark: Option<String>,
+}
+
+/// Speed/quality Pareto frontier of a model's configurations
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ParetoResponse {
+    /// Model the configurations belong to
+    pub model_name: String,
+
+    /// Benchmark used for quality scoring
+    pub benchmark: String,
+
+    /// Configurations not beaten on both speed and quality by another,
+    /// slowest first
+    pub frontier: Vec<QuantizationPerformance>,
+
+    /// Configurations with both a speed and a quality score that were
+    /// considered
+    pub candidates: usize,
+}
+
+/// Query parameters for `/api/models/search`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelSearchRequest {
+    /// Text to look for anywhere in the model name (case-insensitive)
+    pub q: String,
+
+    /// Maximum number of names to return (default 10, at most 100)
+    pub limit: Option<i64>,
+}
+
+/// Model names matching a search, for autocomplete
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelSearchResponse {
+    /// Matching names, those starting with the query first, then alphabetical
+    pub models: Vec<String>,
+
+    /// Number of names matching before `limit` was applied
+    pub total_matched: i64,
+}
+
+/// A GPU model seen in hardware profiles, for filter dropdowns
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GpuModelCount {
+    /// GPU model as stored in hardware profiles
+    pub gpu_model: String,
+
+    /// Completed test runs on this GPU model
+    pub test_run_count: i64,
+
+    /// Set when the GPU model is a placeholder ("CPU Only" or the
+    /// benchmark-only profile) rather than a real GPU
+    pub placeholder: Option<HardwarePlaceholder>,
+}
+
+/// A CPU model seen in hardware profiles, for filter dropdowns
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CpuModelCount {
+    /// CPU model as stored in hardware profiles
+    pub cpu_model: String,
+
+    /// CPU architecture (e.g., "x86_64", "Zen2")
+    pub cpu_arch: String,
+
+    /// Completed test runs on this CPU model and architecture
+    pub test_run_count: i64,
+
+    /// Set when the CPU model is the benchmark-only placeholder rather than
+    /// a real CPU
+    pub placeholder: Option<HardwarePlaceholder>,
+}
+
+/// Headline numbers for the dashboard landing page
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlatformStats {
+    /// Completed test runs
+    pub total_test_runs: i64,
+
+    /// Distinct model/quantization/LoRA combinations with a completed test
+    /// run or benchmark scores
+    pub total_model_variants: i64,
+
+    /// Distinct real GPU models with a completed test run, placeholders
+    /// excluded
+    pub total_gpus: usize,
+
+    /// Model variants with a score, keyed by benchmark name
+    pub benchmarks_by_type: HashMap<String, usize>,
+
+    /// Timestamp of the most recent completed test run, None if there are
+    /// none
+    pub newest_run_timestamp: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for `/api/model-variant/benchmarks`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelVariantBenchmarksRequest {
+    /// Model name
+    pub model: String,
+
+    /// Quantization of the model variant
+    pub quantization: String,
+
+    /// LoRA adapter name (omit for base model)
+    pub lora: Option<String>,
+}
+
+/// Query parameters for `/api/export`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportRequest {
+    /// Only export runs timestamped after this (RFC 3339), for incremental
+    /// backups
+    pub since: Option<DateTime<Utc>>,
+}
+
+/// Query parameters for `/api/history`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreHistoryRequest {
+    /// Model name
+    pub model: String,
+
+    /// Quantization of the model variant
+    pub quantization: String,
+
+    /// Benchmark (mmlu, gsm8k, humaneval, hellaswag, truthfulqa, or a
+    /// generic benchmark name)
+    pub benchmark: String,
+
+    /// LoRA adapter of the variant (default: base model)
+    pub lora_adapter: Option<String>,
+}
+
+/// One uploaded score of a model variant on a benchmark
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreHistoryEntry {
+    /// When the score was measured
+    pub timestamp: DateTime<Utc>,
+
+    /// MMLU category or GPQA subset; None for other benchmarks
+    pub category: Option<String>,
+
+    /// Score (0-100)
+    pub score: f64,
+
+    /// Whether this is the set read endpoints currently use (the latest
+    /// timestamp wins)
+    pub current: bool,
+}
+
+/// Every score kept for a model variant on one benchmark
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoreHistoryResponse {
+    pub model_name: String,
+    pub quantization: String,
+    pub benchmark: String,
+
+    /// Scores ordered by timestamp, oldest first
+    pub entries: Vec<ScoreHistoryEntry>,
+}
+
+/// SLO constraints for `/api/find`
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct FindConfigRequest {
+    /// Minimum quality score on `benchmark` (0-100)
+    pub min_quality: Option<f64>,
+
+    /// Maximum time to first token in milliseconds
+    pub max_ttft_ms: Option<f64>,
+
+    /// Benchmark the quality SLO applies to (default: mmlu)
+    pub benchmark: Option<String>,
+
+    /// Number of runners-up to return (default: 5)
+    pub limit: Option<i64>,
+}
+
+/// A test run that meets the requested SLOs
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigCandidate {
+    /// Test run ID
+    pub test_run_id: Uuid,
+
+    /// Model name
+    pub model_name: String,
+
+    /// Quantization scheme
+    pub quantization: String,
+
+    /// LoRA adapter name (empty for the base model)
+    pub lora_adapter: String,
+
+    /// Backend used
+    pub backend: String,
+
+    /// Hardware summary ("GPU / CPU")
+    pub hardware: String,
+
+    /// Quality score on the requested benchmark
+    pub quality_score: Option<f64>,
+
+    /// Time to first token in milliseconds
+    pub first_token_latency_ms: Option<f64>,
+
+    /// Generation speed in tok/s
+    pub tokens_per_second: f64,
+
+    /// Average GPU power draw in watts
+    pub gpu_power_watts: Option<f64>,
+
+    /// Energy efficiency in tokens per kWh (None without power data)
+    pub tokens_per_kwh: Option<f64>,
+}
+
+/// Most efficient configuration meeting the SLOs, plus runners-up
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FindConfigResponse {
+    /// Benchmark the quality SLO was checked against
+    pub benchmark: String,
+
+    /// Number of runs meeting all SLOs
+    pub qualifying_count: i64,
+
+    /// Best qualifying run, or None if no run meets the SLOs
+    pub best: Option<ConfigCandidate>,
+
+    /// Next-best qualifying runs, best first
+    pub runners_up: Vec<ConfigCandidate>,
+}
+
+/// Run-to-run variance of a test run's speed measurement
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetricVarianceResponse {
+    /// Test run ID
+    pub test_run_id: Uuid,
+
+    /// Metric the samples belong to
+    pub metric_name: String,
+
+    /// Unit of the samples
+    pub unit: String,
+
+    /// Value reported for the metric
+    pub reported_value: f64,
+
+    /// Statistics over the raw samples
+    #[serde(flatten)]
+    pub statistics: SampleStatistics,
+}
+
+/// Request to fold one model variant's benchmark scores into another
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeVariantsRequest {
+    /// Variant to merge away (deleted afterwards)
+    pub from_id: Uuid,
+
+    /// Variant that receives the scores
+    pub into_id: Uuid,
+}
+
+/// Outcome of a model variant merge
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergeVariantsResponse {
+    /// Variant that received the scores
+    pub into_id: Uuid,
+
+    /// Per-benchmark counts of score rows moved or dropped
+    pub benchmarks: Vec<MergedBenchmarkRows>,
+}
+
+/// Score rows affected by a merge for one benchmark table
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MergedBenchmarkRows {
+    /// Benchmark name
+    pub benchmark: String,
+
+    /// Rows repointed to the surviving variant
+    pub moved: u64,
+
+    /// Rows dropped because the other variant had newer scores
+    pub discarded: u64,
+}
+
+/// Request to rename a model in every test run and model variant, merging
+/// it into another model of that name if there is one
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameModelRequest {
+    /// Current model name
+    pub from_model_name: String,
+
+    /// New model name, which may already exist
+    pub to_model_name: String,
+}
+
+/// Rows changed by a model rename
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameModelResponse {
+    /// Test runs whose model name changed
+    pub test_runs_updated: u64,
+
+    /// Model variants renamed in place
+    pub variants_renamed: u64,
+
+    /// Model variants merged into an existing variant of the new name (same
+    /// quantization and LoRA adapter) and deleted
+    pub variants_merged: u64,
+
+    /// Per-benchmark score rows moved or dropped by the merges
+    pub benchmarks: Vec<MergedBenchmarkRows>,
+}
+
+/// Result of attaching benchmark scores to an existing test run
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachBenchmarksResponse {
+    /// Test run the scores were attached to
+    pub test_run_id: Uuid,
+
+    /// Number of benchmark scores stored
+    pub scores_uploaded: usize,
+}
+
+/// Request to attach a console log to an existing test run
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachLogRequest {
+    /// Raw backend console output (stdout/stderr)
+    pub log: String,
+}
+
+/// Result of attaching a console log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachLogResponse {
+    /// Test run the log belongs to
+    pub test_run_id: Uuid,
+
+    /// Size of the log as uploaded, in bytes
+    pub original_bytes: i64,
+
+    /// Whether the start of the log was dropped to fit the size limit
+    pub truncated: bool,
+}
+
+/// A test run's stored console log
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestRunLog {
+    /// Test run the log belongs to
+    pub test_run_id: Uuid,
+
+    /// Log text; if truncated, the most recent output prefixed with a note
+    pub log: String,
+
+    /// Size of the log as uploaded, in bytes
+    pub original_bytes: i64,
+
+    /// Whether the start of the log was dropped to fit the size limit
+    pub truncated: bool,
+}
+
+/// Result of annotating a test run
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AnnotateRunResponse {
+    /// Annotated test run
+    pub test_run_id: Uuid,
+
+    /// Annotation now stored (None if the request cleared it)
+    pub annotation: Option<RunAnnotation>,
+}
+
+/// Event pushed to `/api/events` subscribers when an upload commits a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunUploadedEvent {
+    /// ID of the new (or re-uploaded) test run
+    pub test_run_id: Uuid,
+
+    /// Model name of the run
+    pub model_name: String,
+
+    /// When the upload committed
+    pub timestamp: DateTime<Utc>,
 }
 
 /// Health check response
@@ -400,13 +1386,68 @@ pub struct HealthResponse {
     pub version: Option<String>,
 }
 
+/// Readiness check response
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// Whether the service can handle requests
+    pub ready: bool,
+
+    /// Current timestamp
+    pub timestamp: DateTime<Utc>,
+
+    /// Database connectivity
+    pub database: bool,
+
+    /// Required tables that don't exist yet (migrations not applied)
+    pub missing_tables: Vec<String>,
+}
+
+/// Machine-readable cause of an API failure, sent as `ErrorResponse.code`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The request is malformed
+    BadRequest,
+    /// The request is well-formed but its contents fail validation
+    Validation,
+    /// The request lacks a valid API key
+    Unauthorized,
+    /// The requested resource doesn't exist
+    NotFound,
+    /// The write conflicts with existing data
+    Conflict,
+    /// The resource existed but has expired
+    Gone,
+    /// A dependency isn't available yet
+    Unavailable,
+    /// The database failed
+    #[serde(rename = "database_error")]
+    Database,
+}
+
+impl ErrorCode {
+    /// The code as it appears in responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "bad_request",
+            ErrorCode::Validation => "validation",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::Gone => "gone",
+            ErrorCode::Unavailable => "unavailable",
+            ErrorCode::Database => "database_error",
+        }
+    }
+}
+
 /// Error response for API failures
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ErrorResponse {
     /// Error message
     pub error: String,
 
-    /// Error code
+    /// Error code, one of the [`ErrorCode`] strings
     pub code: Option<String>,
 
     /// Additional details
@@ -446,6 +1487,18 @@ impl UploadExperimentResponse {
             warnings: Vec::new(),
         }
     }
+
+    /// Create a failure response for an upload that duplicates an existing
+    /// run, carrying that run's ID
+    pub fn conflict(existing_test_run_id: Uuid) -> Self {
+        Self {
+            test_run_id: Some(existing_test_run_id),
+            ..Self::failure(format!(
+                "An identical run already exists ({}); upload with force to overwrite it",
+                existing_test_run_id
+            ))
+        }
+    }
 }
 
 impl ErrorResponse {
@@ -468,6 +1521,11 @@ impl ErrorResponse {
             timestamp: Utc::now(),
         }
     }
+
+    /// Create an error response with one of the standard codes
+    pub fn with_error_code(error: String, code: ErrorCode) -> Self {
+        Self::with_code(error, code.as_str().to_string())
+    }
 }
 
 impl HealthResponse {
@@ -490,4 +1548,87 @@ impl HealthResponse {
             version: None,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configuration_cursor_round_trip() {
+        let cursor = ConfigurationCursor {
+            timestamp: Some(
+                DateTime::parse_from_rfc3339("2026-10-18T12:34:56.789012Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+            ),
+            id: Uuid::nil(),
+        };
+        let encoded = cursor.to_string();
+        assert_eq!(encoded, "2026-10-18T12:34:56.789012Z,00000000-0000-0000-0000-000000000000");
+        assert_eq!(encoded.parse::<ConfigurationCursor>().unwrap(), cursor);
+
+        let untimed = ConfigurationCursor { timestamp: None, id: Uuid::nil() };
+        assert_eq!(untimed.to_string(), "null,00000000-0000-0000-0000-000000000000");
+        assert_eq!(untimed.to_string().parse::<ConfigurationCursor>().unwrap(), untimed);
+
+        assert!("2026-10-18T12:34:56Z".parse::<ConfigurationCursor>().is_err());
+        assert!("yesterday,00000000-0000-0000-0000-000000000000".parse::<ConfigurationCursor>().is_err());
+    }
+
+    #[test]
+    fn test_grid_request_lists() {
+        let request: PerformanceGridRequest =
+            serde_json::from_value(serde_json::json!({ "backends": "llama.cpp, vllm", "models": ["a,b"] })).unwrap();
+        assert_eq!(request.backends, Some(vec!["llama.cpp".to_string(), "vllm".to_string()]));
+        assert_eq!(request.models, Some(vec!["a,b".to_string()]));
+        assert_eq!(request.hardware_types, None);
+    }
+
+    #[test]
+    fn test_metric_filters_from_query_pairs() {
+        let pairs = |q: &[(&str, &str)]| -> Vec<(String, String)> {
+            q.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+        };
+
+        let filters = MetricFilter::from_query_pairs(&pairs(&[
+            ("metric", "ttft_p95_ms"),
+            ("op", "lt"),
+            ("value", "200"),
+            ("metric", "tokens_per_second"),
+            ("op", "ge"),
+            ("value", "50.5"),
+            ("min_speed", "10"),
+        ]))
+        .unwrap();
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].metric, "ttft_p95_ms");
+        assert_eq!(filters[0].op, MetricFilterOp::Lt);
+        assert_eq!(filters[1].op.as_sql(), ">=");
+        assert_eq!(filters[1].value, 50.5);
+
+        assert!(MetricFilter::from_query_pairs(&pairs(&[])).unwrap().is_empty());
+        assert!(MetricFilter::from_query_pairs(&pairs(&[("metric", "ttft_p95_ms"), ("op", "lt")])).is_err());
+        assert!(MetricFilter::from_query_pairs(&pairs(&[("metric", "ttft_p95_ms"), ("op", "ne"), ("value", "1")])).is_err());
+        assert!(MetricFilter::from_query_pairs(&pairs(&[("metric", "bogus"), ("op", "lt"), ("value", "1")])).is_err());
+        assert!(MetricFilter::from_query_pairs(&pairs(&[("metric", "ttft_p95_ms"), ("op", "lt"), ("value", "NaN")])).is_err());
+    }
+
+    #[test]
+    fn test_error_code_strings() {
+        for code in [
+            ErrorCode::BadRequest,
+            ErrorCode::Validation,
+            ErrorCode::Unauthorized,
+            ErrorCode::NotFound,
+            ErrorCode::Conflict,
+            ErrorCode::Gone,
+            ErrorCode::Unavailable,
+            ErrorCode::Database,
+        ] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+
+        let response = ErrorResponse::with_error_code("Test run not found".to_string(), ErrorCode::NotFound);
+        assert_eq!(response.code.as_deref(), Some("not_found"));
+    }
+}
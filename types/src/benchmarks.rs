@@ -1,19 +1,88 @@
 // llm-benchmark-types/src/benchmarks.rs
 // Benchmark-specific score types
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
-use crate::{ValidationError, ValidationResult};
+use crate::{ValidationError, ValidationResult, EARLIEST_SCORE_YEAR, FUTURE_TIMESTAMP_LIMIT_DAYS};
 
 /// Base trait for all benchmark scores
+///
+/// Percentage fields (`score`, `accuracy`, `pass_at_*`, ...) and
+/// `overall_score` are on a 0–100 scale, never a 0–1 fraction.
 pub trait BenchmarkScore {
     fn benchmark_name(&self) -> &str;
     fn overall_score(&self) -> f64;
     fn timestamp(&self) -> DateTime<Utc>;
     fn validate(&self) -> ValidationResult<()>;
+
+    /// Non-fatal issues with otherwise valid scores
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Convert a 0–1 fraction to the 0–100 scale used by benchmark scores.
+/// Values above 1.0 are assumed to already be percentages.
+pub fn normalize_fraction_to_percent(value: f64) -> f64 {
+    if (0.0..=1.0).contains(&value) {
+        value * 100.0
+    } else {
+        value
+    }
 }
 
-/// MMLU-Pro benchmark with detailed subcategories
+/// Whether a 0–100 `value` looks like it was reported as a 0–1 fraction.
+///
+/// With known counts the value is compared against the count-derived
+/// percentage. Without them any non-zero value up to 1.0 is taken as a
+/// fraction: a real score of 1% or less is far rarer than a 0–1 upload.
+fn looks_like_fraction(value: f64, total: i32, correct: Option<i32>) -> bool {
+    if value <= 0.0 || value > 1.0 || total <= 0 {
+        return false;
+    }
+
+    match correct {
+        Some(correct) => {
+            let expected = correct as f64 / total as f64 * 100.0;
+            (value * 100.0 - expected).abs() < (value - expected).abs()
+        }
+        None => true,
+    }
+}
+
+/// Reject a score timestamp from before `EARLIEST_SCORE_YEAR` or more than
+/// `FUTURE_TIMESTAMP_LIMIT_DAYS` ahead; either means the machine's clock
+/// was wrong
+fn validate_timestamp(timestamp: DateTime<Utc>) -> ValidationResult<()> {
+    let earliest = Utc.with_ymd_and_hms(EARLIEST_SCORE_YEAR, 1, 1, 0, 0, 0).unwrap();
+    let latest = Utc::now() + Duration::days(FUTURE_TIMESTAMP_LIMIT_DAYS);
+    if timestamp < earliest || timestamp > latest {
+        return Err(ValidationError::OutOfRange {
+            field: "timestamp".to_string(),
+            value: timestamp.to_rfc3339(),
+            range: format!(
+                "{} to {} days in the future",
+                earliest.date_naive(),
+                FUTURE_TIMESTAMP_LIMIT_DAYS
+            ),
+        });
+    }
+    Ok(())
+}
+
+fn fraction_warning(field: &str, value: f64) -> String {
+    format!(
+        "{} is {} but scores are on a 0-100 scale - was a 0-1 fraction uploaded? ({}%)",
+        field,
+        value,
+        normalize_fraction_to_percent(value)
+    )
+}
+
+/// MMLU-Pro benchmark with detailed subcategories and known question counts.
+///
+/// Kept so existing `"type": "MMLU"` payloads still deserialize; new uploads
+/// should use `MMLUProScore`, which doesn't require counts.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MMLUScore {
     pub categories: Vec<MMLUCategoryScore>,
@@ -29,6 +98,28 @@ pub struct MMLUCategoryScore {
     pub correct_answers: i32,
 }
 
+/// MMLU-Pro benchmark: up to 14 categories of 10-option questions.
+///
+/// Stored and reported as the `mmlu` benchmark, which has always held
+/// MMLU-Pro results. Question counts are optional because common reports
+/// (e.g. `report.txt`) only give per-category accuracies.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MMLUProScore {
+    pub categories: Vec<MMLUProCategoryScore>,
+    /// Questions answered across all categories, if known
+    pub num_questions_total: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub context: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MMLUProCategoryScore {
+    pub category: String,
+    pub score: f64,
+    pub total_questions: Option<i32>,
+    pub correct_answers: Option<i32>,
+}
+
 /// GSM8K mathematical reasoning benchmark
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GSM8KScore {
@@ -69,13 +160,31 @@ pub struct TruthfulQAScore {
     pub context: Option<serde_json::Value>,
 }
 
+/// GPQA graduate-level science question benchmark
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GpqaScore {
+    pub accuracy: f64,
+    pub total_questions: i32,
+    pub correct_answers: i32,
+    /// Question set: "main", "diamond" or "extended"
+    pub subset: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub context: Option<serde_json::Value>,
+}
+
 /// Generic benchmark score for unknown or simple benchmarks
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GenericBenchmarkScore {
+    #[serde(alias = "benchmark")]
     pub benchmark_name: String,
+    /// Overall score; stored as `overall_score` in the v2 tables
+    #[serde(alias = "overall_score")]
     pub score: f64,
     pub total_questions: Option<i32>,
     pub correct_answers: Option<i32>,
+    /// Per-subtask results as the benchmark reports them
+    #[serde(default)]
+    pub sub_scores: Option<serde_json::Value>,
     pub timestamp: DateTime<Utc>,
     pub context: Option<serde_json::Value>,
 }
@@ -85,13 +194,98 @@ pub struct GenericBenchmarkScore {
 #[serde(tag = "type", content = "data")]
 pub enum BenchmarkScoreType {
     MMLU(MMLUScore),
+    MMLUPro(MMLUProScore),
     GSM8K(GSM8KScore),
     HumanEval(HumanEvalScore),
     HellaSwag(HellaSwagScore),
     TruthfulQA(TruthfulQAScore),
+    #[serde(alias = "GPQA")]
+    Gpqa(GpqaScore),
     Generic(GenericBenchmarkScore),
 }
 
+/// Benchmark score read from a file written by another tool.
+///
+/// Accepts the tagged `{"type": "GSM8K", "data": {...}}` form as well as
+/// flat objects holding a score's fields directly. A flat object with a
+/// top-level `type` (matched case-insensitively) is read as that type;
+/// without one it is recognised by its required fields:
+///
+/// | shape       | required fields                                    |
+/// |-------------|----------------------------------------------------|
+/// | MMLU        | `categories` with counts, `timestamp`              |
+/// | MMLU-Pro    | `categories`, `timestamp`                          |
+/// | GSM8K       | `problems_solved`, `total_problems`, `timestamp`   |
+/// | HumanEval   | `pass_at_1`, `total_problems`, `timestamp`         |
+/// | HellaSwag   | `accuracy`, `total_questions`, `correct_answers`, `timestamp` |
+/// | TruthfulQA  | `truthful_score`, `total_questions`, `timestamp`   |
+/// | Generic     | `benchmark_name` (or `benchmark`), `score`, `timestamp` |
+///
+/// GPQA's fields are the same as HellaSwag's, so a flat GPQA score needs
+/// `"type": "GPQA"`; without it the object is read as HellaSwag.
+///
+/// Convert with `BenchmarkScoreType::from`.
+#[derive(Debug)]
+pub struct LenientBenchmarkScore(BenchmarkScoreType);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FlatBenchmarkScore {
+    Tagged(BenchmarkScoreType),
+    Mmlu(MMLUScore),
+    MmluPro(MMLUProScore),
+    Gsm8k(GSM8KScore),
+    HumanEval(HumanEvalScore),
+    HellaSwag(HellaSwagScore),
+    TruthfulQa(TruthfulQAScore),
+    Generic(GenericBenchmarkScore),
+}
+
+/// Tags accepted in a flat object's `type`, matched case-insensitively
+const BENCHMARK_SCORE_TAGS: [&str; 9] = [
+    "MMLU", "MMLUPro", "GSM8K", "HumanEval", "HellaSwag", "TruthfulQA", "Gpqa", "GPQA", "Generic",
+];
+
+impl<'de> Deserialize<'de> for LenientBenchmarkScore {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+
+        // Honour an explicit type rather than guessing from the fields
+        if let Some(object) = value.as_object_mut().filter(|o| !o.contains_key("data")) {
+            if let Some(kind) = object.remove("type") {
+                let tag = kind
+                    .as_str()
+                    .and_then(|kind| BENCHMARK_SCORE_TAGS.iter().find(|t| t.eq_ignore_ascii_case(kind)))
+                    .ok_or_else(|| D::Error::custom(format!("unknown benchmark type {}", kind)))?;
+                let data = serde_json::Value::Object(std::mem::take(object));
+                return BenchmarkScoreType::deserialize(serde_json::json!({ "type": tag, "data": data }))
+                    .map(LenientBenchmarkScore)
+                    .map_err(D::Error::custom);
+            }
+        }
+
+        let score = FlatBenchmarkScore::deserialize(value).map_err(D::Error::custom)?;
+        Ok(LenientBenchmarkScore(match score {
+            FlatBenchmarkScore::Tagged(score) => score,
+            FlatBenchmarkScore::Mmlu(score) => BenchmarkScoreType::MMLU(score),
+            FlatBenchmarkScore::MmluPro(score) => BenchmarkScoreType::MMLUPro(score),
+            FlatBenchmarkScore::Gsm8k(score) => BenchmarkScoreType::GSM8K(score),
+            FlatBenchmarkScore::HumanEval(score) => BenchmarkScoreType::HumanEval(score),
+            FlatBenchmarkScore::HellaSwag(score) => BenchmarkScoreType::HellaSwag(score),
+            FlatBenchmarkScore::TruthfulQa(score) => BenchmarkScoreType::TruthfulQA(score),
+            FlatBenchmarkScore::Generic(score) => BenchmarkScoreType::Generic(score),
+        }))
+    }
+}
+
+impl From<LenientBenchmarkScore> for BenchmarkScoreType {
+    fn from(score: LenientBenchmarkScore) -> Self {
+        score.0
+    }
+}
+
 // Implement BenchmarkScore trait for all types
 
 impl BenchmarkScore for MMLUScore {
@@ -112,6 +306,8 @@ impl BenchmarkScore for MMLUScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if self.categories.is_empty() {
             return Err(ValidationError::InvalidField {
                 field: "categories".to_string(),
@@ -155,6 +351,87 @@ impl BenchmarkScore for MMLUScore {
     }
 }
 
+impl BenchmarkScore for MMLUProScore {
+    fn benchmark_name(&self) -> &str {
+        "mmlu"
+    }
+
+    fn overall_score(&self) -> f64 {
+        if self.categories.is_empty() {
+            0.0
+        } else {
+            self.categories.iter().map(|c| c.score).sum::<f64>() / self.categories.len() as f64
+        }
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
+        if self.categories.is_empty() {
+            return Err(ValidationError::InvalidField {
+                field: "categories".to_string(),
+                message: "MMLU-Pro must have at least one category".to_string(),
+            });
+        }
+
+        if let Some(total) = self.num_questions_total {
+            if total <= 0 {
+                return Err(ValidationError::OutOfRange {
+                    field: "num_questions_total".to_string(),
+                    value: total.to_string(),
+                    range: "> 0".to_string(),
+                });
+            }
+        }
+
+        for (i, category) in self.categories.iter().enumerate() {
+            if category.category.trim().is_empty() {
+                return Err(ValidationError::MissingField {
+                    field: format!("categories[{}].category", i),
+                });
+            }
+
+            if !(0.0..=100.0).contains(&category.score) {
+                return Err(ValidationError::OutOfRange {
+                    field: format!("categories[{}].score", i),
+                    value: category.score.to_string(),
+                    range: "0-100".to_string(),
+                });
+            }
+
+            if let Some(total) = category.total_questions {
+                if total <= 0 {
+                    return Err(ValidationError::OutOfRange {
+                        field: format!("categories[{}].total_questions", i),
+                        value: total.to_string(),
+                        range: "> 0".to_string(),
+                    });
+                }
+            }
+
+            if let Some(correct) = category.correct_answers {
+                let max = category.total_questions.unwrap_or(i32::MAX);
+                if correct < 0 || correct > max {
+                    return Err(ValidationError::OutOfRange {
+                        field: format!("categories[{}].correct_answers", i),
+                        value: correct.to_string(),
+                        range: match category.total_questions {
+                            Some(total) => format!("0-{}", total),
+                            None => ">= 0".to_string(),
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl BenchmarkScore for GSM8KScore {
     fn benchmark_name(&self) -> &str {
         "gsm8k"
@@ -173,6 +450,8 @@ impl BenchmarkScore for GSM8KScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if self.total_problems <= 0 {
             return Err(ValidationError::OutOfRange {
                 field: "total_problems".to_string(),
@@ -207,6 +486,8 @@ impl BenchmarkScore for HumanEvalScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if !(0.0..=100.0).contains(&self.pass_at_1) {
             return Err(ValidationError::OutOfRange {
                 field: "pass_at_1".to_string(),
@@ -245,6 +526,13 @@ impl BenchmarkScore for HumanEvalScore {
 
         Ok(())
     }
+
+    fn warnings(&self) -> Vec<String> {
+        self.fraction_fields()
+            .into_iter()
+            .map(|(field, value)| fraction_warning(field, value))
+            .collect()
+    }
 }
 
 impl BenchmarkScore for HellaSwagScore {
@@ -261,6 +549,8 @@ impl BenchmarkScore for HellaSwagScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if !(0.0..=100.0).contains(&self.accuracy) {
             return Err(ValidationError::OutOfRange {
                 field: "accuracy".to_string(),
@@ -287,6 +577,16 @@ impl BenchmarkScore for HellaSwagScore {
 
         Ok(())
     }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if looks_like_fraction(self.accuracy, self.total_questions, Some(self.correct_answers)) {
+            warnings.push(fraction_warning("accuracy", self.accuracy));
+        }
+
+        warnings
+    }
 }
 
 impl BenchmarkScore for TruthfulQAScore {
@@ -303,6 +603,8 @@ impl BenchmarkScore for TruthfulQAScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if !(0.0..=100.0).contains(&self.truthful_score) {
             return Err(ValidationError::OutOfRange {
                 field: "truthful_score".to_string(),
@@ -333,6 +635,73 @@ impl BenchmarkScore for TruthfulQAScore {
     }
 }
 
+impl BenchmarkScore for GpqaScore {
+    fn benchmark_name(&self) -> &str {
+        "gpqa"
+    }
+
+    fn overall_score(&self) -> f64 {
+        self.accuracy
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
+        if !(0.0..=100.0).contains(&self.accuracy) {
+            return Err(ValidationError::OutOfRange {
+                field: "accuracy".to_string(),
+                value: self.accuracy.to_string(),
+                range: "0-100".to_string(),
+            });
+        }
+
+        if self.total_questions <= 0 {
+            return Err(ValidationError::OutOfRange {
+                field: "total_questions".to_string(),
+                value: self.total_questions.to_string(),
+                range: "> 0".to_string(),
+            });
+        }
+
+        if self.correct_answers < 0 || self.correct_answers > self.total_questions {
+            return Err(ValidationError::OutOfRange {
+                field: "correct_answers".to_string(),
+                value: self.correct_answers.to_string(),
+                range: format!("0-{}", self.total_questions),
+            });
+        }
+
+        if let Some(subset) = &self.subset {
+            if !GpqaScore::SUBSETS.contains(&subset.as_str()) {
+                return Err(ValidationError::InvalidField {
+                    field: "subset".to_string(),
+                    message: format!(
+                        "Unknown GPQA subset '{}' (expected one of {})",
+                        subset,
+                        GpqaScore::SUBSETS.join(", ")
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if looks_like_fraction(self.accuracy, self.total_questions, Some(self.correct_answers)) {
+            warnings.push(fraction_warning("accuracy", self.accuracy));
+        }
+
+        warnings
+    }
+}
+
 impl BenchmarkScore for GenericBenchmarkScore {
     fn benchmark_name(&self) -> &str {
         &self.benchmark_name
@@ -347,6 +716,8 @@ impl BenchmarkScore for GenericBenchmarkScore {
     }
 
     fn validate(&self) -> ValidationResult<()> {
+        validate_timestamp(self.timestamp)?;
+
         if self.benchmark_name.trim().is_empty() {
             return Err(ValidationError::MissingField {
                 field: "benchmark_name".to_string(),
@@ -399,10 +770,12 @@ impl BenchmarkScore for BenchmarkScoreType {
     fn benchmark_name(&self) -> &str {
         match self {
             BenchmarkScoreType::MMLU(score) => score.benchmark_name(),
+            BenchmarkScoreType::MMLUPro(score) => score.benchmark_name(),
             BenchmarkScoreType::GSM8K(score) => score.benchmark_name(),
             BenchmarkScoreType::HumanEval(score) => score.benchmark_name(),
             BenchmarkScoreType::HellaSwag(score) => score.benchmark_name(),
             BenchmarkScoreType::TruthfulQA(score) => score.benchmark_name(),
+            BenchmarkScoreType::Gpqa(score) => score.benchmark_name(),
             BenchmarkScoreType::Generic(score) => score.benchmark_name(),
         }
     }
@@ -410,10 +783,12 @@ impl BenchmarkScore for BenchmarkScoreType {
     fn overall_score(&self) -> f64 {
         match self {
             BenchmarkScoreType::MMLU(score) => score.overall_score(),
+            BenchmarkScoreType::MMLUPro(score) => score.overall_score(),
             BenchmarkScoreType::GSM8K(score) => score.overall_score(),
             BenchmarkScoreType::HumanEval(score) => score.overall_score(),
             BenchmarkScoreType::HellaSwag(score) => score.overall_score(),
             BenchmarkScoreType::TruthfulQA(score) => score.overall_score(),
+            BenchmarkScoreType::Gpqa(score) => score.overall_score(),
             BenchmarkScoreType::Generic(score) => score.overall_score(),
         }
     }
@@ -421,10 +796,12 @@ impl BenchmarkScore for BenchmarkScoreType {
     fn timestamp(&self) -> DateTime<Utc> {
         match self {
             BenchmarkScoreType::MMLU(score) => score.timestamp(),
+            BenchmarkScoreType::MMLUPro(score) => score.timestamp(),
             BenchmarkScoreType::GSM8K(score) => score.timestamp(),
             BenchmarkScoreType::HumanEval(score) => score.timestamp(),
             BenchmarkScoreType::HellaSwag(score) => score.timestamp(),
             BenchmarkScoreType::TruthfulQA(score) => score.timestamp(),
+            BenchmarkScoreType::Gpqa(score) => score.timestamp(),
             BenchmarkScoreType::Generic(score) => score.timestamp(),
         }
     }
@@ -432,13 +809,65 @@ impl BenchmarkScore for BenchmarkScoreType {
     fn validate(&self) -> ValidationResult<()> {
         match self {
             BenchmarkScoreType::MMLU(score) => score.validate(),
+            BenchmarkScoreType::MMLUPro(score) => score.validate(),
             BenchmarkScoreType::GSM8K(score) => score.validate(),
             BenchmarkScoreType::HumanEval(score) => score.validate(),
             BenchmarkScoreType::HellaSwag(score) => score.validate(),
             BenchmarkScoreType::TruthfulQA(score) => score.validate(),
+            BenchmarkScoreType::Gpqa(score) => score.validate(),
             BenchmarkScoreType::Generic(score) => score.validate(),
         }
     }
+
+    fn warnings(&self) -> Vec<String> {
+        match self {
+            BenchmarkScoreType::MMLU(score) => score.warnings(),
+            BenchmarkScoreType::MMLUPro(score) => score.warnings(),
+            BenchmarkScoreType::GSM8K(score) => score.warnings(),
+            BenchmarkScoreType::HumanEval(score) => score.warnings(),
+            BenchmarkScoreType::HellaSwag(score) => score.warnings(),
+            BenchmarkScoreType::TruthfulQA(score) => score.warnings(),
+            BenchmarkScoreType::Gpqa(score) => score.warnings(),
+            BenchmarkScoreType::Generic(score) => score.warnings(),
+        }
+    }
+}
+
+impl BenchmarkScoreType {
+    /// Rescale percentage fields that look like 0–1 fractions to 0–100,
+    /// returning the names of the fields that were changed
+    pub fn normalize_fractions(&mut self) -> Vec<&'static str> {
+        let mut normalized = Vec::new();
+
+        match self {
+            BenchmarkScoreType::HumanEval(score) => {
+                for (field, value) in score.fraction_fields() {
+                    let percent = Some(normalize_fraction_to_percent(value));
+                    match field {
+                        "pass_at_1" => score.pass_at_1 = normalize_fraction_to_percent(value),
+                        "pass_at_10" => score.pass_at_10 = percent,
+                        _ => score.pass_at_100 = percent,
+                    }
+                    normalized.push(field);
+                }
+            }
+            BenchmarkScoreType::HellaSwag(score)
+                if looks_like_fraction(score.accuracy, score.total_questions, Some(score.correct_answers)) =>
+            {
+                score.accuracy = normalize_fraction_to_percent(score.accuracy);
+                normalized.push("accuracy");
+            }
+            BenchmarkScoreType::Gpqa(score)
+                if looks_like_fraction(score.accuracy, score.total_questions, Some(score.correct_answers)) =>
+            {
+                score.accuracy = normalize_fraction_to_percent(score.accuracy);
+                normalized.push("accuracy");
+            }
+            _ => {}
+        }
+
+        normalized
+    }
 }
 
 // Helper constructors
@@ -452,6 +881,39 @@ impl MMLUScore {
     }
 }
 
+impl MMLUProScore {
+    pub fn new(categories: Vec<MMLUProCategoryScore>) -> Self {
+        Self {
+            categories,
+            num_questions_total: None,
+            timestamp: Utc::now(),
+            context: None,
+        }
+    }
+}
+
+/// Legacy MMLU scores are MMLU-Pro results with known counts
+impl From<MMLUScore> for MMLUProScore {
+    fn from(score: MMLUScore) -> Self {
+        let total = score.categories.iter().map(|c| c.total_questions).sum();
+        Self {
+            categories: score
+                .categories
+                .into_iter()
+                .map(|c| MMLUProCategoryScore {
+                    category: c.category,
+                    score: c.score,
+                    total_questions: Some(c.total_questions),
+                    correct_answers: Some(c.correct_answers),
+                })
+                .collect(),
+            num_questions_total: Some(total),
+            timestamp: score.timestamp,
+            context: score.context,
+        }
+    }
+}
+
 impl GSM8KScore {
     pub fn new(problems_solved: i32, total_problems: i32) -> Self {
         Self {
@@ -464,6 +926,31 @@ impl GSM8KScore {
 }
 
 impl HumanEvalScore {
+    /// Pass@k fields that look like 0–1 fractions. Once pass@1 does, any
+    /// pass@k at or below 1.0 is assumed to share its scale.
+    fn fraction_fields(&self) -> Vec<(&'static str, f64)> {
+        let pass_at_1_fraction = looks_like_fraction(self.pass_at_1, self.total_problems, None);
+        let mut fields = Vec::new();
+
+        if pass_at_1_fraction {
+            fields.push(("pass_at_1", self.pass_at_1));
+        }
+        for (field, value) in [("pass_at_10", self.pass_at_10), ("pass_at_100", self.pass_at_100)] {
+            if let Some(v) = value {
+                let fraction = if pass_at_1_fraction {
+                    v > 0.0 && v <= 1.0
+                } else {
+                    looks_like_fraction(v, self.total_problems, None)
+                };
+                if fraction {
+                    fields.push((field, v));
+                }
+            }
+        }
+
+        fields
+    }
+
     pub fn new(pass_at_1: f64, total_problems: i32) -> Self {
         Self {
             pass_at_1,
@@ -501,6 +988,23 @@ impl TruthfulQAScore {
     }
 }
 
+impl GpqaScore {
+    /// Known GPQA question sets
+    pub const SUBSETS: [&'static str; 3] = ["main", "diamond", "extended"];
+
+    pub fn new(correct_answers: i32, total_questions: i32) -> Self {
+        let accuracy = (correct_answers as f64 / total_questions as f64) * 100.0;
+        Self {
+            accuracy,
+            total_questions,
+            correct_answers,
+            subset: None,
+            timestamp: Utc::now(),
+            context: None,
+        }
+    }
+}
+
 impl GenericBenchmarkScore {
     pub fn new(benchmark_name: String, score: f64) -> Self {
         Self {
@@ -508,8 +1012,280 @@ impl GenericBenchmarkScore {
             score,
             total_questions: None,
             correct_answers: None,
+            sub_scores: None,
             timestamp: Utc::now(),
             context: None,
         }
     }
-}
\ No newline at end of file
+}
+/// MMLU-Pro category names in the standard reporting order
+pub mod mmlu_categories {
+    use std::cmp::Ordering;
+
+    pub const BIOLOGY: &str = "biology";
+    pub const BUSINESS: &str = "business";
+    pub const CHEMISTRY: &str = "chemistry";
+    pub const COMPUTER_SCIENCE: &str = "computer science";
+    pub const ECONOMICS: &str = "economics";
+    pub const ENGINEERING: &str = "engineering";
+    pub const HEALTH: &str = "health";
+    pub const HISTORY: &str = "history";
+    pub const LAW: &str = "law";
+    pub const MATH: &str = "math";
+    pub const PHILOSOPHY: &str = "philosophy";
+    pub const PHYSICS: &str = "physics";
+    pub const PSYCHOLOGY: &str = "psychology";
+    pub const OTHER: &str = "other";
+
+    /// All categories in canonical order (matches the MMLU-Pro report table)
+    pub const ALL: [&str; 14] = [
+        BIOLOGY,
+        BUSINESS,
+        CHEMISTRY,
+        COMPUTER_SCIENCE,
+        ECONOMICS,
+        ENGINEERING,
+        HEALTH,
+        HISTORY,
+        LAW,
+        MATH,
+        PHILOSOPHY,
+        PHYSICS,
+        PSYCHOLOGY,
+        OTHER,
+    ];
+
+    /// Position of a category in the canonical order (case-insensitive)
+    pub fn canonical_index(category: &str) -> Option<usize> {
+        let category = category.trim();
+        ALL.iter().position(|c| c.eq_ignore_ascii_case(category))
+    }
+
+    /// Compare two category names by canonical order.
+    ///
+    /// Known categories come first in canonical order; unknown categories
+    /// follow, sorted alphabetically.
+    pub fn compare(a: &str, b: &str) -> Ordering {
+        match (canonical_index(a), canonical_index(b)) {
+            (Some(ia), Some(ib)) => ia.cmp(&ib),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.cmp(b),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmlu_category_canonical_order() {
+        let mut categories = vec!["psychology", "zoology", "Biology", "other", "computer science", "astronomy"];
+        categories.sort_by(|a, b| mmlu_categories::compare(a, b));
+        assert_eq!(
+            categories,
+            vec!["Biology", "computer science", "psychology", "other", "astronomy", "zoology"]
+        );
+    }
+
+    #[test]
+    fn test_fraction_scores_warn() {
+        let fraction = HumanEvalScore::new(0.45, 164);
+        assert_eq!(fraction.warnings().len(), 1);
+        assert!(HumanEvalScore::new(45.0, 164).warnings().is_empty());
+        assert!(HumanEvalScore::new(0.0, 164).warnings().is_empty());
+
+        // Without counts the whole (0, 1] range reads as a fraction, not
+        // just values worth less than one problem
+        for pass_at_1 in [0.75, 1.0] {
+            let mut score = BenchmarkScoreType::HumanEval(HumanEvalScore::new(pass_at_1, 164));
+            assert_eq!(score.normalize_fractions(), vec!["pass_at_1"]);
+            assert!((score.overall_score() - pass_at_1 * 100.0).abs() < 1e-9);
+        }
+        assert!(HumanEvalScore::new(1.01, 164).warnings().is_empty());
+
+        let mut hellaswag = HellaSwagScore::new(7000, 10000);
+        assert!(hellaswag.warnings().is_empty());
+        hellaswag.accuracy = 0.7;
+        assert_eq!(hellaswag.warnings().len(), 1);
+
+        // A genuine sub-1% accuracy agrees with its counts
+        let low = HellaSwagScore::new(5, 1000);
+        assert!(low.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_normalize_fractions() {
+        assert_eq!(normalize_fraction_to_percent(0.45), 45.0);
+        assert_eq!(normalize_fraction_to_percent(45.0), 45.0);
+
+        let mut humaneval = HumanEvalScore::new(0.45, 164);
+        humaneval.pass_at_10 = Some(0.8);
+        let mut score = BenchmarkScoreType::HumanEval(humaneval);
+        assert_eq!(score.normalize_fractions(), vec!["pass_at_1", "pass_at_10"]);
+        assert!((score.overall_score() - 45.0).abs() < 1e-9);
+        assert!(score.warnings().is_empty());
+        assert!(score.normalize_fractions().is_empty());
+    }
+
+    #[test]
+    fn test_score_timestamp_bounds() {
+        let mut score = HumanEvalScore::new(45.0, 164);
+        assert!(score.validate().is_ok());
+
+        score.timestamp = Utc.with_ymd_and_hms(2019, 12, 31, 23, 59, 59).unwrap();
+        let err = score.validate().unwrap_err();
+        assert!(err.to_string().contains("2019-12-31T23:59:59+00:00"));
+
+        score.timestamp = Utc::now() + Duration::days(FUTURE_TIMESTAMP_LIMIT_DAYS + 1);
+        assert!(BenchmarkScoreType::HumanEval(score).validate().is_err());
+    }
+
+    #[test]
+    fn test_gpqa_score() {
+        let mut gpqa = GpqaScore::new(99, 198);
+        gpqa.subset = Some("diamond".to_string());
+        assert_eq!(gpqa.benchmark_name(), "gpqa");
+        assert!((gpqa.overall_score() - 50.0).abs() < 1e-9);
+        assert!(gpqa.validate().is_ok());
+
+        let json = serde_json::to_value(BenchmarkScoreType::Gpqa(gpqa.clone())).unwrap();
+        assert_eq!(json["type"], "Gpqa");
+        assert_eq!(json["data"]["subset"], "diamond");
+        let parsed: BenchmarkScoreType = serde_json::from_value(serde_json::json!({
+            "type": "GPQA",
+            "data": json["data"],
+        }))
+        .unwrap();
+        assert!(matches!(parsed, BenchmarkScoreType::Gpqa(ref score) if score.correct_answers == 99));
+
+        let invalid = |f: fn(&mut GpqaScore)| {
+            let mut score = gpqa.clone();
+            f(&mut score);
+            score.validate().is_err()
+        };
+        assert!(invalid(|s| s.accuracy = 101.0));
+        assert!(invalid(|s| s.total_questions = 0));
+        assert!(invalid(|s| s.correct_answers = 199));
+        assert!(invalid(|s| s.subset = Some("hard".to_string())));
+    }
+
+    #[test]
+    fn test_mmlu_pro_optional_counts() {
+        let category = |score, total, correct| MMLUProCategoryScore {
+            category: "math".to_string(),
+            score,
+            total_questions: total,
+            correct_answers: correct,
+        };
+
+        let unknown = MMLUProScore::new(vec![category(60.0, None, None), category(40.0, None, None)]);
+        assert!(unknown.validate().is_ok());
+        assert!((unknown.overall_score() - 50.0).abs() < 1e-9);
+
+        assert!(MMLUProScore::new(vec![category(60.0, Some(10), Some(6))]).validate().is_ok());
+        assert!(MMLUProScore::new(vec![category(60.0, Some(10), Some(11))]).validate().is_err());
+        assert!(MMLUProScore::new(vec![category(60.0, Some(0), None)]).validate().is_err());
+        assert!(MMLUProScore::new(vec![category(160.0, None, None)]).validate().is_err());
+        assert!(MMLUProScore::new(Vec::new()).validate().is_err());
+
+        // Old MMLU payloads still deserialize and convert with their counts
+        let legacy: BenchmarkScoreType = serde_json::from_value(serde_json::json!({
+            "type": "MMLU",
+            "data": {
+                "categories": [{ "category": "law", "score": 50.0, "total_questions": 10, "correct_answers": 5 }],
+                "timestamp": "2026-01-01T00:00:00Z",
+                "context": null
+            }
+        }))
+        .unwrap();
+        let BenchmarkScoreType::MMLU(legacy) = legacy else { panic!("expected MMLU") };
+        let pro = MMLUProScore::from(legacy);
+        assert_eq!(pro.num_questions_total, Some(10));
+        assert_eq!(pro.categories[0].correct_answers, Some(5));
+    }
+
+    #[test]
+    fn test_lenient_score_shapes() {
+        let parse = |json: serde_json::Value| {
+            BenchmarkScoreType::from(serde_json::from_value::<LenientBenchmarkScore>(json).unwrap())
+        };
+        let ts = "2026-01-01T00:00:00Z";
+
+        let tagged = parse(serde_json::json!({
+            "type": "GSM8K",
+            "data": { "problems_solved": 80, "total_problems": 100, "timestamp": ts, "context": null }
+        }));
+        assert!(matches!(tagged, BenchmarkScoreType::GSM8K(_)));
+
+        let mmlu = parse(serde_json::json!({
+            "categories": [{ "category": "math", "score": 50.0, "total_questions": 10, "correct_answers": 5 }],
+            "timestamp": ts
+        }));
+        assert!(matches!(mmlu, BenchmarkScoreType::MMLU(_)));
+
+        // Without counts, categories are read as MMLU-Pro
+        let mmlu_pro = parse(serde_json::json!({
+            "categories": [{ "category": "math", "score": 50.0 }],
+            "timestamp": ts
+        }));
+        assert!(matches!(mmlu_pro, BenchmarkScoreType::MMLUPro(_)));
+        assert_eq!(mmlu_pro.benchmark_name(), "mmlu");
+
+        // A top-level `type` without `data` is read as a flat object
+        let gsm8k = parse(serde_json::json!({
+            "type": "GSM8K", "problems_solved": 80, "total_problems": 100, "timestamp": ts
+        }));
+        assert!((gsm8k.overall_score() - 80.0).abs() < 1e-9);
+
+        // A flat GPQA score shares HellaSwag's fields; its type decides
+        for kind in ["GPQA", "gpqa", "Gpqa"] {
+            let gpqa = parse(serde_json::json!({
+                "type": kind, "accuracy": 40.0, "total_questions": 198, "correct_answers": 79,
+                "subset": "diamond", "timestamp": ts
+            }));
+            let BenchmarkScoreType::Gpqa(gpqa) = gpqa else { panic!("{} read as {:?}", kind, gpqa) };
+            assert_eq!(gpqa.subset.as_deref(), Some("diamond"));
+        }
+        assert!(serde_json::from_value::<LenientBenchmarkScore>(serde_json::json!({
+            "type": "nope", "accuracy": 40.0, "total_questions": 198, "correct_answers": 79, "timestamp": ts
+        }))
+        .is_err());
+
+        let humaneval = parse(serde_json::json!({ "pass_at_1": 45.0, "total_problems": 164, "timestamp": ts }));
+        assert!(matches!(humaneval, BenchmarkScoreType::HumanEval(_)));
+
+        let hellaswag = parse(serde_json::json!({
+            "accuracy": 70.0, "total_questions": 100, "correct_answers": 70, "timestamp": ts
+        }));
+        assert!(matches!(hellaswag, BenchmarkScoreType::HellaSwag(_)));
+
+        let truthfulqa = parse(serde_json::json!({ "truthful_score": 39.0, "total_questions": 817, "timestamp": ts }));
+        assert!(matches!(truthfulqa, BenchmarkScoreType::TruthfulQA(_)));
+
+        let generic = parse(serde_json::json!({ "benchmark": "winogrande", "score": 74.0, "timestamp": ts }));
+        assert_eq!(generic.benchmark_name(), "winogrande");
+
+        assert!(serde_json::from_value::<LenientBenchmarkScore>(serde_json::json!({ "score": 1.0 })).is_err());
+    }
+
+    #[test]
+    fn test_generic_score_sub_scores() {
+        let mut score = GenericBenchmarkScore::new("bbh".to_string(), 61.5);
+        score.sub_scores = Some(serde_json::json!({ "date_understanding": 70.0, "navigate": 53.0 }));
+
+        let value = serde_json::to_value(&score).unwrap();
+        assert_eq!(value["sub_scores"]["navigate"], 53.0);
+        let round_trip: GenericBenchmarkScore = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip.sub_scores, score.sub_scores);
+
+        // Older payloads have no sub_scores; v2 rows name the score overall_score
+        let old: GenericBenchmarkScore = serde_json::from_value(serde_json::json!({
+            "benchmark_name": "bbh", "overall_score": 61.5, "timestamp": score.timestamp
+        }))
+        .unwrap();
+        assert_eq!(old.overall_score(), 61.5);
+        assert_eq!(old.sub_scores, None);
+    }
+}
@@ -20,12 +20,25 @@ pub struct ExperimentRun {
     /// Quantization scheme (e.g., "Q8_0", "Q4_0", "FP16")
     pub quantization: String,
 
-    /// Backend used for inference (e.g., "llama.cpp", "vllm")
+    /// LoRA adapter applied to the model. None/empty means base model.
+    #[serde(default)]
+    pub lora_adapter: Option<String>,
+
+    /// Backend used for inference (e.g., "llama.cpp", "vllm"); see
+    /// [`ExperimentRun::backend_kind`] for the parsed form
     pub backend: String,
 
     /// Version of the backend
     pub backend_version: String,
 
+    /// Size of the model weights in bytes, if known
+    #[serde(default)]
+    pub model_size_bytes: Option<i64>,
+
+    /// Number of model parameters, if known
+    #[serde(default)]
+    pub model_n_params: Option<i64>,
+
     /// Hardware configuration used for the test
     pub hardware_config: HardwareConfig,
 
@@ -64,6 +77,111 @@ pub struct ExperimentRun {
     /// GPU power limit in watts (e.g., 300 for limited RTX 4090)
     #[serde(default)]
     pub gpu_power_limit_watts: Option<i32>,
+
+    /// Campaign label for grouping related runs (e.g. "driver-550-retest")
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// Raw backend console output (stdout/stderr), stored apart from the
+    /// run and truncated server-side if too large
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>,
+}
+
+/// Known inference backend. Serialized as its canonical name; parsing is
+/// case-insensitive and accepts common aliases (e.g. `llamacpp`,
+/// `text-generation-inference`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Backend {
+    LlamaCpp,
+    Vllm,
+    Transformers,
+    Tgi,
+    Sglang,
+    MlcLlm,
+    Ollama,
+    Ctransformers,
+    Ggml,
+    Exllama,
+    Exllamav2,
+    TensorrtLlm,
+}
+
+impl Backend {
+    /// Every known backend
+    pub const ALL: [Backend; 12] = [
+        Backend::LlamaCpp,
+        Backend::Vllm,
+        Backend::Transformers,
+        Backend::Tgi,
+        Backend::Sglang,
+        Backend::MlcLlm,
+        Backend::Ollama,
+        Backend::Ctransformers,
+        Backend::Ggml,
+        Backend::Exllama,
+        Backend::Exllamav2,
+        Backend::TensorrtLlm,
+    ];
+
+    /// Canonical name, as stored and shown
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Backend::LlamaCpp => "llama.cpp",
+            Backend::Vllm => "vllm",
+            Backend::Transformers => "transformers",
+            Backend::Tgi => "tgi",
+            Backend::Sglang => "sglang",
+            Backend::MlcLlm => "mlc-llm",
+            Backend::Ollama => "ollama",
+            Backend::Ctransformers => "ctransformers",
+            Backend::Ggml => "ggml",
+            Backend::Exllama => "exllama",
+            Backend::Exllamav2 => "exllamav2",
+            Backend::TensorrtLlm => "tensorrt-llm",
+        }
+    }
+}
+
+impl std::fmt::Display for Backend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let canonical = match lower.as_str() {
+            "llamacpp" | "llama-cpp" | "llama_cpp" => "llama.cpp",
+            "text-generation-inference" => "tgi",
+            "tensorrt" | "trt-llm" | "tensorrt_llm" => "tensorrt-llm",
+            "mlc" | "mlc_llm" => "mlc-llm",
+            "sg-lang" => "sglang",
+            other => other,
+        };
+        Backend::ALL
+            .into_iter()
+            .find(|backend| backend.as_str() == canonical)
+            .ok_or_else(|| format!("Unknown backend: {}", s))
+    }
+}
+
+impl TryFrom<String> for Backend {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Backend> for String {
+    fn from(backend: Backend) -> Self {
+        backend.as_str().to_string()
+    }
 }
 
 /// Status of an experiment run
@@ -92,6 +210,31 @@ pub struct ExperimentSummary {
     pub overall_score: Option<f64>,
     pub timestamp: DateTime<Utc>,
     pub status: ExperimentStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<RunAnnotation>,
+}
+
+/// Curation flags on a test run, short of deleting it
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct RunAnnotation {
+    /// The trusted run among repeats of the same config
+    #[serde(default)]
+    pub canonical: bool,
+
+    /// Superseded or known-bad; hidden by `exclude_deprecated` filters
+    #[serde(default)]
+    pub deprecated: bool,
+
+    /// Why the run was marked
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl RunAnnotation {
+    /// Whether the annotation carries no information (posting one clears it)
+    pub fn is_empty(&self) -> bool {
+        !self.canonical && !self.deprecated && self.reason.is_none()
+    }
 }
 
 impl ExperimentRun {
@@ -110,6 +253,8 @@ impl ExperimentRun {
             quantization,
             backend,
             backend_version,
+            model_size_bytes: None,
+            model_n_params: None,
             hardware_config,
             performance_metrics: Vec::new(),
             benchmark_scores: Vec::new(),
@@ -121,9 +266,17 @@ impl ExperimentRun {
             load_pattern: None,
             dataset_name: None,
             gpu_power_limit_watts: None,
+            label: None,
+            lora_adapter: None,
+            log: None,
         }
     }
 
+    /// The backend parsed to a known [`Backend`], or None if unrecognized
+    pub fn backend_kind(&self) -> Option<Backend> {
+        self.backend.parse().ok()
+    }
+
     /// Add a performance metric to this experiment
     pub fn add_performance_metric(&mut self, metric: PerformanceMetric) {
         self.performance_metrics.push(metric);
@@ -179,6 +332,36 @@ impl ExperimentRun {
 
         has_speed && has_memory
     }
+
+    /// Serialize as pretty-printed JSON with every object's keys sorted, so
+    /// the same run always produces byte-identical output for diffing
+    pub fn to_canonical_json(&self) -> serde_json::Result<String> {
+        let mut json = serde_json::to_string_pretty(&sort_keys(serde_json::to_value(self)?))?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+/// Rebuild `value` with object keys in sorted order at every level. Explicit
+/// rather than relying on `serde_json::Map` being a `BTreeMap`, which stops
+/// holding if any dependency enables serde_json's `preserve_order` feature.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sort_keys).collect())
+        }
+        other => other,
+    }
 }
 
 impl ExperimentSummary {
@@ -199,6 +382,83 @@ impl ExperimentSummary {
             overall_score: run.calculate_overall_score(),
             timestamp: run.timestamp,
             status: run.status.clone(),
+            annotation: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_with_context(context: serde_json::Value) -> ExperimentRun {
+        let mut run = ExperimentRun::new(
+            Uuid::nil(),
+            "meta-llama/Llama-3.1-8B-Instruct".to_string(),
+            "Q4_K_M".to_string(),
+            "llama.cpp".to_string(),
+            "b4000".to_string(),
+            HardwareConfig::cpu_only("AMD EPYC 7763".to_string(), "Zen3".to_string(), Some(256), None),
+        );
+        run.timestamp = DateTime::parse_from_rfc3339("2026-10-18T00:00:00Z").unwrap().with_timezone(&Utc);
+        let mut metric = PerformanceMetric::with_context(
+            crate::metric_names::TOKENS_PER_SECOND.to_string(),
+            42.0,
+            "tok/s".to_string(),
+            context,
+        );
+        metric.timestamp = run.timestamp;
+        run.add_performance_metric(metric);
+        run
+    }
+
+    #[test]
+    fn test_canonical_json_is_stable() {
+        let run = run_with_context(serde_json::json!({ "n_prompt": 512, "build": { "commit": "abc", "number": 1 } }));
+        let first = run.to_canonical_json().unwrap();
+        assert_eq!(first, run.to_canonical_json().unwrap());
+
+        // Same content built in a different key order serializes identically
+        let mut context = serde_json::Map::new();
+        context.insert("build".to_string(), serde_json::json!({ "number": 1, "commit": "abc" }));
+        context.insert("n_prompt".to_string(), serde_json::json!(512));
+        let reordered = run_with_context(serde_json::Value::Object(context));
+        assert_eq!(first, reordered.to_canonical_json().unwrap());
+
+        // Struct fields are sorted too
+        assert!(first.find("\"backend\"").unwrap() < first.find("\"model_name\"").unwrap());
+        assert!(first.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_backend_aliases() {
+        let parse = |name: &str| name.parse::<Backend>().unwrap();
+        assert_eq!(parse("llama.cpp"), Backend::LlamaCpp);
+        assert_eq!(parse("llamacpp"), Backend::LlamaCpp);
+        assert_eq!(parse("llama_cpp"), Backend::LlamaCpp);
+        assert_eq!(parse("Llama-CPP"), Backend::LlamaCpp);
+        assert_eq!(parse(" vLLM "), Backend::Vllm);
+        assert_eq!(parse("text-generation-inference"), Backend::Tgi);
+        assert_eq!(parse("TRT-LLM"), Backend::TensorrtLlm);
+        assert_eq!(parse("mlc_llm"), Backend::MlcLlm);
+        assert!("unknown_backend".parse::<Backend>().is_err());
+        assert!("".parse::<Backend>().is_err());
+
+        // Canonical names round-trip through Display
+        for backend in Backend::ALL {
+            assert_eq!(parse(&backend.to_string()), backend);
         }
+
+        // Wire format is the canonical string
+        assert_eq!(serde_json::to_value(Backend::MlcLlm).unwrap(), serde_json::json!("mlc-llm"));
+        assert_eq!(serde_json::from_value::<Backend>(serde_json::json!("LlamaCpp")).unwrap(), Backend::LlamaCpp);
+        assert!(serde_json::from_value::<Backend>(serde_json::json!("nope")).is_err());
+
+        let mut run = run_with_context(serde_json::json!({}));
+        assert_eq!(run.backend_kind(), Some(Backend::LlamaCpp));
+        run.backend = "Text-Generation-Inference".to_string();
+        assert_eq!(run.backend_kind(), Some(Backend::Tgi));
+        run.backend = "custom".to_string();
+        assert_eq!(run.backend_kind(), None);
     }
-}
\ No newline at end of file
+}
@@ -2,15 +2,64 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Whether a `gpu_model` is a placeholder for a run without a GPU
+pub fn is_cpu_only_gpu_model(gpu_model: &str) -> bool {
+    gpu_model == "CPU Only" || gpu_model == "N/A"
+}
+
+/// Whether a GPU or CPU model is the "Generic (Benchmark Only)" placeholder
+/// that benchmark scores without a performance run are stored against
+pub fn is_benchmark_only_model(model: &str) -> bool {
+    model.contains("Generic") || model.contains("Benchmark Only")
+}
+
+/// Kind of placeholder a hardware profile's GPU or CPU model is, rather
+/// than real hardware
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwarePlaceholder {
+    /// "CPU Only" or "N/A" in place of a GPU model
+    CpuOnly,
+    /// The "Generic (Benchmark Only)" profile
+    BenchmarkOnly,
+}
+
+impl HardwarePlaceholder {
+    /// Placeholder kind of a `gpu_model`, None for a real GPU
+    pub fn of_gpu_model(gpu_model: &str) -> Option<Self> {
+        if is_benchmark_only_model(gpu_model) {
+            Some(Self::BenchmarkOnly)
+        } else if is_cpu_only_gpu_model(gpu_model) {
+            Some(Self::CpuOnly)
+        } else {
+            None
+        }
+    }
+
+    /// Placeholder kind of a `cpu_model`, None for a real CPU
+    pub fn of_cpu_model(cpu_model: &str) -> Option<Self> {
+        is_benchmark_only_model(cpu_model).then_some(Self::BenchmarkOnly)
+    }
+}
+
 /// Hardware configuration used for benchmark runs
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct HardwareConfig {
     /// GPU model (e.g., "RTX 4090", "CPU Only")
     pub gpu_model: String,
 
-    /// GPU memory in GB (0 for CPU-only)
+    /// GPU memory in GB per GPU (0 for CPU-only)
     pub gpu_memory_gb: i32,
 
+    /// Number of GPUs used by the run (e.g. 4 for tensor parallelism across
+    /// four cards)
+    #[serde(default = "default_gpu_count")]
+    pub gpu_count: i32,
+
+    /// Combined memory of all GPUs in GB, if reported
+    #[serde(default)]
+    pub total_gpu_memory_gb: Option<i32>,
+
     /// CPU model (e.g., "AMD Threadripper 1950X")
     pub cpu_model: String,
 
@@ -23,6 +72,14 @@ pub struct HardwareConfig {
     /// RAM type (e.g., "DDR4", "DDR5") (optional - may not be available from all sources)
     pub ram_type: Option<String>,
 
+    /// CPU threads used for inference (e.g., llama-bench `n_threads`)
+    #[serde(default)]
+    pub cpu_threads: Option<i32>,
+
+    /// Number of NUMA nodes on the host
+    #[serde(default)]
+    pub numa_nodes: Option<i32>,
+
     /// Virtualization type if applicable (e.g., "KVM", "Docker")
     pub virtualization_type: Option<String>,
 
@@ -30,6 +87,10 @@ pub struct HardwareConfig {
     pub optimizations: Vec<String>,
 }
 
+fn default_gpu_count() -> i32 {
+    1
+}
+
 /// Simplified hardware type for filtering and display
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -48,13 +109,40 @@ pub enum HardwareCategory {
     ConsumerGpu,
     /// Consumer CPU (e.g., Ryzen, Intel Core)
     ConsumerCpu,
-    /// Datacenter GPU (e.g., A100, H100, L4, L40)
+    /// Datacenter GPU (e.g., A100, H100, L4, L40, MI300X)
     DatacenterGpu,
     /// Datacenter CPU (e.g., Xeon, EPYC)
     DatacenterCpu,
 }
 
+/// Substrings of datacenter GPU model names. Consumer "RTX"/"GTX" cards are
+/// checked first, so short names like "L4" and "T4" don't misfire on them.
+const DATACENTER_GPU_MODELS: [&str; 13] = [
+    "A100", "A10", "A40", "H100", "H200", "GH200", "B200", "L4", "L40", "V100", "T4", "MI250", "MI300",
+];
+
+/// Substrings of datacenter CPU model names
+const DATACENTER_CPU_MODELS: [&str; 2] = ["Xeon", "EPYC"];
+
 impl HardwareCategory {
+    /// Category of a GPU and CPU model pair. Unrecognized GPUs are treated
+    /// as consumer GPUs; a CPU-only placeholder GPU falls back to the CPU.
+    pub fn from_models(gpu_model: &str, cpu_model: &str) -> Self {
+        if gpu_model.contains("RTX") || gpu_model.contains("GTX") {
+            HardwareCategory::ConsumerGpu
+        } else if DATACENTER_GPU_MODELS.iter().any(|model| gpu_model.contains(model)) {
+            HardwareCategory::DatacenterGpu
+        } else if is_cpu_only_gpu_model(gpu_model) || gpu_model.starts_with("CPU") {
+            if DATACENTER_CPU_MODELS.iter().any(|model| cpu_model.contains(model)) {
+                HardwareCategory::DatacenterCpu
+            } else {
+                HardwareCategory::ConsumerCpu
+            }
+        } else {
+            HardwareCategory::ConsumerGpu
+        }
+    }
+
     /// Get a human-readable label for the category
     pub fn label(&self) -> &'static str {
         match self {
@@ -79,10 +167,14 @@ impl HardwareConfig {
         Self {
             gpu_model,
             gpu_memory_gb,
+            gpu_count: 1,
+            total_gpu_memory_gb: None,
             cpu_model,
             cpu_arch,
             ram_gb,
             ram_type,
+            cpu_threads: None,
+            numa_nodes: None,
             virtualization_type: None,
             optimizations: Vec::new(),
         }
@@ -106,6 +198,18 @@ impl HardwareConfig {
         self
     }
 
+    /// Set the number of GPUs used by the run
+    pub fn with_gpu_count(mut self, gpu_count: i32) -> Self {
+        self.gpu_count = gpu_count;
+        self
+    }
+
+    /// Set the CPU thread count used for inference
+    pub fn with_cpu_threads(mut self, threads: i32) -> Self {
+        self.cpu_threads = Some(threads);
+        self
+    }
+
     /// Set virtualization type
     pub fn with_virtualization(mut self, virt_type: String) -> Self {
         self.virtualization_type = Some(virt_type);
@@ -123,21 +227,30 @@ impl HardwareConfig {
 
     /// Generate a short hardware summary string
     pub fn summary(&self) -> String {
-        format!("{} / {}", self.gpu_model, self.cpu_arch)
+        format!("{} / {}", self.gpu_label(), self.cpu_arch)
+    }
+
+    /// GPU model, prefixed with the count for multi-GPU runs (e.g. "4x A100")
+    fn gpu_label(&self) -> String {
+        if self.gpu_count > 1 {
+            format!("{}x {}", self.gpu_count, self.gpu_model)
+        } else {
+            self.gpu_model.clone()
+        }
     }
 
     /// Check if this configuration supports a given memory requirement
     pub fn supports_memory_gb(&self, required_gb: i32) -> bool {
         match self.hardware_type() {
-            HardwareType::Gpu => self.gpu_memory_gb >= required_gb,
+            HardwareType::Gpu => self.gpu_memory_gb * self.gpu_count >= required_gb,
             HardwareType::CpuOnly => self.ram_gb.map_or(false, |ram| ram >= required_gb),
         }
     }
 
-    /// Get effective memory for model loading (GPU memory or RAM)
+    /// Get effective memory for model loading (memory across all GPUs, or RAM)
     pub fn effective_memory_gb(&self) -> Option<i32> {
         match self.hardware_type() {
-            HardwareType::Gpu => Some(self.gpu_memory_gb),
+            HardwareType::Gpu => Some(self.gpu_memory_gb * self.gpu_count),
             HardwareType::CpuOnly => self.ram_gb,
         }
     }
@@ -152,26 +265,105 @@ impl HardwareConfig {
         self.optimizations.iter().any(|opt| opt == optimization)
     }
 
-    /// Determine the hardware category based on GPU and CPU model
-    pub fn hardware_category(&self) -> HardwareCategory {
-        // Check GPU first
-        if self.gpu_model.contains("RTX") || self.gpu_model.contains("GTX") {
-            HardwareCategory::ConsumerGpu
-        } else if self.gpu_model.contains("A100") || self.gpu_model.contains("H100") 
-            || self.gpu_model.contains("L4") || self.gpu_model.contains("L40")
-            || self.gpu_model.contains("V100") || self.gpu_model.contains("T4") {
-            HardwareCategory::DatacenterGpu
-        } else if self.gpu_model == "CPU Only" || self.gpu_model == "N/A" || self.gpu_memory_gb == 0 {
-            // CPU only - check CPU model
-            if self.cpu_model.contains("Xeon") || self.cpu_model.contains("EPYC") {
-                HardwareCategory::DatacenterCpu
-            } else {
-                HardwareCategory::ConsumerCpu
+    /// CPU architecture from a CPU model string, e.g. "zen4" for
+    /// "AMD Ryzen 9 7950X" or "apple_m2" for "Apple M2 Pro". AMD chips map
+    /// to their Zen generation by model number, 12th gen Intel Core to
+    /// "alderlake", 13th and 14th gen to "raptorlake" and Xeons and other
+    /// Intel chips to "intel". Anything else is "unknown".
+    pub fn detect_cpu_arch(cpu_info: &str) -> &'static str {
+        let cpu_lower = cpu_info.to_lowercase();
+
+        if let Some(generation) = ["m4", "m3", "m2", "m1"]
+            .into_iter()
+            .find(|generation| cpu_lower.contains(&format!("apple {}", generation)))
+        {
+            return match generation {
+                "m4" => "apple_m4",
+                "m3" => "apple_m3",
+                "m2" => "apple_m2",
+                _ => "apple_m1",
+            };
+        }
+
+        if let Some(number) = model_number_after(&cpu_lower, "epyc") {
+            // The last digit is the generation: 7763 is Milan (Zen 3), 9654 Genoa (Zen 4)
+            return zen_generation(number.chars().last());
+        }
+        if let Some(number) = model_number_after(&cpu_lower, "ryzen")
+            .or_else(|| model_number_after(&cpu_lower, "threadripper"))
+        {
+            // The first digit is the series: 5950X is Zen 3, 7950X Zen 4
+            return match number.chars().next() {
+                Some('1' | '2') => "zen1",
+                Some('3' | '4') => "zen2",
+                Some('5' | '6') => "zen3",
+                Some('7' | '8') => "zen4",
+                Some('9') => "zen5",
+                _ => "unknown",
+            };
+        }
+
+        if cpu_lower.contains("intel") || cpu_lower.contains("xeon") || cpu_lower.contains("core(tm)") {
+            if cpu_lower.contains("xeon") {
+                return "intel";
             }
-        } else {
-            // Unknown GPU, default to consumer
-            HardwareCategory::ConsumerGpu
+            return match intel_core_generation(&cpu_lower) {
+                Some(12) => "alderlake",
+                Some(13 | 14) => "raptorlake",
+                _ => "intel",
+            };
         }
+
+        "unknown"
+    }
+
+    /// Determine the hardware category based on GPU and CPU model
+    pub fn hardware_category(&self) -> HardwareCategory {
+        // Without GPU memory the run was CPU-only, whatever the GPU model says
+        let gpu_model = if self.gpu_memory_gb == 0 { "CPU Only" } else { &self.gpu_model };
+        HardwareCategory::from_models(gpu_model, &self.cpu_model)
+    }
+}
+
+/// Zen generation from the last digit of an EPYC model number
+fn zen_generation(digit: Option<char>) -> &'static str {
+    match digit {
+        Some('1') => "zen1",
+        Some('2') => "zen2",
+        Some('3') => "zen3",
+        Some('4') => "zen4",
+        Some('5') => "zen5",
+        _ => "unknown",
+    }
+}
+
+/// The first four-digit model number after `keyword`, without suffix
+/// letters ("7950" in "ryzen 9 7950x")
+fn model_number_after<'a>(cpu_lower: &'a str, keyword: &str) -> Option<&'a str> {
+    let (_, rest) = cpu_lower.split_once(keyword)?;
+    rest.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|token| {
+            let digits = token.find(|c: char| !c.is_ascii_digit()).unwrap_or(token.len());
+            &token[..digits]
+        })
+        .find(|number| number.len() == 4)
+}
+
+/// Intel Core generation from "13th Gen" or a model number like "i7-14700K"
+fn intel_core_generation(cpu_lower: &str) -> Option<u32> {
+    if let Some((before, _)) = cpu_lower.split_once("th gen") {
+        return before.rsplit(|c: char| !c.is_ascii_digit()).next()?.parse().ok();
+    }
+
+    let start = ["i3-", "i5-", "i7-", "i9-"]
+        .iter()
+        .find_map(|prefix| cpu_lower.find(prefix).map(|i| i + prefix.len()))?;
+    let digits: String = cpu_lower[start..].chars().take_while(char::is_ascii_digit).collect();
+    // Five-digit model numbers start with the generation (12900, 14700)
+    if digits.len() == 5 {
+        digits[..2].parse().ok()
+    } else {
+        None
     }
 }
 
@@ -181,7 +373,7 @@ impl std::fmt::Display for HardwareConfig {
             write!(
                 f,
                 "{} ({}GB) + {} {} ({}GB {})",
-                self.gpu_model,
+                self.gpu_label(),
                 self.gpu_memory_gb,
                 self.cpu_model,
                 self.cpu_arch,
@@ -192,7 +384,7 @@ impl std::fmt::Display for HardwareConfig {
             write!(
                 f,
                 "{} ({}GB) + {} {}",
-                self.gpu_model,
+                self.gpu_label(),
                 self.gpu_memory_gb,
                 self.cpu_model,
                 self.cpu_arch
@@ -226,6 +418,38 @@ mod tests {
         assert_eq!(cpu_config.hardware_type(), HardwareType::CpuOnly);
     }
 
+    #[test]
+    fn test_hardware_category_from_models() {
+        let epyc = "AMD EPYC 9654";
+        assert_eq!(HardwareCategory::from_models("AMD Instinct MI300X", epyc), HardwareCategory::DatacenterGpu);
+        assert_eq!(HardwareCategory::from_models("NVIDIA H200", epyc), HardwareCategory::DatacenterGpu);
+        for gpu in ["AMD Instinct MI250X", "NVIDIA GH200", "NVIDIA B200", "NVIDIA A10G", "NVIDIA A40"] {
+            assert_eq!(HardwareCategory::from_models(gpu, epyc), HardwareCategory::DatacenterGpu, "{}", gpu);
+        }
+
+        assert_eq!(HardwareCategory::from_models("RTX 4090", epyc), HardwareCategory::ConsumerGpu);
+        assert_eq!(HardwareCategory::from_models("Radeon RX 7900 XTX", epyc), HardwareCategory::ConsumerGpu);
+        assert_eq!(HardwareCategory::from_models("CPU Only", epyc), HardwareCategory::DatacenterCpu);
+        assert_eq!(HardwareCategory::from_models("N/A", "AMD Ryzen 9 7950X"), HardwareCategory::ConsumerCpu);
+    }
+
+    #[test]
+    fn test_hardware_placeholder() {
+        assert_eq!(HardwarePlaceholder::of_gpu_model("RTX 4090"), None);
+        assert_eq!(HardwarePlaceholder::of_gpu_model("CPU Only"), Some(HardwarePlaceholder::CpuOnly));
+        assert_eq!(HardwarePlaceholder::of_gpu_model("N/A"), Some(HardwarePlaceholder::CpuOnly));
+        assert_eq!(
+            HardwarePlaceholder::of_gpu_model("Generic (Benchmark Only)"),
+            Some(HardwarePlaceholder::BenchmarkOnly)
+        );
+
+        assert_eq!(HardwarePlaceholder::of_cpu_model("AMD Ryzen 9 7950X"), None);
+        assert_eq!(
+            HardwarePlaceholder::of_cpu_model("Generic (Benchmark Only)"),
+            Some(HardwarePlaceholder::BenchmarkOnly)
+        );
+    }
+
     #[test]
     fn test_memory_requirements() {
         let config = HardwareConfig::new(
@@ -240,5 +464,69 @@ mod tests {
         assert!(config.supports_memory_gb(20));
         assert!(!config.supports_memory_gb(32));
         assert_eq!(config.effective_memory_gb(), Some(24));
+
+        // Tensor parallelism pools memory across the cards
+        let config = HardwareConfig::new(
+            "A100".to_string(),
+            80,
+            "AMD EPYC 7763".to_string(),
+            "Zen3".to_string(),
+            Some(512),
+            None,
+        )
+        .with_gpu_count(4);
+        assert!(config.supports_memory_gb(200));
+        assert_eq!(config.effective_memory_gb(), Some(320));
+        assert_eq!(config.summary(), "4x A100 / Zen3");
+    }
+
+    #[test]
+    fn test_gpu_count_defaults_to_one() {
+        let config: HardwareConfig = serde_json::from_value(serde_json::json!({
+            "gpu_model": "RTX 4090",
+            "gpu_memory_gb": 24,
+            "cpu_model": "AMD Threadripper",
+            "cpu_arch": "Zen2",
+            "ram_gb": 64,
+            "ram_type": "DDR4",
+            "virtualization_type": null,
+            "optimizations": []
+        }))
+        .unwrap();
+        assert_eq!(config.gpu_count, 1);
+        assert_eq!(config.total_gpu_memory_gb, None);
+    }
+
+    #[test]
+    fn test_detect_cpu_arch() {
+        let detect = HardwareConfig::detect_cpu_arch;
+
+        // Ryzen and Threadripper by series
+        assert_eq!(detect("AMD Ryzen 9 7950X 16-Core Processor"), "zen4");
+        assert_eq!(detect("AMD Ryzen 9 5950X 16-Core Processor"), "zen3");
+        assert_eq!(detect("AMD Ryzen 7 3700X 8-Core Processor"), "zen2");
+        assert_eq!(detect("AMD Ryzen 9 9950X"), "zen5");
+        assert_eq!(detect("AMD Ryzen Threadripper 3970X 32-Core Processor"), "zen2");
+        assert_eq!(detect("AMD Ryzen Threadripper PRO 5995WX"), "zen3");
+
+        // EPYC by the generation digit
+        assert_eq!(detect("AMD EPYC 7742 64-Core Processor"), "zen2");
+        assert_eq!(detect("AMD EPYC 7763 64-Core Processor"), "zen3");
+        assert_eq!(detect("AMD EPYC 9654 96-Core Processor"), "zen4");
+        assert_eq!(detect("AMD EPYC 9755"), "zen5");
+
+        // Intel
+        assert_eq!(detect("12th Gen Intel(R) Core(TM) i9-12900K"), "alderlake");
+        assert_eq!(detect("13th Gen Intel(R) Core(TM) i7-13700K"), "raptorlake");
+        assert_eq!(detect("Intel(R) Core(TM) i9-14900K"), "raptorlake");
+        assert_eq!(detect("Intel(R) Xeon(R) Platinum 8480+"), "intel");
+        assert_eq!(detect("Intel(R) Core(TM) i7-8700K CPU @ 3.70GHz"), "intel");
+
+        // Apple
+        assert_eq!(detect("Apple M1 Max"), "apple_m1");
+        assert_eq!(detect("Apple M3 Pro"), "apple_m3");
+        assert_eq!(detect("Apple M4"), "apple_m4");
+
+        assert_eq!(detect("Some RISC-V board"), "unknown");
     }
 }
@@ -13,6 +13,7 @@ pub mod hardware;
 pub mod metrics;
 pub mod validation;
 pub mod model_variant;
+pub mod weighting;
 
 // Re-export commonly used types
 pub use api::*;
@@ -22,6 +23,7 @@ pub use hardware::*;
 pub use metrics::*;
 pub use validation::*;
 pub use model_variant::*;
+pub use weighting::*;
 
 // Re-export metric names for easy access
 pub use metrics::metric_names;
@@ -1,12 +1,19 @@
 // llm-benchmark-types/src/metrics.rs
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-/// A single performance metric measurement
+/// A single performance metric measurement.
+///
+/// Older payloads name the metric `name` and carry no timestamp; both are
+/// still accepted.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PerformanceMetric {
     /// Name of the metric (e.g., "tokens_per_second", "memory_usage_gb")
+    #[serde(alias = "name")]
     pub metric_name: String,
 
     /// Measured value
@@ -15,7 +22,8 @@ pub struct PerformanceMetric {
     /// Unit of measurement (e.g., "tok/s", "GB", "ms")
     pub unit: String,
 
-    /// When the metric was measured
+    /// When the metric was measured (defaults to when it was received)
+    #[serde(default = "Utc::now")]
     pub timestamp: DateTime<Utc>,
 
     /// Optional context or metadata
@@ -59,6 +67,71 @@ pub mod metric_names {
     pub const PEAK_MEMORY_GB: &str = "peak_memory_gb";
     pub const MODEL_SIZE_GB: &str = "model_size_gb";
     pub const GPU_POWER_WATTS: &str = "gpu_power_watts";
+    pub const GPU_UTILIZATION_PERCENT: &str = "gpu_utilization_percent";
+    pub const GPU_VRAM_USED_GB: &str = "gpu_vram_used_gb";
+    pub const TOKENS_PER_KWH: &str = "tokens_per_kwh";
+    pub const TTFT_P50_MS: &str = "ttft_p50_ms";
+    pub const TTFT_P95_MS: &str = "ttft_p95_ms";
+    pub const TPOT_P50_MS: &str = "tpot_p50_ms";
+    pub const TPOT_P95_MS: &str = "tpot_p95_ms";
+    pub const ITL_P50_MS: &str = "itl_p50_ms";
+    pub const ITL_P95_MS: &str = "itl_p95_ms";
+}
+
+/// Context key for raw per-repetition speed samples (tok/s)
+pub const SPEED_SAMPLES_CONTEXT_KEY: &str = "speed_samples";
+
+/// Summary statistics over repeated measurements of one metric
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SampleStatistics {
+    /// Number of samples
+    pub sample_count: usize,
+
+    /// Arithmetic mean
+    pub mean: f64,
+
+    /// Sample standard deviation (n - 1); 0 for a single sample
+    pub stddev: f64,
+
+    /// Smallest sample
+    pub min: f64,
+
+    /// Largest sample
+    pub max: f64,
+
+    /// Standard deviation divided by the mean (None when the mean is 0)
+    pub coefficient_of_variation: Option<f64>,
+}
+
+impl SampleStatistics {
+    /// Compute statistics from samples, ignoring non-finite values.
+    /// Returns None if no finite samples remain.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let values: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let stddev = if values.len() > 1 {
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let coefficient_of_variation = if mean != 0.0 { Some(stddev / mean.abs()) } else { None };
+
+        Some(Self {
+            sample_count: values.len(),
+            mean,
+            stddev,
+            min,
+            max,
+            coefficient_of_variation,
+        })
+    }
 }
 
 /// Known metric names for validation
@@ -73,9 +146,32 @@ pub fn metric_names() -> Vec<&'static str> {
         metric_names::THROUGHPUT_TOKENS_PER_SECOND,
         metric_names::PEAK_MEMORY_GB,
         metric_names::MODEL_SIZE_GB,
+        metric_names::GPU_POWER_WATTS,
+        metric_names::GPU_UTILIZATION_PERCENT,
+        metric_names::GPU_VRAM_USED_GB,
+        metric_names::TOKENS_PER_KWH,
     ]
 }
 
+/// Latency series recorded by inference-server load tests, one metric per
+/// statistic named `<series>_<stat>_ms` (e.g. `ttft_p95_ms`)
+const LATENCY_SERIES: &[&str] = &["ttft", "tpot", "itl", "request"];
+const LATENCY_STATS: &[&str] = &["mean", "p50", "p90", "p95", "p99"];
+
+/// Whether `name` is a known metric name, including the latency percentiles
+pub fn is_known_metric_name(name: &str) -> bool {
+    metric_names().contains(&name) || latency_series(name).is_some()
+}
+
+/// The series of a `<series>_<stat>_ms` latency metric name, e.g. "tpot"
+/// for `tpot_p99_ms`
+fn latency_series(name: &str) -> Option<&str> {
+    name.strip_suffix("_ms")
+        .and_then(|rest| rest.rsplit_once('_'))
+        .filter(|(series, stat)| LATENCY_SERIES.contains(series) && LATENCY_STATS.contains(stat))
+        .map(|(series, _)| series)
+}
+
 /// Known benchmark names for validation
 pub fn benchmark_names() -> Vec<&'static str> {
     vec![
@@ -85,6 +181,7 @@ pub fn benchmark_names() -> Vec<&'static str> {
         "truthfulqa",
         "gsm8k",
         "humaneval",
+        "gpqa",
         "arc_challenge",
         "arc_easy",
         "commonsense_qa",
@@ -120,10 +217,199 @@ impl PerformanceMetric {
         }
     }
 
+    /// Mean GPU utilization over the run (0–100)
+    pub fn gpu_utilization_percent(value: f64) -> Self {
+        Self::new(metric_names::GPU_UTILIZATION_PERCENT.to_string(), value, "%".to_string())
+    }
+
+    /// VRAM actually in use on the GPU, as opposed to what the backend
+    /// reserved up front
+    pub fn gpu_vram_used_gb(value: f64) -> Self {
+        Self::new(metric_names::GPU_VRAM_USED_GB.to_string(), value, "GB".to_string())
+    }
+
+    /// Median time to first token under load
+    pub fn ttft_p50_ms(value: f64) -> Self {
+        Self::new(metric_names::TTFT_P50_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// 95th percentile time to first token under load
+    pub fn ttft_p95_ms(value: f64) -> Self {
+        Self::new(metric_names::TTFT_P95_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// Median time per output token after the first
+    pub fn tpot_p50_ms(value: f64) -> Self {
+        Self::new(metric_names::TPOT_P50_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// 95th percentile time per output token after the first
+    pub fn tpot_p95_ms(value: f64) -> Self {
+        Self::new(metric_names::TPOT_P95_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// Median inter-token latency
+    pub fn itl_p50_ms(value: f64) -> Self {
+        Self::new(metric_names::ITL_P50_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// 95th percentile inter-token latency
+    pub fn itl_p95_ms(value: f64) -> Self {
+        Self::new(metric_names::ITL_P95_MS.to_string(), value, "ms".to_string())
+    }
+
+    /// Tokens per second equivalent to a time per output token in ms
+    /// (20 ms/token is 50 tok/s). A zero, negative or NaN latency isn't a
+    /// measurement and gives 0.0, the grid's value for a missing speed.
+    pub fn tpot_ms_to_tps(tpot_ms: f64) -> f64 {
+        if tpot_ms > 0.0 {
+            1000.0 / tpot_ms
+        } else {
+            0.0
+        }
+    }
+
+    /// Time per output token in ms equivalent to a speed in tokens per
+    /// second; the inverse of `tpot_ms_to_tps`, with the same 0.0 for a
+    /// zero, negative or NaN speed
+    pub fn tps_to_tpot_ms(tokens_per_second: f64) -> f64 {
+        if tokens_per_second > 0.0 {
+            1000.0 / tokens_per_second
+        } else {
+            0.0
+        }
+    }
+
+    /// Steady-state generation speed in tok/s implied by a TPOT metric
+    /// (`tpot_mean_ms`, `tpot_p50_ms`, ... `tpot_p99_ms`), for showing next
+    /// to measured speeds. A higher TPOT percentile is a lower speed. None
+    /// for any other metric.
+    pub fn tpot_tokens_per_second(&self) -> Option<f64> {
+        (latency_series(&self.metric_name) == Some("tpot")).then(|| Self::tpot_ms_to_tps(self.value))
+    }
+
+    /// Energy efficiency from generation speed and power draw:
+    /// `tokens_per_second × 3,600,000 / watts`. None without a positive
+    /// power reading.
+    pub fn tokens_per_kwh(tokens_per_second: f64, watts: f64) -> Option<Self> {
+        (watts > 0.0).then(|| {
+            Self::new(
+                metric_names::TOKENS_PER_KWH.to_string(),
+                tokens_per_second * 3_600_000.0 / watts,
+                "tokens/kWh".to_string(),
+            )
+        })
+    }
+
     /// Check if this is a valid known metric
     pub fn is_known_metric(&self) -> bool {
-        metric_names().contains(&self.metric_name.as_str())
+        is_known_metric_name(&self.metric_name)
+    }
+
+    /// Whether larger values of `metric_name` are better (speeds,
+    /// efficiency) rather than worse (latencies, memory, size, load time,
+    /// power)
+    pub fn higher_is_better(metric_name: &str) -> bool {
+        !Self::lower_is_better(metric_name)
+    }
+
+    /// Whether smaller values of `metric_name` are better; the complement
+    /// of `higher_is_better`
+    pub fn lower_is_better(metric_name: &str) -> bool {
+        metric_name != metric_names::TOKENS_PER_KWH
+            && (metric_name.ends_with("_ms")
+                || metric_name.ends_with("_gb")
+                || metric_name.ends_with("_watts")
+                || metric_name == metric_names::MODEL_LOADING_TIME)
     }
+
+    /// Order two values of `metric_name` better first: descending for
+    /// higher-is-better metrics (including unknown ones), ascending for
+    /// lower-is-better ones. NaN sorts last either way.
+    pub fn compare_better(a: f64, b: f64, metric_name: &str) -> Ordering {
+        match (a.is_nan(), b.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            _ if Self::higher_is_better(metric_name) => b.total_cmp(&a),
+            _ => a.total_cmp(&b),
+        }
+    }
+
+    /// Raw per-repetition speed samples stored in the context, if any
+    pub fn speed_samples(&self) -> Option<Vec<f64>> {
+        speed_samples_from_context(self.context.as_ref()?)
+    }
+
+    /// Value and unit for display, with precision chosen from the value's
+    /// magnitude (e.g. "0.003 ms", "4500 ms", "12.5 tok/s")
+    pub fn display(&self) -> String {
+        format!("{} {}", format_significant(self.value, DISPLAY_SIGNIFICANT_DIGITS), self.unit)
+    }
+
+    /// Value and unit for display with exactly `decimals` decimal places
+    pub fn display_with_precision(&self, decimals: usize) -> String {
+        format!("{:.*} {}", decimals, self.value, self.unit)
+    }
+}
+
+/// Significant digits used by the `display` helpers
+pub const DISPLAY_SIGNIFICANT_DIGITS: usize = 3;
+
+/// Most decimal places `format_significant` will emit
+const MAX_DISPLAY_DECIMALS: usize = 12;
+
+/// Format `value` with about `digits` significant digits.
+///
+/// Large values keep all their integer digits (4512.7 → "4513") and small
+/// ones get as many decimals as they need (0.003142 → "0.00314"); trailing
+/// zeros are dropped.
+pub fn format_significant(value: f64, digits: usize) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+
+    let magnitude = value.abs().log10().floor() as i64;
+    let decimals = (digits.max(1) as i64 - 1 - magnitude).clamp(0, MAX_DISPLAY_DECIMALS as i64) as usize;
+    let formatted = format!("{:.*}", decimals, value);
+
+    if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        formatted
+    }
+}
+
+/// Collapse `(metric_name, value)` pairs into one value per metric.
+///
+/// A run may record a metric more than once (e.g. speed at several context
+/// lengths); duplicates keep the best value, the max for higher-is-better
+/// metrics and the min otherwise, so the result doesn't depend on row order.
+pub fn build_perf_map(metrics: impl IntoIterator<Item = (String, f64)>) -> HashMap<String, f64> {
+    let mut perf_map: HashMap<String, f64> = HashMap::new();
+    for (metric_name, value) in metrics {
+        match perf_map.get_mut(&metric_name) {
+            Some(best) => {
+                if PerformanceMetric::compare_better(value, *best, &metric_name) == Ordering::Less {
+                    *best = value;
+                }
+            }
+            None => {
+                perf_map.insert(metric_name, value);
+            }
+        }
+    }
+    perf_map
+}
+
+/// Extract the `speed_samples` array from a metric context
+pub fn speed_samples_from_context(context: &serde_json::Value) -> Option<Vec<f64>> {
+    context
+        .get(SPEED_SAMPLES_CONTEXT_KEY)?
+        .as_array()?
+        .iter()
+        .map(|v| v.as_f64())
+        .collect()
 }
 
 impl QualityScore {
@@ -173,4 +459,207 @@ impl QualityScore {
             self.score
         }
     }
-}
\ No newline at end of file
+
+    /// Score as a percentage, with precision chosen from its magnitude
+    pub fn display(&self) -> String {
+        format!("{}%", format_significant(self.as_percentage(), DISPLAY_SIGNIFICANT_DIGITS))
+    }
+
+    /// Score as a percentage with exactly `decimals` decimal places
+    pub fn display_with_precision(&self, decimals: usize) -> String {
+        format!("{:.*}%", decimals, self.as_percentage())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_perf_map_keeps_best_duplicate() {
+        let perf_map = build_perf_map(vec![
+            (metric_names::TOKENS_PER_SECOND.to_string(), 42.0),
+            (metric_names::FIRST_TOKEN_LATENCY_MS.to_string(), 180.0),
+            (metric_names::TOKENS_PER_SECOND.to_string(), 55.5),
+            (metric_names::MEMORY_USAGE_GB.to_string(), 9.0),
+            (metric_names::FIRST_TOKEN_LATENCY_MS.to_string(), 120.0),
+            (metric_names::TOKENS_PER_SECOND.to_string(), 30.0),
+            (metric_names::MEMORY_USAGE_GB.to_string(), 11.0),
+        ]);
+
+        assert_eq!(perf_map.len(), 3);
+        assert_eq!(perf_map[metric_names::TOKENS_PER_SECOND], 55.5);
+        assert_eq!(perf_map[metric_names::FIRST_TOKEN_LATENCY_MS], 120.0);
+        assert_eq!(perf_map[metric_names::MEMORY_USAGE_GB], 9.0);
+
+        assert!(PerformanceMetric::higher_is_better(metric_names::PROMPT_PROCESSING_SPEED));
+        assert!(!PerformanceMetric::higher_is_better(metric_names::MODEL_LOADING_TIME));
+        assert!(!PerformanceMetric::higher_is_better(metric_names::GPU_POWER_WATTS));
+        assert!(!PerformanceMetric::higher_is_better("ttft_p99_ms"));
+        assert!(PerformanceMetric::higher_is_better(metric_names::TOKENS_PER_KWH));
+        assert!(PerformanceMetric::lower_is_better(metric_names::GPU_POWER_WATTS));
+    }
+
+    #[test]
+    fn test_compare_better() {
+        let sorted = |metric: &str| {
+            let mut values = vec![20.0, f64::NAN, 5.0, 12.5];
+            values.sort_by(|a, b| PerformanceMetric::compare_better(*a, *b, metric));
+            values
+        };
+        let finite = |values: Vec<f64>| values[..3].to_vec();
+
+        // Faster first for speeds
+        assert_eq!(finite(sorted(metric_names::TOKENS_PER_SECOND)), [20.0, 12.5, 5.0]);
+
+        // Smaller first for memory and latency percentiles
+        for metric in [
+            metric_names::MEMORY_USAGE_GB,
+            metric_names::TTFT_P95_MS,
+            metric_names::TPOT_P95_MS,
+            metric_names::ITL_P95_MS,
+        ] {
+            assert_eq!(finite(sorted(metric)), [5.0, 12.5, 20.0], "{}", metric);
+        }
+
+        // Unknown metrics are treated as higher-is-better
+        assert_eq!(finite(sorted("mystery_metric")), [20.0, 12.5, 5.0]);
+
+        // NaN goes last in both directions
+        assert!(sorted(metric_names::TOKENS_PER_SECOND)[3].is_nan());
+        assert!(sorted(metric_names::TTFT_P95_MS)[3].is_nan());
+        assert_eq!(PerformanceMetric::compare_better(1.0, 1.0, metric_names::TTFT_P95_MS), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_metric_serde_accepts_legacy_names() {
+        let legacy: PerformanceMetric =
+            serde_json::from_value(serde_json::json!({ "name": "tokens_per_second", "value": 42.0, "unit": "tok/s" }))
+                .unwrap();
+        assert_eq!(legacy.metric_name, metric_names::TOKENS_PER_SECOND);
+        assert!(legacy.context.is_none());
+
+        let metric = PerformanceMetric::with_context(
+            metric_names::FIRST_TOKEN_LATENCY_MS.to_string(),
+            120.0,
+            "ms".to_string(),
+            serde_json::json!({ "concurrency": 4 }),
+        );
+        let json = serde_json::to_value(&metric).unwrap();
+        assert!(json.get("metric_name").is_some() && json.get("name").is_none());
+
+        let round_trip: PerformanceMetric = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.metric_name, metric.metric_name);
+        assert_eq!(round_trip.value, metric.value);
+        assert_eq!(round_trip.timestamp, metric.timestamp);
+        assert_eq!(round_trip.context, metric.context);
+    }
+
+    #[test]
+    fn test_tokens_per_kwh() {
+        let metric = PerformanceMetric::tokens_per_kwh(50.0, 300.0).unwrap();
+        assert_eq!(metric.metric_name, metric_names::TOKENS_PER_KWH);
+        assert!((metric.value - 600_000.0).abs() < 1e-6);
+        assert_eq!(metric.unit, "tokens/kWh");
+        assert!(metric.is_known_metric());
+
+        assert!(PerformanceMetric::tokens_per_kwh(50.0, 0.0).is_none());
+        assert!(PerformanceMetric::tokens_per_kwh(50.0, -5.0).is_none());
+    }
+
+    #[test]
+    fn test_latency_percentile_constructors() {
+        for (metric, name) in [
+            (PerformanceMetric::ttft_p50_ms(120.0), metric_names::TTFT_P50_MS),
+            (PerformanceMetric::ttft_p95_ms(120.0), metric_names::TTFT_P95_MS),
+            (PerformanceMetric::tpot_p50_ms(120.0), metric_names::TPOT_P50_MS),
+            (PerformanceMetric::tpot_p95_ms(120.0), metric_names::TPOT_P95_MS),
+            (PerformanceMetric::itl_p50_ms(120.0), metric_names::ITL_P50_MS),
+            (PerformanceMetric::itl_p95_ms(120.0), metric_names::ITL_P95_MS),
+        ] {
+            assert_eq!(metric.metric_name, name);
+            assert_eq!(metric.value, 120.0);
+            assert_eq!(metric.unit, "ms");
+            assert!(metric.is_known_metric(), "{}", name);
+            assert!(PerformanceMetric::lower_is_better(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_tpot_tps_conversion() {
+        assert!((PerformanceMetric::tpot_ms_to_tps(20.0) - 50.0).abs() < 1e-9);
+        assert!((PerformanceMetric::tps_to_tpot_ms(50.0) - 20.0).abs() < 1e-9);
+        for tpot_ms in [0.5, 20.0, 37.3, 1250.0] {
+            let round_trip = PerformanceMetric::tps_to_tpot_ms(PerformanceMetric::tpot_ms_to_tps(tpot_ms));
+            assert!((round_trip - tpot_ms).abs() < 1e-9, "{}", tpot_ms);
+        }
+
+        for invalid in [0.0, -5.0, f64::NAN] {
+            assert_eq!(PerformanceMetric::tpot_ms_to_tps(invalid), 0.0);
+            assert_eq!(PerformanceMetric::tps_to_tpot_ms(invalid), 0.0);
+        }
+
+        assert_eq!(PerformanceMetric::tpot_p50_ms(20.0).tpot_tokens_per_second(), Some(50.0));
+        assert_eq!(PerformanceMetric::tpot_p95_ms(40.0).tpot_tokens_per_second(), Some(25.0));
+        for name in ["tpot_mean_ms", "tpot_p90_ms", "tpot_p99_ms"] {
+            let metric = PerformanceMetric::new(name.to_string(), 100.0, "ms".to_string());
+            assert_eq!(metric.tpot_tokens_per_second(), Some(10.0), "{}", name);
+        }
+        let unknown_stat = PerformanceMetric::new("tpot_p42_ms".to_string(), 100.0, "ms".to_string());
+        assert_eq!(unknown_stat.tpot_tokens_per_second(), None);
+        assert_eq!(PerformanceMetric::ttft_p50_ms(20.0).tpot_tokens_per_second(), None);
+    }
+
+    #[test]
+    fn test_display_across_magnitudes() {
+        let ms = |value| PerformanceMetric::new("first_token_latency_ms".to_string(), value, "ms".to_string());
+
+        assert_eq!(ms(0.003).display(), "0.003 ms");
+        assert_eq!(ms(0.0031416).display(), "0.00314 ms");
+        assert_eq!(ms(1.5).display(), "1.5 ms");
+        assert_eq!(ms(12.345).display(), "12.3 ms");
+        assert_eq!(ms(4500.0).display(), "4500 ms");
+        assert_eq!(ms(4512.7).display(), "4513 ms");
+        assert_eq!(ms(1.2e9).display(), "1200000000 ms");
+        assert_eq!(ms(0.0).display(), "0 ms");
+        assert_eq!(ms(-0.25).display(), "-0.25 ms");
+        assert_eq!(ms(0.003).display_with_precision(2), "0.00 ms");
+        assert_eq!(ms(4500.0).display_with_precision(1), "4500.0 ms");
+
+        let score = |value| QualityScore::new("mmlu".to_string(), "overall".to_string(), value);
+        assert_eq!(score(0.7123).display(), "71.2%");
+        assert_eq!(score(0.0004).display(), "0.04%");
+        assert_eq!(score(85.0).display(), "85%");
+        assert_eq!(score(0.7123).display_with_precision(2), "71.23%");
+    }
+
+    #[test]
+    fn test_sample_statistics() {
+        let stats = SampleStatistics::from_samples(&[10.0, 12.0, 14.0]).unwrap();
+        assert_eq!(stats.sample_count, 3);
+        assert_eq!(stats.mean, 12.0);
+        assert_eq!(stats.stddev, 2.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 14.0);
+        assert_eq!(stats.coefficient_of_variation, Some(2.0 / 12.0));
+
+        let single = SampleStatistics::from_samples(&[5.0]).unwrap();
+        assert_eq!(single.stddev, 0.0);
+
+        assert!(SampleStatistics::from_samples(&[]).is_none());
+        assert!(SampleStatistics::from_samples(&[f64::NAN]).is_none());
+    }
+
+    #[test]
+    fn test_speed_samples_from_context() {
+        let metric = PerformanceMetric::with_context(
+            metric_names::TOKENS_PER_SECOND.to_string(),
+            100.0,
+            "tok/s".to_string(),
+            serde_json::json!({ "n_gen": 128, "speed_samples": [99.0, 100.0, 101.0] }),
+        );
+        assert_eq!(metric.speed_samples(), Some(vec![99.0, 100.0, 101.0]));
+
+        let without = PerformanceMetric::new("tokens_per_second".to_string(), 1.0, "tok/s".to_string());
+        assert_eq!(without.speed_samples(), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
@@ -40,6 +42,16 @@ pub struct UploadBenchmarkRequest {
     pub lora_adapter: Option<String>,
     pub benchmark_scores: Vec<crate::benchmarks::BenchmarkScoreType>,
     pub timestamp: Option<DateTime<Utc>>,
+    /// Keep the scores this upload replaces in the score history instead of
+    /// deleting them. Reads still use the set with the latest timestamp.
+    #[serde(default)]
+    pub keep_history: bool,
+    /// Merge MMLU categories into the stored set (update the uploaded
+    /// categories, insert new ones, keep the rest) instead of replacing it.
+    /// Other benchmarks hold one score and are replaced either way.
+    /// Cannot be combined with `keep_history`.
+    #[serde(default)]
+    pub merge: bool,
 }
 
 /// Response for benchmark upload
@@ -49,4 +61,291 @@ pub struct UploadBenchmarkResponse {
     pub model_variant_id: Option<Uuid>,
     pub message: String,
     pub scores_uploaded: usize,
-}
\ No newline at end of file
+}
+
+/// Request to upload a test run together with benchmark scores for its
+/// model variant, written in a single transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadCompleteRequest {
+    pub experiment_run: crate::ExperimentRun,
+    /// Scores for the run's model/quantization, stored in the model variant tables
+    #[serde(default)]
+    pub benchmark_scores: Vec<crate::benchmarks::BenchmarkScoreType>,
+}
+
+/// Response for a combined performance and benchmark upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadCompleteResponse {
+    pub success: bool,
+    pub test_run_id: Option<Uuid>,
+    pub model_variant_id: Option<Uuid>,
+    pub scores_uploaded: usize,
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Known model families, matched as substrings of the lowercased model name.
+/// More specific names come before names they contain (e.g. "mixtral" before "mistral").
+const KNOWN_FAMILIES: &[&str] = &[
+    "gpt-oss", "llama", "mixtral", "mistral", "qwen", "gemma", "phi", "deepseek",
+    "granite", "command", "falcon", "olmo", "smollm", "internlm", "glm", "yi",
+];
+
+/// Infer the model family from a model name (e.g. "meta-llama/Llama-3.1-8B" -> "llama")
+pub fn model_family(model_name: &str) -> Option<String> {
+    let base = model_name.rsplit('/').next().unwrap_or(model_name).to_lowercase();
+
+    if let Some(family) = KNOWN_FAMILIES.iter().find(|f| base.contains(*f)) {
+        return Some(family.to_string());
+    }
+
+    // Fall back to the leading alphabetic part of the first name segment
+    let first = base.split(['-', '_', '.', ' ']).next().unwrap_or("");
+    let family: String = first.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if family.is_empty() {
+        None
+    } else {
+        Some(family)
+    }
+}
+
+/// Display name of a model variant: "Llama-3.1-8B Q4_K_M", with
+/// " + <adapter>" appended for a LoRA variant
+pub fn variant_display_name(model_name: &str, quantization: &str, lora_adapter: &str) -> String {
+    if lora_adapter.is_empty() {
+        format!("{} {}", model_name, quantization)
+    } else {
+        format!("{} {} + {}", model_name, quantization, lora_adapter)
+    }
+}
+
+/// Parse the parameter count in billions from a model name
+/// (e.g. "Llama-3.1-8B" -> 8.0, "Mixtral-8x7B" -> 56.0, "SmolLM-135M" -> 0.135).
+///
+/// Active-parameter suffixes such as the "A3B" in "Qwen3-30B-A3B" are ignored.
+pub fn parameter_count_billions(model_name: &str) -> Option<f64> {
+    let base = model_name.rsplit('/').next().unwrap_or(model_name);
+
+    base.split(['-', '_', ' ']).find_map(parse_parameter_token)
+}
+
+fn parse_parameter_token(token: &str) -> Option<f64> {
+    let token = token.to_lowercase();
+    let (number, scale) = if let Some(n) = token.strip_suffix('b') {
+        (n, 1.0)
+    } else if let Some(n) = token.strip_suffix('m') {
+        (n, 0.001)
+    } else {
+        return None;
+    };
+
+    // Mixture-of-experts names like "8x7b"
+    let value = match number.split_once('x') {
+        Some((experts, size)) => experts.parse::<f64>().ok()? * size.parse::<f64>().ok()?,
+        None => number.parse::<f64>().ok()?,
+    };
+
+    if value > 0.0 {
+        Some(value * scale)
+    } else {
+        None
+    }
+}
+
+/// Multiplier applied to raw weight size to account for KV cache, activations
+/// and runtime buffers (matches the uploader's model-size based estimate)
+pub const MEMORY_OVERHEAD_FACTOR: f64 = 1.2;
+
+/// Approximate storage bits per weight for a quantization scheme
+pub fn bits_per_weight(quantization: &str) -> Option<f64> {
+    let quant = crate::normalize_quantization(quantization);
+    let bits = match quant.as_str() {
+        "F32" | "FP32" => 32.0,
+        "BF16" | "F16" | "FP16" => 16.0,
+        "FP8" | "FP8_DYNAMIC" | "W8A8" | "W8A16" | "INT8" => 8.0,
+        "Q8_0" => 8.5,
+        "Q6_K" => 6.56,
+        "Q5_K_M" => 5.69,
+        "Q5_K_S" => 5.54,
+        "Q5_1" => 6.0,
+        "Q5_0" => 5.5,
+        "Q4_K_M" => 4.85,
+        "Q4_K_S" => 4.58,
+        "Q4_1" => 5.0,
+        "Q4_0" => 4.55,
+        "IQ4_XS" => 4.25,
+        "IQ4_NL" => 4.5,
+        "Q3_K_L" => 4.27,
+        "Q3_K_M" => 3.91,
+        "Q3_K_S" => 3.5,
+        "IQ3_M" => 3.66,
+        "IQ3_S" => 3.44,
+        "IQ3_XS" => 3.3,
+        "Q2_K" => 3.35,
+        "IQ3_XXS" => 3.06,
+        "IQ2_M" => 2.7,
+        "IQ2_S" => 2.5,
+        "IQ2_XS" => 2.31,
+        "IQ2_XXS" => 2.06,
+        "IQ1_M" => 1.75,
+        "IQ1_S" => 1.56,
+        "W4A16" | "W4A8" | "INT4" | "AWQ" | "GPTQ" => 4.25,
+        _ => return None,
+    };
+    Some(bits)
+}
+
+/// Estimate the memory needed to run a model in GB from its parameter count
+/// (in billions) and quantization, including runtime overhead
+pub fn estimate_memory_gb(parameter_count_b: f64, quantization: &str) -> Option<f64> {
+    let bits = bits_per_weight(quantization)?;
+    let weights_gb = parameter_count_b * bits / 8.0;
+    Some(weights_gb * MEMORY_OVERHEAD_FACTOR)
+}
+
+/// Whether a quantization stores weights at 16 bits or more
+pub fn is_full_precision(quantization: &str) -> bool {
+    bits_per_weight(quantization).is_some_and(|bits| bits >= 16.0)
+}
+
+/// Sort key putting quantizations in a logical order: full precision
+/// (FP32, BF16, FP16), FP8, weight-only (`W*A16`), weight-activation
+/// (`W*A8`), GGUF, then anything else.
+///
+/// Within a group schemes are ordered from most to fewest bits per weight,
+/// so GGUF runs Q8_0 > Q6_K > Q5_K_M > ... > Q2_K with IQ types slotted in
+/// by size. Schemes without a known size sort last in their group.
+pub fn quantization_sort_key(quantization: &str) -> (u8, Reverse<u32>, String) {
+    let normalized = crate::normalize_quantization(quantization);
+    let group = match normalized.as_str() {
+        "FP32" | "F32" => 0,
+        "BF16" => 1,
+        "FP16" | "F16" => 2,
+        "FP8_DYNAMIC" => 3,
+        "FP8" => 4,
+        q if q.starts_with('W') && q.contains("A16") => 10,
+        q if q.starts_with('W') && q.contains("A8") => 11,
+        q if q.starts_with('W') => 20,
+        q if q.starts_with('Q') || q.starts_with("IQ") => 30,
+        _ => 99,
+    };
+    let bits = bits_per_weight(&normalized).map_or(0, |bits| (bits * 100.0) as u32);
+    (group, Reverse(bits), quantization.to_string())
+}
+
+/// Ordering key for choosing a quality baseline; higher is better.
+///
+/// Ranks FP32 > BF16 > FP16 > FP8 > other quantizations (by bits per
+/// weight) > unknown schemes. BF16 wins the 16-bit tie for its FP32-sized
+/// exponent range.
+fn baseline_rank(quantization: &str) -> (u8, u32, bool) {
+    let normalized = crate::normalize_quantization(quantization);
+    let Some(bits) = bits_per_weight(&normalized) else {
+        return (0, 0, false);
+    };
+
+    let tier = if bits >= 16.0 {
+        3
+    } else if normalized.starts_with("FP8") {
+        2
+    } else {
+        1
+    };
+    (tier, (bits * 100.0) as u32, normalized == "BF16")
+}
+
+/// Pick the highest-precision quantization to measure quality retention
+/// against. The flag is false when no 16-bit or wider variant is available.
+pub fn select_baseline_quantization<'a, I>(quantizations: I) -> Option<(&'a str, bool)>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    quantizations
+        .into_iter()
+        .max_by_key(|q| baseline_rank(q))
+        .map(|q| (q, is_full_precision(q)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_family() {
+        assert_eq!(model_family("meta-llama/Llama-3.1-8B-Instruct").as_deref(), Some("llama"));
+        assert_eq!(model_family("Qwen/Qwen3-30B-A3B").as_deref(), Some("qwen"));
+        assert_eq!(model_family("mistralai/Mixtral-8x7B-v0.1").as_deref(), Some("mixtral"));
+        assert_eq!(model_family("openai/gpt-oss-20b").as_deref(), Some("gpt-oss"));
+        assert_eq!(model_family("TheDrummer/Snowpiercer-15B-v1").as_deref(), Some("snowpiercer"));
+        assert_eq!(model_family("42"), None);
+    }
+
+    #[test]
+    fn test_variant_display_name() {
+        assert_eq!(variant_display_name("Llama-3.1-8B", "Q4_K_M", ""), "Llama-3.1-8B Q4_K_M");
+        assert_eq!(variant_display_name("Llama-3.1-8B", "Q4_K_M", "sql-lora"), "Llama-3.1-8B Q4_K_M + sql-lora");
+    }
+
+    #[test]
+    fn test_parameter_count_billions() {
+        assert_eq!(parameter_count_billions("meta-llama/Llama-3.1-8B-Instruct"), Some(8.0));
+        assert_eq!(parameter_count_billions("Qwen/Qwen2.5-1.5B-Instruct"), Some(1.5));
+        assert_eq!(parameter_count_billions("Qwen/Qwen3-30B-A3B"), Some(30.0));
+        assert_eq!(parameter_count_billions("mistralai/Mixtral-8x7B-v0.1"), Some(56.0));
+        assert_eq!(parameter_count_billions("HuggingFaceTB/SmolLM-135M"), Some(0.135));
+        assert_eq!(parameter_count_billions("some-model-Q4_K_M"), None);
+    }
+
+    #[test]
+    fn test_estimate_memory_gb() {
+        // 8B at 16 bits is 16 GB of weights
+        let fp16 = estimate_memory_gb(8.0, "FP16").unwrap();
+        assert!((fp16 - 16.0 * MEMORY_OVERHEAD_FACTOR).abs() < 1e-9);
+
+        let q4 = estimate_memory_gb(8.0, "Q4_K_M-GGUF").unwrap();
+        assert!(q4 < fp16 / 3.0);
+
+        assert_eq!(estimate_memory_gb(8.0, "mystery"), None);
+    }
+
+    #[test]
+    fn test_select_baseline_prefers_highest_precision() {
+        let quants = ["Q4_K_M", "FP16", "Q8_0", "BF16"];
+        assert_eq!(select_baseline_quantization(quants), Some(("BF16", true)));
+
+        let quants = ["FP16", "FP32", "BF16"];
+        assert_eq!(select_baseline_quantization(quants), Some(("FP32", true)));
+
+        let quants = ["Q4_0", "F16-GGUF"];
+        assert_eq!(select_baseline_quantization(quants), Some(("F16-GGUF", true)));
+    }
+
+    #[test]
+    fn test_quantization_sort_key_order() {
+        let expected = [
+            "FP32", "BF16", "F16-GGUF", "FP16", "FP8_DYNAMIC", "FP8", "W8A16", "W4A16", "W8A8",
+            "W4A8", "Q8_0", "Q6_K", "Q5_1", "Q5_K_M", "Q5_K_S", "Q5_0", "Q4_1", "Q4_K_M",
+            "Q4_K_S", "Q4_0", "IQ4_NL", "Q3_K_L", "IQ4_XS", "Q3_K_M", "IQ3_M", "Q3_K_S", "Q2_K",
+            "INT8", "AWQ",
+        ];
+
+        let mut quants = expected;
+        quants.reverse();
+        quants.sort_by_key(|q| quantization_sort_key(q));
+        assert_eq!(quants, expected);
+
+        // The GGUF suffix doesn't change a scheme's place
+        assert!(quantization_sort_key("Q8_0-GGUF") < quantization_sort_key("Q6_K"));
+    }
+
+    #[test]
+    fn test_select_baseline_without_full_precision() {
+        let quants = ["Q4_K_M", "Q8_0", "W4A16", "FP8"];
+        assert_eq!(select_baseline_quantization(quants), Some(("FP8", false)));
+
+        let quants = ["Q4_K_M", "mystery", "Q8_0"];
+        assert_eq!(select_baseline_quantization(quants), Some(("Q8_0", false)));
+
+        assert_eq!(select_baseline_quantization(Vec::<&str>::new()), None);
+    }
+}
@@ -1,10 +1,30 @@
 // llm-benchmark-types/src/validation.rs
 
+use chrono::{Duration, Utc};
+
 use crate::{
-    ExperimentRun, HardwareConfig, PerformanceMetric, BenchmarkScore,
-    QualityScore, ValidationError, ValidationResult, metric_names,
+    Backend, ExperimentRun, HardwareConfig, PerformanceMetric, BenchmarkScore,
+    QualityScore, RunAnnotation, UploadCompleteRequest, ValidationError, ValidationResult, metric_names,
+    estimate_memory_gb, parameter_count_billions,
 };
 
+/// Run timestamps further ahead than this draw a warning (small clock skew
+/// is tolerated)
+pub const FUTURE_TIMESTAMP_WARNING_HOURS: i64 = 1;
+
+/// Run timestamps further ahead than this are rejected as a mis-set clock
+pub const FUTURE_TIMESTAMP_LIMIT_DAYS: i64 = 365;
+
+/// Benchmark score timestamps before the start of this year are rejected as
+/// a mis-set clock; no results here predate it
+pub const EARLIEST_SCORE_YEAR: i32 = 2020;
+
+/// How far measured memory may stray from the params+quantization estimate,
+/// as a factor in either direction, before it's flagged. Generous because
+/// backends that preallocate KV cache legitimately use several times the
+/// weight size.
+pub const MEMORY_ESTIMATE_TOLERANCE: f64 = 4.0;
+
 /// Validation trait for experiment data
 pub trait Validate {
     /// Validate the data and return any errors
@@ -46,13 +66,30 @@ impl Validate for ExperimentRun {
             });
         }
 
-        if !is_valid_backend(&self.backend) {
+        if self.backend_kind().is_none() {
             return Err(ValidationError::InvalidField {
                 field: "backend".to_string(),
                 message: format!("Unknown backend: {}", self.backend),
             });
         }
 
+        // A far-future timestamp would sort above every real run
+        if self.timestamp > Utc::now() + Duration::days(FUTURE_TIMESTAMP_LIMIT_DAYS) {
+            return Err(ValidationError::OutOfRange {
+                field: "timestamp".to_string(),
+                value: self.timestamp.to_rfc3339(),
+                range: format!("at most {} days in the future", FUTURE_TIMESTAMP_LIMIT_DAYS),
+            });
+        }
+
+        // Labels are filter keys, so reject blank ones rather than storing them
+        if self.label.as_deref().is_some_and(|label| label.trim().is_empty()) {
+            return Err(ValidationError::InvalidField {
+                field: "label".to_string(),
+                message: "Label must not be blank".to_string(),
+            });
+        }
+
         // Validate hardware config
         self.hardware_config.validate()?;
 
@@ -122,17 +159,101 @@ impl Validate for ExperimentRun {
                     }
                 }
                 metric_names::MEMORY_USAGE_GB => {
-                    if metric.value > 200.0 {
-                        warnings.push(format!(
-                            "Unusually high memory_usage_gb: {}", 
-                            metric.value
-                        ));
+                    let estimated = parameter_count_billions(&self.model_name)
+                        .and_then(|params_b| estimate_memory_gb(params_b, &self.quantization));
+                    match estimated {
+                        Some(estimated) if metric.value > estimated * MEMORY_ESTIMATE_TOLERANCE => {
+                            warnings.push(format!(
+                                "Unusually high memory_usage_gb: {} (expected about {:.1} for {} {})",
+                                metric.value, estimated, self.model_name, self.quantization
+                            ));
+                        }
+                        Some(estimated) if metric.value < estimated / MEMORY_ESTIMATE_TOLERANCE => {
+                            warnings.push(format!(
+                                "Unusually low memory_usage_gb: {} (expected about {:.1} for {} {})",
+                                metric.value, estimated, self.model_name, self.quantization
+                            ));
+                        }
+                        None if metric.value > 200.0 => {
+                            warnings.push(format!(
+                                "Unusually high memory_usage_gb: {}",
+                                metric.value
+                            ));
+                        }
+                        _ => {}
                     }
                 }
                 _ => {}
             }
         }
 
+        // Check for unrecognized (likely misspelled) metric context keys
+        for (i, metric) in self.performance_metrics.iter().enumerate() {
+            for warning in metric.validate_context().unwrap_or_default() {
+                warnings.push(format!("performance_metrics[{}]: {}", i, warning));
+            }
+        }
+
+        // Check for scores that look like they were reported on the wrong scale
+        for (i, score) in self.benchmark_scores.iter().enumerate() {
+            for warning in score.warnings() {
+                warnings.push(format!("benchmark_scores[{}]: {}", i, warning));
+            }
+        }
+
+        if self.timestamp > Utc::now() + Duration::hours(FUTURE_TIMESTAMP_WARNING_HOURS) {
+            warnings.push(format!(
+                "timestamp {} is in the future - is the benchmark machine's clock set correctly?",
+                self.timestamp.to_rfc3339()
+            ));
+        }
+
+        warnings
+    }
+}
+
+impl Validate for RunAnnotation {
+    fn validate(&self) -> ValidationResult<()> {
+        if self.canonical && self.deprecated {
+            return Err(ValidationError::InvalidField {
+                field: "deprecated".to_string(),
+                message: "A run can't be both canonical and deprecated".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for UploadCompleteRequest {
+    fn validate(&self) -> ValidationResult<()> {
+        self.experiment_run.validate()?;
+
+        for (i, score) in self.benchmark_scores.iter().enumerate() {
+            score.validate().map_err(|e| match e {
+                ValidationError::InvalidField { field, message } => ValidationError::InvalidField {
+                    field: format!("benchmark_scores[{}].{}", i, field),
+                    message,
+                },
+                ValidationError::MissingField { field } => ValidationError::MissingField {
+                    field: format!("benchmark_scores[{}].{}", i, field),
+                },
+                other => other,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut warnings = self.experiment_run.warnings();
+
+        for (i, score) in self.benchmark_scores.iter().enumerate() {
+            for warning in score.warnings() {
+                warnings.push(format!("benchmark_scores[{}]: {}", i, warning));
+            }
+        }
+
         warnings
     }
 }
@@ -155,6 +276,24 @@ impl Validate for HardwareConfig {
             });
         }
         
+        if self.gpu_count < 1 {
+            return Err(ValidationError::OutOfRange {
+                field: "gpu_count".to_string(),
+                value: self.gpu_count.to_string(),
+                range: ">= 1".to_string(),
+            });
+        }
+
+        if let Some(total_gpu_memory_gb) = self.total_gpu_memory_gb {
+            if total_gpu_memory_gb < 0 {
+                return Err(ValidationError::OutOfRange {
+                    field: "total_gpu_memory_gb".to_string(),
+                    value: total_gpu_memory_gb.to_string(),
+                    range: ">= 0".to_string(),
+                });
+            }
+        }
+
         // Check consistency: if GPU is "CPU Only", memory should be 0
         if (self.gpu_model == "CPU Only" || self.gpu_model == "N/A") && self.gpu_memory_gb > 0 {
             return Err(ValidationError::InvalidField {
@@ -203,6 +342,27 @@ impl Validate for HardwareConfig {
             }
         }
 
+        // Validate CPU topology (if provided)
+        if let Some(cpu_threads) = self.cpu_threads {
+            if cpu_threads < 1 {
+                return Err(ValidationError::OutOfRange {
+                    field: "cpu_threads".to_string(),
+                    value: cpu_threads.to_string(),
+                    range: ">= 1".to_string(),
+                });
+            }
+        }
+
+        if let Some(numa_nodes) = self.numa_nodes {
+            if numa_nodes < 1 {
+                return Err(ValidationError::OutOfRange {
+                    field: "numa_nodes".to_string(),
+                    value: numa_nodes.to_string(),
+                    range: ">= 1".to_string(),
+                });
+            }
+        }
+
         Ok(())
     }
 }
@@ -252,15 +412,122 @@ impl Validate for PerformanceMetric {
                     });
                 }
             }
+            metric_names::FIRST_TOKEN_LATENCY_MS | metric_names::GPU_VRAM_USED_GB
+                if !self.value.is_finite() || self.value < 0.0 =>
+            {
+                return Err(ValidationError::OutOfRange {
+                    field: "value".to_string(),
+                    value: self.value.to_string(),
+                    range: "finite, ≥ 0".to_string(),
+                });
+            }
+            metric_names::GPU_UTILIZATION_PERCENT if !(0.0..=100.0).contains(&self.value) => {
+                return Err(ValidationError::OutOfRange {
+                    field: "value".to_string(),
+                    value: self.value.to_string(),
+                    range: "0-100".to_string(),
+                });
+            }
             _ => {
                 // Allow any value for unknown metrics
             }
         }
 
+        self.validate_context()?;
+
         Ok(())
     }
 }
 
+/// JSON type a recognized metric context key must have (null is allowed too)
+#[derive(Debug, Clone, Copy)]
+enum ContextType {
+    Integer,
+    Bool,
+    String,
+    Numbers,
+}
+
+impl ContextType {
+    fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            ContextType::Integer => value.is_i64() || value.is_u64(),
+            ContextType::Bool => value.is_boolean(),
+            ContextType::String => value.is_string(),
+            ContextType::Numbers => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(serde_json::Value::is_number)),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ContextType::Integer => "an integer",
+            ContextType::Bool => "a boolean",
+            ContextType::String => "a string",
+            ContextType::Numbers => "an array of numbers",
+        }
+    }
+}
+
+/// Context keys of the speed metrics, as the uploader's llama-bench, Ollama
+/// and inference-server parsers write them
+const SPEED_CONTEXT_KEYS: &[(&str, ContextType)] = &[
+    ("n_prompt", ContextType::Integer),
+    ("n_gen", ContextType::Integer),
+    ("n_batch", ContextType::Integer),
+    ("n_ubatch", ContextType::Integer),
+    ("n_threads", ContextType::Integer),
+    ("n_gpu_layers", ContextType::Integer),
+    ("split_mode", ContextType::String),
+    ("tensor_split", ContextType::String),
+    ("n_depth", ContextType::Integer),
+    ("flash_attn", ContextType::Bool),
+    ("use_mmap", ContextType::Bool),
+    ("generations", ContextType::Integer),
+    ("total_input_tokens", ContextType::Integer),
+    ("total_output_tokens", ContextType::Integer),
+    (crate::SPEED_SAMPLES_CONTEXT_KEY, ContextType::Numbers),
+];
+
+impl PerformanceMetric {
+    /// Check the context of `tokens_per_second` and
+    /// `prompt_processing_speed`: it must be an object whose recognized keys
+    /// have the right JSON type. Unrecognized keys (often typos) are
+    /// returned as warnings. Other metrics' context is unrestricted.
+    pub fn validate_context(&self) -> ValidationResult<Vec<String>> {
+        let known_keys = match self.metric_name.as_str() {
+            metric_names::TOKENS_PER_SECOND | metric_names::PROMPT_PROCESSING_SPEED => SPEED_CONTEXT_KEYS,
+            _ => return Ok(Vec::new()),
+        };
+        let Some(context) = &self.context else {
+            return Ok(Vec::new());
+        };
+        let Some(context) = context.as_object() else {
+            return Err(ValidationError::InvalidField {
+                field: "context".to_string(),
+                message: "must be a JSON object".to_string(),
+            });
+        };
+
+        let mut warnings = Vec::new();
+        for (key, value) in context {
+            match known_keys.iter().find(|(known, _)| known == key) {
+                Some((_, expected)) if !value.is_null() && !expected.matches(value) => {
+                    return Err(ValidationError::InvalidField {
+                        field: format!("context.{}", key),
+                        message: format!("must be {}, got {}", expected.name(), value),
+                    });
+                }
+                Some(_) => {}
+                None => warnings.push(format!("unknown context key '{}' on {}", key, self.metric_name)),
+            }
+        }
+
+        Ok(warnings)
+    }
+}
+
 impl Validate for QualityScore {
     fn validate(&self) -> ValidationResult<()> {
         // Validate benchmark name
@@ -350,20 +617,21 @@ impl Validate for QualityScore {
 
 // Helper functions for validation
 
-/// Normalize a quantization string by stripping redundant suffixes like `-GGUF`.
+/// Normalize a quantization string to its canonical, uppercase form.
 /// The GGUF format is already implied by the backend (llama.cpp), so `-GGUF`
-/// suffixes on quant names like `Q8_0-GGUF` are redundant and cause mismatches.
+/// and `.gguf` suffixes on quant names like `Q8_0-GGUF` are redundant and
+/// cause mismatches, as does mixed case (`q4_k_m` vs `Q4_K_M`).
 pub fn normalize_quantization(quantization: &str) -> String {
-    let stripped = quantization
+    let upper = quantization.trim().to_uppercase();
+    let stripped = upper
         .strip_suffix("-GGUF")
-        .or_else(|| quantization.strip_suffix("-gguf"))
-        .unwrap_or(quantization);
+        .or_else(|| upper.strip_suffix(".GGUF"))
+        .unwrap_or(&upper);
     stripped.to_string()
 }
 
 fn is_valid_quantization(quantization: &str) -> bool {
-    let normalized = normalize_quantization(quantization);
-    let quant_upper = normalized.to_uppercase();
+    let quant_upper = normalize_quantization(quantization);
 
     // Check exact matches first
     let exact_match = matches!(
@@ -375,7 +643,8 @@ fn is_valid_quantization(quantization: &str) -> bool {
         "Q4_K_M" | "Q4_K_S" | "Q4_1" | "Q4_0" |
         "Q3_K_L" | "Q3_K_M" | "Q3_K_S" | "Q2_K" |
         // IQ (Integer Quantization) formats
-        "IQ4_XS" | "IQ4_NL" | "IQ3_M" |
+        "IQ1_S" | "IQ1_M" | "IQ2_XXS" | "IQ2_XS" | "IQ2_S" | "IQ2_M" |
+        "IQ3_XXS" | "IQ3_XS" | "IQ3_S" | "IQ3_M" | "IQ4_XS" | "IQ4_NL" |
         // Weight-Activation quantization formats (vLLM, TensorRT-LLM)
         "W8A8" | "W4A16" | "W4A8" | "W8A16" |
         // Other formats
@@ -391,29 +660,42 @@ fn is_valid_quantization(quantization: &str) -> bool {
         .or_else(|| quant_upper.strip_suffix("-AWQ"))
         .or_else(|| quant_upper.strip_suffix("-GPTQ"))
     {
-        // Check if the base part matches W*A* pattern
-        if base.starts_with('W') && base.contains("A") {
-            return true;
-        }
+        return is_weight_activation_scheme(base);
     }
 
     false
 }
 
-fn is_valid_backend(backend: &str) -> bool {
-    matches!(
-        backend.to_lowercase().as_str(),
-        "llama.cpp" | "vllm" | "transformers" | "tgi" | "text-generation-inference" |
-        "ctransformers" | "ggml" | "llamacpp" | "exllama" | "exllamav2" | "tensorrt-llm"
-    )
+/// Whether `quantization` is a bare W<bits>A<bits> scheme such as `W4A16`
+fn is_weight_activation_scheme(quantization: &str) -> bool {
+    let Some((weight_bits, activation_bits)) = quantization
+        .strip_prefix('W')
+        .and_then(|rest| rest.split_once('A'))
+    else {
+        return false;
+    };
+
+    [weight_bits, activation_bits]
+        .iter()
+        .all(|bits| !bits.is_empty() && bits.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Canonical name of a known [`Backend`] (e.g. `text-generation-inference`
+/// to `tgi`, `llamacpp` to `llama.cpp`); unknown names are just lowercased
+pub fn normalize_backend(backend: &str) -> String {
+    match backend.parse::<Backend>() {
+        Ok(backend) => backend.to_string(),
+        Err(_) => backend.trim().to_lowercase(),
+    }
 }
 
 fn is_valid_cpu_arch(cpu_arch: &str) -> bool {
     matches!(
         cpu_arch.to_lowercase().as_str(),
         "x86_64" | "x64" | "amd64" | "arm64" | "aarch64" | "armv7" | "armv8" |
-        "zen2" | "zen3" | "zen4" | "haswell" | "skylake" | "icelake" | "alderlake" |
-        "apple_m1" | "apple_m2" | "apple_m3" | "generic" | "unknown"
+        "zen1" | "zen2" | "zen3" | "zen4" | "zen5" | "haswell" | "skylake" | "icelake" |
+        "alderlake" | "raptorlake" | "intel" |
+        "apple_m1" | "apple_m2" | "apple_m3" | "apple_m4" | "generic" | "unknown"
     )
 }
 
@@ -421,9 +703,28 @@ fn is_valid_cpu_arch(cpu_arch: &str) -> bool {
 mod tests {
     use super::*;
     use crate::{HardwareConfig, PerformanceMetric};
-    use chrono::Utc;
     use uuid::Uuid;
 
+    #[test]
+    fn test_detected_cpu_arches_are_valid() {
+        for cpu in [
+            "AMD Ryzen Threadripper 1950X",
+            "AMD Ryzen 9 3950X",
+            "AMD Ryzen 9 5950X",
+            "AMD Ryzen 9 7950X",
+            "AMD Ryzen 9 9950X",
+            "AMD EPYC 9654",
+            "12th Gen Intel(R) Core(TM) i9-12900K",
+            "Intel(R) Core(TM) i9-14900K",
+            "Intel(R) Xeon(R) Gold 6338",
+            "Apple M4 Max",
+            "Unknown CPU",
+        ] {
+            let arch = HardwareConfig::detect_cpu_arch(cpu);
+            assert!(is_valid_cpu_arch(arch), "{} -> {}", cpu, arch);
+        }
+    }
+
     #[test]
     fn test_valid_quantization() {
         // Test floating point formats
@@ -447,6 +748,21 @@ mod tests {
         assert!(is_valid_quantization("IQ4_XS"));
         assert!(is_valid_quantization("IQ4_NL"));
         assert!(is_valid_quantization("IQ3_M"));
+        assert!(is_valid_quantization("IQ1_S"));
+        assert!(is_valid_quantization("IQ2_XXS"));
+        assert!(is_valid_quantization("IQ3_XS"));
+
+        // Test vLLM / llmcompressor formats
+        assert!(is_valid_quantization("FP8"));
+        assert!(is_valid_quantization("fp8_dynamic"));
+        assert!(is_valid_quantization("W4A16"));
+        assert!(is_valid_quantization("W8A8"));
+        assert!(is_valid_quantization("W8A16"));
+        assert!(is_valid_quantization("AWQ"));
+        assert!(is_valid_quantization("GPTQ"));
+        assert!(is_valid_quantization("W4A16-CT"));
+        assert!(is_valid_quantization("w4a16-awq"));
+        assert!(is_valid_quantization("W8A8-GPTQ"));
         
         // Test case insensitivity
         assert!(is_valid_quantization("q4_k_m"));
@@ -461,23 +777,63 @@ mod tests {
         // Test invalid formats
         assert!(!is_valid_quantization("INVALID"));
         assert!(!is_valid_quantization("Q3_K_XL"));
+        assert!(!is_valid_quantization(""));
+        assert!(!is_valid_quantization("   "));
+        assert!(!is_valid_quantization("IQ5_XS"));
+        assert!(!is_valid_quantization("WHAT-CT"));
+        assert!(!is_valid_quantization("W4A-AWQ"));
+        assert!(!is_valid_quantization("-GGUF"));
     }
 
     #[test]
     fn test_normalize_quantization() {
-        assert_eq!(normalize_quantization("Q8_0-GGUF"), "Q8_0");
-        assert_eq!(normalize_quantization("Q4_K_M-GGUF"), "Q4_K_M");
-        assert_eq!(normalize_quantization("FP16-gguf"), "FP16");
-        assert_eq!(normalize_quantization("Q8_0"), "Q8_0");
-        assert_eq!(normalize_quantization("BF16"), "BF16");
-        assert_eq!(normalize_quantization("W4A16-AWQ"), "W4A16-AWQ");
+        let cases = [
+            ("Q8_0-GGUF", "Q8_0"),
+            ("Q4_K_M-GGUF", "Q4_K_M"),
+            ("FP16-gguf", "FP16"),
+            ("Q8_0", "Q8_0"),
+            ("BF16", "BF16"),
+            ("W4A16-AWQ", "W4A16-AWQ"),
+            ("q4_k_m", "Q4_K_M"),
+            ("q4_k_m-gguf", "Q4_K_M"),
+            ("Q5_K_S.gguf", "Q5_K_S"),
+            ("fp16", "FP16"),
+            ("f16", "F16"),
+            ("bf16", "BF16"),
+            ("  Q6_K  ", "Q6_K"),
+            ("iq4_xs", "IQ4_XS"),
+            ("mxfp4", "MXFP4"),
+            ("awq", "AWQ"),
+            ("gptq", "GPTQ"),
+            ("w4a16-gptq", "W4A16-GPTQ"),
+            ("W4A16-CT", "W4A16-CT"),
+            ("fp8_dynamic", "FP8_DYNAMIC"),
+            ("GGUF", "GGUF"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(normalize_quantization(input), expected, "input: {:?}", input);
+        }
     }
 
     #[test]
     fn test_valid_backend() {
+        let is_valid_backend = |backend: &str| backend.parse::<Backend>().is_ok();
         assert!(is_valid_backend("llama.cpp"));
         assert!(is_valid_backend("VLLM")); // case insensitive
+        assert!(is_valid_backend("sglang"));
+        assert!(is_valid_backend("tgi"));
+        assert!(is_valid_backend("text-generation-inference"));
+        assert!(is_valid_backend("mlc-llm"));
+        assert!(is_valid_backend("exllamav2"));
+        assert!(is_valid_backend("Ollama"));
+        assert!(is_valid_backend("llamacpp"));
         assert!(!is_valid_backend("unknown_backend"));
+        assert!(!is_valid_backend(""));
+
+        assert_eq!(normalize_backend("Text-Generation-Inference"), "tgi");
+        assert_eq!(normalize_backend("llamacpp"), "llama.cpp");
+        assert_eq!(normalize_backend(" SGLang "), "sglang");
     }
 
     #[test]
@@ -501,15 +857,92 @@ mod tests {
         assert!(invalid_metric.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_context() {
+        let metric = |name: &str, context: serde_json::Value| PerformanceMetric {
+            metric_name: name.to_string(),
+            value: 50.0,
+            unit: "tok/s".to_string(),
+            timestamp: Utc::now(),
+            context: Some(context),
+        };
+
+        let valid = metric(
+            metric_names::TOKENS_PER_SECOND,
+            serde_json::json!({ "n_batch": 2048, "flash_attn": true, "split_mode": "layer", "tensor_split": "0.60/0.40", "n_depth": 4096, "speed_samples": [49.5, 50.5] }),
+        );
+        assert_eq!(valid.validate_context().unwrap(), Vec::<String>::new());
+        assert!(valid.validate().is_ok());
+
+        // Null stands in for an absent value
+        let null = metric(metric_names::TOKENS_PER_SECOND, serde_json::json!({ "speed_samples": null }));
+        assert!(null.validate_context().unwrap().is_empty());
+
+        let typo = metric(metric_names::PROMPT_PROCESSING_SPEED, serde_json::json!({ "n_gpu_layer": 99 }));
+        assert_eq!(
+            typo.validate_context().unwrap(),
+            vec!["unknown context key 'n_gpu_layer' on prompt_processing_speed"]
+        );
+        assert!(typo.validate().is_ok());
+
+        let wrong_type = metric(metric_names::TOKENS_PER_SECOND, serde_json::json!({ "flash_attn": "yes" }));
+        assert!(matches!(
+            wrong_type.validate(),
+            Err(ValidationError::InvalidField { field, .. }) if field == "context.flash_attn"
+        ));
+        assert!(metric(metric_names::TOKENS_PER_SECOND, serde_json::json!({ "n_batch": 1.5 })).validate().is_err());
+        assert!(metric(metric_names::TOKENS_PER_SECOND, serde_json::json!([1, 2])).validate().is_err());
+
+        // Other metrics' context isn't checked
+        let other = metric("requests_per_second", serde_json::json!({ "n_batch": "any", "anything": 1 }));
+        assert!(other.validate_context().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_first_token_latency_validation() {
+        let ttft = |value: f64| PerformanceMetric::new(
+            metric_names::FIRST_TOKEN_LATENCY_MS.to_string(),
+            value,
+            "ms".to_string(),
+        );
+        assert!(ttft(0.0).validate().is_ok());
+        assert!(ttft(182.5).validate().is_ok());
+        assert!(ttft(-1.0).validate().is_err());
+        assert!(ttft(f64::NAN).validate().is_err());
+        assert!(ttft(f64::INFINITY).validate().is_err());
+    }
+
+    #[test]
+    fn test_cpu_topology_validation() {
+        let config = HardwareConfig::cpu_only("AMD EPYC 7763".to_string(), "Zen3".to_string(), Some(256), None)
+            .with_cpu_threads(64);
+        assert!(config.validate().is_ok());
+
+        let config = config.with_cpu_threads(0);
+        assert!(config.validate().is_err());
+
+        let mut config = HardwareConfig::cpu_only("AMD EPYC 7763".to_string(), "Zen3".to_string(), Some(256), None);
+        config.numa_nodes = Some(0);
+        assert!(config.validate().is_err());
+
+        let config = HardwareConfig::new("A100".to_string(), 80, "AMD EPYC 7763".to_string(), "Zen3".to_string(), None, None);
+        assert!(config.clone().with_gpu_count(4).validate().is_ok());
+        assert!(config.with_gpu_count(0).validate().is_err());
+    }
+
     #[test]
     fn test_experiment_run_warnings() {
         let hardware_config = HardwareConfig {
             gpu_model: "RTX 4090".to_string(),
             gpu_memory_gb: 24,
+            gpu_count: 1,
+            total_gpu_memory_gb: None,
             cpu_model: "Intel i9".to_string(),
             cpu_arch: "x86_64".to_string(),
             ram_gb: Some(32),
             ram_type: Some("DDR4".to_string()),
+            cpu_threads: None,
+            numa_nodes: None,
             virtualization_type: None,
             optimizations: vec![],
         };
@@ -543,4 +976,120 @@ mod tests {
         let warnings_after = experiment.warnings();
         assert!(warnings_after.len() < warnings.len()); // Should have fewer warnings now
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_memory_warning_uses_estimate() {
+        let memory_warnings = |model: &str, quantization: &str, memory_gb: f64| {
+            let hardware_config = HardwareConfig::new(
+                "H100".to_string(),
+                80,
+                "EPYC".to_string(),
+                "x86_64".to_string(),
+                None,
+                None,
+            );
+            let mut experiment = ExperimentRun::new(
+                Uuid::new_v4(),
+                model.to_string(),
+                quantization.to_string(),
+                "vllm".to_string(),
+                "1.0".to_string(),
+                hardware_config,
+            );
+            experiment.add_performance_metric(PerformanceMetric::new(
+                metric_names::MEMORY_USAGE_GB.to_string(),
+                memory_gb,
+                "GB".to_string(),
+            ));
+            experiment
+                .warnings()
+                .into_iter()
+                .filter(|w| w.contains("memory_usage_gb"))
+                .collect::<Vec<_>>()
+        };
+
+        // 70B FP16 at ~140 GB is legitimate
+        assert!(memory_warnings("Llama-3.1-70B", "FP16", 140.0).is_empty());
+        // A 7B Q4 can't need 60 GB, or run in half a GB
+        assert!(memory_warnings("Mistral-7B", "Q4_K_M", 60.0)[0].contains("Unusually high"));
+        assert!(memory_warnings("Mistral-7B", "Q4_K_M", 0.5)[0].contains("Unusually low"));
+        assert!(memory_warnings("Mistral-7B", "Q4_K_M", 5.0).is_empty());
+        // Without a parameter count only the flat ceiling applies
+        assert!(memory_warnings("Mystery Model", "FP16", 150.0).is_empty());
+        assert_eq!(memory_warnings("Mystery Model", "FP16", 250.0).len(), 1);
+    }
+
+    #[test]
+    fn test_future_timestamp() {
+        let hardware_config = HardwareConfig::new(
+            "RTX 4090".to_string(),
+            24,
+            "Intel i9".to_string(),
+            "x86_64".to_string(),
+            Some(32),
+            None,
+        );
+        let mut experiment = ExperimentRun::new(
+            Uuid::new_v4(),
+            "Test Model".to_string(),
+            "FP16".to_string(),
+            "llama.cpp".to_string(),
+            "1.0".to_string(),
+            hardware_config,
+        );
+        let future_warning = |experiment: &ExperimentRun| {
+            experiment.warnings().iter().any(|w| w.contains("in the future"))
+        };
+
+        // Small skew is tolerated
+        experiment.timestamp = Utc::now() + Duration::minutes(50);
+        assert!(experiment.validate().is_ok());
+        assert!(!future_warning(&experiment));
+
+        // Beyond an hour ahead warns but still validates
+        experiment.timestamp = Utc::now() + Duration::minutes(70);
+        assert!(experiment.validate().is_ok());
+        assert!(future_warning(&experiment));
+
+        experiment.timestamp = Utc::now() + Duration::days(364);
+        assert!(experiment.validate().is_ok());
+        assert!(future_warning(&experiment));
+
+        // Beyond a year ahead is rejected
+        experiment.timestamp = Utc::now() + Duration::days(366);
+        assert!(matches!(
+            experiment.validate(),
+            Err(ValidationError::OutOfRange { field, .. }) if field == "timestamp"
+        ));
+    }
+
+    #[test]
+    fn test_run_annotation_validation() {
+        let canonical = RunAnnotation {
+            canonical: true,
+            ..Default::default()
+        };
+        assert!(canonical.validate().is_ok());
+        assert!(!canonical.is_empty());
+        assert!(RunAnnotation::default().is_empty());
+
+        let both = RunAnnotation {
+            canonical: true,
+            deprecated: true,
+            reason: Some("conflicting".to_string()),
+        };
+        assert!(matches!(
+            both.validate(),
+            Err(ValidationError::InvalidField { field, .. }) if field == "deprecated"
+        ));
+    }
+
+    #[test]
+    fn test_gpu_stats_metric_ranges() {
+        assert!(PerformanceMetric::gpu_utilization_percent(87.5).validate().is_ok());
+        assert!(PerformanceMetric::gpu_utilization_percent(120.0).validate().is_err());
+        assert!(PerformanceMetric::gpu_utilization_percent(-1.0).validate().is_err());
+        assert!(PerformanceMetric::gpu_vram_used_gb(21.3).validate().is_ok());
+        assert!(PerformanceMetric::gpu_vram_used_gb(-0.5).validate().is_err());
+    }
+}
@@ -0,0 +1,172 @@
+// llm-benchmark-types/src/weighting.rs
+// Benchmark weighting profiles for use-case specific composite scores
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Named benchmark weighting for a use case.
+///
+/// Default weights per profile:
+///
+/// | profile     | mmlu | gsm8k | humaneval | hellaswag | truthfulqa |
+/// |-------------|------|-------|-----------|-----------|------------|
+/// | `coding`    | 0.2  | 0.2   | 0.6       | –         | –          |
+/// | `reasoning` | 0.35 | 0.35  | 0.15      | 0.15      | –          |
+/// | `chat`      | 0.3  | –     | –         | 0.3       | 0.4        |
+/// | `general`   | 0.2  | 0.2   | 0.2       | 0.2       | 0.2        |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightingProfile {
+    Coding,
+    Reasoning,
+    Chat,
+    General,
+}
+
+impl WeightingProfile {
+    /// Benchmark → weight pairs for this profile
+    pub fn default_weights(self) -> &'static [(&'static str, f64)] {
+        match self {
+            WeightingProfile::Coding => &[("mmlu", 0.2), ("gsm8k", 0.2), ("humaneval", 0.6)],
+            WeightingProfile::Reasoning => &[
+                ("mmlu", 0.35),
+                ("gsm8k", 0.35),
+                ("humaneval", 0.15),
+                ("hellaswag", 0.15),
+            ],
+            WeightingProfile::Chat => &[("mmlu", 0.3), ("hellaswag", 0.3), ("truthfulqa", 0.4)],
+            WeightingProfile::General => &[
+                ("mmlu", 0.2),
+                ("gsm8k", 0.2),
+                ("humaneval", 0.2),
+                ("hellaswag", 0.2),
+                ("truthfulqa", 0.2),
+            ],
+        }
+    }
+}
+
+impl FromStr for WeightingProfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coding" => Ok(WeightingProfile::Coding),
+            "reasoning" => Ok(WeightingProfile::Reasoning),
+            "chat" => Ok(WeightingProfile::Chat),
+            "general" => Ok(WeightingProfile::General),
+            _ => Err(format!(
+                "Unknown profile '{}' (expected coding, reasoning, chat or general)",
+                s
+            )),
+        }
+    }
+}
+
+/// Benchmark weights used to combine per-benchmark scores into one
+/// quality score
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkWeights(pub BTreeMap<String, f64>);
+
+impl From<WeightingProfile> for BenchmarkWeights {
+    fn from(profile: WeightingProfile) -> Self {
+        Self(
+            profile
+                .default_weights()
+                .iter()
+                .map(|(benchmark, weight)| (benchmark.to_string(), *weight))
+                .collect(),
+        )
+    }
+}
+
+impl BenchmarkWeights {
+    /// Build weights from the `profile` and `weights` query parameters.
+    ///
+    /// `weights` is a comma-separated list of `benchmark:weight` pairs that
+    /// override (or, without a profile, make up) the profile's weights, e.g.
+    /// `?profile=coding&weights=truthfulqa:0.1`. Returns `None` when neither
+    /// parameter is given.
+    pub fn from_params(profile: Option<&str>, weights: Option<&str>) -> Result<Option<Self>, String> {
+        if profile.is_none() && weights.is_none() {
+            return Ok(None);
+        }
+
+        let mut result = match profile {
+            Some(profile) => Self::from(profile.parse::<WeightingProfile>()?),
+            None => Self::default(),
+        };
+
+        for pair in weights.unwrap_or("").split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (benchmark, weight) = pair
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid weight '{}' (expected benchmark:weight)", pair))?;
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid weight '{}' for benchmark '{}'", weight, benchmark))?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!("Weight for benchmark '{}' must be a non-negative number", benchmark));
+            }
+            result.0.insert(benchmark.trim().to_lowercase(), weight);
+        }
+
+        if result.0.values().all(|weight| *weight == 0.0) {
+            return Err("At least one benchmark weight must be positive".to_string());
+        }
+
+        Ok(Some(result))
+    }
+
+    /// Weighted mean of the given `(benchmark, score)` pairs.
+    ///
+    /// Benchmarks without a score are left out and the remaining weights
+    /// renormalized, so a model isn't penalized for a missing result. Returns
+    /// `None` if no weighted benchmark has a score.
+    pub fn weighted_score<'a>(&self, scores: impl IntoIterator<Item = (&'a str, f64)>) -> Option<f64> {
+        let (total, weight_sum) = scores
+            .into_iter()
+            .filter_map(|(benchmark, score)| {
+                self.0
+                    .get(benchmark)
+                    .filter(|weight| **weight > 0.0)
+                    .map(|weight| (score * weight, *weight))
+            })
+            .fold((0.0, 0.0), |(total, sum), (weighted, weight)| (total + weighted, sum + weight));
+
+        (weight_sum > 0.0).then(|| total / weight_sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_score_profiles_and_overrides() {
+        let weights = BenchmarkWeights::from_params(Some("coding"), None).unwrap().unwrap();
+        let score = weights
+            .weighted_score([("mmlu", 50.0), ("gsm8k", 50.0), ("humaneval", 80.0), ("hellaswag", 10.0)])
+            .unwrap();
+        assert!((score - 68.0).abs() < 1e-9);
+
+        // Missing benchmarks renormalize over the ones present
+        let score = weights.weighted_score([("humaneval", 80.0)]).unwrap();
+        assert!((score - 80.0).abs() < 1e-9);
+        assert_eq!(weights.weighted_score([("truthfulqa", 90.0)]), None);
+
+        let custom = BenchmarkWeights::from_params(Some("coding"), Some("humaneval:0, truthfulqa:1"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(custom.0.get("humaneval"), Some(&0.0));
+        assert_eq!(custom.0.get("truthfulqa"), Some(&1.0));
+
+        assert_eq!(BenchmarkWeights::from_params(None, None).unwrap(), None);
+        assert!(BenchmarkWeights::from_params(Some("poetry"), None).is_err());
+        assert!(BenchmarkWeights::from_params(None, Some("mmlu")).is_err());
+        assert!(BenchmarkWeights::from_params(None, Some("mmlu:-1")).is_err());
+        assert!(BenchmarkWeights::from_params(None, Some("mmlu:0")).is_err());
+    }
+}
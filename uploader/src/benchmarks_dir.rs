@@ -0,0 +1,125 @@
+// uploader/src/benchmarks_dir.rs
+// Read benchmark scores from a directory of per-benchmark JSON files
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tokio::task::JoinSet;
+
+use llm_benchmark_types::{BenchmarkScoreType, LenientBenchmarkScore};
+
+use crate::error::{parse_json, UploaderError};
+
+/// Contents of one score file: a single score or a list of them, each in
+/// any form `LenientBenchmarkScore` accepts
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScoreFile {
+    Many(Vec<LenientBenchmarkScore>),
+    One(LenientBenchmarkScore),
+}
+
+/// Scores read from a directory, with the files that couldn't be read
+#[derive(Debug, Default)]
+pub struct DirScores {
+    /// Scores of every file that parsed, in file name order
+    pub scores: Vec<BenchmarkScoreType>,
+    /// Files that parsed, with how many scores each held
+    pub files: Vec<(PathBuf, usize)>,
+    /// Files that couldn't be read or parsed, with the reason
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Read every `*.json` file directly in `dir` concurrently. A file that
+/// can't be read or parsed is recorded in `failed` rather than failing the
+/// whole directory.
+pub async fn read_scores(dir: &Path) -> Result<DirScores, UploaderError> {
+    let io_error = |source| UploaderError::Io { path: dir.to_path_buf(), source };
+
+    let mut paths = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(io_error)?;
+    while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "json") && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut reads = JoinSet::new();
+    for (index, path) in paths.iter().cloned().enumerate() {
+        reads.spawn(async move { (index, read_score_file(&path).await) });
+    }
+
+    let mut results = Vec::with_capacity(paths.len());
+    while let Some(result) = reads.join_next().await {
+        results.push(result.expect("score file task panicked"));
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut dir_scores = DirScores::default();
+    for ((_, result), path) in results.into_iter().zip(paths) {
+        match result {
+            Ok(scores) => {
+                dir_scores.files.push((path, scores.len()));
+                dir_scores.scores.extend(scores);
+            }
+            Err(e) => dir_scores.failed.push((path, format!("{:#}", anyhow::Error::new(e)))),
+        }
+    }
+
+    Ok(dir_scores)
+}
+
+async fn read_score_file(path: &Path) -> Result<Vec<BenchmarkScoreType>, UploaderError> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| UploaderError::Io { path: path.to_path_buf(), source })?;
+    let scores = match parse_json::<ScoreFile>(&content, path)? {
+        ScoreFile::Many(scores) => scores,
+        ScoreFile::One(score) => vec![score],
+    };
+    Ok(scores.into_iter().map(BenchmarkScoreType::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_benchmark_types::BenchmarkScore;
+
+    #[tokio::test]
+    async fn test_read_scores() {
+        let dir = std::env::temp_dir().join(format!("llm-perf-benchmarks-dir-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let timestamp = "2026-10-18T00:00:00Z";
+        std::fs::write(
+            dir.join("a_gsm8k.json"),
+            format!(r#"{{"problems_solved": 80, "total_problems": 100, "timestamp": "{}"}}"#, timestamp),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b_both.json"),
+            format!(
+                r#"[{{"pass_at_1": 45.0, "total_problems": 164, "timestamp": "{0}"}},
+                   {{"benchmark_name": "arc", "score": 60.0, "timestamp": "{0}"}}]"#,
+                timestamp
+            ),
+        )
+        .unwrap();
+        std::fs::write(dir.join("c_broken.json"), "{ not json").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let read = read_scores(&dir).await.unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = read.scores.iter().map(|score| score.benchmark_name()).collect();
+        assert_eq!(names, vec!["gsm8k", "humaneval", "arc"]);
+        assert_eq!(
+            read.files,
+            vec![(dir.join("a_gsm8k.json"), 1), (dir.join("b_both.json"), 2)]
+        );
+        assert_eq!(read.failed.len(), 1);
+        assert_eq!(read.failed[0].0, dir.join("c_broken.json"));
+        assert!(read.failed[0].1.starts_with("Invalid JSON in"));
+    }
+}
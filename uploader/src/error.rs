@@ -0,0 +1,115 @@
+// uploader/src/error.rs
+// Categorized uploader failures, each with its own process exit code
+
+use std::path::{Path, PathBuf};
+
+use llm_benchmark_types::ErrorResponse;
+use serde::de::DeserializeOwned;
+
+/// Uploader failure categories scripts can tell apart by exit code
+#[derive(Debug, thiserror::Error)]
+pub enum UploaderError {
+    #[error("Failed to read {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid JSON in {}", path.display())]
+    Json {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("Missing required field: {0}")]
+    MissingField(String),
+
+    #[error("Unrecognized format: {0}")]
+    UnrecognizedFormat(String),
+
+    #[error("Upload failed: {0}")]
+    Upload(String),
+
+    #[error("Validation failed: {0}")]
+    Validation(String),
+
+    #[error("Server reported {} warning(s): {}", .0.len(), .0.join("; "))]
+    Warnings(Vec<String>),
+
+    #[error("An identical run is already stored as test run {0}; pass --overwrite to replace it")]
+    Duplicate(uuid::Uuid),
+}
+
+impl UploaderError {
+    /// Process exit code for this failure (1 is left for uncategorized
+    /// errors and 2 for clap's usage errors)
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            UploaderError::Io { .. } => 3,
+            UploaderError::Json { .. } => 4,
+            UploaderError::MissingField(_) => 5,
+            UploaderError::UnrecognizedFormat(_) => 6,
+            UploaderError::Upload(_) => 7,
+            UploaderError::Validation(_) => 8,
+            UploaderError::Warnings(_) => 9,
+            UploaderError::Duplicate(_) => 10,
+        }
+    }
+}
+
+impl From<reqwest::Error> for UploaderError {
+    fn from(e: reqwest::Error) -> Self {
+        UploaderError::Upload(e.to_string())
+    }
+}
+
+/// Error for a non-2xx API response, taking the message from its
+/// `ErrorResponse` body. Rejections the server reports as 400 are
+/// validation failures.
+pub async fn response_error(response: reqwest::Response) -> UploaderError {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<ErrorResponse>(&body)
+        .map(|e| e.error)
+        .unwrap_or(body);
+
+    if status == reqwest::StatusCode::BAD_REQUEST {
+        UploaderError::Validation(message)
+    } else {
+        UploaderError::Upload(format!("HTTP {}: {}", status, message))
+    }
+}
+
+/// Exit code for an error returned from a subcommand. Errors that didn't
+/// start as an `UploaderError` are categorized by their root cause.
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    if let Some(e) = error.downcast_ref::<UploaderError>() {
+        e.exit_code()
+    } else if error.downcast_ref::<std::io::Error>().is_some() {
+        3
+    } else if error.downcast_ref::<serde_json::Error>().is_some() {
+        4
+    } else if error.downcast_ref::<reqwest::Error>().is_some() {
+        7
+    } else {
+        1
+    }
+}
+
+/// Read a file to a string
+pub fn read_file(path: &Path) -> Result<String, UploaderError> {
+    std::fs::read_to_string(path).map_err(|source| UploaderError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Parse JSON read from `path`
+pub fn parse_json<T: DeserializeOwned>(content: &str, path: &Path) -> Result<T, UploaderError> {
+    serde_json::from_str(content).map_err(|source| UploaderError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
@@ -0,0 +1,146 @@
+// uploader/src/gpu_info.rs
+// GPU model and memory from llama-bench's gpu_info string
+
+/// VRAM of known cards in GB, most specific entries first. Every token in
+/// an entry must appear in the lowercased GPU name.
+const GPU_MEMORY_GB: &[(&[&str], i32)] = &[
+    // NVIDIA consumer
+    (&["rtx 5090"], 32),
+    (&["rtx 5080"], 16),
+    (&["rtx 5070 ti"], 16),
+    (&["rtx 5070"], 12),
+    (&["rtx 5060 ti", "16"], 16),
+    (&["rtx 5060 ti"], 8),
+    (&["rtx 5060"], 8),
+    (&["rtx 4090"], 24),
+    (&["rtx 4080"], 16),
+    (&["rtx 4070 ti super"], 16),
+    (&["rtx 4070 ti"], 12),
+    (&["rtx 4070"], 12),
+    (&["rtx 4060 ti", "16"], 16),
+    (&["rtx 4060 ti"], 8),
+    (&["rtx 4060"], 8),
+    (&["rtx 3090"], 24),
+    (&["rtx 3080 ti"], 12),
+    (&["rtx 3080"], 10),
+    (&["rtx 3070"], 8),
+    (&["rtx 3060 ti"], 8),
+    (&["rtx 3060"], 12),
+    // NVIDIA workstation and datacenter
+    (&["rtx pro 6000"], 96),
+    (&["rtx 6000 ada"], 48),
+    (&["rtx a6000"], 48),
+    (&["rtx a5000"], 24),
+    (&["rtx a4000"], 16),
+    (&["b200"], 192),
+    (&["h200"], 141),
+    (&["h100"], 80),
+    (&["a100", "80"], 80),
+    (&["a100"], 40),
+    (&["a10g"], 24),
+    (&["a10"], 24),
+    (&["l40"], 48),
+    (&["l4"], 24),
+    (&["v100", "32"], 32),
+    (&["v100"], 16),
+    (&["tesla t4"], 16),
+    // AMD
+    (&["mi300x"], 192),
+    (&["mi250"], 128),
+    (&["9070"], 16),
+    (&["7900 xtx"], 24),
+    (&["7900 xt"], 20),
+    (&["7900 gre"], 16),
+    (&["7900"], 24),
+    (&["7800 xt"], 16),
+    (&["7700 xt"], 12),
+    (&["7600 xt"], 16),
+    (&["7600"], 8),
+    // Intel
+    (&["arc b580"], 12),
+    (&["arc a770"], 16),
+    (&["arc a750"], 8),
+];
+
+/// GPU memory in GB and a cleaned-up model name from a llama-bench
+/// `gpu_info` string. A memory size in the string itself (e.g.
+/// "NVIDIA GeForce RTX 4090 (24 GiB)") wins over the lookup table; memory
+/// is 0 when neither knows the card.
+pub fn parse_gpu_info(gpu_info: &str) -> (i32, String) {
+    // Handle CPU-only systems
+    if gpu_info.trim().is_empty() {
+        return (0, "CPU Only".to_string());
+    }
+
+    let (name, hinted_gb) = match split_memory_hint(gpu_info) {
+        Some((name, gb)) => (name, Some(gb)),
+        None => (gpu_info.trim(), None),
+    };
+
+    // Clean up the GPU name - remove common prefixes
+    let clean_name = name
+        .replace("NVIDIA GeForce ", "")
+        .replace("NVIDIA ", "")
+        .replace("AMD Radeon ", "")
+        .replace("Intel Arc ", "Arc ")
+        .trim()
+        .to_string();
+
+    let memory_gb = hinted_gb.or_else(|| lookup_memory_gb(name)).unwrap_or(0);
+
+    (memory_gb, clean_name)
+}
+
+/// Split a trailing memory size ("24 GiB", "(16GB)", "24576 MiB") off a
+/// GPU name, returning the name and the size in GB
+fn split_memory_hint(gpu_info: &str) -> Option<(&str, i32)> {
+    let trimmed = gpu_info.trim().trim_end_matches(')').trim_end();
+    let lower = trimmed.to_lowercase();
+
+    let (unit, mib_per_unit) = [("gib", 1024.0), ("gb", 1024.0), ("mib", 1.0), ("mb", 1.0)]
+        .into_iter()
+        .find(|(unit, _)| lower.ends_with(unit))?;
+    let number_part = trimmed[..trimmed.len() - unit.len()].trim_end();
+    let number_start = number_part
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map_or(0, |i| i + 1);
+    let mib = number_part[number_start..].parse::<f64>().ok()? * mib_per_unit;
+    if mib <= 0.0 {
+        return None;
+    }
+
+    let name = number_part[..number_start].trim_end().trim_end_matches(['(', ',', '-']).trim_end();
+    Some((name, (mib / 1024.0).round() as i32))
+}
+
+/// VRAM of a known card, from `GPU_MEMORY_GB`
+fn lookup_memory_gb(name: &str) -> Option<i32> {
+    let lower = name.to_lowercase();
+    GPU_MEMORY_GB
+        .iter()
+        .find(|(tokens, _)| tokens.iter().all(|token| lower.contains(token)))
+        .map(|(_, gb)| *gb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpu_info() {
+        // A memory hint in the string wins, in any of the usual spellings
+        assert_eq!(parse_gpu_info("NVIDIA GeForce RTX 4090 (24 GiB)"), (24, "RTX 4090".to_string()));
+        assert_eq!(parse_gpu_info("NVIDIA GeForce RTX 4060 Ti 16GB"), (16, "RTX 4060 Ti".to_string()));
+        assert_eq!(parse_gpu_info("AMD Radeon RX 7800 XT, 16368 MiB"), (16, "RX 7800 XT".to_string()));
+
+        // Otherwise the table, most specific entry first
+        assert_eq!(parse_gpu_info("NVIDIA GeForce RTX 4060 Ti"), (8, "RTX 4060 Ti".to_string()));
+        assert_eq!(parse_gpu_info("AMD Radeon RX 7900 XT"), (20, "RX 7900 XT".to_string()));
+        assert_eq!(parse_gpu_info("NVIDIA A100-SXM4-80GB").0, 80);
+        assert_eq!(parse_gpu_info("NVIDIA A10G").0, 24);
+        assert_eq!(parse_gpu_info("NVIDIA L40S").0, 48);
+
+        assert_eq!(parse_gpu_info("Some Future GPU"), (0, "Some Future GPU".to_string()));
+        assert_eq!(parse_gpu_info(""), (0, "CPU Only".to_string()));
+    }
+}
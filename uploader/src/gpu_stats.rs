@@ -0,0 +1,248 @@
+// uploader/src/gpu_stats.rs
+// Parse nvidia-smi sidecar logs into GPU utilization and VRAM metrics
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use llm_benchmark_types::PerformanceMetric;
+
+use crate::error::{parse_json, UploaderError};
+
+const UTILIZATION_COLUMN: &str = "utilization.gpu";
+const MEMORY_USED_COLUMN: &str = "memory.used";
+const MIB_PER_GB: f64 = 1024.0;
+
+/// GPU stats summarized over a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuStats {
+    /// Mean utilization over all samples (0–100)
+    pub utilization_percent: Option<f64>,
+
+    /// Peak VRAM used by any one GPU
+    pub vram_used_gb: Option<f64>,
+
+    /// Peak VRAM used by all GPUs together. nvidia-smi CSV rows are not
+    /// grouped by sample time, so there it is the busiest GPU's peak
+    pub total_vram_used_gb: Option<f64>,
+
+    /// Number of sample rows read
+    pub samples: usize,
+}
+
+/// One GPU in a `gpustat --json` snapshot, keyed by nvidia-smi query names
+#[derive(Deserialize)]
+struct SnapshotGpu {
+    #[serde(rename = "utilization.gpu")]
+    utilization: Option<f64>,
+    #[serde(rename = "memory.used")]
+    memory_used_mib: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct Snapshot {
+    gpus: Vec<SnapshotGpu>,
+}
+
+/// A single snapshot or a list of them taken over the run
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SnapshotFile {
+    Many(Vec<Snapshot>),
+    One(Snapshot),
+}
+
+/// Parse a GPU stats sidecar file: JSON snapshots (see
+/// [`parse_snapshot_json`]) or nvidia-smi CSV (see [`parse_nvidia_smi_csv`])
+pub fn parse_gpu_stats(content: &str, path: &Path) -> Result<GpuStats, UploaderError> {
+    if content.trim_start().starts_with(['{', '[']) {
+        parse_snapshot_json(content, path)
+    } else {
+        parse_nvidia_smi_csv(content)
+    }
+}
+
+/// Parse JSON written by `gpustat --json`: an object with a `gpus` list
+/// whose entries carry `utilization.gpu` (%) and `memory.used` (MiB), or a
+/// list of such objects. Each GPU entry counts as one sample, and total
+/// VRAM is the peak of the memory summed over each snapshot's GPUs.
+pub fn parse_snapshot_json(content: &str, path: &Path) -> Result<GpuStats, UploaderError> {
+    let snapshots = match parse_json::<SnapshotFile>(content, path)? {
+        SnapshotFile::Many(snapshots) => snapshots,
+        SnapshotFile::One(snapshot) => vec![snapshot],
+    };
+    let total_vram_used_gb = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.gpus.iter().any(|gpu| gpu.memory_used_mib.is_some()))
+        .map(|snapshot| snapshot.gpus.iter().filter_map(|gpu| gpu.memory_used_mib).sum::<f64>() / MIB_PER_GB)
+        .reduce(f64::max);
+
+    let stats = summarize(
+        snapshots
+            .into_iter()
+            .flat_map(|snapshot| snapshot.gpus)
+            .map(|gpu| (gpu.utilization, gpu.memory_used_mib)),
+    )?;
+    Ok(GpuStats { total_vram_used_gb, ..stats })
+}
+
+/// Parse CSV written by
+/// `nvidia-smi --query-gpu=utilization.gpu,memory.used --format=csv -l 1`.
+///
+/// Headers and units are optional (`--format=csv,noheader,nounits` works
+/// too); without a header the columns are taken to be utilization then
+/// memory in MiB. With several GPUs nvidia-smi writes one row per GPU, so
+/// utilization is averaged across them and VRAM is the busiest GPU's peak.
+pub fn parse_nvidia_smi_csv(content: &str) -> Result<GpuStats, UploaderError> {
+    let mut utilization_index = 0;
+    let mut memory_index = 1;
+    let mut samples = Vec::new();
+
+    for line in content.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+
+        if columns.iter().any(|c| c.starts_with(UTILIZATION_COLUMN) || c.starts_with(MEMORY_USED_COLUMN)) {
+            let position = |name: &str| columns.iter().position(|c| c.starts_with(name));
+            utilization_index = position(UTILIZATION_COLUMN).unwrap_or(usize::MAX);
+            memory_index = position(MEMORY_USED_COLUMN).unwrap_or(usize::MAX);
+            continue;
+        }
+
+        let util = columns.get(utilization_index).and_then(|c| leading_number(c));
+        let memory_mib = columns.get(memory_index).and_then(|c| leading_number(c));
+        samples.push((util, memory_mib));
+    }
+
+    summarize(samples)
+}
+
+/// Mean utilization and peak VRAM over (utilization %, memory MiB) samples,
+/// skipping samples with neither
+fn summarize(samples: impl IntoIterator<Item = (Option<f64>, Option<f64>)>) -> Result<GpuStats, UploaderError> {
+    let mut utilization = Vec::new();
+    let mut vram_used_gb: Option<f64> = None;
+    let mut count = 0;
+
+    for (util, memory_mib) in samples {
+        if util.is_none() && memory_mib.is_none() {
+            continue;
+        }
+
+        count += 1;
+        utilization.extend(util);
+        if let Some(gb) = memory_mib.map(|mib| mib / MIB_PER_GB) {
+            vram_used_gb = Some(vram_used_gb.map_or(gb, |peak| peak.max(gb)));
+        }
+    }
+
+    if count == 0 {
+        return Err(UploaderError::UnrecognizedFormat(
+            "no utilization.gpu or memory.used samples found in GPU stats file".to_string(),
+        ));
+    }
+
+    Ok(GpuStats {
+        utilization_percent: (!utilization.is_empty())
+            .then(|| utilization.iter().sum::<f64>() / utilization.len() as f64),
+        vram_used_gb,
+        total_vram_used_gb: vram_used_gb,
+        samples: count,
+    })
+}
+
+impl GpuStats {
+    /// Metrics for the stats that were recorded
+    pub fn to_metrics(&self, timestamp: DateTime<Utc>) -> Vec<PerformanceMetric> {
+        let context = Some(serde_json::json!({ "source": "nvidia-smi", "samples": self.samples }));
+
+        self.utilization_percent
+            .map(PerformanceMetric::gpu_utilization_percent)
+            .into_iter()
+            .chain(self.vram_used_gb.map(PerformanceMetric::gpu_vram_used_gb))
+            .map(|metric| PerformanceMetric {
+                timestamp,
+                context: context.clone(),
+                ..metric
+            })
+            .collect()
+    }
+}
+
+/// Number at the start of a value such as `87 %` or `15234 MiB`; `[N/A]`
+/// and other non-numeric values give None
+fn leading_number(value: &str) -> Option<f64> {
+    value.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nvidia_smi_csv() {
+        let with_header = "utilization.gpu [%], memory.used [MiB]\n\
+                           80 %, 10240 MiB\n\
+                           100 %, 20480 MiB\n\
+                           [N/A], [N/A]\n";
+        let stats = parse_nvidia_smi_csv(with_header).unwrap();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.utilization_percent, Some(90.0));
+        assert_eq!(stats.vram_used_gb, Some(20.0));
+        assert_eq!(stats.total_vram_used_gb, Some(20.0));
+
+        let metrics = stats.to_metrics(Utc::now());
+        assert_eq!(metrics.len(), 2);
+        assert!(metrics.iter().all(|m| m.is_known_metric()));
+
+        // Column order follows the header
+        let reordered = parse_nvidia_smi_csv("memory.used [MiB], utilization.gpu [%]\n1024 MiB, 50 %\n").unwrap();
+        assert_eq!(reordered.utilization_percent, Some(50.0));
+        assert_eq!(reordered.vram_used_gb, Some(1.0));
+
+        let no_header = parse_nvidia_smi_csv("40, 2048\n60, 1024\n").unwrap();
+        assert_eq!(no_header.utilization_percent, Some(50.0));
+        assert_eq!(no_header.vram_used_gb, Some(2.0));
+
+        assert!(matches!(
+            parse_nvidia_smi_csv("timestamp\n"),
+            Err(UploaderError::UnrecognizedFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_snapshot_json() {
+        let path = Path::new("gpu_stats.json");
+        let snapshot = r#"{"hostname": "bench", "gpus": [
+            {"index": 0, "utilization.gpu": 90, "memory.used": 18432, "memory.total": 24564},
+            {"index": 1, "utilization.gpu": 70, "memory.used": 9216, "memory.total": 24564}
+        ]}"#;
+        let stats = parse_gpu_stats(snapshot, path).unwrap();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.utilization_percent, Some(80.0));
+        assert_eq!(stats.vram_used_gb, Some(18.0));
+        assert_eq!(stats.total_vram_used_gb, Some(27.0));
+
+        let series = r#"[{"gpus": [{"utilization.gpu": null, "memory.used": 1024}]},
+                         {"gpus": [{"utilization.gpu": 50, "memory.used": 2048}]}]"#;
+        let stats = parse_gpu_stats(series, path).unwrap();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.utilization_percent, Some(50.0));
+        assert_eq!(stats.vram_used_gb, Some(2.0));
+        assert_eq!(stats.total_vram_used_gb, Some(2.0));
+
+        // Totals are per snapshot, not summed over the run
+        let multi_gpu_series = r#"[{"gpus": [{"memory.used": 4096}, {"memory.used": 1024}]},
+                                   {"gpus": [{"memory.used": 3072}, {"memory.used": 3072}]},
+                                   {"gpus": [{"utilization.gpu": 10}]}]"#;
+        let stats = parse_gpu_stats(multi_gpu_series, path).unwrap();
+        assert_eq!(stats.vram_used_gb, Some(4.0));
+        assert_eq!(stats.total_vram_used_gb, Some(6.0));
+
+        // CSV still goes to the nvidia-smi parser
+        assert_eq!(parse_gpu_stats("40, 2048\n", path).unwrap().vram_used_gb, Some(2.0));
+
+        assert!(matches!(parse_gpu_stats(r#"{"gpus": []}"#, path), Err(UploaderError::UnrecognizedFormat(_))));
+        assert!(matches!(parse_gpu_stats("{ not json", path), Err(UploaderError::Json { .. })));
+    }
+}
@@ -0,0 +1,212 @@
+// uploader/src/hf_leaderboard.rs
+// Parse HuggingFace Open LLM Leaderboard results into benchmark scores
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use llm_benchmark_types::{
+    BenchmarkScoreType, GSM8KScore, GenericBenchmarkScore, HellaSwagScore, TruthfulQAScore,
+};
+
+use crate::error::UploaderError;
+
+// Evaluation set sizes; the results only report accuracies, so answer
+// counts are estimated from these
+const ARC_CHALLENGE_QUESTIONS: i32 = 1172;
+const HELLASWAG_QUESTIONS: i32 = 10042;
+const MMLU_QUESTIONS: i32 = 14042;
+const TRUTHFULQA_QUESTIONS: i32 = 817;
+const WINOGRANDE_QUESTIONS: i32 = 1267;
+const GSM8K_PROBLEMS: i32 = 1319;
+
+/// Convert a leaderboard results file into benchmark scores, using the
+/// metric the leaderboard ranks each task by: `acc_norm` for ARC and
+/// HellaSwag, `mc2` for TruthfulQA and `acc` for the rest.
+///
+/// Accepts both leaderboard task keys (`harness|arc:challenge|25`) and plain
+/// lm-evaluation-harness keys (`arc_challenge`). MMLU is the mean over its
+/// subject tasks. It's the original 57-subject MMLU, not the MMLU-Pro our
+/// `mmlu` scores hold, so it's kept as a generic `hf_mmlu` score.
+pub fn parse_results(
+    results: &Value,
+    timestamp: DateTime<Utc>,
+) -> Result<Vec<BenchmarkScoreType>, UploaderError> {
+    let tasks = results
+        .get("results")
+        .and_then(Value::as_object)
+        .ok_or_else(|| UploaderError::MissingField("results".to_string()))?;
+
+    let mut arc = None;
+    let mut hellaswag = None;
+    let mut truthfulqa = None;
+    let mut winogrande = None;
+    let mut gsm8k = None;
+    let mut mmlu_subjects = Vec::new();
+
+    for (key, metrics) in tasks {
+        // "harness|arc:challenge|25" -> "arc:challenge"
+        let task = key.split('|').nth(1).unwrap_or(key);
+        match task {
+            "arc:challenge" | "arc_challenge" => arc = metric(metrics, &["acc_norm", "acc"]),
+            "hellaswag" => hellaswag = metric(metrics, &["acc_norm", "acc"]),
+            "truthfulqa:mc" | "truthfulqa_mc2" => truthfulqa = metric(metrics, &["mc2", "acc"]),
+            "winogrande" => winogrande = metric(metrics, &["acc"]),
+            "gsm8k" => gsm8k = metric(metrics, &["acc", "exact_match"]),
+            _ if task.starts_with("hendrycksTest-") || task.starts_with("mmlu_") => {
+                mmlu_subjects.extend(metric(metrics, &["acc"]));
+            }
+            _ => {}
+        }
+    }
+
+    let context = |task: &str, metric: &str| {
+        Some(serde_json::json!({
+            "source": "hf-open-llm-leaderboard",
+            "task": task,
+            "metric": metric,
+            "note": "Question counts are estimated from the evaluation set size",
+        }))
+    };
+    let correct = |accuracy: f64, total: i32| (accuracy * total as f64).round() as i32;
+
+    let mut scores = Vec::new();
+    if let Some(acc) = arc {
+        scores.push(BenchmarkScoreType::Generic(GenericBenchmarkScore {
+            benchmark_name: "arc_challenge".to_string(),
+            score: acc * 100.0,
+            total_questions: Some(ARC_CHALLENGE_QUESTIONS),
+            correct_answers: Some(correct(acc, ARC_CHALLENGE_QUESTIONS)),
+            sub_scores: None,
+            timestamp,
+            context: context("arc:challenge", "acc_norm"),
+        }));
+    }
+    if let Some(acc) = hellaswag {
+        scores.push(BenchmarkScoreType::HellaSwag(HellaSwagScore {
+            accuracy: acc * 100.0,
+            total_questions: HELLASWAG_QUESTIONS,
+            correct_answers: correct(acc, HELLASWAG_QUESTIONS),
+            timestamp,
+            context: context("hellaswag", "acc_norm"),
+        }));
+    }
+    if !mmlu_subjects.is_empty() {
+        let acc = mmlu_subjects.iter().sum::<f64>() / mmlu_subjects.len() as f64;
+        scores.push(BenchmarkScoreType::Generic(GenericBenchmarkScore {
+            benchmark_name: "hf_mmlu".to_string(),
+            score: acc * 100.0,
+            total_questions: Some(MMLU_QUESTIONS),
+            correct_answers: Some(correct(acc, MMLU_QUESTIONS)),
+            sub_scores: None,
+            timestamp,
+            context: context("mmlu", "acc"),
+        }));
+    }
+    if let Some(mc2) = truthfulqa {
+        scores.push(BenchmarkScoreType::TruthfulQA(TruthfulQAScore {
+            truthful_score: mc2 * 100.0,
+            helpful_score: None,
+            total_questions: TRUTHFULQA_QUESTIONS,
+            timestamp,
+            context: context("truthfulqa:mc", "mc2"),
+        }));
+    }
+    if let Some(acc) = winogrande {
+        scores.push(BenchmarkScoreType::Generic(GenericBenchmarkScore {
+            benchmark_name: "winogrande".to_string(),
+            score: acc * 100.0,
+            total_questions: Some(WINOGRANDE_QUESTIONS),
+            correct_answers: Some(correct(acc, WINOGRANDE_QUESTIONS)),
+            sub_scores: None,
+            timestamp,
+            context: context("winogrande", "acc"),
+        }));
+    }
+    if let Some(acc) = gsm8k {
+        scores.push(BenchmarkScoreType::GSM8K(GSM8KScore {
+            problems_solved: correct(acc, GSM8K_PROBLEMS),
+            total_problems: GSM8K_PROBLEMS,
+            timestamp,
+            context: context("gsm8k", "acc"),
+        }));
+    }
+
+    if scores.is_empty() {
+        return Err(UploaderError::UnrecognizedFormat(
+            "no ARC, HellaSwag, MMLU, TruthfulQA, Winogrande or GSM8K results found".to_string(),
+        ));
+    }
+
+    Ok(scores)
+}
+
+/// Quantization matching the leaderboard's `config_general.model_dtype`
+pub fn quantization_from_dtype(results: &Value) -> Option<&'static str> {
+    let dtype = results.pointer("/config_general/model_dtype")?.as_str()?;
+    match dtype.trim_start_matches("torch.") {
+        "float16" => Some("FP16"),
+        "bfloat16" => Some("BF16"),
+        "float32" => Some("FP32"),
+        _ => None,
+    }
+}
+
+/// First of `names` present in a task's metrics. Newer harness output
+/// suffixes metric keys with a filter (`acc,none`), which is ignored.
+fn metric(metrics: &Value, names: &[&str]) -> Option<f64> {
+    let metrics = metrics.as_object()?;
+    names.iter().find_map(|name| {
+        metrics
+            .iter()
+            .find(|(key, _)| key.split(',').next() == Some(*name))
+            .and_then(|(_, value)| value.as_f64())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_benchmark_types::BenchmarkScore;
+
+    #[test]
+    fn test_parse_leaderboard_results() {
+        let results = serde_json::json!({
+            "config_general": { "model_name": "meta-llama/Llama-2-7b-hf", "model_dtype": "torch.float16" },
+            "results": {
+                "harness|arc:challenge|25": { "acc": 0.49, "acc_norm": 0.53 },
+                "harness|hellaswag|10": { "acc": 0.58, "acc_norm": 0.78 },
+                "harness|hendrycksTest-abstract_algebra|5": { "acc": 0.30 },
+                "harness|hendrycksTest-anatomy|5": { "acc": 0.50 },
+                "harness|truthfulqa:mc|0": { "mc1": 0.25, "mc2": 0.39 },
+                "harness|winogrande|5": { "acc": 0.74 },
+                "harness|gsm8k|5": { "acc": 0.14 },
+                "all": { "acc": 0.5 }
+            }
+        });
+
+        let scores = parse_results(&results, Utc::now()).unwrap();
+        let by_name = |name: &str| {
+            scores
+                .iter()
+                .find(|s| s.benchmark_name() == name)
+                .map(|s| s.overall_score())
+                .unwrap()
+        };
+
+        assert_eq!(scores.len(), 6);
+        assert!((by_name("arc_challenge") - 53.0).abs() < 1e-9);
+        assert!((by_name("hellaswag") - 78.0).abs() < 1e-9);
+        assert!((by_name("hf_mmlu") - 40.0).abs() < 1e-9);
+        assert!((by_name("truthfulqa") - 39.0).abs() < 1e-9);
+        assert!((by_name("winogrande") - 74.0).abs() < 1e-9);
+        assert!((by_name("gsm8k") - 14.0).abs() < 0.1);
+        assert!(scores.iter().all(|s| s.validate().is_ok()));
+        assert_eq!(quantization_from_dtype(&results), Some("FP16"));
+
+        let empty = serde_json::json!({ "results": { "harness|drop|3": { "f1": 0.1 } } });
+        assert!(matches!(
+            parse_results(&empty, Utc::now()),
+            Err(UploaderError::UnrecognizedFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+// uploader/src/http.rs
+// HTTP client settings and retries for requests to the API server
+
+use std::io::Write;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use flate2::{write::GzEncoder, Compression};
+use reqwest::header::{CONTENT_ENCODING, CONTENT_TYPE};
+use serde::Serialize;
+
+use crate::error::{self, UploaderError};
+
+/// Delay before the first retry; each further retry doubles it
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Client settings from the global CLI flags
+#[derive(Debug, Clone)]
+pub struct HttpSettings {
+    /// Retries after the first attempt of an upload
+    pub max_retries: u32,
+
+    /// Per-request timeout (None waits indefinitely)
+    pub timeout: Option<Duration>,
+
+    /// Sent as a bearer token with requests to the API server
+    pub api_key: Option<String>,
+
+    /// Gzip JSON request bodies (sent with `Content-Encoding: gzip`)
+    pub compress: bool,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self { max_retries: 3, timeout: None, api_key: None, compress: false }
+    }
+}
+
+/// Set once from the global CLI flags, read whenever a client is built
+static HTTP_SETTINGS: OnceLock<HttpSettings> = OnceLock::new();
+
+/// Apply the CLI's HTTP settings; later calls are ignored
+pub fn init(settings: HttpSettings) {
+    HTTP_SETTINGS.get_or_init(|| settings);
+}
+
+fn settings() -> HttpSettings {
+    HTTP_SETTINGS.get().cloned().unwrap_or_default()
+}
+
+/// A client using the configured timeout. Fails if the TLS backend can't be
+/// set up, rather than silently dropping the timeout.
+pub fn client() -> Result<reqwest::Client, UploaderError> {
+    let mut builder = reqwest::Client::builder();
+    if let Some(timeout) = settings().timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder
+        .build()
+        .map_err(|e| UploaderError::Upload(format!("Failed to set up the HTTP client: {}", e)))
+}
+
+/// POST `body` as JSON to `url` on the API server, with the API key if one
+/// is set and gzipped if compression is on, retrying connection failures,
+/// timeouts and 5xx responses with exponential backoff (1s, 2s, 4s, ...).
+/// Other responses, including 4xx, are returned to the caller as they are.
+pub async fn post_json<T: Serialize + ?Sized>(url: &str, body: &T) -> Result<reqwest::Response, UploaderError> {
+    let client = client()?;
+    let settings = settings();
+    let attempts = settings.max_retries + 1;
+    let body = encode_body(body, settings.compress)?;
+
+    for attempt in 1..=attempts {
+        let last_attempt = attempt == attempts;
+
+        let mut request = client.post(url).header(CONTENT_TYPE, "application/json").body(body.clone());
+        if settings.compress {
+            request = request.header(CONTENT_ENCODING, "gzip");
+        }
+        if let Some(api_key) = &settings.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        match request.send().await {
+            Ok(response) if response.status().is_server_error() && !last_attempt => {
+                println!(
+                    "⚠️  Server answered {} (attempt {}/{}), retrying...",
+                    response.status(),
+                    attempt,
+                    attempts
+                );
+            }
+            Ok(response) if response.status().is_server_error() => {
+                return Err(match error::response_error(response).await {
+                    UploaderError::Upload(message) => {
+                        UploaderError::Upload(format!("{} (gave up after {} attempts)", message, attempts))
+                    }
+                    other => other,
+                });
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if (e.is_connect() || e.is_timeout()) && !last_attempt => {
+                println!("⚠️  {} (attempt {}/{}), retrying...", e, attempt, attempts);
+            }
+            Err(e) if e.is_connect() || e.is_timeout() => {
+                return Err(UploaderError::Upload(format!("{} (gave up after {} attempts)", e, attempts)));
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        tokio::time::sleep(backoff(attempt)).await;
+    }
+
+    unreachable!("the last attempt always returns")
+}
+
+/// Serialize `body` to JSON, gzipped when `compress` is set
+fn encode_body<T: Serialize + ?Sized>(body: &T, compress: bool) -> Result<Vec<u8>, UploaderError> {
+    let json = serde_json::to_vec(body)
+        .map_err(|e| UploaderError::Upload(format!("Failed to encode request body: {}", e)))?;
+    if !compress {
+        return Ok(json);
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| UploaderError::Upload(format!("Failed to compress request body: {}", e)))
+}
+
+/// Delay after the given (1-based) failed attempt
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF * 2u32.saturating_pow(attempt.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles() {
+        let delays: Vec<u64> = (1..=4).map(|attempt| backoff(attempt).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn test_encode_body_gzip() {
+        use std::io::Read;
+
+        let body = serde_json::json!({ "samples_ns": vec![1_000_000u64; 1000] });
+        let plain = encode_body(&body, false).unwrap();
+        let compressed = encode_body(&body, true).unwrap();
+        assert!(compressed.len() < plain.len());
+
+        let mut decoded = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+    }
+}
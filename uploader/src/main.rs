@@ -1,15 +1,27 @@
 use anyhow::{anyhow, Result};
-use chrono::{DateTime, Utc, NaiveDateTime};
-use clap::{Parser, Subcommand};
+use chrono::{DateTime, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
-use llm_benchmark_types::{*, benchmarks::{MMLUScore, MMLUCategoryScore}};
+use llm_benchmark_types::{*, benchmarks::MMLUProScore};
 use parquet::file::reader::{FileReader, SerializedFileReader};
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use arrow::array::{AsArray, Array};
 use arrow::datatypes::{UInt64Type, Int64Type};
 use std::fs::File;
+use std::process::ExitCode;
+use std::sync::OnceLock;
+
+mod benchmarks_dir;
+mod error;
+mod gpu_info;
+mod gpu_stats;
+mod hf_leaderboard;
+mod http;
+mod mmlu_pro;
+
+use error::{parse_json, read_file, UploaderError};
 
 /// LLM Performance Tool - Record and import LLM benchmark experiments
 #[derive(Parser)]
@@ -18,8 +30,53 @@ use std::fs::File;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Exit non-zero if the server reports warnings for an upload
+    #[arg(long, global = true, conflicts_with = "quiet_warnings")]
+    warnings_as_errors: bool,
+
+    /// Don't print warnings the server reports for an upload
+    #[arg(long, global = true)]
+    quiet_warnings: bool,
+
+    /// Times to retry an upload after a connection error or 5xx response
+    #[arg(long, global = true, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Per-request timeout in seconds (default: none)
+    #[arg(long, global = true)]
+    timeout_secs: Option<u64>,
+
+    /// API key for a server that requires one to upload
+    #[arg(long, global = true)]
+    api_key: Option<String>,
+
+    /// Gzip upload bodies, for large results over slow links
+    #[arg(long, global = true)]
+    compress: bool,
+
+    /// Replace an identical run already on the server (same model,
+    /// quantization, backend, version, hardware and timestamp) instead of
+    /// failing the upload as a duplicate
+    #[arg(long, global = true)]
+    overwrite: bool,
+}
+
+/// How warnings in a successful upload response are handled
+#[derive(Clone, Copy, Default)]
+enum WarningPolicy {
+    #[default]
+    Print,
+    Quiet,
+    Error,
 }
 
+/// Set once from the global CLI flags, read by every upload path
+static WARNING_POLICY: OnceLock<WarningPolicy> = OnceLock::new();
+
+/// Set once from `--overwrite`, read by experiment uploads
+static OVERWRITE: OnceLock<bool> = OnceLock::new();
+
 #[derive(Subcommand)]
 enum Commands {
     /// Record system configuration and model info during experiment
@@ -52,6 +109,10 @@ enum Commands {
         #[arg(long)]
         lora: Option<String>,
 
+        /// Campaign label stored in llm.json and applied to runs uploaded with it
+        #[arg(long)]
+        label: Option<String>,
+
         /// Output file path (default: llm.json)
         #[arg(short, long, default_value = "llm.json")]
         output: PathBuf,
@@ -69,6 +130,10 @@ enum Commands {
         /// API server URL to upload to (default: http://localhost:3000)
         #[arg(short, long, default_value = "http://localhost:3000")]
         server: String,
+
+        /// Campaign label for the imported runs (overrides the one in llm.json)
+        #[arg(long)]
+        label: Option<String>,
     },
 
     /// Upload benchmark results with llm.json configuration
@@ -84,6 +149,103 @@ enum Commands {
         /// API server URL to upload to (default: http://localhost:3000)
         #[arg(short, long, default_value = "http://localhost:3000")]
         server: String,
+
+        /// Backend console output (stdout/stderr) to attach to the run
+        #[arg(long)]
+        log_file: Option<PathBuf>,
+
+        /// Campaign label for the run (overrides the one in llm.json)
+        #[arg(long)]
+        label: Option<String>,
+
+        /// nvidia-smi CSV recorded during the run, for GPU utilization and
+        /// VRAM used (`nvidia-smi --query-gpu=utilization.gpu,memory.used --format=csv -l 1`),
+        /// or `gpustat --json` snapshots
+        #[arg(long)]
+        gpu_stats_file: Option<PathBuf>,
+    },
+
+    /// Upload llama-bench `-o json` results
+    LlamaBench {
+        /// Path to the llama-bench JSON output
+        #[arg(short = 'f', long)]
+        file: PathBuf,
+
+        /// Model name (default: parsed from the model filename)
+        #[arg(short = 'm', long)]
+        model: Option<String>,
+
+        /// Quantization (default: parsed from the model filename)
+        #[arg(short = 'q', long)]
+        quantization: Option<String>,
+
+        /// API server URL to upload to (default: http://localhost:3000)
+        #[arg(short, long, default_value = "http://localhost:3000")]
+        server: String,
+
+        /// Notes to store with the run
+        #[arg(short = 'n', long)]
+        notes: Option<String>,
+
+        /// Benchmark scores to upload with the run
+        #[arg(short = 'b', long)]
+        benchmarks_file: Option<PathBuf>,
+
+        /// GPU memory in GB (default: from the GPU info string or a table
+        /// of known cards)
+        #[arg(long)]
+        gpu_memory_gb: Option<i32>,
+
+        /// Measured peak memory use in GB, recorded instead of an estimate
+        #[arg(long)]
+        memory_gb: Option<f64>,
+
+        /// nvidia-smi CSV or `gpustat --json` snapshots recorded during the
+        /// run; its peak VRAM across all GPUs is recorded as memory use
+        /// unless `--memory-gb` is given, along with GPU utilization
+        #[arg(long)]
+        gpu_stats_file: Option<PathBuf>,
+
+        /// Multiple of the model size used to estimate memory use when it
+        /// wasn't measured
+        #[arg(long, default_value_t = DEFAULT_MEMORY_OVERHEAD_FACTOR)]
+        memory_overhead_factor: f64,
+
+        /// Campaign label for the run
+        #[arg(long)]
+        label: Option<String>,
+    },
+
+    /// Upload Ollama `/api/generate` responses: one JSON object, an array,
+    /// or newline-delimited JSON of several generations
+    Ollama {
+        /// Path to the Ollama response JSON
+        #[arg(short = 'f', long)]
+        file: PathBuf,
+
+        /// API server URL to upload to (default: http://localhost:3000)
+        #[arg(short, long, default_value = "http://localhost:3000")]
+        server: String,
+
+        /// Model name (default: the responses' `model` tag without its quantization)
+        #[arg(short = 'm', long)]
+        model_name: Option<String>,
+
+        /// Quantization (default: parsed from the model tag, e.g. `llama3.1:8b-instruct-q4_K_M`)
+        #[arg(short = 'q', long)]
+        quantization: Option<String>,
+
+        /// Ollama version (will try to auto-detect if not provided)
+        #[arg(short = 'v', long)]
+        backend_version: Option<String>,
+
+        /// Notes to store with the run
+        #[arg(short = 'n', long)]
+        notes: Option<String>,
+
+        /// Campaign label for the run
+        #[arg(long)]
+        label: Option<String>,
     },
 
     /// Upload MMLU-Pro evaluation results
@@ -104,6 +266,85 @@ enum Commands {
         #[arg(long)]
         lora: Option<String>,
     },
+
+    /// Attach benchmark scores to an existing test run
+    Benchmarks {
+        /// Test run to attach the scores to
+        #[arg(short = 't', long)]
+        test_run_id: String,
+
+        /// JSON file with a list of benchmark scores
+        #[arg(short = 'f', long)]
+        file: PathBuf,
+
+        /// API server URL to upload to (default: http://localhost:3000)
+        #[arg(short, long, default_value = "http://localhost:3000")]
+        server: String,
+    },
+
+    /// Upload every benchmark score file in a directory as one batch for a
+    /// model variant
+    BenchmarksDir {
+        /// Directory of JSON files, each holding one benchmark score or a list
+        #[arg(short = 'd', long)]
+        dir: PathBuf,
+
+        /// API server URL to upload to (default: http://localhost:3000)
+        #[arg(short, long, default_value = "http://localhost:3000")]
+        server: String,
+
+        /// Model name to store the scores under
+        #[arg(short = 'm', long)]
+        model_name: String,
+
+        /// Quantization of the model variant
+        #[arg(short = 'q', long)]
+        quantization: String,
+    },
+
+    /// Upload HuggingFace Open LLM Leaderboard results as reference scores
+    HfLeaderboard {
+        /// Path to the leaderboard results JSON for the model
+        #[arg(short = 'f', long)]
+        results_file: PathBuf,
+
+        /// Model slug to store the scores under (e.g. meta-llama/Llama-2-7b-hf)
+        #[arg(short = 'm', long)]
+        model: String,
+
+        /// Quantization (default: from the results' model_dtype)
+        #[arg(short = 'q', long)]
+        quantization: Option<String>,
+
+        /// API server URL to upload to (default: http://localhost:3000)
+        #[arg(short, long, default_value = "http://localhost:3000")]
+        server: String,
+    },
+
+    /// Check a result file against the upload validation rules without uploading
+    Lint {
+        /// Path to llama-bench JSON, MMLU-Pro report.txt or experiment JSON
+        file: PathBuf,
+
+        /// File format (default: detect from file contents)
+        #[arg(short, long, value_enum)]
+        format: Option<LintFormat>,
+
+        /// API server URL to also validate against (default: local checks only)
+        #[arg(short, long)]
+        server: Option<String>,
+    },
+}
+
+/// Result file formats understood by the `lint` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum LintFormat {
+    /// llama-bench `-o json` output
+    LlamaBench,
+    /// MMLU-Pro report.txt
+    MmluPro,
+    /// ExperimentRun JSON
+    Custom,
 }
 
 /// Benchmark artifact - captures system configuration and model info
@@ -116,6 +357,8 @@ struct BenchmarkArtifact {
     cpu_model: String,
     cpu_arch: String,
     ram_gb: Option<i32>,
+    #[serde(default)]
+    numa_nodes: Option<i32>,
 
     // Model configuration
     model_name: String,
@@ -130,6 +373,8 @@ struct BenchmarkArtifact {
     backend_version: Option<String>,
     concurrent_requests: Option<i32>,
     max_context_length: Option<i32>,
+    #[serde(default)]
+    label: Option<String>,
 
     // Metadata
     hostname: String,
@@ -267,8 +512,13 @@ struct LlamaBenchResult {
     flash_attn: bool,
     use_mmap: bool,
     embeddings: bool,
+    /// GPU split proportions, e.g. "0.60/0.40" ("0.00" when not split).
+    /// Absent from older llama-bench versions.
+    tensor_split: Option<String>,
     n_prompt: i32,
     n_gen: i32,
+    /// KV cache depth the test ran at (`-d`). Absent from older versions.
+    n_depth: Option<i32>,
     test_time: DateTime<Utc>,
     avg_ns: i64,
     stddev_ns: i64,
@@ -278,6 +528,39 @@ struct LlamaBenchResult {
     samples_ts: Vec<f64>,
 }
 
+/// One generation from Ollama's `/api/generate`. Timings are only on the
+/// final record of a streamed response; durations are in nanoseconds.
+#[derive(Debug, Deserialize)]
+struct OllamaGeneration {
+    model: String,
+    created_at: Option<DateTime<Utc>>,
+    eval_count: Option<i64>,
+    eval_duration: Option<i64>,
+    prompt_eval_count: Option<i64>,
+    prompt_eval_duration: Option<i64>,
+}
+
+impl OllamaGeneration {
+    /// Generation speed in tokens/s
+    fn eval_rate(&self) -> Option<f64> {
+        ollama_rate(self.eval_count, self.eval_duration)
+    }
+
+    /// Prompt processing speed in tokens/s (absent when the prompt was cached)
+    fn prompt_eval_rate(&self) -> Option<f64> {
+        ollama_rate(self.prompt_eval_count, self.prompt_eval_duration)
+    }
+}
+
+fn ollama_rate(count: Option<i64>, duration_ns: Option<i64>) -> Option<f64> {
+    match (count, duration_ns) {
+        (Some(count), Some(duration_ns)) if count > 0 && duration_ns > 0 => {
+            Some(count as f64 / (duration_ns as f64 / 1e9))
+        }
+        _ => None,
+    }
+}
+
 /// Parsed model information from filename
 #[derive(Debug)]
 struct ModelInfo {
@@ -288,12 +571,39 @@ struct ModelInfo {
 #[derive(Debug, Serialize)]
 struct UploadRequest {
     experiment_run: ExperimentRun,
+    force: bool,
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            ExitCode::from(error::exit_code(&e))
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let policy = if cli.warnings_as_errors {
+        WarningPolicy::Error
+    } else if cli.quiet_warnings {
+        WarningPolicy::Quiet
+    } else {
+        WarningPolicy::Print
+    };
+    WARNING_POLICY.get_or_init(|| policy);
+    OVERWRITE.get_or_init(|| cli.overwrite);
+    http::init(http::HttpSettings {
+        max_retries: cli.max_retries,
+        timeout: cli.timeout_secs.map(std::time::Duration::from_secs),
+        api_key: cli.api_key.clone(),
+        compress: cli.compress,
+    });
+
     match cli.command {
         Commands::Record {
             model_path,
@@ -303,6 +613,7 @@ async fn main() -> Result<()> {
             concurrent_requests,
             max_context_length,
             lora,
+            label,
             output,
         } => {
             record_config(
@@ -313,6 +624,7 @@ async fn main() -> Result<()> {
                 concurrent_requests,
                 max_context_length,
                 lora,
+                label,
                 output,
             ).await?;
         }
@@ -320,15 +632,53 @@ async fn main() -> Result<()> {
             id,
             systemslab_url,
             server,
+            label,
         } => {
-            import_from_systemslab(id, systemslab_url, server).await?;
+            import_from_systemslab(id, systemslab_url, server, label).await?;
         }
         Commands::Upload {
             llm_json,
             results_json,
             server,
+            log_file,
+            label,
+            gpu_stats_file,
         } => {
-            upload_local_results(llm_json, results_json, server).await?;
+            upload_local_results(llm_json, results_json, server, log_file, label, gpu_stats_file).await?;
+        }
+        Commands::LlamaBench {
+            file,
+            model,
+            quantization,
+            server,
+            notes,
+            benchmarks_file,
+            gpu_memory_gb,
+            memory_gb,
+            gpu_stats_file,
+            memory_overhead_factor,
+            label,
+        } => {
+            let details = RunDetails { model_name: model, quantization, notes, label };
+            let memory = LlamaBenchMemory {
+                gpu_memory_gb,
+                memory_gb,
+                gpu_stats_file,
+                overhead_factor: memory_overhead_factor,
+            };
+            upload_llama_bench(file, server, details, benchmarks_file, memory).await?;
+        }
+        Commands::Ollama {
+            file,
+            server,
+            model_name,
+            quantization,
+            backend_version,
+            notes,
+            label,
+        } => {
+            let details = RunDetails { model_name, quantization, notes, label };
+            upload_ollama(file, server, details, backend_version).await?;
         }
         Commands::UploadMmlu {
             report_file,
@@ -343,32 +693,197 @@ async fn main() -> Result<()> {
                 None, // model
                 None, // quantization
                 server,
-                "llama.cpp".to_string(), // backend
+                Backend::LlamaCpp.to_string(),
                 None, // notes
                 lora,
             ).await?;
         }
+        Commands::Benchmarks {
+            test_run_id,
+            file,
+            server,
+        } => {
+            upload_benchmarks_only(test_run_id, file, server).await?;
+        }
+        Commands::BenchmarksDir {
+            dir,
+            server,
+            model_name,
+            quantization,
+        } => {
+            upload_benchmarks_dir(dir, server, model_name, quantization).await?;
+        }
+        Commands::HfLeaderboard {
+            results_file,
+            model,
+            quantization,
+            server,
+        } => {
+            upload_hf_leaderboard(results_file, model, quantization, server).await?;
+        }
+        Commands::Lint {
+            file,
+            format,
+            server,
+        } => {
+            lint_file(file, format, server).await?;
+        }
     }
 
     Ok(())
 }
 
-async fn upload_llama_bench(
-    file: PathBuf,
-    server: String,
+/// Details of a run given on the command line rather than read from the
+/// benchmark output
+struct RunDetails {
+    /// Model name (default: parsed from the output)
     model_name: Option<String>,
+    /// Quantization (default: parsed from the output)
     quantization: Option<String>,
     notes: Option<String>,
+    /// Campaign label
+    label: Option<String>,
+}
+
+/// Default multiple of the model size used to estimate llama-bench memory use
+const DEFAULT_MEMORY_OVERHEAD_FACTOR: f64 = 1.2;
+
+/// GPU memory capacity and use options for a llama-bench upload
+struct LlamaBenchMemory {
+    /// GPU memory capacity override
+    gpu_memory_gb: Option<i32>,
+    /// Measured value given on the command line
+    memory_gb: Option<f64>,
+    /// Sidecar GPU stats file whose peak total VRAM stands in for a measurement
+    gpu_stats_file: Option<PathBuf>,
+    /// Estimate multiplier used when nothing was measured
+    overhead_factor: f64,
+}
+
+/// How `memory_usage_gb` is filled in for a llama-bench run
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemoryUsage {
+    /// Measured peak in GB, with where it came from
+    Measured { gb: f64, source: &'static str },
+    /// Model size times this factor
+    Estimated { overhead_factor: f64 },
+}
+
+impl Default for MemoryUsage {
+    fn default() -> Self {
+        MemoryUsage::Estimated { overhead_factor: DEFAULT_MEMORY_OVERHEAD_FACTOR }
+    }
+}
+
+impl MemoryUsage {
+    /// `--memory-gb` if given, else the GPU stats' peak total VRAM, else
+    /// the estimate
+    fn choose(memory: &LlamaBenchMemory, gpu_stats: Option<&gpu_stats::GpuStats>) -> Self {
+        match (memory.memory_gb, gpu_stats.and_then(|stats| stats.total_vram_used_gb)) {
+            (Some(gb), _) => MemoryUsage::Measured { gb, source: "--memory-gb" },
+            (None, Some(gb)) => MemoryUsage::Measured { gb, source: "gpu-stats-file" },
+            (None, None) => MemoryUsage::Estimated { overhead_factor: memory.overhead_factor },
+        }
+    }
+}
+
+async fn upload_llama_bench(
+    file: PathBuf,
+    server: String,
+    details: RunDetails,
     benchmarks_file: Option<PathBuf>,
+    memory: LlamaBenchMemory,
 ) -> Result<()> {
+    if !(memory.overhead_factor.is_finite() && memory.overhead_factor > 0.0) {
+        return Err(anyhow!("--memory-overhead-factor must be a positive number"));
+    }
+    if memory.memory_gb.is_some_and(|gb| !(gb.is_finite() && gb > 0.0)) {
+        return Err(anyhow!("--memory-gb must be a positive number"));
+    }
+
     // Read and parse llama-bench output
-    let content = std::fs::read_to_string(&file)?;
-    let results: Vec<LlamaBenchResult> = serde_json::from_str(&content)?;
+    let content = read_file(&file)?;
+    let results: Vec<LlamaBenchResult> = parse_json(&content, &file)?;
     
     if results.is_empty() {
-        return Err(anyhow!("No results found in llama-bench output"));
+        return Err(UploaderError::UnrecognizedFormat("No results found in llama-bench output".to_string()).into());
     }
+
+    let gpu_stats = match &memory.gpu_stats_file {
+        Some(path) => {
+            println!("Reading GPU stats from: {}", path.display());
+            Some(gpu_stats::parse_gpu_stats(&read_file(path)?, path)?)
+        }
+        None => None,
+    };
+
+    let memory_usage = MemoryUsage::choose(&memory, gpu_stats.as_ref());
     
+    // Load benchmark scores if provided
+    let benchmark_scores = match benchmarks_file {
+        Some(benchmarks_file) => read_benchmark_scores(&benchmarks_file)?,
+        None => Vec::new(),
+    };
+
+    // Generate or load experiment ID
+    let experiment_dir = file.parent()
+        .ok_or_else(|| anyhow!("Could not determine parent directory of benchmark file"))?;
+    let experiment_id_path = experiment_dir.join(".experiment-id");
+
+    let exp_uuid = if experiment_id_path.exists() {
+        let id_str = std::fs::read_to_string(&experiment_id_path)?;
+        Uuid::parse_str(id_str.trim())
+            .map_err(|e| anyhow!("Failed to parse existing experiment ID: {}", e))?
+    } else {
+        let new_id = Uuid::now_v7();
+        std::fs::write(&experiment_id_path, new_id.to_string())?;
+        println!("Generated new experiment ID: {}", new_id);
+        new_id
+    };
+
+    let mut experiment_run = llama_bench_experiment_run(
+        &results,
+        exp_uuid,
+        details.model_name,
+        details.quantization,
+        details.notes,
+        memory.gpu_memory_gb,
+        memory_usage,
+    )?;
+    experiment_run.label = details.label;
+
+    if let Some(stats) = gpu_stats {
+        if let Some(util) = stats.utilization_percent {
+            println!("  GPU Utilization: {:.0}%", util);
+        }
+        if let Some(vram) = stats.vram_used_gb {
+            println!("  VRAM Used: {:.1} GB", vram);
+        }
+        let timestamp = results[0].test_time;
+        experiment_run.performance_metrics.extend(stats.to_metrics(timestamp));
+    }
+
+    // Upload to server. Benchmark scores belong to the model variant, so they
+    // go up with the run in one request to land in the same transaction.
+    if benchmark_scores.is_empty() {
+        upload_experiment(experiment_run, &server).await?;
+    } else {
+        upload_complete(experiment_run, benchmark_scores, &server).await?;
+    }
+    
+    Ok(())
+}
+
+/// Build an experiment run from parsed llama-bench results
+fn llama_bench_experiment_run(
+    results: &[LlamaBenchResult],
+    id: Uuid,
+    model_name: Option<String>,
+    quantization: Option<String>,
+    notes: Option<String>,
+    gpu_memory_gb: Option<i32>,
+    memory_usage: MemoryUsage,
+) -> Result<ExperimentRun> {
     // Use the first result for hardware info (they should all be the same)
     let first_result = &results[0];
     
@@ -380,50 +895,81 @@ async fn upload_llama_bench(
     let quantization = quantization.unwrap_or(model_info.quantization);
     
     // Parse hardware info
-    let hardware_config = parse_hardware_info(first_result)?;
+    let hardware_config = parse_hardware_info(first_result, gpu_memory_gb)?;
     
     // Create performance metrics from all results
     let mut performance_metrics = Vec::new();
     
+    // A combined pp+tg test (n_prompt > 0 and n_gen > 0) only reports one
+    // speed over both phases; it stands in for whichever separate test is
+    // missing, and its context keeps both n_prompt and n_gen to show that
+    let combined_result = results.iter().find(|r| r.n_prompt > 0 && r.n_gen > 0);
+
     // Find prompt processing result (n_prompt > 0, n_gen = 0)
-    if let Some(prompt_result) = results.iter().find(|r| r.n_prompt > 0 && r.n_gen == 0) {
+    if let Some(prompt_result) = results
+        .iter()
+        .find(|r| r.n_prompt > 0 && r.n_gen == 0)
+        .or(combined_result)
+    {
+        let mut context = serde_json::json!({
+            "n_prompt": prompt_result.n_prompt,
+            "n_batch": prompt_result.n_batch,
+            "n_ubatch": prompt_result.n_ubatch,
+            "n_threads": prompt_result.n_threads,
+            "n_gpu_layers": prompt_result.n_gpu_layers,
+            "split_mode": prompt_result.split_mode,
+            "tensor_split": prompt_result.tensor_split,
+            "n_depth": prompt_result.n_depth,
+            "flash_attn": prompt_result.flash_attn,
+            "use_mmap": prompt_result.use_mmap,
+        });
+        if prompt_result.n_gen > 0 {
+            context["n_gen"] = prompt_result.n_gen.into();
+        }
         performance_metrics.push(PerformanceMetric {
             metric_name: "prompt_processing_speed".to_string(),
             value: prompt_result.avg_ts,
             unit: "tokens/sec".to_string(),
             timestamp: prompt_result.test_time,
-            context: Some(serde_json::json!({
-                "n_prompt": prompt_result.n_prompt,
-                "n_batch": prompt_result.n_batch,
-                "n_ubatch": prompt_result.n_ubatch,
-                "n_threads": prompt_result.n_threads,
-                "n_gpu_layers": prompt_result.n_gpu_layers,
-                "split_mode": prompt_result.split_mode,
-                "flash_attn": prompt_result.flash_attn,
-                "use_mmap": prompt_result.use_mmap,
-            })),
+            context: Some(context),
         });
     }
     
     // Find text generation result (n_prompt = 0, n_gen > 0)
-    if let Some(gen_result) = results.iter().find(|r| r.n_prompt == 0 && r.n_gen > 0) {
+    if let Some(gen_result) = results
+        .iter()
+        .find(|r| r.n_prompt == 0 && r.n_gen > 0)
+        .or(combined_result)
+    {
+        let mut context = serde_json::json!({
+            "n_gen": gen_result.n_gen,
+            "n_batch": gen_result.n_batch,
+            "n_ubatch": gen_result.n_ubatch,
+            "n_threads": gen_result.n_threads,
+            "n_gpu_layers": gen_result.n_gpu_layers,
+            "split_mode": gen_result.split_mode,
+            "tensor_split": gen_result.tensor_split,
+            "n_depth": gen_result.n_depth,
+            "flash_attn": gen_result.flash_attn,
+            "use_mmap": gen_result.use_mmap,
+            // Per-repetition speeds for variance analysis
+            "speed_samples": (!gen_result.samples_ts.is_empty()).then(|| gen_result.samples_ts.clone()),
+        });
+        if gen_result.n_prompt > 0 {
+            context["n_prompt"] = gen_result.n_prompt.into();
+        }
         performance_metrics.push(PerformanceMetric {
             metric_name: "tokens_per_second".to_string(),
             value: gen_result.avg_ts,
             unit: "tokens/sec".to_string(),
             timestamp: gen_result.test_time,
-            context: Some(serde_json::json!({
-                "n_gen": gen_result.n_gen,
-                "n_batch": gen_result.n_batch,
-                "n_ubatch": gen_result.n_ubatch,
-                "n_threads": gen_result.n_threads,
-                "n_gpu_layers": gen_result.n_gpu_layers,
-                "split_mode": gen_result.split_mode,
-                "flash_attn": gen_result.flash_attn,
-                "use_mmap": gen_result.use_mmap,
-            })),
+            context: Some(context),
         });
     }
+
+    if performance_metrics.is_empty() {
+        println!("⚠️  No llama-bench test has n_prompt or n_gen above 0; uploading the run without speed metrics");
+    }
     
     // Add model size metric
     performance_metrics.push(PerformanceMetric {
@@ -437,31 +983,75 @@ async fn upload_llama_bench(
         })),
     });
     
-    // Estimate memory usage (rough estimate based on model size + overhead)
-    let memory_gb = (first_result.model_size as f64 / (1024.0 * 1024.0 * 1024.0)) * 1.2;
+    // Measured memory use if there is one, otherwise model size + overhead
+    let mut memory_context = serde_json::json!({
+        "model_params": first_result.model_n_params,
+        "n_gpu_layers": first_result.n_gpu_layers,
+    });
+    let memory_gb = match memory_usage {
+        MemoryUsage::Measured { gb, source } => {
+            memory_context["estimated"] = false.into();
+            memory_context["source"] = source.into();
+            gb
+        }
+        MemoryUsage::Estimated { overhead_factor } => {
+            memory_context["estimated"] = true.into();
+            memory_context["overhead_factor"] = overhead_factor.into();
+            (first_result.model_size as f64 / (1024.0 * 1024.0 * 1024.0)) * overhead_factor
+        }
+    };
     performance_metrics.push(PerformanceMetric {
         metric_name: "memory_usage_gb".to_string(),
         value: memory_gb,
         unit: "GB".to_string(),
         timestamp: first_result.test_time,
-        context: Some(serde_json::json!({
-            "estimated": true,
-            "model_params": first_result.model_n_params,
-            "n_gpu_layers": first_result.n_gpu_layers,
-        })),
+        context: Some(memory_context),
     });
     
-    // Load benchmark scores if provided
-    let benchmark_scores = if let Some(benchmarks_file) = benchmarks_file {
-        let content = std::fs::read_to_string(benchmarks_file)?;
-        serde_json::from_str(&content)?
-    } else {
-        Vec::new()
+    // Create experiment run
+    Ok(ExperimentRun {
+        id,
+        model_name,
+        quantization,
+        lora_adapter: None,
+        backend: Backend::LlamaCpp.to_string(),
+        backend_version: format!("{}#{}", first_result.build_commit, first_result.build_number),
+        model_size_bytes: Some(first_result.model_size),
+        model_n_params: Some(first_result.model_n_params),
+        hardware_config,
+        performance_metrics,
+        benchmark_scores: Vec::new(),
+        timestamp: first_result.test_time,
+        status: ExperimentStatus::Completed,
+        notes,
+        concurrent_requests: None, // llama-bench doesn't provide this
+        max_context_length: None,  // llama-bench doesn't provide this
+        load_pattern: None,        // llama-bench doesn't provide this
+        dataset_name: None,        // llama-bench doesn't provide this
+        gpu_power_limit_watts: None, // llama-bench doesn't provide this
+        label: None,
+        log: None,
+    })
+}
+
+async fn upload_ollama(
+    file: PathBuf,
+    server: String,
+    details: RunDetails,
+    backend_version: Option<String>,
+) -> Result<()> {
+    let content = read_file(&file)?;
+    let generations = parse_ollama_generations(&content, &file)?;
+
+    let backend_version = match backend_version {
+        Some(version) => version,
+        None => detect_backend_version(Some(Backend::Ollama.as_str()))?.unwrap_or_else(|| "unknown".to_string()),
     };
+    let hardware_config = detect_system_hardware()?;
 
     // Generate or load experiment ID
     let experiment_dir = file.parent()
-        .ok_or_else(|| anyhow!("Could not determine parent directory of benchmark file"))?;
+        .ok_or_else(|| anyhow!("Could not determine parent directory of Ollama output"))?;
     let experiment_id_path = experiment_dir.join(".experiment-id");
 
     let exp_uuid = if experiment_id_path.exists() {
@@ -475,30 +1065,146 @@ async fn upload_llama_bench(
         new_id
     };
 
-    // Create experiment run
-    let experiment_run = ExperimentRun {
-        id: exp_uuid,
+    let mut experiment_run = ollama_experiment_run(
+        &generations,
+        exp_uuid,
+        hardware_config,
+        details.model_name,
+        details.quantization,
+        backend_version,
+        details.notes,
+    )?;
+    experiment_run.label = details.label;
+
+    upload_experiment(experiment_run, &server).await?;
+
+    Ok(())
+}
+
+/// Finished generations in Ollama output, which may be a single response,
+/// an array of them or newline-delimited JSON. Streamed chunks without
+/// timings are skipped.
+fn parse_ollama_generations(content: &str, file: &std::path::Path) -> Result<Vec<OllamaGeneration>, UploaderError> {
+    let json_error = |source| UploaderError::Json { path: file.to_path_buf(), source };
+
+    let mut generations = Vec::new();
+    for value in serde_json::Deserializer::from_str(content).into_iter::<serde_json::Value>() {
+        let records = match value.map_err(json_error)? {
+            serde_json::Value::Array(records) => records,
+            record => vec![record],
+        };
+        for record in records {
+            let generation: OllamaGeneration = serde_json::from_value(record).map_err(json_error)?;
+            if generation.eval_rate().is_some() {
+                generations.push(generation);
+            }
+        }
+    }
+
+    if generations.is_empty() {
+        return Err(UploaderError::UnrecognizedFormat(
+            "No finished generations (with eval_count and eval_duration) in Ollama output".to_string(),
+        ));
+    }
+    Ok(generations)
+}
+
+/// Build an experiment run from Ollama generations, averaging their speeds
+fn ollama_experiment_run(
+    generations: &[OllamaGeneration],
+    id: Uuid,
+    hardware_config: HardwareConfig,
+    model_name: Option<String>,
+    quantization: Option<String>,
+    backend_version: String,
+    notes: Option<String>,
+) -> Result<ExperimentRun> {
+    let first = &generations[0];
+    let (tag_model, tag_quantization) = split_ollama_model(&first.model);
+
+    let model_name = model_name.unwrap_or(tag_model);
+    let quantization = quantization
+        .map(|q| normalize_quantization(&q))
+        .or(tag_quantization)
+        .ok_or_else(|| {
+            UploaderError::MissingField(format!(
+                "quantization (model tag '{}' doesn't name one; pass --quantization)",
+                first.model
+            ))
+        })?;
+    let timestamp = first.created_at.unwrap_or_else(Utc::now);
+
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    let speeds: Vec<f64> = generations.iter().filter_map(OllamaGeneration::eval_rate).collect();
+    let prompt_speeds: Vec<f64> = generations.iter().filter_map(OllamaGeneration::prompt_eval_rate).collect();
+
+    let mut performance_metrics = vec![PerformanceMetric {
+        metric_name: "tokens_per_second".to_string(),
+        value: mean(&speeds),
+        unit: "tokens/sec".to_string(),
+        timestamp,
+        context: Some(serde_json::json!({
+            "generations": speeds.len(),
+            // Per-generation speeds for variance analysis
+            "speed_samples": speeds,
+        })),
+    }];
+    if !prompt_speeds.is_empty() {
+        performance_metrics.push(PerformanceMetric {
+            metric_name: "prompt_processing_speed".to_string(),
+            value: mean(&prompt_speeds),
+            unit: "tokens/sec".to_string(),
+            timestamp,
+            context: Some(serde_json::json!({
+                "generations": prompt_speeds.len(),
+            })),
+        });
+    }
+
+    Ok(ExperimentRun {
+        id,
         model_name,
         quantization,
-        backend: "llama.cpp".to_string(),
-        backend_version: format!("{}#{}", first_result.build_commit, first_result.build_number),
+        lora_adapter: None,
+        backend: Backend::Ollama.to_string(),
+        backend_version,
+        model_size_bytes: None,
+        model_n_params: None,
         hardware_config,
         performance_metrics,
-        benchmark_scores,
-        timestamp: first_result.test_time,
+        benchmark_scores: Vec::new(),
+        timestamp,
         status: ExperimentStatus::Completed,
         notes,
-        concurrent_requests: None, // llama-bench doesn't provide this
-        max_context_length: None,  // llama-bench doesn't provide this
-        load_pattern: None,        // llama-bench doesn't provide this
-        dataset_name: None,        // llama-bench doesn't provide this
-        gpu_power_limit_watts: None, // llama-bench doesn't provide this
+        concurrent_requests: None,
+        max_context_length: None,
+        load_pattern: None,
+        dataset_name: None,
+        gpu_power_limit_watts: None,
+        label: None,
+        log: None,
+    })
+}
+
+/// Split an Ollama model tag into a model name and, when the tag ends in
+/// one, its quantization: `llama3.1:8b-instruct-q4_K_M` is
+/// `llama3.1:8b-instruct` at Q4_K_M. Bare tags like `llama3.1:8b` name no
+/// quantization.
+fn split_ollama_model(tag: &str) -> (String, Option<String>) {
+    let Some((name, suffix)) = tag.rsplit_once('-').filter(|(name, _)| name.contains(':')) else {
+        return (tag.to_string(), None);
     };
-    
-    // Upload to server
-    upload_experiment(experiment_run, &server).await?;
-    
-    Ok(())
+
+    let lower = suffix.to_lowercase();
+    let is_quantization = ["q", "iq"]
+        .iter()
+        .any(|prefix| lower.strip_prefix(prefix).is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit())))
+        || matches!(lower.as_str(), "fp16" | "f16" | "bf16" | "f32" | "fp32");
+    if is_quantization {
+        (name.to_string(), Some(normalize_quantization(suffix)))
+    } else {
+        (tag.to_string(), None)
+    }
 }
 
 async fn upload_inference_server(
@@ -511,6 +1217,7 @@ async fn upload_inference_server(
     memory_gb: Option<f64>,
     gpu_power_limit_watts: Option<i32>,
     notes: Option<String>,
+    label: Option<String>,
 ) -> Result<()> {
     // Read and parse inference benchmark output
     let content = std::fs::read_to_string(&file)?;
@@ -560,21 +1267,26 @@ async fn upload_inference_server(
                 "success_rate": result.summary.success_rate,
             })),
         },
-        // TTFT metrics - store all percentiles separately
+        // Mean TTFT under the standard name the grid, detail and comparison views read
         PerformanceMetric {
-            metric_name: "ttft_mean_ms".to_string(),
+            metric_name: metric_names::FIRST_TOKEN_LATENCY_MS.to_string(),
             value: result.latency.ttft_mean_ms,
             unit: "ms".to_string(),
             timestamp,
             context: None,
         },
+        // TTFT metrics - store all percentiles separately
         PerformanceMetric {
-            metric_name: "ttft_p50_ms".to_string(),
-            value: result.latency.ttft_p50_ms,
+            metric_name: "ttft_mean_ms".to_string(),
+            value: result.latency.ttft_mean_ms,
             unit: "ms".to_string(),
             timestamp,
             context: None,
         },
+        PerformanceMetric {
+            timestamp,
+            ..PerformanceMetric::ttft_p50_ms(result.latency.ttft_p50_ms)
+        },
         PerformanceMetric {
             metric_name: "ttft_p90_ms".to_string(),
             value: result.latency.ttft_p90_ms,
@@ -583,11 +1295,8 @@ async fn upload_inference_server(
             context: None,
         },
         PerformanceMetric {
-            metric_name: "ttft_p95_ms".to_string(),
-            value: result.latency.ttft_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::ttft_p95_ms(result.latency.ttft_p95_ms)
         },
         PerformanceMetric {
             metric_name: "ttft_p99_ms".to_string(),
@@ -605,11 +1314,8 @@ async fn upload_inference_server(
             context: None,
         },
         PerformanceMetric {
-            metric_name: "tpot_p50_ms".to_string(),
-            value: result.latency.tpot_p50_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::tpot_p50_ms(result.latency.tpot_p50_ms)
         },
         PerformanceMetric {
             metric_name: "tpot_p90_ms".to_string(),
@@ -619,11 +1325,8 @@ async fn upload_inference_server(
             context: None,
         },
         PerformanceMetric {
-            metric_name: "tpot_p95_ms".to_string(),
-            value: result.latency.tpot_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::tpot_p95_ms(result.latency.tpot_p95_ms)
         },
         PerformanceMetric {
             metric_name: "tpot_p99_ms".to_string(),
@@ -641,11 +1344,8 @@ async fn upload_inference_server(
             context: None,
         },
         PerformanceMetric {
-            metric_name: "itl_p50_ms".to_string(),
-            value: result.latency.itl_p50_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::itl_p50_ms(result.latency.itl_p50_ms)
         },
         PerformanceMetric {
             metric_name: "itl_p90_ms".to_string(),
@@ -655,11 +1355,8 @@ async fn upload_inference_server(
             context: None,
         },
         PerformanceMetric {
-            metric_name: "itl_p95_ms".to_string(),
-            value: result.latency.itl_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::itl_p95_ms(result.latency.itl_p95_ms)
         },
         PerformanceMetric {
             metric_name: "itl_p99_ms".to_string(),
@@ -805,8 +1502,11 @@ async fn upload_inference_server(
         id: exp_uuid,
         model_name,
         quantization,
+        lora_adapter: None,
         backend,
         backend_version,
+        model_size_bytes: None,
+        model_n_params: None,
         hardware_config,
         performance_metrics,
         benchmark_scores: Vec::new(),
@@ -818,6 +1518,8 @@ async fn upload_inference_server(
         load_pattern: Some(result.configuration.load_pattern.clone()),
         dataset_name,
         gpu_power_limit_watts,
+        label,
+        log: None,
     };
 
     // Upload to server
@@ -1037,7 +1739,7 @@ fn extract_model_info_from_path(model_path: &str) -> Result<(String, String)> {
             base_name.to_string()
         };
 
-        return Ok((model_name, quantization));
+        return Ok((model_name, normalize_quantization(&quantization)));
     }
 
     // For non-GGUF files, use the original path-based detection
@@ -1088,7 +1790,7 @@ fn extract_model_info_from_path(model_path: &str) -> Result<(String, String)> {
         "unknown".to_string()
     };
 
-    Ok((model_name, quantization))
+    Ok((model_name, normalize_quantization(&quantization)))
 }
 
 fn parse_timestamp(timestamp_str: &str) -> Result<DateTime<Utc>> {
@@ -1131,7 +1833,7 @@ fn detect_system_hardware() -> Result<HardwareConfig> {
         "Unknown CPU".to_string()
     };
 
-    let cpu_arch = detect_cpu_arch(&cpu_info);
+    let cpu_arch = HardwareConfig::detect_cpu_arch(&cpu_info);
 
     // Detect GPU info
     let (gpu_memory_gb, gpu_model) = detect_gpu_info()?;
@@ -1139,10 +1841,14 @@ fn detect_system_hardware() -> Result<HardwareConfig> {
     Ok(HardwareConfig {
         gpu_model,
         gpu_memory_gb,
+        gpu_count: 1,
+        total_gpu_memory_gb: None,
         cpu_model: cpu_info,
         cpu_arch: cpu_arch.to_string(),
         ram_gb: detect_ram_gb(),
         ram_type: None,
+        cpu_threads: None,
+        numa_nodes: detect_numa_nodes(),
         virtualization_type: None,
         optimizations: Vec::new(),
     })
@@ -1261,19 +1967,34 @@ fn detect_ram_gb() -> Option<i32> {
     None
 }
 
-async fn upload_benchmark_scores(request: llm_benchmark_types::UploadBenchmarkRequest, server: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+fn detect_numa_nodes() -> Option<i32> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let nodes = std::fs::read_dir("/sys/devices/system/node")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("node"))
+                .is_some_and(|id| id.parse::<u32>().is_ok())
+        })
+        .count();
+
+    (nodes > 0).then_some(nodes as i32)
+}
+
+async fn upload_benchmark_scores(request: llm_benchmark_types::UploadBenchmarkRequest, server: &str) -> Result<(), UploaderError> {
     let url = format!("{}/api/benchmarks/upload", server);
     
     println!("Uploading benchmark scores to {}...", url);
     println!("Model: {}/{}", request.model_name, request.quantization);
     println!("Benchmarks: {} scores", request.benchmark_scores.len());
     
-    let response = client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await?;
+    let response = http::post_json(&url, &request).await?;
     
     if response.status().is_success() {
         let result: llm_benchmark_types::UploadBenchmarkResponse = response.json().await?;
@@ -1284,31 +2005,166 @@ async fn upload_benchmark_scores(request: llm_benchmark_types::UploadBenchmarkRe
                 println!("Model variant ID: {}", variant_id);
             }
         } else {
-            return Err(anyhow!("Upload failed: {}", result.message));
+            return Err(UploaderError::Upload(result.message));
         }
     } else {
-        let error_text = response.text().await?;
-        return Err(anyhow!("Upload failed: {}", error_text));
+        return Err(error::response_error(response).await);
     }
     
     Ok(())
 }
 
-async fn upload_custom(file: PathBuf, server: String) -> Result<()> {
-    let content = std::fs::read_to_string(&file)?;
-    let experiment_run: ExperimentRun = serde_json::from_str(&content)?;
+async fn upload_custom(file: PathBuf, server: String) -> Result<(), UploaderError> {
+    let content = read_file(&file)?;
+    let experiment_run: ExperimentRun = parse_json(&content, &file)?;
     
     upload_experiment(experiment_run, &server).await?;
     
     Ok(())
 }
 
-fn parse_hardware_info(result: &LlamaBenchResult) -> Result<HardwareConfig> {
+async fn lint_file(file: PathBuf, format: Option<LintFormat>, server: Option<String>) -> Result<(), UploaderError> {
+    let content = read_file(&file)?;
+    let format = format.unwrap_or_else(|| detect_lint_format(&content));
+    let path = file.display().to_string();
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    match format {
+        LintFormat::MmluPro => {
+            // report.txt only carries benchmark scores, so lint those directly
+            let score = BenchmarkScoreType::MMLUPro(mmlu_pro::parse_report(&content)?);
+            if let Err(e) = score.validate() {
+                errors.push(format!("{}: error: {}", path, e));
+            }
+            warnings.extend(score.warnings().into_iter().map(|w| format!("{}: warning: {}", path, w)));
+            if server.is_some() {
+                println!("ℹ️  Server validation only applies to experiment files, skipping");
+            }
+        }
+        LintFormat::LlamaBench | LintFormat::Custom => {
+            let experiment_run = parse_lint_experiment(&content, format, &file)?;
+
+            if let Err(e) = experiment_run.validate() {
+                let field = match &e {
+                    ValidationError::InvalidField { field, .. }
+                    | ValidationError::MissingField { field }
+                    | ValidationError::OutOfRange { field, .. } => field.as_str(),
+                };
+                let line = match format {
+                    LintFormat::Custom => field_line(&content, field),
+                    _ => None,
+                };
+                match line {
+                    Some(line) => errors.push(format!("{}:{}: error: {}", path, line, e)),
+                    None => errors.push(format!("{}: error: {}", path, e)),
+                }
+            }
+            warnings.extend(
+                experiment_run
+                    .warnings()
+                    .into_iter()
+                    .map(|w| format!("{}: warning: {}", path, w)),
+            );
+
+            if let Some(server) = server {
+                let result = validate_on_server(experiment_run, &server).await?;
+                // The server applies the same rules, so only report its
+                // error if it caught something the local checks didn't
+                if let Some(error) = result.error.filter(|_| errors.is_empty()) {
+                    errors.push(format!("{}: error (server): {}", path, error));
+                }
+                for warning in result.warnings {
+                    let warning = format!("{}: warning: {}", path, warning);
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            }
+        }
+    }
+
+    for warning in &warnings {
+        println!("⚠️  {}", warning);
+    }
+    for error in &errors {
+        eprintln!("❌ {}", error);
+    }
+
+    if errors.is_empty() {
+        println!("✅ {} passed with {} warning(s)", path, warnings.len());
+        Ok(())
+    } else {
+        Err(UploaderError::Validation(format!("{} failed with {} error(s)", path, errors.len())))
+    }
+}
+
+/// Guess a result file's format: llama-bench emits a JSON array, experiment
+/// files are a JSON object, anything else is treated as an MMLU-Pro report
+fn detect_lint_format(content: &str) -> LintFormat {
+    match content.trim_start().chars().next() {
+        Some('[') => LintFormat::LlamaBench,
+        Some('{') => LintFormat::Custom,
+        _ => LintFormat::MmluPro,
+    }
+}
+
+/// Parse a JSON result file into an experiment run
+fn parse_lint_experiment(
+    content: &str,
+    format: LintFormat,
+    file: &std::path::Path,
+) -> Result<ExperimentRun, UploaderError> {
+    if format == LintFormat::Custom {
+        return parse_json(content, file);
+    }
+
+    let results: Vec<LlamaBenchResult> = parse_json(content, file)?;
+    if results.is_empty() {
+        return Err(UploaderError::UnrecognizedFormat("No results found in llama-bench output".to_string()));
+    }
+
+    llama_bench_experiment_run(&results, Uuid::now_v7(), None, None, None, None, MemoryUsage::default())
+        .map_err(|e| UploaderError::UnrecognizedFormat(e.to_string()))
+}
+
+/// Line number of the JSON key named by the last segment of a validation
+/// field path, if that key appears exactly once in the file
+fn field_line(content: &str, field: &str) -> Option<usize> {
+    let key = field.rsplit('.').next()?.split('[').next()?;
+    let needle = format!("\"{}\"", key);
+    let mut matches = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(&needle));
+
+    let (line, _) = matches.next()?;
+    matches.next().is_none().then_some(line + 1)
+}
+
+async fn validate_on_server(
+    experiment_run: ExperimentRun,
+    server: &str,
+) -> Result<llm_benchmark_types::ValidateExperimentResponse, UploaderError> {
+    let url = format!("{}/api/validate-experiment", server);
+
+    let response = http::post_json(&url, &UploadRequest { experiment_run, force: false }).await?;
+
+    if !response.status().is_success() {
+        return Err(error::response_error(response).await);
+    }
+
+    Ok(response.json().await?)
+}
+
+fn parse_hardware_info(result: &LlamaBenchResult, gpu_memory_override: Option<i32>) -> Result<HardwareConfig> {
     // Parse CPU architecture from CPU info string
-    let cpu_arch = detect_cpu_arch(&result.cpu_info);
+    let cpu_arch = HardwareConfig::detect_cpu_arch(&result.cpu_info);
     
-    // Determine GPU memory from GPU info
-    let (gpu_memory_gb, gpu_model) = parse_gpu_info(&result.gpu_info);
+    // Determine GPU memory from GPU info, unless given on the command line
+    let (detected_memory_gb, gpu_model) = gpu_info::parse_gpu_info(&result.gpu_info);
+    let gpu_memory_gb = gpu_memory_override.unwrap_or(detected_memory_gb);
     
     // Extract optimizations from backends
     let mut optimizations = Vec::new();
@@ -1334,92 +2190,25 @@ fn parse_hardware_info(result: &LlamaBenchResult) -> Result<HardwareConfig> {
     Ok(HardwareConfig {
         gpu_model,
         gpu_memory_gb,
+        gpu_count: 1,
+        total_gpu_memory_gb: None,
         cpu_model: result.cpu_info.clone(),
         cpu_arch: cpu_arch.to_string(),
         ram_gb: None, // Not available in llama-bench output
         ram_type: None, // Not available in llama-bench output
+        cpu_threads: Some(result.n_threads),
+        numa_nodes: None, // Not available in llama-bench output
         virtualization_type: None,
         optimizations,
     })
 }
 
-fn detect_cpu_arch(cpu_info: &str) -> &'static str {
-    let cpu_lower = cpu_info.to_lowercase();
-    
-    if cpu_lower.contains("threadripper 3") || cpu_lower.contains("ryzen 3") {
-        "zen2"
-    } else if cpu_lower.contains("threadripper 5") || cpu_lower.contains("ryzen 5") {
-        "zen3"
-    } else if cpu_lower.contains("threadripper 7") || cpu_lower.contains("ryzen 7") || cpu_lower.contains("ryzen 9") {
-        "zen4"
-    } else if cpu_lower.contains("intel") && cpu_lower.contains("12th") {
-        "alderlake"
-    } else if cpu_lower.contains("intel") && cpu_lower.contains("13th") {
-        "raptorlake"
-    } else if cpu_lower.contains("apple m1") {
-        "apple_m1"
-    } else if cpu_lower.contains("apple m2") {
-        "apple_m2"  
-    } else if cpu_lower.contains("apple m3") {
-        "apple_m3"
-    } else {
-        "unknown"
-    }
-}
-
-fn parse_gpu_info(gpu_info: &str) -> (i32, String) {
-    // Handle CPU-only systems
-    if gpu_info.is_empty() || gpu_info == "" {
-        return (0, "CPU Only".to_string());
-    }
-    
-    let gpu_lower = gpu_info.to_lowercase();
-    
-    // Clean up the GPU name - remove common prefixes
-    let clean_name = gpu_info
-        .replace("NVIDIA GeForce ", "")
-        .replace("NVIDIA ", "")
-        .replace("AMD Radeon ", "")
-        .replace("Intel Arc ", "Arc ")
-        .trim()
-        .to_string();
-    
-    // Determine memory based on GPU model
-    let memory_gb = if gpu_lower.contains("rtx 4090") {
-        24
-    } else if gpu_lower.contains("rtx 4080") {
-        16
-    } else if gpu_lower.contains("rtx 4070 ti") {
-        12
-    } else if gpu_lower.contains("rtx 4070") {
-        12
-    } else if gpu_lower.contains("rtx 3090") {
-        24
-    } else if gpu_lower.contains("rtx 3080") {
-        10
-    } else if gpu_lower.contains("a100") && gpu_lower.contains("80") {
-        80
-    } else if gpu_lower.contains("a100") && gpu_lower.contains("40") {
-        40
-    } else if gpu_lower.contains("a100") {
-        40 // Default A100 size
-    } else if gpu_lower.contains("h100") {
-        80
-    } else if gpu_lower.contains("7900") {
-        24
-    } else {
-        0 // Unknown or CPU-only
-    };
-    
-    (memory_gb, clean_name)
-}
-
-fn parse_model_filename(filename: &str) -> Result<ModelInfo> {
+fn parse_model_filename(filename: &str) -> Result<ModelInfo, UploaderError> {
     // Example: /mnt/llm-models/GGUF/TheDrummer/Snowpiercer-15B-v1/Snowpiercer-15B-v1.Q3_K_L.gguf
     let path = std::path::Path::new(filename);
     let file_name = path.file_stem()
         .and_then(|s| s.to_str())
-        .ok_or_else(|| anyhow!("Invalid filename"))?;
+        .ok_or_else(|| UploaderError::UnrecognizedFormat(format!("Invalid model filename: {}", filename)))?;
     
     // Extract parent directories for model name
     let mut path_components = Vec::new();
@@ -1452,9 +2241,9 @@ fn parse_model_filename(filename: &str) -> Result<ModelInfo> {
         // Look for quantization patterns
         let potential_quant = parts[parts.len() - 1];
         if potential_quant.starts_with('Q') || potential_quant.starts_with('F') {
-            potential_quant.to_string()
+            normalize_quantization(potential_quant)
         } else if parts.len() >= 3 {
-            parts[parts.len() - 2].to_string()
+            normalize_quantization(parts[parts.len() - 2])
         } else {
             "unknown".to_string()
         }
@@ -1468,18 +2257,92 @@ fn parse_model_filename(filename: &str) -> Result<ModelInfo> {
     })
 }
 
-async fn upload_benchmarks_only(test_run_id: String, file: PathBuf, _server: String) -> Result<()> {
-    println!("Uploading benchmark scores for test run {}...", test_run_id);
-    
+/// Read a list of benchmark scores from `path`, in our tagged form or the
+/// flat layouts other tools write, with 0-1 fractions converted to percent
+fn read_benchmark_scores(path: &Path) -> Result<Vec<BenchmarkScoreType>, UploaderError> {
+    let content = read_file(path)?;
+    let mut scores: Vec<BenchmarkScoreType> = parse_json::<Vec<LenientBenchmarkScore>>(&content, path)?
+        .into_iter()
+        .map(BenchmarkScoreType::from)
+        .collect();
+    normalize_score_fractions(&mut scores);
+    Ok(scores)
+}
+
+/// Convert scores given as 0-1 fractions to percent, warning about each
+fn normalize_score_fractions(scores: &mut [BenchmarkScoreType]) {
+    for score in scores {
+        for field in score.normalize_fractions() {
+            println!("⚠️  {} {} looks like a 0-1 fraction, converted to percent", score.benchmark_name(), field);
+        }
+    }
+}
+
+async fn upload_benchmarks_only(test_run_id: String, file: PathBuf, server: String) -> Result<()> {
+    let test_run_id: Uuid = test_run_id
+        .parse()
+        .map_err(|e| anyhow!("Invalid test run ID '{}': {}", test_run_id, e))?;
+
     // Read benchmark scores
-    let content = std::fs::read_to_string(&file)?;
-    let benchmark_scores: Vec<BenchmarkScoreType> = serde_json::from_str(&content)?;
-    
-    // TODO: Implement API endpoint for adding benchmarks to existing test run
-    // For now, this is a placeholder
-    println!("⚠️  Note: The API endpoint for adding benchmarks to existing test runs is not yet implemented.");
-    println!("Benchmark scores loaded: {} benchmarks", benchmark_scores.len());
-    
+    let benchmark_scores = read_benchmark_scores(&file)?;
+
+    let url = format!("{}/api/test-run/{}/benchmarks", server, test_run_id);
+    println!("Uploading {} benchmark scores to {}...", benchmark_scores.len(), url);
+
+    let response = http::post_json(&url, &benchmark_scores).await?;
+
+    if !response.status().is_success() {
+        return Err(error::response_error(response).await.into());
+    }
+
+    let result: AttachBenchmarksResponse = response.json().await.map_err(UploaderError::from)?;
+    println!("✅ Attached {} benchmark scores to test run {}", result.scores_uploaded, result.test_run_id);
+
+    Ok(())
+}
+
+/// Upload the scores of every `*.json` file in `dir` in one request. Files
+/// that can't be parsed are reported and left out.
+async fn upload_benchmarks_dir(
+    dir: PathBuf,
+    server: String,
+    model_name: String,
+    quantization: String,
+) -> Result<()> {
+    let read = benchmarks_dir::read_scores(&dir).await?;
+    for (file, count) in &read.files {
+        println!("  {}: {} score(s)", file.display(), count);
+    }
+    for (file, error) in &read.failed {
+        println!("⚠️  Skipping {}: {}", file.display(), error);
+    }
+
+    let mut benchmark_scores = read.scores;
+    if benchmark_scores.is_empty() {
+        return Err(UploaderError::MissingField(format!(
+            "benchmark scores (no readable *.json files in {})",
+            dir.display()
+        ))
+        .into());
+    }
+    normalize_score_fractions(&mut benchmark_scores);
+
+    let upload_request = llm_benchmark_types::UploadBenchmarkRequest {
+        model_name,
+        quantization,
+        lora_adapter: None,
+        timestamp: benchmark_scores.iter().map(|score| score.timestamp()).max(),
+        benchmark_scores,
+        keep_history: false,
+        merge: false,
+    };
+
+    upload_benchmark_scores(upload_request, &server).await?;
+
+    if !read.failed.is_empty() {
+        println!("⚠️  {} file(s) in {} were not uploaded", read.failed.len(), dir.display());
+    }
+
     Ok(())
 }
 
@@ -1504,93 +2367,13 @@ async fn upload_mmlu_pro(
     };
 
     // Read and parse the report.txt file
-    let content = std::fs::read_to_string(&file)?;
-    let mut categories = Vec::new();
-    let mut overall_score = 0.0;
-    let mut test_timestamp = chrono::Utc::now();
-    
-    // Parse lines looking for category scores
-    let lines: Vec<&str> = content.lines().collect();
-    
-    for line in lines {
-        // Look for timestamp at the beginning (only if line looks like a timestamp)
-        if line.len() > 20 && line.chars().nth(4) == Some('-') && line.chars().nth(7) == Some('-') {
-            // Parse without timezone first, then convert to UTC
-            if let Ok(naive_dt) = NaiveDateTime::parse_from_str(line.trim(), "%Y-%m-%d %H:%M:%S%.f") {
-                test_timestamp = DateTime::from_naive_utc_and_offset(naive_dt, Utc);
-            }
-        }
-        
-        // Skip individual category parsing since we'll use the markdown table
-        // The report format has individual categories but we'll parse from the summary table
-        
-        // Look for the markdown table with all scores
-        if line.starts_with("| overall") {
-            // Skip the header and separator lines
-            continue;
-        } else if line.starts_with("| ") && line.contains(" | ") && !line.contains("---") {
-            // This is the data row with all scores
-            let parts: Vec<&str> = line.split(" | ").map(|s| s.trim_matches('|').trim()).collect();
-            if parts.len() >= 15 {
-                // Parse overall score
-                overall_score = parts[0].parse::<f64>().unwrap_or(0.0);
-                
-                // Category names in order from the header
-                let category_names = vec![
-                    "biology", "business", "chemistry", "computer science", "economics",
-                    "engineering", "health", "history", "law", "math",
-                    "philosophy", "physics", "psychology", "other"
-                ];
-                
-                // Actual MMLU-Pro question counts per category
-                let question_counts: Vec<i32> = vec![
-                    71,   // biology
-                    78,   // business
-                    113,  // chemistry
-                    41,   // computer science
-                    84,   // economics
-                    96,   // engineering
-                    81,   // health
-                    38,   // history
-                    110,  // law
-                    135,  // math
-                    49,   // philosophy
-                    129,  // physics
-                    79,   // psychology
-                    92    // other
-                ];
-                
-                // Parse individual category scores
-                for (i, category_name) in category_names.iter().enumerate() {
-                    if let Ok(score) = parts[i + 1].parse::<f64>() {
-                        // Use actual MMLU-Pro question counts
-                        let total_questions = question_counts[i];
-                        let estimated_correct = (score / 100.0 * total_questions as f64).round() as i32;
-                        
-                        categories.push(MMLUCategoryScore {
-                            category: category_name.to_string(),
-                            score,
-                            total_questions,
-                            correct_answers: estimated_correct,
-                        });
-                    }
-                }
-            }
-        }
+    let content = read_file(&file)?;
+    let mut mmlu_score = mmlu_pro::parse_report(&content)?;
+    let test_timestamp = mmlu_score.timestamp;
+    if let Some(serde_json::Value::Object(context)) = &mut mmlu_score.context {
+        context.insert("report_file".to_string(), serde_json::json!(file.to_string_lossy()));
     }
     
-    // Create MMLU score
-    let mmlu_score = MMLUScore {
-        categories,
-        timestamp: test_timestamp,
-        context: Some(serde_json::json!({
-            "source": "mmlu-pro",
-            "report_file": file.to_string_lossy(),
-            "overall_score": overall_score,
-            "note": "Question counts are estimated as report.txt doesn't include them"
-        })),
-    };
-    
     // Check if we're uploading to an existing test run or creating benchmark scores
     if let Some(_test_id) = test_run_id {
         // TODO: Implement adding benchmarks to existing test run
@@ -1599,16 +2382,18 @@ async fn upload_mmlu_pro(
     }
     
     // Ensure we have model and quantization
-    let model = model.ok_or_else(|| anyhow!("Model name is required when not specifying test run ID"))?;
-    let quantization = quantization.ok_or_else(|| anyhow!("Quantization is required when not specifying test run ID"))?;
+    let model = model.ok_or_else(|| UploaderError::MissingField("model name (required when not specifying test run ID)".to_string()))?;
+    let quantization = quantization.ok_or_else(|| UploaderError::MissingField("quantization (required when not specifying test run ID)".to_string()))?;
     
     // Upload benchmark scores to the new endpoint
     let upload_request = llm_benchmark_types::UploadBenchmarkRequest {
         model_name: model,
         quantization,
         lora_adapter,
-        benchmark_scores: vec![BenchmarkScoreType::MMLU(mmlu_score)],
+        benchmark_scores: vec![BenchmarkScoreType::MMLUPro(mmlu_score)],
         timestamp: Some(test_timestamp),
+        keep_history: false,
+        merge: false,
     };
     
     upload_benchmark_scores(upload_request, &server).await?;
@@ -1616,19 +2401,63 @@ async fn upload_mmlu_pro(
     Ok(())
 }
 
-async fn upload_experiment(experiment_run: ExperimentRun, server: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+async fn upload_hf_leaderboard(
+    file: PathBuf,
+    model: String,
+    quantization: Option<String>,
+    server: String,
+) -> Result<()> {
+    let content = read_file(&file)?;
+    let results: serde_json::Value = parse_json(&content, &file)?;
+
+    let quantization = quantization
+        .or_else(|| hf_leaderboard::quantization_from_dtype(&results).map(str::to_string))
+        .ok_or_else(|| UploaderError::MissingField("quantization (results have no recognized model_dtype)".to_string()))?;
+
+    let timestamp = Utc::now();
+    let benchmark_scores = hf_leaderboard::parse_results(&results, timestamp)?;
+    for score in &benchmark_scores {
+        println!("  {}: {:.2}", score.benchmark_name(), score.overall_score());
+    }
+
+    let upload_request = llm_benchmark_types::UploadBenchmarkRequest {
+        model_name: model,
+        quantization,
+        lora_adapter: None,
+        benchmark_scores,
+        timestamp: Some(timestamp),
+        keep_history: false,
+        merge: false,
+    };
+
+    upload_benchmark_scores(upload_request, &server).await?;
+
+    Ok(())
+}
+
+async fn upload_experiment(experiment_run: ExperimentRun, server: &str) -> Result<(), UploaderError> {
     let url = format!("{}/api/upload-experiment", server);
 
-    let request = UploadRequest { experiment_run };
+    let request = UploadRequest {
+        experiment_run,
+        force: OVERWRITE.get().copied().unwrap_or_default(),
+    };
 
     println!("Uploading experiment to {}...", url);
 
-    let response = client
-        .post(&url)
-        .json(&request)
-        .send()
-        .await?;
+    let response = http::post_json(&url, &request).await?;
+
+    if response.status() == reqwest::StatusCode::CONFLICT {
+        let result: llm_benchmark_types::UploadExperimentResponse = response.json().await?;
+        return Err(match result.test_run_id {
+            Some(existing) => UploaderError::Duplicate(existing),
+            None => UploaderError::Upload(result.error.unwrap_or_else(|| "Duplicate run".to_string())),
+        });
+    }
+
+    if !response.status().is_success() {
+        return Err(error::response_error(response).await);
+    }
 
     let result: llm_benchmark_types::UploadExperimentResponse = response.json().await?;
 
@@ -1637,15 +2466,69 @@ async fn upload_experiment(experiment_run: ExperimentRun, server: &str) -> Resul
         if let Some(test_run_id) = result.test_run_id {
             println!("Test run ID: {}", test_run_id);
         }
-        if !result.warnings.is_empty() {
+        report_warnings(result.warnings)?;
+    } else {
+        let error_msg = result.error.unwrap_or_else(|| "Unknown error".to_string());
+        return Err(UploaderError::Upload(error_msg));
+    }
+
+    Ok(())
+}
+
+/// Print the warnings from an accepted upload, or fail on them under
+/// `--warnings-as-errors`
+fn report_warnings(warnings: Vec<String>) -> Result<(), UploaderError> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+
+    match WARNING_POLICY.get().copied().unwrap_or_default() {
+        WarningPolicy::Quiet => Ok(()),
+        WarningPolicy::Error => Err(UploaderError::Warnings(warnings)),
+        WarningPolicy::Print => {
             println!("⚠️  Warnings:");
-            for warning in &result.warnings {
+            for warning in &warnings {
                 println!("  - {}", warning);
             }
+            Ok(())
+        }
+    }
+}
+
+async fn upload_complete(
+    experiment_run: ExperimentRun,
+    benchmark_scores: Vec<BenchmarkScoreType>,
+    server: &str,
+) -> Result<(), UploaderError> {
+    let url = format!("{}/api/upload-complete", server);
+
+    let request = UploadCompleteRequest {
+        experiment_run,
+        benchmark_scores,
+    };
+
+    println!("Uploading experiment and {} benchmark scores to {}...", request.benchmark_scores.len(), url);
+
+    let response = http::post_json(&url, &request).await?;
+
+    if !response.status().is_success() {
+        return Err(error::response_error(response).await);
+    }
+
+    let result: UploadCompleteResponse = response.json().await?;
+
+    if result.success {
+        println!("✅ Upload successful!");
+        if let Some(test_run_id) = result.test_run_id {
+            println!("Test run ID: {}", test_run_id);
         }
+        if let Some(model_variant_id) = result.model_variant_id {
+            println!("Model variant ID: {} ({} scores)", model_variant_id, result.scores_uploaded);
+        }
+        report_warnings(result.warnings)?;
     } else {
         let error_msg = result.error.unwrap_or_else(|| "Unknown error".to_string());
-        return Err(anyhow!("Upload failed: {}", error_msg));
+        return Err(UploaderError::Upload(error_msg));
     }
 
     Ok(())
@@ -1759,6 +2642,9 @@ async fn upload_local_results(
     llm_json_path: PathBuf,
     results_json_path: PathBuf,
     server: String,
+    log_file: Option<PathBuf>,
+    label: Option<String>,
+    gpu_stats_file: Option<PathBuf>,
 ) -> Result<()> {
     println!("📤 Uploading local benchmark results...");
 
@@ -1806,10 +2692,14 @@ async fn upload_local_results(
     let hardware_config = HardwareConfig {
         gpu_model: artifact.gpu_model.clone(),
         gpu_memory_gb: artifact.gpu_memory_gb,
+        gpu_count: artifact.gpu_count.max(1),
+        total_gpu_memory_gb: None,
         cpu_model: artifact.cpu_model.clone(),
         cpu_arch: artifact.cpu_arch.clone(),
         ram_gb: artifact.ram_gb,
         ram_type: None,
+        cpu_threads: None,
+        numa_nodes: artifact.numa_nodes,
         virtualization_type: None,
         optimizations: Vec::new(),
     };
@@ -1818,7 +2708,19 @@ async fn upload_local_results(
     let timestamp = parse_timestamp(&result.timestamp)?;
 
     // Create performance metrics
-    let performance_metrics = build_performance_metrics(&result, timestamp);
+    let mut performance_metrics = build_performance_metrics(&result, timestamp);
+
+    if let Some(path) = gpu_stats_file {
+        println!("Reading GPU stats from: {}", path.display());
+        let stats = gpu_stats::parse_gpu_stats(&read_file(&path)?, &path)?;
+        if let Some(util) = stats.utilization_percent {
+            println!("  GPU Utilization: {:.0}%", util);
+        }
+        if let Some(vram) = stats.vram_used_gb {
+            println!("  VRAM Used: {:.1} GB", vram);
+        }
+        performance_metrics.extend(stats.to_metrics(timestamp));
+    }
 
     // Build notes
     let notes_str = format!(
@@ -1851,13 +2753,26 @@ async fn upload_local_results(
         new_id
     };
 
+    // Read the console log lossily, since backends can emit invalid UTF-8
+    let log = match log_file {
+        Some(path) => {
+            println!("Reading console log from: {}", path.display());
+            let bytes = std::fs::read(&path).map_err(|source| UploaderError::Io { path, source })?;
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        None => None,
+    };
+
     // Create experiment run
     let experiment_run = ExperimentRun {
         id: exp_uuid,
         model_name: artifact.model_name.clone(),
         quantization: artifact.quantization.clone(),
+        lora_adapter: artifact.lora_adapter.clone(),
         backend: artifact.backend_name.clone().unwrap_or_else(|| "Unknown".to_string()),
         backend_version: artifact.backend_version.clone().unwrap_or_else(|| result.version.clone()),
+        model_size_bytes: None,
+        model_n_params: None,
         hardware_config,
         performance_metrics,
         benchmark_scores: Vec::new(),
@@ -1869,6 +2784,8 @@ async fn upload_local_results(
         load_pattern: Some(result.configuration.load_pattern.clone()),
         dataset_name,
         gpu_power_limit_watts: artifact.gpu_power_limit_watts,
+        label: label.or(artifact.label.clone()),
+        log,
     };
 
     // Upload to server
@@ -1885,8 +2802,9 @@ async fn import_from_systemslab(
     id: String,
     systemslab_url: String,
     server: String,
+    label: Option<String>,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = http::client()?;
 
     // Try to fetch as an experiment first
     let experiment_query = serde_json::json!({
@@ -1910,7 +2828,7 @@ async fn import_from_systemslab(
         && experiment_result.data.is_some()
         && experiment_result.data.as_ref().unwrap().experiment_by_id.is_some() {
         println!("Detected as experiment ID");
-        return import_single_experiment(id, systemslab_url, server, &client).await;
+        return import_single_experiment(id, systemslab_url, server, label, &client).await;
     }
 
     // Try as a context
@@ -1977,6 +2895,7 @@ async fn import_from_systemslab(
             experiment.id.clone(),
             systemslab_url.clone(),
             server.clone(),
+            label.clone(),
             &client,
         ).await {
             Ok(_) => {
@@ -2008,6 +2927,7 @@ async fn import_single_experiment(
     experiment_id: String,
     systemslab_url: String,
     server: String,
+    label: Option<String>,
     client: &reqwest::Client,
 ) -> Result<()> {
     println!("Fetching experiment: {}", experiment_id);
@@ -2059,6 +2979,7 @@ async fn import_single_experiment(
             experiment_id,
             systemslab_url,
             server,
+            label,
             client,
             llm_artifact.id.clone(),
             &experiment.artifact,
@@ -2083,6 +3004,7 @@ async fn upload_performance_from_systemslab(
     experiment_id: String,
     systemslab_url: String,
     server: String,
+    label: Option<String>,
     client: &reqwest::Client,
     llm_artifact_id: String,
     all_artifacts: &[SystemslabArtifact],
@@ -2143,10 +3065,14 @@ async fn upload_performance_from_systemslab(
     let hardware_config = HardwareConfig {
         gpu_model: artifact.gpu_model.clone(),
         gpu_memory_gb: artifact.gpu_memory_gb,
+        gpu_count: artifact.gpu_count.max(1),
+        total_gpu_memory_gb: None,
         cpu_model: artifact.cpu_model.clone(),
         cpu_arch: artifact.cpu_arch.clone(),
         ram_gb: artifact.ram_gb,
         ram_type: None,
+        cpu_threads: None,
+        numa_nodes: artifact.numa_nodes,
         virtualization_type: None,
         optimizations: Vec::new(),
     };
@@ -2193,8 +3119,11 @@ async fn upload_performance_from_systemslab(
         id: exp_uuid,
         model_name: artifact.model_name.clone(),
         quantization: artifact.quantization.clone(),
+        lora_adapter: artifact.lora_adapter.clone(),
         backend: artifact.backend_name.clone().unwrap_or_else(|| "vLLM".to_string()),
         backend_version: artifact.backend_version.clone().unwrap_or_else(|| result.version.clone()),
+        model_size_bytes: None,
+        model_n_params: None,
         hardware_config,
         performance_metrics,
         benchmark_scores: Vec::new(),
@@ -2206,6 +3135,8 @@ async fn upload_performance_from_systemslab(
         load_pattern: Some(result.configuration.load_pattern.clone()),
         dataset_name,
         gpu_power_limit_watts: artifact.gpu_power_limit_watts,
+        label: label.or(artifact.label.clone()),
+        log: None,
     };
 
     // Upload to server
@@ -2266,10 +3197,10 @@ async fn upload_mmlu_from_systemslab(
     // Parse MMLU results based on format
     let mmlu_score = if mmlu_artifact.name == "mmlu-results.json" {
         // Parse JSON format
-        serde_json::from_str::<MMLUScore>(&mmlu_content)?
+        serde_json::from_str::<MMLUProScore>(&mmlu_content)?
     } else {
         // Parse report.txt format (MMLU-Pro)
-        parse_mmlu_pro_report(&mmlu_content)?
+        mmlu_pro::parse_report(&mmlu_content)?
     };
 
     println!("Parsed MMLU results: {:.2}% overall", mmlu_score.overall_score());
@@ -2279,8 +3210,10 @@ async fn upload_mmlu_from_systemslab(
         model_name: artifact.model_name.clone(),
         quantization: artifact.quantization.clone(),
         lora_adapter: artifact.lora_adapter.clone(),
-        benchmark_scores: vec![benchmarks::BenchmarkScoreType::MMLU(mmlu_score)],
+        benchmark_scores: vec![benchmarks::BenchmarkScoreType::MMLUPro(mmlu_score)],
         timestamp: Some(Utc::now()),
+        keep_history: false,
+        merge: false,
     };
 
     // Upload to server
@@ -2292,62 +3225,6 @@ async fn upload_mmlu_from_systemslab(
     Ok(())
 }
 
-/// Parse MMLU-Pro report.txt format
-fn parse_mmlu_pro_report(content: &str) -> Result<MMLUScore> {
-    let mut categories = Vec::new();
-    let lines: Vec<&str> = content.lines().collect();
-
-    for line in lines {
-        // Look for the markdown table with all scores
-        if line.starts_with("| ") && line.contains(" | ") && !line.contains("---") && !line.starts_with("| overall") {
-            // This is the data row with all scores
-            let parts: Vec<&str> = line.split(" | ").map(|s| s.trim_matches('|').trim()).collect();
-            if parts.len() >= 15 {
-                // Category names in order from the header
-                let category_names = vec![
-                    "biology", "business", "chemistry", "computer science", "economics",
-                    "engineering", "health", "history", "law", "math",
-                    "philosophy", "physics", "psychology", "other"
-                ];
-
-                // Actual MMLU-Pro question counts per category
-                let question_counts: Vec<i32> = vec![
-                    71, 78, 113, 41, 84, 96, 81, 38, 110, 135, 49, 129, 79, 92
-                ];
-
-                // Parse individual category scores
-                for (i, category_name) in category_names.iter().enumerate() {
-                    if let Ok(score) = parts[i + 1].parse::<f64>() {
-                        let total_questions = question_counts[i];
-                        let estimated_correct = (score / 100.0 * total_questions as f64).round() as i32;
-
-                        categories.push(MMLUCategoryScore {
-                            category: category_name.to_string(),
-                            score,
-                            total_questions,
-                            correct_answers: estimated_correct,
-                        });
-                    }
-                }
-                break;
-            }
-        }
-    }
-
-    if categories.is_empty() {
-        return Err(anyhow!("Failed to parse MMLU-Pro report - no scores found"));
-    }
-
-    Ok(MMLUScore {
-        categories,
-        timestamp: Utc::now(),
-        context: Some(serde_json::json!({
-            "source": "mmlu-pro",
-            "format": "report.txt"
-        })),
-    })
-}
-
 async fn upload_from_systemslab(
     experiment_id: String,
     systemslab_url: String,
@@ -2356,7 +3233,7 @@ async fn upload_from_systemslab(
 ) -> Result<()> {
     println!("Fetching experiment from SystemsLab: {}", experiment_id);
 
-    let client = reqwest::Client::new();
+    let client = http::client()?;
 
     // Query GraphQL API for experiment metadata
     let graphql_query = serde_json::json!({
@@ -2496,7 +3373,7 @@ async fn upload_from_systemslab(
 
     // Build hardware config from systeminfo.json
     let cpu_model = systeminfo.hwinfo.cpus[0].model_name.clone();
-    let cpu_arch = detect_cpu_arch(&cpu_model);
+    let cpu_arch = HardwareConfig::detect_cpu_arch(&cpu_model);
     let ram_gb = Some((systeminfo.hwinfo.memory.total_bytes / 1024 / 1024 / 1024) as i32);
 
     // Try to detect GPU from local system as fallback
@@ -2506,10 +3383,14 @@ async fn upload_from_systemslab(
     let hardware_config = HardwareConfig {
         gpu_model,
         gpu_memory_gb,
+        gpu_count: 1,
+        total_gpu_memory_gb: None,
         cpu_model,
         cpu_arch: cpu_arch.to_string(),
         ram_gb,
         ram_type: None,
+        cpu_threads: None,
+        numa_nodes: None,
         virtualization_type: None,
         optimizations: Vec::new(),
     };
@@ -2543,8 +3424,11 @@ async fn upload_from_systemslab(
         id: exp_uuid,
         model_name,
         quantization,
+        lora_adapter: None,
         backend,
         backend_version,
+        model_size_bytes: None,
+        model_n_params: None,
         hardware_config,
         performance_metrics,
         benchmark_scores: Vec::new(),
@@ -2556,6 +3440,8 @@ async fn upload_from_systemslab(
         load_pattern: Some(result.configuration.load_pattern.clone()),
         dataset_name,
         gpu_power_limit_watts: gpu_power_limit,
+        label: None,
+        log: None,
     };
 
     // Upload to server
@@ -2572,7 +3458,7 @@ async fn upload_from_systemslab_context(
 ) -> Result<()> {
     println!("Fetching context from SystemsLab: {}", context_id);
 
-    let client = reqwest::Client::new();
+    let client = http::client()?;
 
     // Query GraphQL API for context metadata
     let graphql_query = serde_json::json!({
@@ -3015,22 +3901,27 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
         },
     ];
 
-    // Add all TTFT percentiles
+    // Add all TTFT percentiles, plus the mean under the standard name the
+    // grid, detail and comparison views read
     performance_metrics.extend(vec![
         PerformanceMetric {
-            metric_name: "ttft_mean_ms".to_string(),
+            metric_name: metric_names::FIRST_TOKEN_LATENCY_MS.to_string(),
             value: result.latency.ttft_mean_ms,
             unit: "ms".to_string(),
             timestamp,
             context: None,
         },
         PerformanceMetric {
-            metric_name: "ttft_p50_ms".to_string(),
-            value: result.latency.ttft_p50_ms,
+            metric_name: "ttft_mean_ms".to_string(),
+            value: result.latency.ttft_mean_ms,
             unit: "ms".to_string(),
             timestamp,
             context: None,
         },
+        PerformanceMetric {
+            timestamp,
+            ..PerformanceMetric::ttft_p50_ms(result.latency.ttft_p50_ms)
+        },
         PerformanceMetric {
             metric_name: "ttft_p90_ms".to_string(),
             value: result.latency.ttft_p90_ms,
@@ -3039,11 +3930,8 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
             context: None,
         },
         PerformanceMetric {
-            metric_name: "ttft_p95_ms".to_string(),
-            value: result.latency.ttft_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::ttft_p95_ms(result.latency.ttft_p95_ms)
         },
         PerformanceMetric {
             metric_name: "ttft_p99_ms".to_string(),
@@ -3064,11 +3952,8 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
             context: None,
         },
         PerformanceMetric {
-            metric_name: "tpot_p50_ms".to_string(),
-            value: result.latency.tpot_p50_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::tpot_p50_ms(result.latency.tpot_p50_ms)
         },
         PerformanceMetric {
             metric_name: "tpot_p90_ms".to_string(),
@@ -3078,11 +3963,8 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
             context: None,
         },
         PerformanceMetric {
-            metric_name: "tpot_p95_ms".to_string(),
-            value: result.latency.tpot_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::tpot_p95_ms(result.latency.tpot_p95_ms)
         },
         PerformanceMetric {
             metric_name: "tpot_p99_ms".to_string(),
@@ -3099,11 +3981,8 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
             context: None,
         },
         PerformanceMetric {
-            metric_name: "itl_p50_ms".to_string(),
-            value: result.latency.itl_p50_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::itl_p50_ms(result.latency.itl_p50_ms)
         },
         PerformanceMetric {
             metric_name: "itl_p90_ms".to_string(),
@@ -3113,11 +3992,8 @@ fn build_performance_metrics(result: &InferenceServerResult, timestamp: DateTime
             context: None,
         },
         PerformanceMetric {
-            metric_name: "itl_p95_ms".to_string(),
-            value: result.latency.itl_p95_ms,
-            unit: "ms".to_string(),
             timestamp,
-            context: None,
+            ..PerformanceMetric::itl_p95_ms(result.latency.itl_p95_ms)
         },
         PerformanceMetric {
             metric_name: "itl_p99_ms".to_string(),
@@ -3212,6 +4088,7 @@ async fn record_config(
     concurrent_requests: Option<i32>,
     max_context_length: Option<i32>,
     lora: Option<String>,
+    label: Option<String>,
     output: PathBuf,
 ) -> Result<()> {
     println!("🔍 Recording system configuration...");
@@ -3287,6 +4164,7 @@ async fn record_config(
         cpu_model: hardware.cpu_model.clone(),
         cpu_arch: hardware.cpu_arch.clone(),
         ram_gb: hardware.ram_gb,
+        numa_nodes: hardware.numa_nodes,
 
         // Model
         model_name: model_name.clone(),
@@ -3300,6 +4178,7 @@ async fn record_config(
         backend_version: detected_backend_version,
         concurrent_requests,
         max_context_length,
+        label,
 
         // Metadata
         hostname,
@@ -3341,6 +4220,9 @@ async fn record_config(
     if let Some(ctx_len) = artifact.max_context_length {
         println!("  Max Context Length: {}", ctx_len);
     }
+    if let Some(label) = &artifact.label {
+        println!("  Label: {}", label);
+    }
 
     Ok(())
 }
@@ -3407,10 +4289,10 @@ fn detect_power_limit() -> Result<Option<i32>> {
 fn detect_backend_version(backend: Option<&str>) -> Result<Option<String>> {
     use std::process::Command;
 
-    let backend_name = backend.unwrap_or("vllm");
+    let backend_name = backend.unwrap_or(Backend::Vllm.as_str());
 
-    match backend_name {
-        "vllm" => {
+    match backend_name.parse::<Backend>() {
+        Ok(Backend::Vllm) => {
             // Try vllm --version
             let output = Command::new("vllm")
                 .arg("--version")
@@ -3441,7 +4323,22 @@ fn detect_backend_version(backend: Option<&str>) -> Result<Option<String>> {
                 }
             }
         }
-        "llama.cpp" => {
+        Ok(Backend::Ollama) => {
+            // ollama outputs something like "ollama version is 0.5.7"
+            let output = Command::new("ollama")
+                .arg("--version")
+                .output();
+
+            if let Ok(output) = output {
+                if output.status.success() {
+                    let version_str = String::from_utf8_lossy(&output.stdout);
+                    if let Some(version) = version_str.split_whitespace().last() {
+                        return Ok(Some(version.to_string()));
+                    }
+                }
+            }
+        }
+        Ok(Backend::LlamaCpp) => {
             // Try to get version from llama-cli or main
             for binary in &["llama-cli", "main"] {
                 let output = Command::new(binary)
@@ -3476,4 +4373,80 @@ fn detect_backend_version(backend: Option<&str>) -> Result<Option<String>> {
     }
 
     Ok(None)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_usage_metric(memory_usage: MemoryUsage) -> PerformanceMetric {
+        let results: Vec<LlamaBenchResult> = serde_json::from_str(include_str!("../../llama-bench.json")).unwrap();
+        let run = llama_bench_experiment_run(&results, Uuid::nil(), None, None, None, Some(24), memory_usage).unwrap();
+        run.performance_metrics
+            .into_iter()
+            .find(|metric| metric.metric_name == "memory_usage_gb")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_read_benchmark_scores_normalizes_fractions() {
+        let path = std::env::temp_dir().join(format!("llm-perf-benchmarks-{}.json", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            r#"[{"pass_at_1": 0.45, "total_problems": 164, "timestamp": "2026-10-18T00:00:00Z"}]"#,
+        )
+        .unwrap();
+
+        let scores = read_benchmark_scores(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let scores = scores.unwrap();
+        assert_eq!(scores.len(), 1);
+        assert!((scores[0].overall_score() - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_memory_usage_source() {
+        let memory = |memory_gb| LlamaBenchMemory {
+            gpu_memory_gb: None,
+            memory_gb,
+            gpu_stats_file: None,
+            overhead_factor: 1.5,
+        };
+        let stats = gpu_stats::parse_gpu_stats(
+            r#"{"gpus": [{"memory.used": 10240}, {"memory.used": 8192}]}"#,
+            Path::new("gpu_stats.json"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            MemoryUsage::choose(&memory(Some(21.5)), Some(&stats)),
+            MemoryUsage::Measured { gb: 21.5, source: "--memory-gb" }
+        );
+        assert_eq!(
+            MemoryUsage::choose(&memory(None), Some(&stats)),
+            MemoryUsage::Measured { gb: 18.0, source: "gpu-stats-file" }
+        );
+        assert_eq!(
+            MemoryUsage::choose(&memory(None), None),
+            MemoryUsage::Estimated { overhead_factor: 1.5 }
+        );
+    }
+
+    #[test]
+    fn test_llama_bench_memory_usage() {
+        let measured = memory_usage_metric(MemoryUsage::Measured { gb: 21.5, source: "gpu-stats-file" });
+        let context = measured.context.unwrap();
+        assert_eq!(measured.value, 21.5);
+        assert_eq!(context["estimated"], false);
+        assert_eq!(context["source"], "gpu-stats-file");
+        assert!(context.get("overhead_factor").is_none());
+
+        let estimated = memory_usage_metric(MemoryUsage::Estimated { overhead_factor: 1.5 });
+        let context = estimated.context.unwrap();
+        let model_size_gb = 7982305280.0 / (1024.0 * 1024.0 * 1024.0);
+        assert!((estimated.value - model_size_gb * 1.5).abs() < 1e-9);
+        assert_eq!(context["estimated"], true);
+        assert_eq!(context["overhead_factor"], 1.5);
+        assert!(context.get("source").is_none());
+    }
+}
@@ -0,0 +1,155 @@
+// uploader/src/mmlu_pro.rs
+// Parse the MMLU-Pro harness's report.txt into an MMLU-Pro score
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use serde_json::json;
+
+use llm_benchmark_types::benchmarks::{MMLUProCategoryScore, MMLUProScore};
+
+use crate::error::UploaderError;
+
+/// Parse a report.txt. Scores come from the markdown summary table at the
+/// end, matched to categories by its header row. Question counts come from
+/// the `Total, correct/total, pct%` line after each `Finished testing
+/// <category>` line; a category without one has no counts and is marked
+/// `"estimated": true` under `categories` in the context.
+///
+/// The timestamp is the one the report starts with, or now if it has none.
+pub fn parse_report(content: &str) -> Result<MMLUProScore, UploaderError> {
+    let mut timestamp = None;
+    let mut counts: HashMap<&str, (i32, i32)> = HashMap::new();
+    let mut num_questions_total = None;
+    let mut header: Option<Vec<&str>> = None;
+    let mut values: Option<Vec<&str>> = None;
+    // What the next `Total` line counts: a category, or the whole run (None)
+    let mut section: Option<Option<&str>> = None;
+
+    for line in content.lines().map(str::trim) {
+        if timestamp.is_none() {
+            if let Ok(naive_dt) = NaiveDateTime::parse_from_str(line, "%Y-%m-%d %H:%M:%S%.f") {
+                timestamp = Some(naive_dt.and_utc());
+                continue;
+            }
+        }
+
+        if let Some(rest) = line.strip_prefix("Finished testing ") {
+            section = rest.rsplit_once(" in ").map(|(category, _)| Some(category));
+        } else if line.starts_with("Finished the benchmark") {
+            section = Some(None);
+        } else if let Some(rest) = line.strip_prefix("Total, ") {
+            match (section.take(), parse_count(rest)) {
+                (Some(Some(category)), Some(count)) => {
+                    counts.insert(category, count);
+                }
+                (Some(None), Some((_, total))) => num_questions_total = Some(total),
+                _ => {}
+            }
+        } else if line.starts_with('|') && !line.contains("---") {
+            // The first table row names the columns, the next holds the scores
+            let cells = line.trim_matches('|').split('|').map(str::trim).collect();
+            if header.is_none() {
+                header = Some(cells);
+            } else if values.is_none() {
+                values = Some(cells);
+            }
+        }
+    }
+
+    let (Some(header), Some(values)) = (header, values) else {
+        return Err(UploaderError::UnrecognizedFormat("MMLU-Pro report has no score table".to_string()));
+    };
+
+    let mut overall_score = None;
+    let mut categories = Vec::new();
+    let mut category_context = serde_json::Map::new();
+    for (&name, value) in header.iter().zip(&values) {
+        let Ok(score) = value.parse::<f64>() else {
+            continue;
+        };
+        if name == "overall" {
+            overall_score = Some(score);
+            continue;
+        }
+
+        let count = counts.get(name);
+        category_context.insert(name.to_string(), json!({ "estimated": count.is_none() }));
+        categories.push(MMLUProCategoryScore {
+            category: name.to_string(),
+            score,
+            total_questions: count.map(|&(_, total)| total),
+            correct_answers: count.map(|&(correct, _)| correct),
+        });
+    }
+
+    if categories.is_empty() {
+        return Err(UploaderError::UnrecognizedFormat("MMLU-Pro report has no category scores".to_string()));
+    }
+
+    Ok(MMLUProScore {
+        categories,
+        num_questions_total,
+        timestamp: timestamp.unwrap_or_else(Utc::now),
+        context: Some(json!({
+            "source": "mmlu-pro",
+            "format": "report.txt",
+            "overall_score": overall_score,
+            "categories": category_context,
+        })),
+    })
+}
+
+/// `(correct, total)` from the `56/71, 78.87%` after `Total, `
+fn parse_count(rest: &str) -> Option<(i32, i32)> {
+    let (fraction, _) = rest.split_once(',')?;
+    let (correct, total) = fraction.trim().split_once('/')?;
+    Some((correct.parse().ok()?, total.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use llm_benchmark_types::BenchmarkScore;
+
+    #[test]
+    fn test_parse_report() {
+        let report = "\
+2025-05-02 10:41:28.045121
+Finished testing biology in 27 minutes 15 seconds.
+Total, 56/71, 78.87%
+Random Guess Attempts, 0/71, 0.00%
+Correct Random Guesses, division by zero error
+Adjusted Score Without Random Guesses, 56/71, 78.87%
+Finished testing computer science in 15 minutes 59 seconds.
+Total, 28/41, 68.29%
+Finished the benchmark in 8 hours 21 seconds.
+Total, 84/150, 56.00%
+Markdown Table:
+| overall | computer science | biology | law |
+| ------- | ---------------- | ------- | --- |
+| 56.00 | 68.29 | 78.87 | 45.45 |
+";
+        let score = parse_report(report).unwrap();
+        assert_eq!(score.timestamp.to_rfc3339(), "2025-05-02T10:41:28.045121+00:00");
+        assert_eq!(score.num_questions_total, Some(150));
+
+        // Columns are matched by the header, not by position
+        let category = |name: &str| score.categories.iter().find(|c| c.category == name).unwrap();
+        assert_eq!(category("biology").score, 78.87);
+        assert_eq!(category("biology").correct_answers, Some(56));
+        assert_eq!(category("computer science").total_questions, Some(41));
+
+        // law has a score but no counts in the report
+        assert_eq!(category("law").score, 45.45);
+        assert_eq!(category("law").total_questions, None);
+
+        let context = score.context.as_ref().unwrap();
+        assert_eq!(context["overall_score"], 56.0);
+        assert_eq!(context["categories"]["law"]["estimated"], true);
+        assert_eq!(context["categories"]["biology"]["estimated"], false);
+        assert!(score.validate().is_ok());
+
+        assert!(parse_report("no table here").is_err());
+    }
+}